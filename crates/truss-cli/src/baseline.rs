@@ -0,0 +1,201 @@
+//! Baseline files for `truss baseline` and `truss validate --baseline`.
+//!
+//! A baseline records the diagnostics a codebase already has, so CI can fail
+//! only on new ones. Entries are keyed by file, rule id, and normalized
+//! message rather than by position, so they survive edits that move code
+//! around. Each entry keeps a count: if a file had two identical diagnostics
+//! when the baseline was written, a third one is still reported.
+
+use crate::report::FileResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::Path;
+use truss_core::Diagnostic;
+
+/// Baseline path used when `truss baseline` is given no `--output`.
+pub const DEFAULT_BASELINE: &str = ".truss-baseline.json";
+
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    file: String,
+    rule_id: String,
+    message: String,
+    count: usize,
+}
+
+/// Known diagnostics loaded from a baseline file.
+pub struct Baseline {
+    /// File → (rule id, normalized message) → occurrences.
+    entries: HashMap<String, HashMap<(String, String), usize>>,
+}
+
+impl Baseline {
+    /// Read a baseline written by `truss baseline`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let file: BaselineFile = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if file.version != FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "format version {} is not supported (expected {}). Regenerate it with 'truss baseline'.",
+                    file.version, FORMAT_VERSION
+                ),
+            ));
+        }
+
+        let mut entries: HashMap<String, HashMap<(String, String), usize>> = HashMap::new();
+        for entry in file.entries {
+            *entries
+                .entry(normalize_path(&entry.file))
+                .or_default()
+                .entry((entry.rule_id, entry.message))
+                .or_default() += entry.count;
+        }
+        Ok(Self { entries })
+    }
+
+    /// Remove the diagnostics in `file` that the baseline already knows about.
+    ///
+    /// Returns how many were removed.
+    pub fn suppress(&self, file: &str, diagnostics: &mut Vec<Diagnostic>) -> usize {
+        let mut remaining = match self.entries.get(&normalize_path(file)) {
+            Some(known) => known.clone(),
+            None => return 0,
+        };
+        let before = diagnostics.len();
+        diagnostics.retain(|d| {
+            match remaining.get_mut(&(d.rule_id.clone(), d.normalized_message())) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+        before - diagnostics.len()
+    }
+}
+
+/// Write every diagnostic in `results` to a baseline at `path`.
+///
+/// Returns the number of diagnostics recorded.
+pub fn write(path: &Path, results: &[&FileResult]) -> io::Result<usize> {
+    // Sorted so regenerating an unchanged baseline gives an identical file
+    let mut counts: BTreeMap<(String, String, String), usize> = BTreeMap::new();
+    for result in results {
+        for diagnostic in &result.diagnostics {
+            let key = (
+                normalize_path(&result.file),
+                diagnostic.rule_id.clone(),
+                diagnostic.normalized_message(),
+            );
+            *counts.entry(key).or_default() += 1;
+        }
+    }
+
+    let total = counts.values().sum();
+    let file = BaselineFile {
+        version: FORMAT_VERSION,
+        entries: counts
+            .into_iter()
+            .map(|((file, rule_id, message), count)| Entry {
+                file,
+                rule_id,
+                message,
+                count,
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(io::Error::other)?;
+    fs::write(path, json + "\n")?;
+    Ok(total)
+}
+
+/// `./ci.yml` and `ci.yml`, or `a\b.yml` and `a/b.yml`, name the same entry.
+pub fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::FileMetadata;
+    use truss_core::{RuleCategory, Severity, Span};
+
+    fn diagnostic(message: &str, start: usize) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            severity: Severity::Warning,
+            span: Span {
+                start,
+                end: start + 4,
+            },
+            rule_id: "action_pinning".to_string(),
+            category: RuleCategory::Security,
+            related: Vec::new(),
+        }
+    }
+
+    fn file_result(file: &str, diagnostics: Vec<Diagnostic>) -> FileResult {
+        FileResult {
+            file: file.to_string(),
+            valid: true,
+            diagnostics,
+            duration_ms: 1.0,
+            metadata: FileMetadata {
+                file_size: 10,
+                lines: 1,
+            },
+            rule_timings: Vec::new(),
+            suppressed: 0,
+        }
+    }
+
+    #[test]
+    fn suppresses_known_diagnostics_regardless_of_position() {
+        let path = std::env::temp_dir().join(format!("truss-baseline-{}.json", std::process::id()));
+        let recorded = file_result(
+            "./.github/workflows/ci.yml",
+            vec![
+                diagnostic("Action 'actions/checkout' is pinned to 'v4'.", 10),
+                diagnostic("Action 'actions/checkout' is pinned to 'v4'.", 50),
+            ],
+        );
+        assert_eq!(write(&path, &[&recorded]).unwrap(), 2);
+
+        let baseline = Baseline::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        // Lines moved and one more occurrence was added: only the extra one remains
+        let mut diagnostics = vec![
+            diagnostic("Action 'actions/checkout' is pinned to 'v4'.", 110),
+            diagnostic("Action 'actions/checkout' is pinned to 'v4'.", 150),
+            diagnostic("Action 'actions/checkout' is pinned to 'v4'.", 190),
+            diagnostic("Action 'actions/setup-node' is pinned to 'v4'.", 230),
+        ];
+        let suppressed = baseline.suppress(".github/workflows/ci.yml", &mut diagnostics);
+        assert_eq!(suppressed, 2);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].span.start, 190);
+
+        let mut other = vec![diagnostic(
+            "Action 'actions/checkout' is pinned to 'v4'.",
+            10,
+        )];
+        assert_eq!(baseline.suppress("release.yml", &mut other), 0);
+        assert_eq!(other.len(), 1);
+    }
+}
@@ -0,0 +1,266 @@
+//! On-disk cache of analysis results for `truss validate --cache`.
+//!
+//! Results are stored in a single JSON index (`index.json`) inside the cache
+//! directory, keyed by a hash of the file content, the analysis options, and
+//! the Truss version. Unchanged files are served from the index instead of
+//! being re-analyzed; any change to the content, config, or binary version
+//! produces a different key. Rule filters (`--only-rule`, `--severity`, ...)
+//! are applied after lookup, so they never invalidate entries.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use truss_core::{AnalysisOptions, TrussResult};
+
+/// Directory used when `--cache` is given without `--cache-dir`.
+pub const DEFAULT_CACHE_DIR: &str = ".truss-cache";
+
+const INDEX_FILE: &str = "index.json";
+
+/// Once the index holds more entries than this, entries not used by the
+/// current run are dropped when it is saved.
+const MAX_ENTRIES: usize = 10_000;
+
+#[derive(Serialize, Deserialize)]
+struct Index {
+    version: String,
+    entries: HashMap<String, TrussResult>,
+}
+
+/// Analysis results loaded from a cache directory, shared across worker threads.
+pub struct Cache {
+    dir: PathBuf,
+    entries: Mutex<HashMap<String, TrussResult>>,
+    used: Mutex<HashSet<String>>,
+}
+
+impl Cache {
+    /// Load the index from `dir`.
+    ///
+    /// A missing, unreadable, or outdated index yields an empty cache: a bad
+    /// cache should only ever cost a re-analysis.
+    pub fn load(dir: &Path) -> Self {
+        let entries = fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<Index>(&content).ok())
+            .filter(|index| index.version == env!("CARGO_PKG_VERSION"))
+            .map(|index| index.entries)
+            .unwrap_or_default();
+        Self {
+            dir: dir.to_path_buf(),
+            entries: Mutex::new(entries),
+            used: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// The cached result for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<TrussResult> {
+        let result = self.entries.lock().unwrap().get(key).cloned()?;
+        self.used.lock().unwrap().insert(key.to_string());
+        Some(result)
+    }
+
+    /// Store the result of analyzing the content behind `key`.
+    pub fn insert(&self, key: String, result: &TrussResult) {
+        self.used.lock().unwrap().insert(key.clone());
+        self.entries.lock().unwrap().insert(key, result.clone());
+    }
+
+    /// Write the index back to the cache directory, creating it if needed.
+    pub fn save(self) -> io::Result<()> {
+        let mut entries = self.entries.into_inner().unwrap();
+        if entries.len() > MAX_ENTRIES {
+            let used = self.used.into_inner().unwrap();
+            entries.retain(|key, _| used.contains(key));
+        }
+        let index = Index {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries,
+        };
+        let json = serde_json::to_string(&index).map_err(io::Error::other)?;
+
+        fs::create_dir_all(&self.dir)?;
+        // Write then rename so a concurrent run never reads a half-written index
+        let tmp = self
+            .dir
+            .join(format!("{}.{}.tmp", INDEX_FILE, std::process::id()));
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, self.dir.join(INDEX_FILE))
+    }
+}
+
+/// Cache key for analyzing `content` with `options` under this Truss version.
+///
+/// Local reusable workflows called from `content` are read through `resolve`
+/// and hashed too, since their inputs and secrets are checked against the
+/// caller's `with:` and `secrets:`.
+pub fn cache_key(
+    content: &str,
+    options: &AnalysisOptions,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> String {
+    let mut overrides: Vec<_> = options.severity_overrides.iter().collect();
+    overrides.sort();
+
+    let mut hash = Fnv64::new();
+    hash.write(env!("CARGO_PKG_VERSION").as_bytes());
+    for (rule, severity) in overrides {
+        hash.write(format!("\0override:{}={:?}", rule, severity).as_bytes());
+    }
+    for environment in &options.allowed_environments {
+        hash.write(format!("\0environment:{}", environment).as_bytes());
+    }
+    if let Some(limit) = options.max_file_size_bytes {
+        hash.write(format!("\0max_file_size:{}", limit).as_bytes());
+    }
+    if let Some(limit) = options.max_matrix_combinations {
+        hash.write(format!("\0max_matrix:{}", limit).as_bytes());
+    }
+    for path in local_uses(content) {
+        hash.write(format!("\0uses:{}\0", path).as_bytes());
+        hash.write(resolve(path).as_deref().unwrap_or("\0missing").as_bytes());
+    }
+    hash.write(b"\0content:");
+    hash.write(content.as_bytes());
+    format!("{:016x}", hash.finish())
+}
+
+/// Every `uses: ./...` path in `content`, found by a line scan.
+///
+/// Over-matching (comments, local actions) only adds to the key, so this
+/// doesn't need the parse tree.
+fn local_uses(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_start().trim_start_matches("- ");
+            let value = line.strip_prefix("uses:")?.trim_start();
+            let value = value.trim_start_matches(['"', '\'']);
+            if !value.starts_with("./") {
+                return None;
+            }
+            let end = value
+                .find(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '#')
+                .unwrap_or(value.len());
+            Some(&value[..end])
+        })
+        .collect()
+}
+
+/// 64-bit FNV-1a. The index outlives the process, so the hash must not
+/// depend on the Rust version the way `DefaultHasher` may.
+struct Fnv64(u64);
+
+impl Fnv64 {
+    fn new() -> Self {
+        Fnv64(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truss_core::{Diagnostic, RuleCategory, Severity, Span};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("truss-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn result() -> TrussResult {
+        TrussResult {
+            diagnostics: vec![Diagnostic {
+                message: "Job 'build' is missing runs-on".to_string(),
+                severity: Severity::Error,
+                span: Span { start: 3, end: 8 },
+                rule_id: "runs_on_required".to_string(),
+                category: RuleCategory::Schema,
+                related: Vec::new(),
+            }],
+            line_index: None,
+        }
+    }
+
+    fn unresolved(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn key_depends_on_content_and_options() {
+        let options = AnalysisOptions::default();
+        let key = cache_key("on: push\n", &options, &unresolved);
+        assert_eq!(key, cache_key("on: push\n", &options, &unresolved));
+        assert_ne!(key, cache_key("on: pull_request\n", &options, &unresolved));
+
+        let mut overridden = AnalysisOptions::default();
+        overridden
+            .severity_overrides
+            .insert("timeout".to_string(), Severity::Warning);
+        assert_ne!(key, cache_key("on: push\n", &overridden, &unresolved));
+    }
+
+    #[test]
+    fn key_depends_on_called_local_workflows() {
+        let options = AnalysisOptions::default();
+        let caller = "jobs:\n  call:\n    uses: './.github/workflows/build.yml' # local\n";
+        assert_eq!(local_uses(caller), ["./.github/workflows/build.yml"]);
+
+        let v1 = |_: &str| Some("inputs: a".to_string());
+        let v2 = |_: &str| Some("inputs: b".to_string());
+        let key = cache_key(caller, &options, &v1);
+        assert_eq!(key, cache_key(caller, &options, &v1));
+        assert_ne!(key, cache_key(caller, &options, &v2));
+        assert_ne!(key, cache_key(caller, &options, &unresolved));
+    }
+
+    #[test]
+    fn saved_entries_are_loaded_on_the_next_run() {
+        let dir = temp_dir("roundtrip");
+        let cache = Cache::load(&dir);
+        assert!(cache.get("abc").is_none());
+        cache.insert("abc".to_string(), &result());
+        cache.save().unwrap();
+
+        let cache = Cache::load(&dir);
+        let cached = cache.get("abc").unwrap();
+        assert_eq!(cached.diagnostics.len(), 1);
+        assert_eq!(cached.diagnostics[0].rule_id, "runs_on_required");
+        assert_eq!(cached.diagnostics[0].span.end, 8);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn index_from_another_version_is_ignored() {
+        let dir = temp_dir("version");
+        fs::create_dir_all(&dir).unwrap();
+        let mut entries = HashMap::new();
+        entries.insert("abc".to_string(), result());
+        let index = Index {
+            version: "0.0.0-old".to_string(),
+            entries,
+        };
+        fs::write(dir.join(INDEX_FILE), serde_json::to_string(&index).unwrap()).unwrap();
+
+        assert!(Cache::load(&dir).get("abc").is_none());
+
+        // A corrupt index is treated as empty too
+        fs::write(dir.join(INDEX_FILE), "{ not json").unwrap();
+        assert!(Cache::load(&dir).get("abc").is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,264 @@
+//! Line filters for `truss validate --changed-lines` and `--diff`.
+//!
+//! A pre-commit check on a large workflow only cares about the lines being
+//! changed, so diagnostics are kept only when their span touches one of
+//! them. Syntax diagnostics (parse failures, empty documents, files skipped
+//! for size) describe the whole file and are always kept.
+
+use crate::baseline::normalize_path;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use truss_core::render::line_col;
+use truss_core::{Diagnostic, LineIndex, RuleCategory};
+
+/// 1-based line ranges per file that diagnostics must touch to be reported.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+    files: HashMap<String, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Parse `--changed-lines` values: comma-separated `file:start-end` or
+    /// `file:line` entries, such as `ci.yml:10-20,ci.yml:42`.
+    ///
+    /// The file is everything before the last `:`, so Windows paths work.
+    /// Use `-` or `<stdin>` for stdin.
+    pub fn parse(specs: &[String]) -> Result<Self, String> {
+        let mut changed = Self::default();
+        for entry in specs.iter().flat_map(|spec| spec.split(',')) {
+            let entry = entry.trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let invalid = || {
+                format!(
+                    "Invalid --changed-lines entry '{}': expected FILE:START-END or FILE:LINE",
+                    entry
+                )
+            };
+            let (file, range) = entry.rsplit_once(':').ok_or_else(invalid)?;
+            let (start, end) = range.split_once('-').unwrap_or((range, range));
+            let start: usize = start.trim().parse().map_err(|_| invalid())?;
+            let end: usize = end.trim().parse().map_err(|_| invalid())?;
+            if file.is_empty() || start == 0 || end < start {
+                return Err(invalid());
+            }
+            changed.add(file, start..=end);
+        }
+        Ok(changed)
+    }
+
+    /// Add the lines a unified diff (`git diff` output) adds or changes.
+    ///
+    /// Paths come from the `+++ b/...` headers, so they are relative to the
+    /// repository root. Lines that are only deleted count as changes to the
+    /// lines on either side of them, since removing a key can break its
+    /// neighbours. Deleted files are skipped.
+    pub fn add_diff(&mut self, diff: &str) {
+        let mut file: Option<String> = None;
+        let mut hunk = Hunk::default();
+        // Set while reading `-` lines that no `+` line has replaced yet
+        let mut deleted = false;
+        for text in diff.lines() {
+            let kind = text.as_bytes().first().copied();
+            if deleted && !matches!(kind, Some(b'-' | b'+' | b'\\')) {
+                if let Some(file) = file.as_deref() {
+                    self.add(file, hunk.line.saturating_sub(1).max(1)..=hunk.line);
+                }
+                deleted = false;
+            }
+            if hunk.is_done() {
+                if let Some(path) = text.strip_prefix("+++ ") {
+                    // Drop a trailing timestamp, as written by `diff -u`
+                    let path = path.split('\t').next().unwrap_or(path);
+                    file = (path != "/dev/null")
+                        .then(|| path.strip_prefix("b/").unwrap_or(path).to_string());
+                } else if let Some(header) = text.strip_prefix("@@ ") {
+                    hunk = Hunk::parse(header).unwrap_or_default();
+                }
+                continue;
+            }
+            match kind {
+                Some(b'+') => {
+                    if let Some(file) = file.as_deref() {
+                        self.add(file, hunk.line..=hunk.line);
+                    }
+                    deleted = false;
+                    hunk.line += 1;
+                    hunk.new_left = hunk.new_left.saturating_sub(1);
+                }
+                Some(b'-') => {
+                    deleted = true;
+                    hunk.old_left = hunk.old_left.saturating_sub(1);
+                }
+                // `\ No newline at end of file`
+                Some(b'\\') => {}
+                _ => {
+                    hunk.line += 1;
+                    hunk.old_left = hunk.old_left.saturating_sub(1);
+                    hunk.new_left = hunk.new_left.saturating_sub(1);
+                }
+            }
+        }
+        if let (true, Some(file)) = (deleted, file.as_deref()) {
+            self.add(file, hunk.line.saturating_sub(1).max(1)..=hunk.line);
+        }
+    }
+
+    fn add(&mut self, file: &str, range: RangeInclusive<usize>) {
+        let file = if file == "-" { "<stdin>" } else { file };
+        self.files
+            .entry(normalize_path(file))
+            .or_default()
+            .push(range);
+    }
+
+    /// Drop the diagnostics in `file` whose span doesn't touch a changed line.
+    ///
+    /// `lines` indexes the file's content, and maps byte spans to lines.
+    pub fn retain(&self, file: &str, lines: &LineIndex, diagnostics: &mut Vec<Diagnostic>) {
+        let ranges = self
+            .files
+            .get(&normalize_path(file))
+            .map_or(&[][..], Vec::as_slice);
+        diagnostics.retain(|d| {
+            if d.category == RuleCategory::Syntax {
+                return true;
+            }
+            let first = line_col(lines, d.span.start).0;
+            // The span end is exclusive, so its line is that of the last byte
+            let last = line_col(lines, d.span.end.saturating_sub(1).max(d.span.start)).0;
+            ranges
+                .iter()
+                .any(|range| *range.start() <= last && first <= *range.end())
+        });
+    }
+}
+
+/// Position within a unified diff hunk, from its `@@ -a,b +c,d @@` header.
+#[derive(Debug, Default)]
+struct Hunk {
+    /// Line in the new file that the next body line is at.
+    line: usize,
+    old_left: usize,
+    new_left: usize,
+}
+
+impl Hunk {
+    /// Parse a header after its leading `@@ `. A missing count means one line.
+    fn parse(header: &str) -> Option<Self> {
+        let mut parts = header.split_whitespace();
+        let (_, old_left) = range(parts.next()?.strip_prefix('-')?)?;
+        let (line, new_left) = range(parts.next()?.strip_prefix('+')?)?;
+        Some(Self {
+            line,
+            old_left,
+            new_left,
+        })
+    }
+
+    /// Whether every line the header announced has been read.
+    fn is_done(&self) -> bool {
+        self.old_left == 0 && self.new_left == 0
+    }
+}
+
+/// `start,count` or `start` from a hunk header.
+fn range(text: &str) -> Option<(usize, usize)> {
+    let (start, count) = text.split_once(',').unwrap_or((text, "1"));
+    Some((start.parse().ok()?, count.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truss_core::{Severity, Span};
+
+    const SOURCE: &str = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps: []\n";
+
+    fn diagnostic(text: &str, category: RuleCategory) -> Diagnostic {
+        let start = SOURCE.find(text).unwrap();
+        Diagnostic {
+            message: text.to_string(),
+            severity: Severity::Error,
+            span: Span {
+                start,
+                end: start + text.len(),
+            },
+            rule_id: "test".to_string(),
+            category,
+            related: Vec::new(),
+        }
+    }
+
+    fn messages(
+        changed: &ChangedLines,
+        file: &str,
+        mut diagnostics: Vec<Diagnostic>,
+    ) -> Vec<String> {
+        changed.retain(file, &LineIndex::new(SOURCE), &mut diagnostics);
+        diagnostics.into_iter().map(|d| d.message).collect()
+    }
+
+    #[test]
+    fn keeps_diagnostics_touching_changed_lines() {
+        let changed = ChangedLines::parse(&["./ci.yml:3-4,ci.yml:1".to_string()]).unwrap();
+        let diagnostics = || {
+            vec![
+                diagnostic("on: push", RuleCategory::Schema),
+                diagnostic("jobs", RuleCategory::Schema),
+                diagnostic("build:\n    runs-on", RuleCategory::Schema),
+                diagnostic("steps", RuleCategory::Style),
+                diagnostic("[]", RuleCategory::Syntax),
+            ]
+        };
+
+        assert_eq!(
+            messages(&changed, "ci.yml", diagnostics()),
+            ["on: push", "build:\n    runs-on", "[]"]
+        );
+        // A file with no changed lines only keeps syntax diagnostics
+        assert_eq!(messages(&changed, "release.yml", diagnostics()), ["[]"]);
+    }
+
+    #[test]
+    fn rejects_malformed_specs() {
+        for spec in ["ci.yml", "ci.yml:0", "ci.yml:5-2", "ci.yml:a-b", ":3"] {
+            assert!(
+                ChangedLines::parse(&[spec.to_string()]).is_err(),
+                "{} should be rejected",
+                spec
+            );
+        }
+        let windows = ChangedLines::parse(&[r"C:\repo\ci.yml:2".to_string()]).unwrap();
+        assert!(windows.files.contains_key("C:/repo/ci.yml"));
+    }
+
+    #[test]
+    fn reads_added_and_deleted_lines_from_a_unified_diff() {
+        let diff = "\
+diff --git a/.github/workflows/ci.yml b/.github/workflows/ci.yml
+--- a/.github/workflows/ci.yml
++++ b/.github/workflows/ci.yml
+@@ -1,4 +1,4 @@
+ on: push
+ jobs:
+-  test:
++  build:
+     runs-on: ubuntu-latest
+@@ -9,2 +9,1 @@ jobs:
+ name: x
+-removed: y
+diff --git a/old.yml b/old.yml
+--- a/old.yml
++++ /dev/null
+@@ -1 +0,0 @@
+-on: push
+";
+        let mut changed = ChangedLines::default();
+        changed.add_diff(diff);
+
+        assert_eq!(changed.files[".github/workflows/ci.yml"], [3..=3, 9..=10]);
+        assert_eq!(changed.files.len(), 1);
+    }
+}
@@ -0,0 +1,264 @@
+//! Applying the edits rules propose, for `truss fix`.
+//!
+//! Each rule keeps its own edits apart, but two rules may still touch the
+//! same bytes. Such a file is left alone rather than guessing which edit
+//! should win.
+
+use std::ops::Range;
+use truss_core::TextEdit;
+
+/// Lines of unchanged context around each hunk of [`unified_diff`].
+const CONTEXT: usize = 3;
+
+/// `source` with `edits` applied.
+///
+/// Edits are applied in start order. Overlapping edits, or one outside the
+/// source or splitting a character, are an error.
+pub fn apply(source: &str, edits: &[TextEdit]) -> Result<String, String> {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| (edit.span.start, edit.span.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut copied = 0;
+    let mut previous: Option<&TextEdit> = None;
+    for edit in edits {
+        let (start, end) = (edit.span.start, edit.span.end);
+        if let Some(previous) = previous.filter(|previous| start < previous.span.end) {
+            return Err(format!(
+                "fixes from '{}' and '{}' overlap at bytes {}..{}",
+                previous.rule_id, edit.rule_id, start, previous.span.end
+            ));
+        }
+        if start > end || source.get(start..end).is_none() {
+            return Err(format!(
+                "fix from '{}' has an invalid range {}..{}",
+                edit.rule_id, start, end
+            ));
+        }
+        fixed.push_str(&source[copied..start]);
+        fixed.push_str(&edit.new_text);
+        copied = end;
+        previous = Some(edit);
+    }
+    fixed.push_str(&source[copied..]);
+    Ok(fixed)
+}
+
+/// The change `edits` make to `source`, as a unified diff of `path`.
+///
+/// `edits` must already have been accepted by [`apply`].
+pub fn unified_diff(path: &str, source: &str, edits: &[TextEdit]) -> String {
+    let lines: Vec<&str> = source.split_inclusive('\n').collect();
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut line_starts = Vec::with_capacity(lines.len() + 1);
+    let mut offset = 0;
+    for line in &lines {
+        line_starts.push(offset);
+        offset += line.len();
+    }
+    line_starts.push(offset);
+    let line_of = |offset: usize| {
+        line_starts[..lines.len()]
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1)
+    };
+
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|edit| edit.span.start);
+
+    // Whole lines each run of edits rewrites, with their new text
+    let mut changes: Vec<(Range<usize>, String)> = Vec::new();
+    let mut pending: Option<(Range<usize>, Vec<&TextEdit>)> = None;
+    for edit in edits {
+        let first = line_of(edit.span.start);
+        let last = line_of(edit.span.end.saturating_sub(1).max(edit.span.start));
+        match pending.as_mut() {
+            Some((lines, group)) if first < lines.end => {
+                lines.end = lines.end.max(last + 1);
+                group.push(edit);
+            }
+            _ => {
+                if let Some((lines, group)) = pending.replace((first..last + 1, vec![edit])) {
+                    changes.push(rewrite_lines(source, &line_starts, lines, &group));
+                }
+            }
+        }
+    }
+    if let Some((lines, group)) = pending {
+        changes.push(rewrite_lines(source, &line_starts, lines, &group));
+    }
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    // Lines the hunks so far added, minus those they removed
+    let mut delta: isize = 0;
+    let mut i = 0;
+    while i < changes.len() {
+        // Changes whose context would touch go in one hunk
+        let mut j = i + 1;
+        while j < changes.len() && changes[j].0.start - changes[j - 1].0.end <= 2 * CONTEXT {
+            j += 1;
+        }
+        let hunk = &changes[i..j];
+        let old_start = hunk[0].0.start.saturating_sub(CONTEXT);
+        let old_end = (hunk[hunk.len() - 1].0.end + CONTEXT).min(lines.len());
+
+        let mut body = String::new();
+        let mut new_count = 0;
+        let mut line = old_start;
+        for (range, new_text) in hunk {
+            for context in &lines[line..range.start] {
+                push_line(&mut body, ' ', context);
+            }
+            new_count += range.start - line;
+            for old in &lines[range.clone()] {
+                push_line(&mut body, '-', old);
+            }
+            for new in new_text.split_inclusive('\n') {
+                push_line(&mut body, '+', new);
+                new_count += 1;
+            }
+            line = range.end;
+        }
+        for context in &lines[line..old_end] {
+            push_line(&mut body, ' ', context);
+        }
+        new_count += old_end - line;
+
+        let old_count = old_end - old_start;
+        let new_start = (old_start as isize + delta) as usize;
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_start, old_count),
+            hunk_range(new_start, new_count)
+        ));
+        out.push_str(&body);
+        delta += new_count as isize - old_count as isize;
+        i = j;
+    }
+    out
+}
+
+/// The new text of whole lines `lines` once `edits`, all within them, are applied.
+fn rewrite_lines(
+    source: &str,
+    line_starts: &[usize],
+    lines: Range<usize>,
+    edits: &[&TextEdit],
+) -> (Range<usize>, String) {
+    let (start, end) = (line_starts[lines.start], line_starts[lines.end]);
+    let mut text = String::new();
+    let mut copied = start;
+    for edit in edits {
+        text.push_str(&source[copied..edit.span.start]);
+        text.push_str(&edit.new_text);
+        copied = edit.span.end;
+    }
+    text.push_str(&source[copied..end]);
+    (lines, text)
+}
+
+/// A hunk header range: the 1-based first line and the count, or for an
+/// empty range the line before it.
+fn hunk_range(start: usize, count: usize) -> String {
+    let first = if count == 0 { start } else { start + 1 };
+    format!("{},{}", first, count)
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    match line.strip_suffix('\n') {
+        Some(line) => {
+            out.push_str(line);
+            out.push('\n');
+        }
+        None => {
+            out.push_str(line);
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truss_core::Span;
+
+    fn edit(source: &str, old: &str, new_text: &str, rule_id: &str) -> TextEdit {
+        let start = source.find(old).unwrap();
+        TextEdit {
+            span: Span {
+                start,
+                end: start + old.len(),
+            },
+            new_text: new_text.to_string(),
+            rule_id: rule_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn applies_edits_and_rejects_overlaps() {
+        let source = "a: 1\nb: 2\nc: 3\n";
+        let edits = [
+            edit(source, "3", "three", "x"),
+            edit(source, "a: 1\n", "", "x"),
+        ];
+        assert_eq!(apply(source, &edits).unwrap(), "b: 2\nc: three\n");
+
+        let overlapping = [edit(source, "b: 2", "", "x"), edit(source, "2", "two", "y")];
+        let error = apply(source, &overlapping).unwrap_err();
+        assert!(error.contains("'x' and 'y' overlap"), "{}", error);
+        let mut past_end = edit(source, "c: 3", "", "x");
+        past_end.span.end = 99;
+        assert!(apply(source, &[past_end]).is_err());
+    }
+
+    #[test]
+    fn diff_has_context_and_merges_nearby_changes() {
+        let source: String = (1..=20).map(|i| format!("line{}\n", i)).collect();
+        let edits = [
+            edit(&source, "line2\n", "", "x"),
+            edit(&source, "line5\n", "five\nfive b\n", "x"),
+            edit(&source, "line18", "eighteen", "x"),
+        ];
+        assert_eq!(
+            unified_diff("./ci.yml", &source, &edits),
+            "--- a/ci.yml
++++ b/ci.yml
+@@ -1,8 +1,8 @@
+ line1
+-line2
+ line3
+ line4
+-line5
++five
++five b
+ line6
+ line7
+ line8
+@@ -15,6 +15,6 @@
+ line15
+ line16
+ line17
+-line18
++eighteen
+ line19
+ line20
+"
+        );
+    }
+
+    #[test]
+    fn diff_marks_missing_final_newline() {
+        let source = "a: 1\nb: 2";
+        assert_eq!(
+            unified_diff("ci.yml", source, &[edit(source, "2", "3", "x")]),
+            "--- a/ci.yml\n+++ b/ci.yml\n@@ -1,2 +1,2 @@\n a: 1\n-b: 2\n\\ No newline at end of file\n+b: 3\n\\ No newline at end of file\n"
+        );
+    }
+}
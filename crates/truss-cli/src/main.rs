@@ -1,12 +1,29 @@
+mod baseline;
+mod cache;
+mod changed_lines;
+mod fix;
+mod report;
+mod style;
+
+use baseline::Baseline;
+use cache::Cache;
+use changed_lines::ChangedLines;
 use clap::{Parser, Subcommand, ValueEnum};
 use glob::glob;
 use rayon::prelude::*;
+use report::{FileMetadata, FileResult};
+use std::collections::HashMap;
 use std::fs;
 use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use style::{ColorChoice, Style};
 use truss_core::config::TrussConfig;
-use truss_core::TrussEngine;
+use truss_core::render;
+use truss_core::{AnalysisOptions, LineIndex, RuleCategory, TextEdit, TrussEngine, TrussResult};
 
 /// Exit code: one or more files had validation errors.
 const EXIT_VALIDATION_FAILED: i32 = 1;
@@ -32,18 +49,38 @@ enum Commands {
         #[arg(num_args = 1..)]
         paths: Vec<String>,
 
-        /// Suppress output (only exit code indicates success/failure)
+        /// Read newline-separated file paths from PATH (`-` for stdin) and validate them as listed
+        #[arg(long, value_name = "PATH")]
+        files_from: Option<String>,
+
+        /// Suppress per-file output, printing only the final summary line
         #[arg(short, long)]
         quiet: bool,
 
-        /// Output results as JSON
+        /// Suppress all output (only exit code indicates success/failure)
+        #[arg(long)]
+        silent: bool,
+
+        /// Output results as JSON (same as `--format json`)
         #[arg(long)]
         json: bool,
 
-        /// Minimum severity level to display and fail on
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Minimum severity level to display
         #[arg(long, value_enum)]
         severity: Option<SeverityFilter>,
 
+        /// Minimum severity level that fails validation (exit code 1)
+        #[arg(long, value_enum, default_value_t = SeverityFilter::Error)]
+        fail_on: SeverityFilter,
+
+        /// Fail on warnings too, keeping their displayed severity (same as `strict: true` in .truss.yml)
+        #[arg(long)]
+        strict: bool,
+
         /// Ignore specific rules by name (can be repeated)
         #[arg(long = "ignore-rule", num_args = 1)]
         ignore_rules: Vec<String>,
@@ -51,6 +88,87 @@ enum Commands {
         /// Run only specific rules by name (can be repeated)
         #[arg(long = "only-rule", num_args = 1)]
         only_rules: Vec<String>,
+
+        /// Run only rules in specific categories (can be repeated)
+        #[arg(long = "only-category", value_enum, num_args = 1)]
+        only_categories: Vec<CategoryFilter>,
+
+        /// Path to .truss.yml config file (auto-discovered if not specified)
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+
+        /// Disable config file auto-discovery
+        #[arg(long)]
+        no_config: bool,
+
+        /// Print how long each rule took, summed over all files (runs rules sequentially)
+        #[arg(long)]
+        timings: bool,
+
+        /// Reuse results for files whose content hasn't changed since the last cached run
+        #[arg(long)]
+        cache: bool,
+
+        /// Directory for the result cache (default: .truss-cache). Implies --cache
+        #[arg(long, value_name = "DIR")]
+        cache_dir: Option<PathBuf>,
+
+        /// Hide diagnostics recorded in a baseline written by `truss baseline`
+        #[arg(long, value_name = "PATH")]
+        baseline: Option<PathBuf>,
+
+        /// Skip files larger than BYTES (overrides max_file_size_bytes in .truss.yml)
+        #[arg(long, value_name = "BYTES")]
+        max_size: Option<usize>,
+
+        /// Only report diagnostics on these lines, as FILE:START-END or FILE:LINE (comma-separated, can be repeated)
+        #[arg(long = "changed-lines", value_name = "SPEC", num_args = 1)]
+        changed_lines: Vec<String>,
+
+        /// Only report diagnostics on lines added or changed in a unified diff read from PATH (`-` for stdin)
+        #[arg(long, value_name = "PATH")]
+        diff: Option<String>,
+
+        /// When to color diagnostics
+        #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+        color: ColorChoice,
+
+        /// Don't color diagnostics (same as `--color never`)
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Record current diagnostics so `validate --baseline` only reports new ones
+    Baseline {
+        /// Path(s), directories, or glob patterns to record. Use `-` for stdin.
+        #[arg(num_args = 1..)]
+        paths: Vec<String>,
+
+        /// Read newline-separated file paths from PATH (`-` for stdin) and record them as listed
+        #[arg(long, value_name = "PATH")]
+        files_from: Option<String>,
+
+        /// Where to write the baseline
+        #[arg(short, long, value_name = "PATH", default_value = baseline::DEFAULT_BASELINE)]
+        output: PathBuf,
+
+        /// Path to .truss.yml config file (auto-discovered if not specified)
+        #[arg(long, value_name = "PATH")]
+        config: Option<PathBuf>,
+
+        /// Disable config file auto-discovery
+        #[arg(long)]
+        no_config: bool,
+    },
+    /// Apply the safe fixes rules propose, rewriting files in place
+    Fix {
+        /// Path(s), directories, or glob patterns to fix
+        #[arg(num_args = 1..)]
+        paths: Vec<String>,
+
+        /// Print the fixes as a unified diff instead of writing them
+        #[arg(long)]
+        dry_run: bool,
+
         /// Path to .truss.yml config file (auto-discovered if not specified)
         #[arg(long, value_name = "PATH")]
         config: Option<PathBuf>,
@@ -59,9 +177,38 @@ enum Commands {
         #[arg(long)]
         no_config: bool,
     },
+    /// Explain what a rule checks, with an example and its fix
+    Explain {
+        /// Rule id as shown in diagnostics (e.g. step_output_reference)
+        rule_id: String,
+    },
 }
 
-#[derive(Debug, Clone, Copy, ValueEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable diagnostics (default)
+    Text,
+    /// JSON document with per-file results and a run summary
+    Json,
+    /// JUnit XML report for CI test dashboards
+    Junit,
+    /// One JSON object per file, printed as each file finishes
+    Ndjson,
+    /// GitHub Actions workflow commands, shown as inline annotations on the PR
+    Github,
+}
+
+impl OutputFormat {
+    /// Whether the output is meant for tools, so progress messages must stay off stdout/stderr.
+    ///
+    /// GitHub annotations are read from a log that humans also read, so they
+    /// keep the text format's messages.
+    fn is_machine_readable(self) -> bool {
+        !matches!(self, OutputFormat::Text | OutputFormat::Github)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum SeverityFilter {
     /// Show only errors
     Error,
@@ -81,6 +228,40 @@ impl SeverityFilter {
             SeverityFilter::Info => true,
         }
     }
+
+    /// This threshold, lowered to at least include warnings.
+    fn strict(self) -> Self {
+        match self {
+            SeverityFilter::Error => SeverityFilter::Warning,
+            other => other,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CategoryFilter {
+    /// YAML syntax and document-level problems
+    Syntax,
+    /// Structure and allowed values
+    Schema,
+    /// Expressions and context references
+    Expression,
+    /// Injection, permissions, and secrets
+    Security,
+    /// Naming and best-practice suggestions
+    Style,
+}
+
+impl CategoryFilter {
+    fn category(self) -> RuleCategory {
+        match self {
+            CategoryFilter::Syntax => RuleCategory::Syntax,
+            CategoryFilter::Schema => RuleCategory::Schema,
+            CategoryFilter::Expression => RuleCategory::Expression,
+            CategoryFilter::Security => RuleCategory::Security,
+            CategoryFilter::Style => RuleCategory::Style,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -138,6 +319,20 @@ fn read_source(path: &str) -> Result<String, TrussError> {
     }
 }
 
+/// Read a newline-separated list of file paths from `source` (`-` for stdin).
+///
+/// Blank lines are skipped. Listed paths are used verbatim: no directory or
+/// glob expansion.
+fn read_file_list(source: &str) -> Result<Vec<String>, TrussError> {
+    let content = read_source(source)?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
 /// Expand a user-provided path into concrete file paths.
 ///
 /// - `-` is returned as-is (stdin marker).
@@ -194,28 +389,22 @@ fn expand_paths(raw_paths: &[String]) -> Result<Vec<String>, TrussError> {
     Ok(expanded)
 }
 
-#[derive(serde::Serialize)]
-struct FileResult {
-    file: String,
-    valid: bool,
-    diagnostics: Vec<truss_core::Diagnostic>,
-    duration_ms: f64,
-    metadata: FileMetadata,
-}
-
-#[derive(serde::Serialize)]
-struct FileMetadata {
-    file_size: u64,
-    lines: usize,
-}
-
 struct ValidateOptions<'a> {
     quiet: bool,
-    json: bool,
+    silent: bool,
+    format: OutputFormat,
     severity_filter: SeverityFilter,
+    fail_on: SeverityFilter,
     ignore_rules: &'a [String],
     only_rules: &'a [String],
+    only_categories: &'a [CategoryFilter],
     config: &'a TrussConfig,
+    timings: bool,
+    cache: Option<&'a Cache>,
+    baseline: Option<&'a Baseline>,
+    max_size: Option<usize>,
+    changed_lines: Option<&'a ChangedLines>,
+    color: ColorChoice,
 }
 
 fn validate_source(
@@ -227,15 +416,42 @@ fn validate_source(
     let file_size = content.len() as u64;
     let lines = content.lines().count();
 
+    let mut options = opts.config.analysis_options_for(label);
+    if opts.max_size.is_some() {
+        options.max_file_size_bytes = opts.max_size;
+    }
+    let resolve = |uses: &str| resolve_local_workflow(label, uses);
     let start = Instant::now();
-    let result = engine.analyze(content);
+    // A cache hit has nothing to time, so --timings always re-analyzes
+    let (mut result, rule_timings) = if opts.timings {
+        engine.analyze_timed_with_resolver(content, &options, resolve)
+    } else if let Some(cache) = opts.cache {
+        (
+            analyze_cached(engine, cache, content, &options, &resolve),
+            Vec::new(),
+        )
+    } else {
+        (
+            engine.analyze_with_resolver(content, &options, resolve),
+            Vec::new(),
+        )
+    };
     let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    // Cached results don't carry their index, so it's built only if needed
+    let mut line_index = result.line_index.take();
 
-    // Filter diagnostics by severity and rule filters
-    let filtered: Vec<truss_core::Diagnostic> = result
+    // Filter diagnostics by rule filters
+    let mut matched: Vec<truss_core::Diagnostic> = result
         .diagnostics
         .into_iter()
-        .filter(|d| opts.severity_filter.includes(d.severity))
+        .filter(|d| opts.config.is_rule_enabled_for(&d.rule_id, label))
+        .filter(|d| {
+            opts.only_categories.is_empty()
+                || opts
+                    .only_categories
+                    .iter()
+                    .any(|c| c.category() == d.category)
+        })
         .filter(|d| {
             if !opts.only_rules.is_empty() {
                 return opts.only_rules.iter().any(|r| r == &d.rule_id);
@@ -246,31 +462,51 @@ fn validate_source(
             true
         })
         .collect();
+    // Syntax errors are kept, so a broken file still fails
+    if let Some(changed_lines) = opts.changed_lines {
+        let lines = line_index.get_or_insert_with(|| LineIndex::new(content));
+        changed_lines.retain(label, lines, &mut matched);
+    }
+    let suppressed = opts
+        .baseline
+        .map_or(0, |baseline| baseline.suppress(label, &mut matched));
 
-    let valid = !filtered
-        .iter()
-        .any(|d| d.severity == truss_core::Severity::Error);
+    // Pass/fail uses --fail-on, independent of which severities --severity displays
+    let valid = !matched.iter().any(|d| opts.fail_on.includes(d.severity));
+    let filtered: Vec<truss_core::Diagnostic> = matched
+        .into_iter()
+        .filter(|d| opts.severity_filter.includes(d.severity))
+        .collect();
 
-    if opts.json {
+    if opts.format.is_machine_readable() {
         return Ok(FileResult {
             file: label.to_string(),
             valid,
             diagnostics: filtered,
             duration_ms,
             metadata: FileMetadata { file_size, lines },
+            rule_timings,
+            suppressed,
         });
     }
 
-    if valid {
-        if !opts.quiet {
-            println!("✓ Valid: {}", label);
+    if !opts.quiet {
+        if opts.format == OutputFormat::Github {
+            // Annotation paths are relative to the repository root
+            let path = label.strip_prefix("./").unwrap_or(label);
+            let lines = line_index.get_or_insert_with(|| LineIndex::new(content));
+            print!("{}", render::github(&filtered, path, lines));
+        } else if valid {
+            let style = Style::stdout(opts.color);
+            println!("{} {}", style.success("✓ Valid:"), label);
             for diagnostic in &filtered {
-                println!("  {}", diagnostic);
+                println!("  {}", style.diagnostic(diagnostic));
+            }
+        } else {
+            let style = Style::stderr(opts.color);
+            for diagnostic in &filtered {
+                eprintln!("  {}", style.diagnostic(diagnostic));
             }
-        }
-    } else if !opts.quiet {
-        for diagnostic in &filtered {
-            eprintln!("  {}", diagnostic);
         }
     }
 
@@ -280,9 +516,49 @@ fn validate_source(
         diagnostics: filtered,
         duration_ms,
         metadata: FileMetadata { file_size, lines },
+        rule_timings,
+        suppressed,
     })
 }
 
+/// Analyze `content`, reusing the cached result when content and options are unchanged.
+fn analyze_cached(
+    engine: &mut TrussEngine,
+    cache: &Cache,
+    content: &str,
+    options: &AnalysisOptions,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> TrussResult {
+    let key = cache::cache_key(content, options, resolve);
+    if let Some(result) = cache.get(&key) {
+        return result;
+    }
+    let result = engine.analyze_with_resolver(content, options, resolve);
+    cache.insert(key, &result);
+    result
+}
+
+/// Read the workflow a local `uses: ./...` in `file` points to.
+///
+/// Local paths are relative to the repository root, so the path is tried
+/// against each directory above `file`, nearest first; for a workflow in
+/// `.github/workflows/` the repository root is two levels up. Stdin is
+/// resolved against the current directory.
+fn resolve_local_workflow(file: &str, uses: &str) -> Option<String> {
+    let file = if file == "<stdin>" {
+        PathBuf::new()
+    } else {
+        // Absolute, so the search can go above the current directory
+        fs::canonicalize(file).unwrap_or_else(|_| PathBuf::from(file))
+    };
+    file.parent()
+        .unwrap_or(Path::new(""))
+        .ancestors()
+        .map(|dir| dir.join(uses))
+        .find(|candidate| candidate.is_file())
+        .and_then(|path| fs::read_to_string(path).ok())
+}
+
 fn validate_file(
     engine: &mut TrussEngine,
     path: &str,
@@ -293,8 +569,22 @@ fn validate_file(
     validate_source(engine, label, &content, opts)
 }
 
-fn validate_files(paths: Vec<String>, opts: &ValidateOptions) -> Result<(), TrussError> {
-    let expanded = expand_paths(&paths)?;
+/// A validated path and its outcome.
+type PathResult = (String, Result<FileResult, TrussError>);
+
+/// Analyze positional `paths` (expanded) followed by `listed` paths (used as-is).
+///
+/// Returns each path with its result, stdin first. With `ndjson`, each
+/// file's result is also sent as a JSON line as soon as it is analyzed; the
+/// sender sits behind a mutex only so each worker thread can clone its own.
+fn analyze_files(
+    paths: Vec<String>,
+    listed: Vec<String>,
+    opts: &ValidateOptions,
+    ndjson: Option<&Mutex<Sender<String>>>,
+) -> Result<Vec<PathResult>, TrussError> {
+    let mut expanded = expand_paths(&paths)?;
+    expanded.extend(listed);
 
     // Apply config ignore patterns
     let expanded: Vec<String> = expanded
@@ -312,43 +602,73 @@ fn validate_files(paths: Vec<String>, opts: &ValidateOptions) -> Result<(), Trus
     let (stdin_paths, file_paths): (Vec<_>, Vec<_>) =
         expanded.iter().partition(|p| p.as_str() == "-");
 
-    let mut all_results: Vec<(String, Result<FileResult, TrussError>)> = Vec::new();
+    let sender = || ndjson.map(|sender| sender.lock().unwrap().clone());
+    let validate = |engine: &mut TrussEngine, path: &str, sender: &Option<Sender<String>>| {
+        let result = validate_file(engine, path, opts);
+        if let (Some(sender), Ok(file_result)) = (sender, &result) {
+            if let Ok(line) = report::ndjson::line(file_result) {
+                // The writer only stops early if stdout is gone
+                let _ = sender.send(line);
+            }
+        }
+        (path.to_string(), result)
+    };
+
+    let mut all_results: Vec<PathResult> = Vec::new();
 
     // Process stdin first (sequential, reuse one engine)
     let mut engine = TrussEngine::new();
+    let stdin_sender = sender();
     for path in &stdin_paths {
-        let result = validate_file(&mut engine, path, opts);
-        all_results.push((path.to_string(), result));
+        all_results.push(validate(&mut engine, path, &stdin_sender));
     }
 
     // For a single file, sequential is faster (avoids rayon thread pool overhead).
     // For multiple files, parallel processing pays off.
-    let file_results: Vec<(String, Result<FileResult, TrussError>)> = if file_paths.len() <= 1 {
+    let file_results: Vec<PathResult> = if file_paths.len() <= 1 {
         file_paths
             .iter()
-            .map(|path| {
-                let result = validate_file(&mut engine, path, opts);
-                (path.to_string(), result)
-            })
+            .map(|path| validate(&mut engine, path, &stdin_sender))
             .collect()
     } else {
+        // Clones share the rule set; each rayon job only gets its own parser
         file_paths
             .par_iter()
-            .map(|path| {
-                let mut engine = TrussEngine::new();
-                let result = validate_file(&mut engine, path, opts);
-                (path.to_string(), result)
-            })
+            .map_init(
+                || (engine.clone(), sender()),
+                |(engine, sender), path| validate(engine, path, sender),
+            )
             .collect()
     };
 
     all_results.extend(file_results);
+    Ok(all_results)
+}
+
+/// Validate positional `paths` (expanded) followed by `listed` paths (used as-is).
+fn validate_files(
+    paths: Vec<String>,
+    listed: Vec<String>,
+    opts: &ValidateOptions,
+) -> Result<(), TrussError> {
+    let start = Instant::now();
+    let all_results = if opts.format == OutputFormat::Ndjson {
+        let (sender, receiver) = mpsc::channel();
+        let writer = thread::spawn(move || report::ndjson::write_lines(receiver, io::stdout()));
+        // Every sender is dropped when analysis returns, which ends the writer
+        let results = analyze_files(paths, listed, opts, Some(&Mutex::new(sender)));
+        writer.join().expect("NDJSON writer thread panicked")?;
+        results?
+    } else {
+        analyze_files(paths, listed, opts, None)?
+    };
 
     // Aggregate results
     let mut has_errors = false;
     let mut has_io_error = false;
     let mut success_count = 0;
     let mut error_count = 0;
+    let mut suppressed_count = 0;
     let mut file_results = Vec::new();
 
     for (path, result) in &all_results {
@@ -360,6 +680,7 @@ fn validate_files(paths: Vec<String>, opts: &ValidateOptions) -> Result<(), Trus
                 } else {
                     success_count += 1;
                 }
+                suppressed_count += file_result.suppressed;
                 file_results.push(file_result);
             }
             Err(e) => {
@@ -368,23 +689,47 @@ fn validate_files(paths: Vec<String>, opts: &ValidateOptions) -> Result<(), Trus
                 if matches!(e, TrussError::Io(_)) {
                     has_io_error = true;
                 }
-                if !opts.quiet && !opts.json {
+                if !opts.quiet && !opts.format.is_machine_readable() {
                     eprintln!("Error validating {}: {}", path, e);
                 }
             }
         }
     }
 
-    if opts.json {
-        let json_output = serde_json::to_string_pretty(&file_results).map_err(|e| {
-            TrussError::Io(io::Error::other(format!("Failed to serialize JSON: {}", e)))
-        })?;
-        println!("{}", json_output);
-    } else if !opts.quiet && expanded.len() > 1 {
-        println!(
-            "\nSummary: {} passed, {} failed",
-            success_count, error_count
-        );
+    match opts.format {
+        OutputFormat::Json => {
+            let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+            let json_output = report::json::render(&file_results, all_results.len(), duration_ms)
+                .map_err(|e| {
+                TrussError::Io(io::Error::other(format!("Failed to serialize JSON: {}", e)))
+            })?;
+            println!("{}", json_output);
+        }
+        OutputFormat::Junit => {
+            print!("{}", report::junit::render(&file_results));
+        }
+        // Already written line by line during analysis
+        OutputFormat::Ndjson | OutputFormat::Github => {}
+        OutputFormat::Text if opts.silent => {}
+        OutputFormat::Text => {
+            // In quiet mode the summary is the only output, even for one file
+            let gap = if opts.quiet { "" } else { "\n" };
+            if opts.baseline.is_some() {
+                println!(
+                    "{}Summary: {} passed, {} failed, {} suppressed by baseline",
+                    gap, success_count, error_count, suppressed_count
+                );
+            } else if opts.quiet || all_results.len() > 1 {
+                println!(
+                    "{}Summary: {} passed, {} failed",
+                    gap, success_count, error_count
+                );
+            }
+        }
+    }
+
+    if opts.timings && !opts.quiet {
+        print_rule_timings(&file_results);
     }
 
     if has_io_error {
@@ -396,74 +741,525 @@ fn validate_files(paths: Vec<String>, opts: &ValidateOptions) -> Result<(), Trus
     }
 }
 
+/// Print each rule's total time across all files to stderr, slowest first.
+///
+/// Goes to stderr so `--timings` can be combined with `--format json`.
+fn print_rule_timings(file_results: &[&FileResult]) {
+    let mut totals: HashMap<&str, Duration> = HashMap::new();
+    for file_result in file_results {
+        for (rule, duration) in &file_result.rule_timings {
+            *totals.entry(rule.as_str()).or_default() += *duration;
+        }
+    }
+
+    let mut totals: Vec<(&str, Duration)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let total: Duration = totals.iter().map(|(_, d)| *d).sum();
+    eprintln!("\nRule timings ({} files):", file_results.len());
+    for (rule, duration) in &totals {
+        eprintln!("  {:>9.3} ms  {}", duration.as_secs_f64() * 1000.0, rule);
+    }
+    eprintln!("  {:>9.3} ms  total", total.as_secs_f64() * 1000.0);
+}
+
+/// Check the file arguments of `truss <command>` and read the `--files-from` list.
+fn listed_files(
+    command: &str,
+    paths: &[String],
+    files_from: Option<&str>,
+) -> Result<Vec<String>, TrussError> {
+    if paths.is_empty() && files_from.is_none() {
+        return Err(TrussError::Usage(format!(
+            "No files provided. Run 'truss {} --help' for usage.",
+            command
+        )));
+    }
+    if files_from == Some("-") && paths.iter().any(|p| p == "-") {
+        return Err(TrussError::Usage(format!(
+            "stdin can't be both a file to {} and the --files-from list.",
+            command
+        )));
+    }
+    Ok(files_from
+        .map(read_file_list)
+        .transpose()?
+        .unwrap_or_default())
+}
+
+/// Build the line filter from `--changed-lines` and `--diff`, if either is given.
+fn load_changed_lines(
+    specs: &[String],
+    diff: Option<&str>,
+    paths: &[String],
+    files_from: Option<&str>,
+) -> Result<Option<ChangedLines>, TrussError> {
+    if specs.is_empty() && diff.is_none() {
+        return Ok(None);
+    }
+    if diff == Some("-") && (files_from == Some("-") || paths.iter().any(|p| p == "-")) {
+        return Err(TrussError::Usage(
+            "stdin can only be read once; pass --diff a file instead.".to_string(),
+        ));
+    }
+    let mut changed = ChangedLines::parse(specs).map_err(TrussError::Usage)?;
+    if let Some(diff) = diff {
+        changed.add_diff(&read_source(diff)?);
+    }
+    Ok(Some(changed))
+}
+
+/// Load config: explicit path > auto-discover > empty default.
+///
+/// An explicit config that fails to parse is a usage error; a discovered one
+/// is skipped with a warning.
+fn load_config(config_path: Option<PathBuf>, no_config: bool, verbose: bool) -> TrussConfig {
+    if let Some(path) = config_path {
+        match TrussConfig::from_file(&path) {
+            Ok(c) => c,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(EXIT_USAGE);
+            }
+        }
+    } else if !no_config {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        match TrussConfig::discover(&cwd) {
+            Some(path) => {
+                if verbose {
+                    eprintln!("Using config: {}", path.display());
+                }
+                match TrussConfig::from_file(&path) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        if verbose {
+                            eprintln!("Warning: failed to parse {}: {}", path.display(), e);
+                        }
+                        TrussConfig::default()
+                    }
+                }
+            }
+            None => TrussConfig::default(),
+        }
+    } else {
+        TrussConfig::default()
+    }
+}
+
+/// Validate the files and record every diagnostic they produce in a baseline.
+///
+/// Nothing is written if any file can't be read, since the baseline would
+/// silently miss it.
+fn write_baseline(
+    paths: Vec<String>,
+    listed: Vec<String>,
+    output: &Path,
+    config: &TrussConfig,
+) -> Result<(), TrussError> {
+    let opts = ValidateOptions {
+        quiet: true,
+        silent: true,
+        format: OutputFormat::Text,
+        severity_filter: SeverityFilter::Info,
+        fail_on: SeverityFilter::Error,
+        ignore_rules: &[],
+        only_rules: &[],
+        only_categories: &[],
+        config,
+        timings: false,
+        cache: None,
+        baseline: None,
+        max_size: None,
+        changed_lines: None,
+        color: ColorChoice::Never,
+    };
+    let all_results = analyze_files(paths, listed, &opts, None)?;
+
+    let mut file_results = Vec::new();
+    let mut failed = false;
+    for (path, result) in &all_results {
+        match result {
+            Ok(file_result) => file_results.push(file_result),
+            Err(e) => {
+                eprintln!("Error reading {}: {}", path, e);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        return Err(TrussError::Io(io::Error::other(
+            "One or more files failed; baseline not written",
+        )));
+    }
+
+    let count = baseline::write(output, &file_results)?;
+    println!(
+        "Wrote {} diagnostics from {} files to {}",
+        count,
+        file_results.len(),
+        output.display()
+    );
+    Ok(())
+}
+
+/// Apply the fixes rules propose to each file, or with `dry_run` print them
+/// as a unified diff on stdout.
+///
+/// Fixes from rules the config disables for a file are skipped. A file whose
+/// fixes overlap is left untouched and fails the run. The summary goes to
+/// stderr, so a dry run's output can be piped to `git apply`.
+fn fix_files(paths: Vec<String>, dry_run: bool, config: &TrussConfig) -> Result<(), TrussError> {
+    if paths.is_empty() {
+        return Err(TrussError::Usage(
+            "No files provided. Run 'truss fix --help' for usage.".to_string(),
+        ));
+    }
+    if paths.iter().any(|p| p == "-") {
+        return Err(TrussError::Usage(
+            "truss fix rewrites files in place, so it can't read stdin.".to_string(),
+        ));
+    }
+    let files: Vec<String> = expand_paths(&paths)?
+        .into_iter()
+        .filter(|p| !config.is_ignored(p))
+        .collect();
+    if files.is_empty() {
+        return Err(TrussError::Usage(
+            "No files found. Run 'truss fix --help' for usage.".to_string(),
+        ));
+    }
+
+    let mut engine = TrussEngine::new();
+    let (mut fix_count, mut file_count) = (0, 0);
+    let (mut io_failed, mut conflicted) = (false, false);
+    for path in &files {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Error reading {}: {}", path, e);
+                io_failed = true;
+                continue;
+            }
+        };
+        let edits: Vec<TextEdit> = engine
+            .fix(&source)
+            .replacements
+            .into_iter()
+            .filter(|edit| config.is_rule_enabled_for(&edit.rule_id, path))
+            .collect();
+        if edits.is_empty() {
+            continue;
+        }
+        let fixed = match fix::apply(&source, &edits) {
+            Ok(fixed) => fixed,
+            Err(e) => {
+                eprintln!("Error fixing {}: {}; file left unchanged", path, e);
+                conflicted = true;
+                continue;
+            }
+        };
+        if dry_run {
+            print!("{}", fix::unified_diff(path, &source, &edits));
+        } else if let Err(e) = fs::write(path, fixed) {
+            eprintln!("Error writing {}: {}", path, e);
+            io_failed = true;
+            continue;
+        }
+        fix_count += edits.len();
+        file_count += 1;
+    }
+
+    if dry_run {
+        eprintln!("{} fixes available in {} files", fix_count, file_count);
+    } else {
+        eprintln!("Applied {} fixes to {} files", fix_count, file_count);
+    }
+    if io_failed {
+        Err(TrussError::Io(io::Error::other("One or more files failed")))
+    } else if conflicted {
+        Err(TrussError::ValidationFailed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Print the explanation for a rule, or list the available rule ids.
+fn explain_rule(rule_id: &str) -> Result<(), TrussError> {
+    let engine = TrussEngine::new();
+
+    let rule = match engine.rule(rule_id) {
+        Some(rule) => rule,
+        None => {
+            let mut ids: Vec<&str> = engine.rules().map(|rule| rule.name()).collect();
+            ids.sort_unstable();
+            return Err(TrussError::Usage(format!(
+                "Unknown rule id '{}'. Available rule ids:\n  {}",
+                rule_id,
+                ids.join("\n  ")
+            )));
+        }
+    };
+
+    let explanation = rule.explanation();
+    println!("{} ({:?})", rule.name(), rule.category());
+    println!();
+    if explanation.summary.is_empty() {
+        println!("No explanation available for this rule.");
+        return Ok(());
+    }
+    println!("{}", explanation.summary);
+    println!();
+    println!("Example that fails:");
+    println!();
+    for line in explanation.bad_example.lines() {
+        println!("    {}", line);
+    }
+    println!();
+    println!("Fixed:");
+    println!();
+    for line in explanation.good_example.lines() {
+        println!("    {}", line);
+    }
+
+    Ok(())
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
         Commands::Validate {
             paths,
+            files_from,
             quiet,
+            silent,
             json,
+            format,
             severity,
+            fail_on,
+            strict,
             ignore_rules,
             only_rules,
+            only_categories,
             config: config_path,
             no_config,
+            timings,
+            cache,
+            cache_dir,
+            baseline: baseline_path,
+            max_size,
+            changed_lines,
+            diff,
+            color,
+            no_color,
         } => {
-            if paths.is_empty() {
-                if !quiet && !json {
-                    eprintln!("Error: No files provided. Run 'truss validate --help' for usage.");
-                }
-                std::process::exit(EXIT_USAGE);
-            }
+            let format = if json { OutputFormat::Json } else { format };
+            let machine_readable = format.is_machine_readable();
+            // --silent implies --quiet; --quiet alone still prints the summary
+            let quiet = quiet || silent;
+            let verbose = !quiet && !machine_readable;
 
-            // Load config: explicit path > auto-discover > empty default
-            let config = if let Some(path) = config_path {
-                match TrussConfig::from_file(&path) {
-                    Ok(c) => c,
-                    Err(e) => {
+            let inputs =
+                listed_files("validate", &paths, files_from.as_deref()).and_then(|listed| {
+                    let changed = load_changed_lines(
+                        &changed_lines,
+                        diff.as_deref(),
+                        &paths,
+                        files_from.as_deref(),
+                    )?;
+                    Ok((listed, changed))
+                });
+            let (listed, changed_lines) = match inputs {
+                Ok(inputs) => inputs,
+                Err(e) => {
+                    if verbose {
                         eprintln!("Error: {}", e);
-                        std::process::exit(EXIT_USAGE);
                     }
+                    std::process::exit(e.exit_code());
                 }
-            } else if !no_config {
-                let cwd = std::env::current_dir().unwrap_or_default();
-                match TrussConfig::discover(&cwd) {
-                    Some(path) => {
-                        if !quiet && !json {
-                            eprintln!("Using config: {}", path.display());
-                        }
-                        match TrussConfig::from_file(&path) {
-                            Ok(c) => c,
-                            Err(e) => {
-                                if !quiet && !json {
-                                    eprintln!("Warning: failed to parse {}: {}", path.display(), e);
-                                }
-                                TrussConfig::default()
-                            }
-                        }
+            };
+
+            let config = load_config(config_path, no_config, verbose);
+
+            let baseline = match baseline_path.as_deref().map(Baseline::load).transpose() {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    if verbose {
+                        eprintln!(
+                            "Error: failed to read baseline {}: {}",
+                            baseline_path.unwrap_or_default().display(),
+                            e
+                        );
                     }
-                    None => TrussConfig::default(),
+                    std::process::exit(EXIT_IO);
                 }
-            } else {
-                TrussConfig::default()
             };
 
+            let cache = (cache || cache_dir.is_some()).then(|| {
+                Cache::load(
+                    cache_dir
+                        .as_deref()
+                        .unwrap_or(Path::new(cache::DEFAULT_CACHE_DIR)),
+                )
+            });
+
             let opts = ValidateOptions {
                 quiet,
-                json,
+                silent,
+                format,
                 severity_filter: severity.unwrap_or(SeverityFilter::Info),
+                fail_on: if strict || config.strict {
+                    fail_on.strict()
+                } else {
+                    fail_on
+                },
                 ignore_rules: &ignore_rules,
                 only_rules: &only_rules,
+                only_categories: &only_categories,
                 config: &config,
+                timings,
+                cache: cache.as_ref(),
+                baseline: baseline.as_ref(),
+                max_size,
+                changed_lines: changed_lines.as_ref(),
+                color: if no_color { ColorChoice::Never } else { color },
             };
 
-            if let Err(e) = validate_files(paths, &opts) {
-                if !opts.quiet && !opts.json {
+            let result = validate_files(paths, listed, &opts);
+
+            // Failing to persist the cache only costs a re-analysis next run
+            if let Some(cache) = cache {
+                if let Err(e) = cache.save() {
+                    if verbose {
+                        eprintln!("Warning: failed to write cache: {}", e);
+                    }
+                }
+            }
+
+            if let Err(e) = result {
+                if verbose {
                     eprintln!("Error: {}", e);
                 }
                 std::process::exit(e.exit_code());
             }
         }
+        Commands::Baseline {
+            paths,
+            files_from,
+            output,
+            config: config_path,
+            no_config,
+        } => {
+            let result =
+                listed_files("baseline", &paths, files_from.as_deref()).and_then(|listed| {
+                    let config = load_config(config_path, no_config, true);
+                    write_baseline(paths, listed, &output, &config)
+                });
+            if let Err(e) = result {
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+        Commands::Fix {
+            paths,
+            dry_run,
+            config: config_path,
+            no_config,
+        } => {
+            let config = load_config(config_path, no_config, true);
+            if let Err(e) = fix_files(paths, dry_run, &config) {
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+        Commands::Explain { rule_id } => {
+            if let Err(e) = explain_rule(&rule_id) {
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(config: &TrussConfig, timings: bool) -> ValidateOptions<'_> {
+        ValidateOptions {
+            quiet: true,
+            silent: true,
+            format: OutputFormat::Json,
+            severity_filter: SeverityFilter::Info,
+            fail_on: SeverityFilter::Error,
+            ignore_rules: &[],
+            only_rules: &[],
+            only_categories: &[],
+            config,
+            timings,
+            cache: None,
+            baseline: None,
+            max_size: None,
+            changed_lines: None,
+            color: ColorChoice::Never,
+        }
+    }
+
+    #[test]
+    fn timings_keep_cross_file_diagnostics() {
+        let dir = std::env::temp_dir().join(format!("truss-timings-{}", std::process::id()));
+        let workflows = dir.join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        fs::write(
+            workflows.join("build.yml"),
+            "on:\n  workflow_call:\n    inputs:\n      target:\n        type: string\n        required: true\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n",
+        )
+        .unwrap();
+        let caller = workflows.join("ci.yml");
+        let source = "on: push\njobs:\n  build:\n    uses: ./.github/workflows/build.yml\n    with:\n      targte: release\n";
+        fs::write(&caller, source).unwrap();
+
+        let config = TrussConfig::default();
+        let label = caller.to_str().unwrap();
+        let mut engine = TrussEngine::new();
+        let plain = validate_source(&mut engine, label, source, &options(&config, false)).unwrap();
+        let timed = validate_source(&mut engine, label, source, &options(&config, true)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(
+            plain
+                .diagnostics
+                .iter()
+                .any(|d| d.message.contains("'targte'")),
+            "{:?}",
+            plain.diagnostics
+        );
+        assert_eq!(
+            format!("{:?}", timed.diagnostics),
+            format!("{:?}", plain.diagnostics)
+        );
+        assert!(!timed.rule_timings.is_empty());
+    }
+
+    #[test]
+    fn fix_leaves_suppressed_lines_alone() {
+        let dir = std::env::temp_dir().join(format!("truss-fix-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ci.yml");
+        let source = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo \"::set-output name=a::1\" # truss-disable-line step_set_output\n      - run: echo \"::set-output name=b::2\"\n";
+        fs::write(&path, source).unwrap();
+
+        let path_str = path.to_str().unwrap().to_string();
+        fix_files(vec![path_str], false, &TrussConfig::default()).unwrap();
+        let fixed = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            fixed,
+            source.replace(
+                "echo \"::set-output name=b::2\"",
+                "echo \"b=2\" >> \"$GITHUB_OUTPUT\""
+            )
+        );
     }
 }
@@ -0,0 +1,152 @@
+//! JSON output for `--format json`.
+//!
+//! The per-file results are wrapped in an envelope with a run summary, so
+//! consumers can read totals without walking every diagnostic.
+
+use super::FileResult;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use truss_core::Severity;
+
+#[derive(Serialize)]
+struct Report<'a> {
+    files: &'a [&'a FileResult],
+    summary: Summary,
+}
+
+#[derive(Serialize)]
+struct Summary {
+    errors: usize,
+    warnings: usize,
+    info: usize,
+    /// Diagnostic count per rule id, sorted so the output is stable.
+    by_rule: BTreeMap<String, usize>,
+    files_scanned: usize,
+    /// Files that failed `--fail-on`/`--strict` or could not be analyzed.
+    files_failed: usize,
+    duration_ms: f64,
+}
+
+/// Render validation results as a pretty-printed `{ files, summary }` document.
+///
+/// `files_scanned` also counts files that could not be read or analyzed, which
+/// have no entry in `results`; they are counted in `files_failed`.
+pub fn render(
+    results: &[&FileResult],
+    files_scanned: usize,
+    duration_ms: f64,
+) -> serde_json::Result<String> {
+    let mut summary = Summary {
+        errors: 0,
+        warnings: 0,
+        info: 0,
+        by_rule: BTreeMap::new(),
+        files_scanned,
+        files_failed: files_scanned - results.len()
+            + results.iter().filter(|result| !result.valid).count(),
+        duration_ms,
+    };
+    for diagnostic in results.iter().flat_map(|result| &result.diagnostics) {
+        match diagnostic.severity {
+            Severity::Error => summary.errors += 1,
+            Severity::Warning => summary.warnings += 1,
+            Severity::Info => summary.info += 1,
+        }
+        // Parse errors and skipped-file notices have no rule id
+        if !diagnostic.rule_id.is_empty() {
+            *summary
+                .by_rule
+                .entry(diagnostic.rule_id.clone())
+                .or_default() += 1;
+        }
+    }
+
+    serde_json::to_string_pretty(&Report {
+        files: results,
+        summary,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::FileMetadata;
+    use truss_core::{Diagnostic, RuleCategory, Span};
+
+    fn diagnostic(rule_id: &str, severity: Severity) -> Diagnostic {
+        Diagnostic {
+            message: "message".to_string(),
+            severity,
+            span: Span { start: 0, end: 1 },
+            rule_id: rule_id.to_string(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        }
+    }
+
+    fn file_result(file: &str, diagnostics: Vec<Diagnostic>) -> FileResult {
+        FileResult {
+            file: file.to_string(),
+            valid: true,
+            diagnostics,
+            duration_ms: 1.0,
+            metadata: FileMetadata {
+                file_size: 10,
+                lines: 1,
+            },
+            rule_timings: Vec::new(),
+            suppressed: 0,
+        }
+    }
+
+    #[test]
+    fn summary_counts_by_severity_and_rule() {
+        let ci = file_result(
+            "ci.yml",
+            vec![
+                diagnostic("timeout", Severity::Error),
+                diagnostic("step_name", Severity::Warning),
+                diagnostic("", Severity::Error),
+            ],
+        );
+        let mut release = file_result(
+            "release.yml",
+            vec![
+                diagnostic("step_name", Severity::Warning),
+                diagnostic("action_pinning", Severity::Info),
+            ],
+        );
+        release.valid = false;
+        let json = render(&[&ci, &release], 3, 12.5).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["files"].as_array().unwrap().len(), 2);
+        assert_eq!(value["files"][0]["file"], "ci.yml");
+        let summary = &value["summary"];
+        assert_eq!(summary["errors"], 2);
+        assert_eq!(summary["warnings"], 2);
+        assert_eq!(summary["info"], 1);
+        assert_eq!(summary["files_scanned"], 3);
+        // release.yml failed, and the third file was never analyzed
+        assert_eq!(summary["files_failed"], 2);
+        assert_eq!(summary["duration_ms"], 12.5);
+        assert_eq!(
+            summary["by_rule"],
+            serde_json::json!({ "action_pinning": 1, "step_name": 2, "timeout": 1 })
+        );
+        // Sorted by rule id
+        let by_rule = &json[json.find("\"by_rule\"").unwrap()..];
+        assert!(by_rule.find("\"action_pinning\"") < by_rule.find("\"timeout\""));
+    }
+
+    #[test]
+    fn empty_run_has_zeroed_summary() {
+        let json = render(&[], 0, 0.0).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["files"], serde_json::json!([]));
+        assert_eq!(value["summary"]["errors"], 0);
+        assert_eq!(value["summary"]["files_failed"], 0);
+        assert_eq!(value["summary"]["by_rule"], serde_json::json!({}));
+    }
+}
@@ -0,0 +1,210 @@
+//! JUnit XML output for CI systems that render test reports.
+//!
+//! Each validated file becomes a `<testsuite>` and each diagnostic a
+//! `<testcase>` whose classname is the rule id. Errors are reported as
+//! `<failure>`, warnings and info as `<skipped>`. A file without diagnostics
+//! gets a single passing testcase so it still shows up in the report.
+
+use super::FileResult;
+use std::fmt::Write;
+use truss_core::{Diagnostic, Severity};
+
+/// Render validation results as a JUnit XML `<testsuites>` document.
+pub fn render(results: &[&FileResult]) -> String {
+    let mut total_tests = 0;
+    let mut total_failures = 0;
+    let mut total_skipped = 0;
+    let mut total_ms = 0.0;
+    let mut suites = String::new();
+
+    for result in results {
+        let failures = count(&result.diagnostics, |s| s == Severity::Error);
+        let skipped = result.diagnostics.len() - failures;
+        let tests = result.diagnostics.len().max(1);
+        total_tests += tests;
+        total_failures += failures;
+        total_skipped += skipped;
+        total_ms += result.duration_ms;
+
+        let _ = writeln!(
+            suites,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+            escape(&result.file),
+            tests,
+            failures,
+            skipped,
+            result.duration_ms / 1000.0
+        );
+        if result.diagnostics.is_empty() {
+            let _ = writeln!(
+                suites,
+                "    <testcase name=\"{}\" classname=\"truss\"/>",
+                escape(&result.file)
+            );
+        }
+        for diagnostic in &result.diagnostics {
+            write_testcase(&mut suites, &result.file, diagnostic);
+        }
+        suites.push_str("  </testsuite>\n");
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"truss\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">",
+        total_tests,
+        total_failures,
+        total_skipped,
+        total_ms / 1000.0
+    );
+    xml.push_str(&suites);
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_testcase(out: &mut String, file: &str, diagnostic: &Diagnostic) {
+    let classname = if diagnostic.rule_id.is_empty() {
+        "truss"
+    } else {
+        &diagnostic.rule_id
+    };
+    let message = format!(
+        "{} ({}..{})",
+        diagnostic.message, diagnostic.span.start, diagnostic.span.end
+    );
+
+    let _ = writeln!(
+        out,
+        "    <testcase name=\"{}\" classname=\"{}\" file=\"{}\">",
+        escape(&diagnostic.message),
+        escape(classname),
+        escape(file)
+    );
+    match diagnostic.severity {
+        Severity::Error => {
+            let _ = writeln!(
+                out,
+                "      <failure message=\"{}\" type=\"error\"/>",
+                escape(&message)
+            );
+        }
+        Severity::Warning | Severity::Info => {
+            let level = if diagnostic.severity == Severity::Warning {
+                "warning"
+            } else {
+                "info"
+            };
+            let _ = writeln!(
+                out,
+                "      <skipped message=\"[{}] {}\"/>",
+                level,
+                escape(&message)
+            );
+        }
+    }
+    out.push_str("    </testcase>\n");
+}
+
+fn count(diagnostics: &[Diagnostic], pred: impl Fn(Severity) -> bool) -> usize {
+    diagnostics.iter().filter(|d| pred(d.severity)).count()
+}
+
+/// Escape text for use in an XML attribute.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::FileMetadata;
+    use truss_core::{RuleCategory, Span};
+
+    fn diagnostic(rule_id: &str, severity: Severity, message: &str) -> Diagnostic {
+        Diagnostic {
+            message: message.to_string(),
+            severity,
+            span: Span { start: 10, end: 20 },
+            rule_id: rule_id.to_string(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        }
+    }
+
+    fn file_result(file: &str, diagnostics: Vec<Diagnostic>) -> FileResult {
+        FileResult {
+            file: file.to_string(),
+            valid: !diagnostics.iter().any(|d| d.severity == Severity::Error),
+            diagnostics,
+            duration_ms: 1.5,
+            metadata: FileMetadata {
+                file_size: 100,
+                lines: 5,
+            },
+            rule_timings: Vec::new(),
+            suppressed: 0,
+        }
+    }
+
+    #[test]
+    fn renders_errors_as_failures_and_warnings_as_skipped() {
+        let result = file_result(
+            "ci.yml",
+            vec![
+                diagnostic(
+                    "timeout",
+                    Severity::Error,
+                    "timeout-minutes must be positive",
+                ),
+                diagnostic("step_name", Severity::Warning, "Step has no name"),
+            ],
+        );
+        let xml = render(&[&result]);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains(
+            "<testsuites name=\"truss\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"1\""
+        ));
+        assert!(xml.contains("<testsuite name=\"ci.yml\" tests=\"2\" failures=\"1\""));
+        assert!(xml.contains("classname=\"timeout\""));
+        assert!(xml.contains(
+            "<failure message=\"timeout-minutes must be positive (10..20)\" type=\"error\"/>"
+        ));
+        assert!(xml.contains("<skipped message=\"[warning] Step has no name (10..20)\"/>"));
+        assert!(xml.trim_end().ends_with("</testsuites>"));
+    }
+
+    #[test]
+    fn valid_file_gets_a_passing_testcase() {
+        let result = file_result("ok.yml", Vec::new());
+        let xml = render(&[&result]);
+
+        assert!(xml.contains("<testsuite name=\"ok.yml\" tests=\"1\" failures=\"0\""));
+        assert!(xml.contains("<testcase name=\"ok.yml\" classname=\"truss\"/>"));
+    }
+
+    #[test]
+    fn escapes_xml_special_characters() {
+        let result = file_result(
+            "a&b.yml",
+            vec![diagnostic("", Severity::Error, "expected '<' or \"x\"")],
+        );
+        let xml = render(&[&result]);
+
+        assert!(xml.contains("name=\"a&amp;b.yml\""));
+        assert!(xml.contains("expected &apos;&lt;&apos; or &quot;x&quot;"));
+        assert!(xml.contains("classname=\"truss\""));
+    }
+}
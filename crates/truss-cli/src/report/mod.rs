@@ -0,0 +1,26 @@
+//! Per-file validation results and the report formats built from them.
+
+pub mod json;
+pub mod junit;
+pub mod ndjson;
+
+#[derive(serde::Serialize)]
+pub struct FileResult {
+    pub file: String,
+    pub valid: bool,
+    pub diagnostics: Vec<truss_core::Diagnostic>,
+    pub duration_ms: f64,
+    pub metadata: FileMetadata,
+    /// Per-rule wall time, only collected with `--timings`.
+    #[serde(skip)]
+    pub rule_timings: Vec<(String, std::time::Duration)>,
+    /// Diagnostics hidden because `--baseline` already lists them.
+    #[serde(skip)]
+    pub suppressed: usize,
+}
+
+#[derive(serde::Serialize)]
+pub struct FileMetadata {
+    pub file_size: u64,
+    pub lines: usize,
+}
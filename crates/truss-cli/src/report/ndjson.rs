@@ -0,0 +1,88 @@
+//! Newline-delimited JSON output for `--format ndjson`.
+//!
+//! Each analyzed file becomes one compact JSON object on its own line, written
+//! as soon as the file finishes. Files are analyzed in parallel, so workers
+//! send finished lines over a channel to a single writer, which keeps lines
+//! whole; their order follows completion, not the command line.
+
+use super::FileResult;
+use std::io::{self, Write};
+use std::sync::mpsc::Receiver;
+
+/// One file's result as a single line of JSON, without the trailing newline.
+pub fn line(result: &FileResult) -> serde_json::Result<String> {
+    serde_json::to_string(result)
+}
+
+/// Write every line received to `out`, flushing after each so consumers see
+/// results while the run is still going. Returns once all senders are gone.
+pub fn write_lines(lines: Receiver<String>, mut out: impl Write) -> io::Result<()> {
+    for line in lines {
+        writeln!(out, "{}", line)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::FileMetadata;
+    use std::sync::mpsc;
+    use std::thread;
+    use truss_core::{Diagnostic, RuleCategory, Severity, Span};
+
+    fn file_result(file: &str) -> FileResult {
+        FileResult {
+            file: file.to_string(),
+            valid: false,
+            diagnostics: vec![Diagnostic {
+                message: "Job 'build' is missing runs-on\nsee docs".to_string(),
+                severity: Severity::Error,
+                span: Span { start: 3, end: 8 },
+                rule_id: "runs_on_required".to_string(),
+                category: RuleCategory::Schema,
+                related: Vec::new(),
+            }],
+            duration_ms: 1.0,
+            metadata: FileMetadata {
+                file_size: 10,
+                lines: 1,
+            },
+            rule_timings: Vec::new(),
+            suppressed: 0,
+        }
+    }
+
+    #[test]
+    fn lines_from_parallel_senders_stay_whole() {
+        let (sender, receiver) = mpsc::channel();
+        let workers: Vec<_> = (0..4)
+            .map(|worker| {
+                let sender = sender.clone();
+                thread::spawn(move || {
+                    for i in 0..25 {
+                        let result = file_result(&format!("w{}-{}.yml", worker, i));
+                        sender.send(line(&result).unwrap()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        drop(sender);
+
+        let mut out = Vec::new();
+        write_lines(receiver, &mut out).unwrap();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let out = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 100);
+        for line in lines {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(value["valid"], false);
+            assert_eq!(value["diagnostics"][0]["rule_id"], "runs_on_required");
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! ANSI colors for the human-readable output of `truss validate`.
+//!
+//! `Diagnostic`'s `Display` stays plain so JSON, JUnit, and library users never
+//! see escape codes; color is only applied here, at print time. Diagnostic
+//! lines are rendered by `truss_core::render`, so other tools color them the
+//! same way.
+
+use std::io::{self, IsTerminal};
+use truss_core::render::{self, RenderOptions};
+use truss_core::Diagnostic;
+
+const GREEN: &str = "32";
+
+/// When to color output, set with `--color`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and `NO_COLOR` is unset (default)
+    Auto,
+    /// Always color, even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Colors for one output stream.
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    enabled: bool,
+}
+
+impl Style {
+    /// Style for text written to stdout.
+    pub fn stdout(choice: ColorChoice) -> Self {
+        Self::new(choice, io::stdout().is_terminal())
+    }
+
+    /// Style for text written to stderr.
+    pub fn stderr(choice: ColorChoice) -> Self {
+        Self::new(choice, io::stderr().is_terminal())
+    }
+
+    fn new(choice: ColorChoice, is_terminal: bool) -> Self {
+        let enabled = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                is_terminal && !no_color
+            }
+        };
+        Self { enabled }
+    }
+
+    /// A diagnostic line: red for errors, yellow for warnings, blue for info.
+    pub fn diagnostic(self, diagnostic: &Diagnostic) -> String {
+        render::diagnostic(
+            diagnostic,
+            &RenderOptions {
+                color: self.enabled,
+            },
+        )
+    }
+
+    /// Text reporting success, in green.
+    pub fn success(self, text: &str) -> String {
+        self.paint(GREEN, text)
+    }
+
+    fn paint(self, color: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", color, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truss_core::{RuleCategory, Severity, Span};
+
+    fn diagnostic(severity: Severity) -> Diagnostic {
+        Diagnostic {
+            message: "Job 'build' is missing runs-on".to_string(),
+            severity,
+            span: Span { start: 3, end: 8 },
+            rule_id: "runs_on_required".to_string(),
+            category: RuleCategory::Schema,
+            related: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn colors_diagnostics_by_severity() {
+        let style = Style::new(ColorChoice::Always, false);
+        let error = diagnostic(Severity::Error);
+        assert_eq!(
+            style.diagnostic(&error),
+            format!("\x1b[31m{}\x1b[0m", error)
+        );
+        assert!(style
+            .diagnostic(&diagnostic(Severity::Warning))
+            .starts_with("\x1b[33m[Warning]"));
+        assert!(style
+            .diagnostic(&diagnostic(Severity::Info))
+            .starts_with("\x1b[34m[Info]"));
+        assert_eq!(style.success("✓ Valid:"), "\x1b[32m✓ Valid:\x1b[0m");
+    }
+
+    #[test]
+    fn never_and_non_terminal_auto_stay_plain() {
+        let error = diagnostic(Severity::Error);
+        for style in [
+            Style::new(ColorChoice::Never, true),
+            Style::new(ColorChoice::Auto, false),
+        ] {
+            assert_eq!(style.diagnostic(&error), error.to_string());
+            assert_eq!(style.success("✓ Valid:"), "✓ Valid:");
+        }
+    }
+}
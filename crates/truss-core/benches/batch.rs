@@ -0,0 +1,65 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use rayon::prelude::*;
+use truss_core::TrussEngine;
+
+const FILE_COUNT: usize = 200;
+
+/// Small workflows that differ slightly, like a repo's `.github/workflows/`.
+fn synthetic_workflows() -> Vec<String> {
+    (0..FILE_COUNT)
+        .map(|i| {
+            format!(
+                r#"name: Workflow {i}
+on:
+  push:
+    branches: [main]
+  pull_request:
+jobs:
+  build-{i}:
+    runs-on: ubuntu-latest
+    timeout-minutes: 15
+    steps:
+      - uses: actions/checkout@v4
+      - id: setup
+        run: echo "version={i}" >> "$GITHUB_OUTPUT"
+      - run: cargo test --all
+        env:
+          VERSION: ${{{{ steps.setup.outputs.version }}}}
+"#
+            )
+        })
+        .collect()
+}
+
+fn batch_fresh_engine(c: &mut Criterion) {
+    let workflows = synthetic_workflows();
+
+    c.bench_function("batch_200_fresh_engine", |b| {
+        b.iter(|| {
+            workflows
+                .par_iter()
+                .map(|source| TrussEngine::new().analyze(source).diagnostics.len())
+                .sum::<usize>()
+        })
+    });
+}
+
+fn batch_cloned_engine(c: &mut Criterion) {
+    let workflows = synthetic_workflows();
+
+    c.bench_function("batch_200_cloned_engine", |b| {
+        b.iter(|| {
+            let engine = TrussEngine::new();
+            workflows
+                .par_iter()
+                .map_init(
+                    || engine.clone(),
+                    |engine, source| engine.analyze(source).diagnostics.len(),
+                )
+                .sum::<usize>()
+        })
+    });
+}
+
+criterion_group!(benches, batch_fresh_engine, batch_cloned_engine);
+criterion_main!(benches);
@@ -9,14 +9,40 @@
 //! rules:
 //!   timeout:
 //!     enabled: false
-//!   script-injection:
+//!   script_injection:
 //!     severity: error
 //!
 //! ignore:
 //!   - "vendor/**"
 //!   - ".github/workflows/generated-*.yml"
+//!
+//! allowed_environments:
+//!   - staging
+//!   - production
+//!
+//! max_file_size_bytes: 10485760
+//!
+//! max_matrix_combinations: 64
+//!
+//! strict: true
+//!
+//! overrides:
+//!   - files: "vendor/**"
+//!     only_rules: [syntax, non_empty]
+//!   - files: ".github/workflows/release-*.yml"
+//!     ignore_rules: [deploy_job_timeout]
+//!     severity:
+//!       action_pinning: error
 //! ```
+//!
+//! # Precedence
+//!
+//! For a given file, the top-level `rules:` apply first, then every entry in
+//! `overrides` whose `files` glob matches the path, in file order, so later
+//! entries win. Within one entry, `only_rules` is applied before
+//! `ignore_rules`, so a rule listed in both is disabled.
 
+use crate::{AnalysisOptions, Severity};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -30,6 +56,52 @@ pub struct TrussConfig {
 
     /// File glob patterns to ignore during validation.
     pub ignore: Vec<String>,
+
+    /// Deployment environment names jobs may use in `environment:`.
+    /// Empty (the default) allows any name.
+    pub allowed_environments: Vec<String>,
+
+    /// Rule settings that only apply to files matching a glob.
+    pub overrides: Vec<ConfigOverride>,
+
+    /// Files larger than this are skipped instead of analyzed.
+    /// Defaults to `DEFAULT_MAX_FILE_SIZE_BYTES`.
+    pub max_file_size_bytes: Option<usize>,
+
+    /// Matrices that expand to more jobs than this are errors. GitHub's own
+    /// limit of 256 always applies, so only lower values have an effect.
+    pub max_matrix_combinations: Option<usize>,
+
+    /// Fail validation on warnings as well as errors, like `truss validate --strict`.
+    pub strict: bool,
+}
+
+/// Size limit used when `max_file_size_bytes` isn't set. Real workflows are
+/// a few kilobytes; anything this large is almost certainly generated.
+pub const DEFAULT_MAX_FILE_SIZE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Rule settings for the files matching `files`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfigOverride {
+    /// Glob pattern matched against the file path, like `ignore` patterns.
+    pub files: String,
+
+    /// Rules to disable for matching files.
+    pub ignore_rules: Vec<String>,
+
+    /// If non-empty, only these rules run for matching files.
+    pub only_rules: Vec<String>,
+
+    /// Rule id → severity ("error", "warning", "info") for matching files.
+    pub severity: HashMap<String, String>,
+}
+
+impl ConfigOverride {
+    /// Whether this override applies to the given file path.
+    pub fn matches(&self, path: &str) -> bool {
+        glob_matches(&self.files, path)
+    }
 }
 
 /// Configuration for an individual rule.
@@ -90,14 +162,9 @@ impl TrussConfig {
 
     /// Check if a file path should be ignored based on the `ignore` patterns.
     pub fn is_ignored(&self, path: &str) -> bool {
-        for pattern in &self.ignore {
-            if let Ok(glob_pattern) = glob::Pattern::new(pattern) {
-                if glob_pattern.matches(path) {
-                    return true;
-                }
-            }
-        }
-        false
+        self.ignore
+            .iter()
+            .any(|pattern| glob_matches(pattern, path))
     }
 
     /// Check if a rule is enabled.
@@ -108,12 +175,101 @@ impl TrussConfig {
         }
     }
 
+    /// Check if a rule is enabled for a file, applying matching `overrides`.
+    pub fn is_rule_enabled_for(&self, rule_name: &str, path: &str) -> bool {
+        let mut enabled = self.is_rule_enabled(rule_name);
+        for overrides in self.overrides.iter().filter(|o| o.matches(path)) {
+            if !overrides.only_rules.is_empty() {
+                enabled = overrides.only_rules.iter().any(|r| r == rule_name);
+            }
+            if overrides.ignore_rules.iter().any(|r| r == rule_name) {
+                enabled = false;
+            }
+        }
+        enabled
+    }
+
     /// Get the severity override for a rule, if any.
     pub fn rule_severity(&self, rule_name: &str) -> Option<&str> {
         self.rules
             .get(rule_name)
             .and_then(|c| c.severity.as_deref())
     }
+
+    /// Collect the severity overrides as a rule id → `Severity` map.
+    ///
+    /// Suitable for `TrussEngine::analyze_with_config`. Values other than
+    /// "error", "warning" and "info" are skipped.
+    pub fn severity_overrides(&self) -> HashMap<String, Severity> {
+        self.rules
+            .iter()
+            .filter_map(|(rule, config)| {
+                Some((rule.clone(), parse_severity(config.severity.as_deref()?)?))
+            })
+            .collect()
+    }
+
+    /// Like `severity_overrides`, with matching `overrides` applied on top.
+    pub fn severity_overrides_for(&self, path: &str) -> HashMap<String, Severity> {
+        let mut severities = self.severity_overrides();
+        for overrides in self.overrides.iter().filter(|o| o.matches(path)) {
+            for (rule, severity) in &overrides.severity {
+                if let Some(severity) = parse_severity(severity) {
+                    severities.insert(rule.clone(), severity);
+                }
+            }
+        }
+        severities
+    }
+
+    /// Build the options for `TrussEngine::analyze_with_options`.
+    pub fn analysis_options(&self) -> AnalysisOptions {
+        AnalysisOptions {
+            severity_overrides: self.severity_overrides(),
+            allowed_environments: self.allowed_environments.clone(),
+            max_file_size_bytes: Some(
+                self.max_file_size_bytes
+                    .unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES),
+            ),
+            max_matrix_combinations: self.max_matrix_combinations,
+        }
+    }
+
+    /// Like `analysis_options`, with matching `overrides` applied for a file.
+    pub fn analysis_options_for(&self, path: &str) -> AnalysisOptions {
+        AnalysisOptions {
+            severity_overrides: self.severity_overrides_for(path),
+            allowed_environments: self.allowed_environments.clone(),
+            max_file_size_bytes: Some(
+                self.max_file_size_bytes
+                    .unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES),
+            ),
+            max_matrix_combinations: self.max_matrix_combinations,
+        }
+    }
+}
+
+fn parse_severity(value: &str) -> Option<Severity> {
+    match value.to_ascii_lowercase().as_str() {
+        "error" => Some(Severity::Error),
+        "warning" => Some(Severity::Warning),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+/// Match a config glob against a file path. Invalid patterns never match.
+///
+/// A leading `./` on the path is ignored, so `vendor/**` also matches
+/// `./vendor/a.yml` as passed on the command line.
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let Ok(glob_pattern) = glob::Pattern::new(pattern) else {
+        return false;
+    };
+    glob_pattern.matches(path)
+        || path
+            .strip_prefix("./")
+            .is_some_and(|p| glob_pattern.matches(p))
 }
 
 /// Errors that can occur when loading configuration.
@@ -169,6 +325,14 @@ rules:
         assert_eq!(config.rule_severity("script-injection"), Some("error"));
     }
 
+    #[test]
+    fn strict_defaults_to_off() {
+        let config: TrussConfig = serde_yaml::from_str("ignore: []").unwrap();
+        assert!(!config.strict);
+        let config: TrussConfig = serde_yaml::from_str("strict: true").unwrap();
+        assert!(config.strict);
+    }
+
     #[test]
     fn parse_ignore_patterns() {
         let yaml = r#"
@@ -180,6 +344,105 @@ ignore:
         assert_eq!(config.ignore.len(), 2);
     }
 
+    #[test]
+    fn severity_overrides_skip_invalid_values() {
+        let yaml = r#"
+rules:
+  step_name:
+    severity: info
+  timeout:
+    severity: Error
+  job_name:
+    severity: fatal
+  secrets:
+    enabled: false
+"#;
+        let config: TrussConfig = serde_yaml::from_str(yaml).unwrap();
+        let overrides = config.severity_overrides();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides.get("step_name"), Some(&Severity::Info));
+        assert_eq!(overrides.get("timeout"), Some(&Severity::Error));
+    }
+
+    #[test]
+    fn allowed_environments_reach_analysis_options() {
+        let yaml = r#"
+allowed_environments:
+  - staging
+  - production
+rules:
+  environment:
+    severity: error
+"#;
+        let config: TrussConfig = serde_yaml::from_str(yaml).unwrap();
+        let options = config.analysis_options();
+        assert_eq!(options.allowed_environments, ["staging", "production"]);
+        assert_eq!(
+            options.severity_overrides.get("environment"),
+            Some(&Severity::Error)
+        );
+    }
+
+    #[test]
+    fn max_file_size_defaults_and_overrides() {
+        let config: TrussConfig = serde_yaml::from_str("ignore: []\n").unwrap();
+        assert_eq!(
+            config.analysis_options().max_file_size_bytes,
+            Some(DEFAULT_MAX_FILE_SIZE_BYTES)
+        );
+
+        let config: TrussConfig = serde_yaml::from_str("max_file_size_bytes: 1024\n").unwrap();
+        assert_eq!(
+            config.analysis_options_for("ci.yml").max_file_size_bytes,
+            Some(1024)
+        );
+    }
+
+    #[test]
+    fn max_matrix_combinations_reaches_analysis_options() {
+        let config: TrussConfig = serde_yaml::from_str("ignore: []\n").unwrap();
+        assert_eq!(config.analysis_options().max_matrix_combinations, None);
+
+        let config: TrussConfig = serde_yaml::from_str("max_matrix_combinations: 64\n").unwrap();
+        assert_eq!(
+            config
+                .analysis_options_for("ci.yml")
+                .max_matrix_combinations,
+            Some(64)
+        );
+    }
+
+    #[test]
+    fn overrides_apply_to_matching_paths_in_order() {
+        let yaml = r#"
+rules:
+  step_name:
+    severity: info
+overrides:
+  - files: "vendor/**"
+    only_rules: [syntax, step_name]
+    severity:
+      step_name: warning
+  - files: "vendor/legacy/*.yml"
+    ignore_rules: [step_name]
+    severity:
+      syntax: info
+"#;
+        let config: TrussConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert!(config.is_rule_enabled_for("timeout", ".github/workflows/ci.yml"));
+        assert!(!config.is_rule_enabled_for("timeout", "vendor/a.yml"));
+        assert!(config.is_rule_enabled_for("step_name", "./vendor/a.yml"));
+        assert!(!config.is_rule_enabled_for("step_name", "vendor/legacy/b.yml"));
+        assert!(config.is_rule_enabled_for("syntax", "vendor/legacy/b.yml"));
+
+        let ci = config.severity_overrides_for(".github/workflows/ci.yml");
+        assert_eq!(ci.get("step_name"), Some(&Severity::Info));
+        let legacy = config.severity_overrides_for("vendor/legacy/b.yml");
+        assert_eq!(legacy.get("step_name"), Some(&Severity::Warning));
+        assert_eq!(legacy.get("syntax"), Some(&Severity::Info));
+    }
+
     #[test]
     fn unknown_rule_is_enabled() {
         let config = TrussConfig::default();
@@ -4,31 +4,45 @@
 //! This crate is editor-agnostic and fully deterministic.
 
 pub mod config;
+mod line_index;
 mod parser;
+pub mod render;
 mod validation;
 
-pub use validation::ValidationRule;
+pub use line_index::LineIndex;
+pub use validation::{FileKind, IndexedJob, RuleExplanation, ValidationRule, WorkflowIndex};
 
 use parser::{ParseError, YamlParser};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use validation::{
-    ActionReferenceRule, ArtifactValidationRule, ConcurrencyRule, DefaultsValidationRule,
-    DeprecatedCommandsRule, EnvironmentRule, EventPayloadValidationRule, ExpressionValidationRule,
-    GitHubActionsSchemaRule, JobContainerRule, JobIfExpressionRule, JobNameRule, JobNeedsRule,
-    JobOutputsRule, JobStrategyValidationRule, MatrixStrategyRule, NonEmptyRule, PermissionsRule,
-    ReusableWorkflowCallRule, RuleSet, RunnerLabelRule, RunsOnRequiredRule, ScriptInjectionRule,
-    SecretsValidationRule, StepContinueOnErrorRule, StepEnvValidationRule, StepIdUniquenessRule,
-    StepIfExpressionRule, StepNameRule, StepOutputReferenceRule, StepShellRule, StepTimeoutRule,
+    ActionBrandingRule, ActionInputNameRule, ActionPinningRule, ActionReferenceRule,
+    ActionRunsUsingRule, ArtifactValidationRule, CompositeActionOutputsRule, CompositeActionRule,
+    ConcurrencyRule, DefaultsValidationRule, DeployJobTimeoutRule, DeprecatedActionRule,
+    DeprecatedCommandsRule, DuplicateKeyRule, EnvironmentRule, EventPayloadValidationRule,
+    ExpressionValidationRule, FilterPatternRule, GitHubActionsSchemaRule, JobContainerRule,
+    JobIfExpressionRule, JobNameRule, JobNeedsRule, JobOutputsRule, JobStrategyValidationRule,
+    MatrixReferenceRule, MatrixStrategyRule, MissingCheckoutRule, NeedsOutputReferenceRule,
+    NonEmptyRule, ParallelJobsRule, PermissionsRule, PullRequestTargetRule,
+    ReusableWorkflowCallRule, RuleSet, RunBlockScalarRule, RunnerLabelRule, RunsOnRequiredRule,
+    ScheduledReusableWorkflowRule, ScriptInjectionRule, SecretsValidationRule,
+    StepContinueOnErrorRule, StepEnvValidationRule, StepIdUniquenessRule, StepIfExpressionRule,
+    StepNameRule, StepOutputReferenceRule, StepSetOutputRule, StepShellRule, StepTimeoutRule,
     StepValidationRule, StepWorkingDirectoryRule, SyntaxRule, TimeoutRule, WorkflowCallInputsRule,
-    WorkflowCallOutputsRule, WorkflowCallSecretsRule, WorkflowInputsRule, WorkflowNameRule,
-    WorkflowTriggerRule,
+    WorkflowCallOutputsRule, WorkflowCallSecretsRule, WorkflowDispatchInputMaxRule,
+    WorkflowInputsRule, WorkflowNameRule, WorkflowTriggerRule,
 };
 
 /// Entry point for the Truss validation engine.
+///
+/// Cloning an engine shares its rules and gives the clone its own parser,
+/// so one engine per worker thread can be made from a single `new()`.
 pub struct TrussEngine {
     parser: YamlParser,
-    rules: RuleSet,
+    rules: Arc<RuleSet>,
 }
 
 impl TrussEngine {
@@ -37,53 +51,24 @@ impl TrussEngine {
     /// The engine maintains parser state for incremental parsing,
     /// but validation rules are stateless and reusable.
     pub fn new() -> Self {
-        let mut rules = RuleSet::new();
-        rules.add_rule(SyntaxRule);
-        rules.add_rule(NonEmptyRule);
-        rules.add_rule(GitHubActionsSchemaRule);
-        rules.add_rule(WorkflowTriggerRule);
-        rules.add_rule(JobNameRule);
-        rules.add_rule(JobNeedsRule);
-        rules.add_rule(StepValidationRule);
-        rules.add_rule(ExpressionValidationRule);
-        rules.add_rule(PermissionsRule);
-        rules.add_rule(EnvironmentRule);
-        rules.add_rule(WorkflowNameRule);
-        rules.add_rule(MatrixStrategyRule);
-        rules.add_rule(RunsOnRequiredRule);
-        rules.add_rule(SecretsValidationRule);
-        rules.add_rule(TimeoutRule);
-        rules.add_rule(WorkflowInputsRule);
-        rules.add_rule(JobOutputsRule);
-        rules.add_rule(ConcurrencyRule);
-        rules.add_rule(ActionReferenceRule);
-        rules.add_rule(StepIdUniquenessRule);
-        rules.add_rule(StepOutputReferenceRule);
-        rules.add_rule(JobStrategyValidationRule);
-        rules.add_rule(StepIfExpressionRule);
-        rules.add_rule(JobIfExpressionRule);
-        rules.add_rule(WorkflowCallInputsRule);
-        rules.add_rule(WorkflowCallSecretsRule);
-        rules.add_rule(ReusableWorkflowCallRule);
-        rules.add_rule(WorkflowCallOutputsRule);
-        rules.add_rule(StepContinueOnErrorRule);
-        rules.add_rule(StepTimeoutRule);
-        rules.add_rule(StepShellRule);
-        rules.add_rule(StepWorkingDirectoryRule);
-        rules.add_rule(ArtifactValidationRule);
-        rules.add_rule(EventPayloadValidationRule);
-        rules.add_rule(RunnerLabelRule);
-        rules.add_rule(StepEnvValidationRule);
-        rules.add_rule(JobContainerRule);
-        rules.add_rule(StepNameRule);
-        rules.add_rule(DefaultsValidationRule);
-        rules.add_rule(DeprecatedCommandsRule);
-        rules.add_rule(ScriptInjectionRule);
+        Self::builder().with_defaults().build()
+    }
 
-        Self {
-            parser: YamlParser::new(),
-            rules,
-        }
+    /// Start building an engine with no rules registered.
+    ///
+    /// ```
+    /// use truss_core::{RuleCategory, TrussEngine};
+    ///
+    /// // Only the cheap syntax and schema checks
+    /// let engine = TrussEngine::builder()
+    ///     .with_category(RuleCategory::Syntax)
+    ///     .with_category(RuleCategory::Schema)
+    ///     .build();
+    /// assert!(engine.rule("syntax").is_some());
+    /// assert!(engine.rule("script_injection").is_none());
+    /// ```
+    pub fn builder() -> TrussEngineBuilder {
+        TrussEngineBuilder::default()
     }
 
     /// Analyze a YAML document and return diagnostics.
@@ -102,6 +87,234 @@ impl TrussEngine {
         self.rules.validate_parallel(&tree, source)
     }
 
+    /// Analyze a YAML document and apply per-rule severity overrides.
+    ///
+    /// `severity_overrides` maps rule ids to the severity their diagnostics
+    /// should be reported at. Overrides for unknown rule ids are ignored.
+    /// Since `is_ok()` only looks at errors, downgrading a rule's errors
+    /// makes the result pass unless another rule also reported an error.
+    pub fn analyze_with_config(
+        &mut self,
+        source: &str,
+        severity_overrides: &HashMap<String, Severity>,
+    ) -> TrussResult {
+        let mut result = self.analyze(source);
+        result.apply_severity_overrides(severity_overrides);
+        result
+    }
+
+    /// Analyze a YAML document with the settings loaded from `.truss.yml`.
+    ///
+    /// Like `analyze_with_config`, and additionally warns on job environments
+    /// missing from `options.allowed_environments` when that list is non-empty,
+    /// and errors on matrices larger than `options.max_matrix_combinations`.
+    /// Sources over `options.max_file_size_bytes` are not parsed.
+    pub fn analyze_with_options(&mut self, source: &str, options: &AnalysisOptions) -> TrussResult {
+        if let Some(result) = Self::size_limit_result(source, options) {
+            return result;
+        }
+        let tree = match self.parser.parse(source) {
+            Ok(tree) => tree,
+            Err(_) => return Self::parse_error_result(source),
+        };
+
+        let result = self.rules.validate_parallel(&tree, source);
+        Self::apply_options(result, &tree, source, options)
+    }
+
+    /// `analyze_with_options`, plus checks that need to read other files.
+    ///
+    /// `resolve` maps the path in a local `uses: ./.github/workflows/x.yml`
+    /// call, as written, to that file's contents, or returns `None` when it
+    /// can't. Resolved workflows are parsed so the caller's `with:` and
+    /// `secrets:` can be checked against their `workflow_call` inputs and
+    /// secrets: required ones must be passed (or, for secrets, inherited),
+    /// unknown inputs are errors, and unknown secrets are warnings. The
+    /// engine itself never touches the filesystem, so this is opt-in;
+    /// `analyze` skips these checks.
+    pub fn analyze_with_resolver(
+        &mut self,
+        source: &str,
+        options: &AnalysisOptions,
+        resolve: impl Fn(&str) -> Option<String>,
+    ) -> TrussResult {
+        if let Some(result) = Self::size_limit_result(source, options) {
+            return result;
+        }
+        let tree = match self.parser.parse(source) {
+            Ok(tree) => tree,
+            Err(_) => return Self::parse_error_result(source),
+        };
+
+        let mut result = self.rules.validate_parallel(&tree, source);
+        self.check_called_workflows(&mut result, &tree, source, &resolve);
+        Self::apply_options(result, &tree, source, options)
+    }
+
+    /// The cross-file checks of `analyze_with_resolver`, added to `result`.
+    fn check_called_workflows(
+        &mut self,
+        result: &mut TrussResult,
+        tree: &tree_sitter::Tree,
+        source: &str,
+        resolve: &dyn Fn(&str) -> Option<String>,
+    ) {
+        // Engines built without the rule shouldn't get its cross-file checks
+        if self.rules.find("reusable_workflow_call").is_none() {
+            return;
+        }
+        result.diagnostics.extend(
+            validation::rules::reusable_workflow_call::check_called_workflows(
+                tree,
+                source,
+                &mut self.parser,
+                resolve,
+            ),
+        );
+        result
+            .diagnostics
+            .sort_by_key(|d| (d.span.start, d.severity));
+    }
+
+    /// Run a single rule on a YAML document.
+    ///
+    /// Returns `None` if no rule with id `rule_id` is registered. Only that
+    /// rule's diagnostics are returned, so tests and library users can check
+    /// a rule in isolation instead of filtering the full analysis. If the
+    /// document can't be parsed at all, the generic parse error is returned.
+    ///
+    /// ```
+    /// use truss_core::TrussEngine;
+    ///
+    /// let mut engine = TrussEngine::new();
+    /// let source = "on: push\njobs:\n  1-build:\n    runs-on: ubuntu-latest\n";
+    /// let diagnostics = engine.analyze_rule(source, "job_name").unwrap();
+    /// assert!(diagnostics.iter().all(|d| d.rule_id == "job_name"));
+    /// assert!(engine.analyze_rule(source, "no_such_rule").is_none());
+    /// ```
+    pub fn analyze_rule(&mut self, source: &str, rule_id: &str) -> Option<Vec<Diagnostic>> {
+        self.rules.find(rule_id)?;
+        let tree = match self.parser.parse(source) {
+            Ok(tree) => tree,
+            Err(_) => return Some(Self::parse_error_result(source).diagnostics),
+        };
+        self.rules
+            .validate_rule(rule_id, &tree, source)
+            .map(|result| result.diagnostics)
+    }
+
+    /// Analyze a YAML document and report how long each rule took.
+    ///
+    /// Rules run sequentially in this mode, so it's slower than `analyze`
+    /// but each duration belongs to a single rule. Meant for finding slow
+    /// rules, not for regular validation.
+    pub fn analyze_timed(&mut self, source: &str) -> (TrussResult, Vec<(String, Duration)>) {
+        self.analyze_timed_with_options(source, &AnalysisOptions::default())
+    }
+
+    /// `analyze_timed` with the settings loaded from `.truss.yml`.
+    pub fn analyze_timed_with_options(
+        &mut self,
+        source: &str,
+        options: &AnalysisOptions,
+    ) -> (TrussResult, Vec<(String, Duration)>) {
+        if let Some(result) = Self::size_limit_result(source, options) {
+            return (result, Vec::new());
+        }
+        let tree = match self.parser.parse(source) {
+            Ok(tree) => tree,
+            Err(_) => return (Self::parse_error_result(source), Vec::new()),
+        };
+
+        let (result, timings) = self.rules.validate_timed(&tree, source);
+        (Self::apply_options(result, &tree, source, options), timings)
+    }
+
+    /// `analyze_timed_with_options` plus the cross-file checks of
+    /// `analyze_with_resolver`, so timing a run doesn't change its diagnostics.
+    ///
+    /// Time spent reading and checking called workflows is added to
+    /// `reusable_workflow_call`.
+    pub fn analyze_timed_with_resolver(
+        &mut self,
+        source: &str,
+        options: &AnalysisOptions,
+        resolve: impl Fn(&str) -> Option<String>,
+    ) -> (TrussResult, Vec<(String, Duration)>) {
+        if let Some(result) = Self::size_limit_result(source, options) {
+            return (result, Vec::new());
+        }
+        let tree = match self.parser.parse(source) {
+            Ok(tree) => tree,
+            Err(_) => return (Self::parse_error_result(source), Vec::new()),
+        };
+
+        let (mut result, mut timings) = self.rules.validate_timed(&tree, source);
+        let start = Instant::now();
+        self.check_called_workflows(&mut result, &tree, source, &resolve);
+        if let Some((_, duration)) = timings
+            .iter_mut()
+            .find(|(rule, _)| rule == "reusable_workflow_call")
+        {
+            *duration += start.elapsed();
+        }
+        (Self::apply_options(result, &tree, source, options), timings)
+    }
+
+    /// The `AnalysisOptions` post-pass shared by `analyze_with_options` and
+    /// `analyze_timed_with_options`.
+    fn apply_options(
+        mut result: TrussResult,
+        tree: &tree_sitter::Tree,
+        source: &str,
+        options: &AnalysisOptions,
+    ) -> TrussResult {
+        if !options.allowed_environments.is_empty() {
+            result
+                .diagnostics
+                .extend(validation::rules::environment::check_allowed_environments(
+                    tree,
+                    source,
+                    &options.allowed_environments,
+                ));
+            result
+                .diagnostics
+                .sort_by_key(|d| (d.span.start, d.severity));
+        }
+        if let Some(limit) = options.max_matrix_combinations {
+            if limit < validation::rules::matrix::GITHUB_MAX_MATRIX_JOBS {
+                result
+                    .diagnostics
+                    .extend(validation::rules::matrix::check_matrix_limit(
+                        tree, source, limit,
+                    ));
+                result
+                    .diagnostics
+                    .sort_by_key(|d| (d.span.start, d.severity));
+            }
+        }
+        // The checks above run outside the rule set, so honor suppression
+        // comments for their diagnostics too
+        validation::apply_inline_suppressions(tree, source, &mut result.diagnostics);
+        result.apply_severity_overrides(&options.severity_overrides);
+        result
+    }
+
+    /// Collect the fixes every rule proposes for a YAML document.
+    ///
+    /// Only rules that implement `ValidationRule::fixes` contribute, and
+    /// nothing is proposed for a document that fails to parse or on a line
+    /// whose diagnostics a `truss-disable` comment suppresses. The source is
+    /// not modified; applying the edits is up to the caller.
+    pub fn fix(&mut self, source: &str) -> Fix {
+        match self.parser.parse(source) {
+            Ok(tree) => Fix {
+                replacements: self.rules.fixes(&tree, source),
+            },
+            Err(_) => Fix::default(),
+        }
+    }
+
     /// Analyze with incremental parsing support.
     ///
     /// If an old_tree is provided, uses incremental parsing for better performance.
@@ -167,6 +380,38 @@ impl TrussEngine {
         }
     }
 
+    /// The result for a source over `options.max_file_size_bytes`, if it is.
+    ///
+    /// Parsing and walking a multi-megabyte generated file can take long
+    /// enough to look like a hang, so such files are skipped with an info
+    /// diagnostic rather than analyzed.
+    fn size_limit_result(source: &str, options: &AnalysisOptions) -> Option<TrussResult> {
+        let limit = options.max_file_size_bytes?;
+        if source.len() <= limit {
+            return None;
+        }
+        Some(TrussResult {
+            diagnostics: vec![Diagnostic {
+                message: format!(
+                    "File exceeds size limit ({} bytes, limit is {} bytes); skipped analysis. Raise max_file_size_bytes in .truss.yml to validate it.",
+                    source.len(),
+                    limit
+                ),
+                severity: Severity::Info,
+                span: Span { start: 0, end: 0 },
+                rule_id: "file_size".to_string(),
+                category: RuleCategory::Syntax,
+                related: Vec::new(),
+            }],
+            line_index: Some(LineIndex::new(source)),
+        })
+    }
+
+    /// Result for when tree-sitter returns no tree at all.
+    ///
+    /// A document with syntax errors still parses into a tree with `ERROR`
+    /// and `MISSING` nodes, which `SyntaxRule` reports with precise spans,
+    /// so this generic diagnostic is only a last resort.
     fn parse_error_result(source: &str) -> TrussResult {
         // Find a safe end position that doesn't split a UTF-8 character
         let mut end = source.len().min(100);
@@ -178,14 +423,152 @@ impl TrussEngine {
                 message: "Failed to parse YAML".to_string(),
                 severity: Severity::Error,
                 span: Span { start: 0, end },
-                rule_id: String::new(),
+                rule_id: "syntax".to_string(),
+                category: RuleCategory::Syntax,
+                related: Vec::new(),
             }],
+            line_index: Some(LineIndex::new(source)),
         }
     }
 
     /// Add a custom validation rule.
+    ///
+    /// Engines cloned earlier keep their rules; only this engine gains the new one.
     pub fn add_rule<R: ValidationRule + 'static>(&mut self, rule: R) {
+        Arc::make_mut(&mut self.rules).add_rule(rule);
+    }
+
+    /// Iterate over the registered rules in registration order.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn ValidationRule> {
+        self.rules.rules()
+    }
+
+    /// Id, category, default severity, and short description of each registered rule.
+    pub fn rule_metadata(
+        &self,
+    ) -> impl Iterator<Item = (&str, RuleCategory, Severity, &'static str)> + '_ {
+        self.rules.metadata()
+    }
+
+    /// Look up a registered rule by id.
+    pub fn rule(&self, rule_id: &str) -> Option<&dyn ValidationRule> {
+        self.rules.find(rule_id)
+    }
+}
+
+/// The built-in rules, in the order `TrussEngine::new` registers them.
+fn default_rules() -> RuleSet {
+    let mut rules = RuleSet::new();
+    rules.add_rule(SyntaxRule);
+    rules.add_rule(NonEmptyRule);
+    rules.add_rule(GitHubActionsSchemaRule);
+    rules.add_rule(WorkflowTriggerRule);
+    rules.add_rule(JobNameRule);
+    rules.add_rule(JobNeedsRule);
+    rules.add_rule(StepValidationRule);
+    rules.add_rule(ExpressionValidationRule);
+    rules.add_rule(PermissionsRule);
+    rules.add_rule(EnvironmentRule);
+    rules.add_rule(WorkflowNameRule);
+    rules.add_rule(MatrixStrategyRule);
+    rules.add_rule(RunsOnRequiredRule);
+    rules.add_rule(SecretsValidationRule);
+    rules.add_rule(TimeoutRule);
+    rules.add_rule(WorkflowInputsRule);
+    rules.add_rule(JobOutputsRule);
+    rules.add_rule(ConcurrencyRule);
+    rules.add_rule(ActionReferenceRule);
+    rules.add_rule(StepIdUniquenessRule);
+    rules.add_rule(StepOutputReferenceRule);
+    rules.add_rule(JobStrategyValidationRule);
+    rules.add_rule(StepIfExpressionRule);
+    rules.add_rule(JobIfExpressionRule);
+    rules.add_rule(WorkflowCallInputsRule);
+    rules.add_rule(WorkflowCallSecretsRule);
+    rules.add_rule(ReusableWorkflowCallRule);
+    rules.add_rule(WorkflowCallOutputsRule);
+    rules.add_rule(StepContinueOnErrorRule);
+    rules.add_rule(StepTimeoutRule);
+    rules.add_rule(StepShellRule);
+    rules.add_rule(StepWorkingDirectoryRule);
+    rules.add_rule(ArtifactValidationRule);
+    rules.add_rule(EventPayloadValidationRule);
+    rules.add_rule(RunnerLabelRule);
+    rules.add_rule(StepEnvValidationRule);
+    rules.add_rule(JobContainerRule);
+    rules.add_rule(StepNameRule);
+    rules.add_rule(DefaultsValidationRule);
+    rules.add_rule(DeprecatedCommandsRule);
+    rules.add_rule(ScriptInjectionRule);
+    rules.add_rule(MatrixReferenceRule);
+    rules.add_rule(ScheduledReusableWorkflowRule);
+    rules.add_rule(DeprecatedActionRule);
+    rules.add_rule(ActionInputNameRule);
+    rules.add_rule(CompositeActionOutputsRule);
+    rules.add_rule(ActionRunsUsingRule);
+    rules.add_rule(ActionBrandingRule);
+    rules.add_rule(ParallelJobsRule);
+    rules.add_rule(DeployJobTimeoutRule);
+    rules.add_rule(DuplicateKeyRule);
+    rules.add_rule(ActionPinningRule);
+    rules.add_rule(PullRequestTargetRule);
+    rules.add_rule(RunBlockScalarRule);
+    rules.add_rule(StepSetOutputRule);
+    rules.add_rule(CompositeActionRule);
+    rules.add_rule(WorkflowDispatchInputMaxRule);
+    rules.add_rule(NeedsOutputReferenceRule);
+    rules.add_rule(MissingCheckoutRule);
+    rules.add_rule(FilterPatternRule);
+    rules
+}
+
+/// Builder for a `TrussEngine` with a chosen set of rules, from `TrussEngine::builder`.
+#[derive(Default)]
+pub struct TrussEngineBuilder {
+    rules: RuleSet,
+}
+
+impl TrussEngineBuilder {
+    /// Add every built-in rule that isn't registered yet.
+    pub fn with_defaults(mut self) -> Self {
+        self.rules.merge(&default_rules(), |_| true);
+        self
+    }
+
+    /// Add the built-in rules in `category` that aren't registered yet.
+    pub fn with_category(mut self, category: RuleCategory) -> Self {
+        self.rules
+            .merge(&default_rules(), |rule| rule.category() == category);
+        self
+    }
+
+    /// Add a custom validation rule.
+    pub fn with_rule<R: ValidationRule + 'static>(mut self, rule: R) -> Self {
         self.rules.add_rule(rule);
+        self
+    }
+
+    /// Remove the rule with id `rule_id`. Unknown ids are ignored.
+    pub fn without(mut self, rule_id: &str) -> Self {
+        self.rules.remove(rule_id);
+        self
+    }
+
+    /// Finish building the engine.
+    pub fn build(self) -> TrussEngine {
+        TrussEngine {
+            parser: YamlParser::new(),
+            rules: Arc::new(self.rules),
+        }
+    }
+}
+
+impl Clone for TrussEngine {
+    fn clone(&self) -> Self {
+        Self {
+            parser: YamlParser::new(),
+            rules: Arc::clone(&self.rules),
+        }
     }
 }
 
@@ -195,10 +578,32 @@ impl Default for TrussEngine {
     }
 }
 
+/// Settings for `TrussEngine::analyze_with_options`, usually built with
+/// `TrussConfig::analysis_options`.
+#[derive(Debug, Clone, Default)]
+pub struct AnalysisOptions {
+    /// Rule id → severity its diagnostics are reported at.
+    pub severity_overrides: HashMap<String, Severity>,
+    /// Deployment environments jobs may use. Empty allows any environment.
+    pub allowed_environments: Vec<String>,
+    /// Sources larger than this many bytes are not analyzed; the result is a
+    /// single info diagnostic instead. `None` means no limit.
+    pub max_file_size_bytes: Option<usize>,
+    /// Matrices that expand to more jobs than this are errors. Only limits
+    /// below GitHub's own 256, which `matrix_strategy` always checks, add
+    /// anything. `None` uses GitHub's limit alone.
+    pub max_matrix_combinations: Option<usize>,
+}
+
 /// Result of a Truss analysis pass.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrussResult {
     pub diagnostics: Vec<Diagnostic>,
+    /// Lines of the analyzed source, for converting diagnostic spans to
+    /// positions without rescanning it. Not serialized, so `None` for a
+    /// result read back from JSON.
+    #[serde(skip)]
+    pub line_index: Option<LineIndex>,
 }
 
 impl TrussResult {
@@ -216,10 +621,19 @@ impl TrussResult {
             .iter()
             .any(|d| d.severity == Severity::Error)
     }
+
+    /// Rewrite the severity of each diagnostic whose rule id has an override.
+    pub fn apply_severity_overrides(&mut self, severity_overrides: &HashMap<String, Severity>) {
+        for diagnostic in &mut self.diagnostics {
+            if let Some(severity) = severity_overrides.get(&diagnostic.rule_id) {
+                diagnostic.severity = *severity;
+            }
+        }
+    }
 }
 
 /// A diagnostic produced by the engine.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Diagnostic {
     pub message: String,
     pub severity: Severity,
@@ -230,10 +644,46 @@ pub struct Diagnostic {
     /// Empty when the diagnostic is synthetic (e.g., parse errors).
     #[serde(default)]
     pub rule_id: String,
+    /// Category of the rule that produced this diagnostic.
+    ///
+    /// Matches the value returned by `ValidationRule::category()`.
+    #[serde(default)]
+    pub category: RuleCategory,
+    /// Other places in the same document that explain this diagnostic, such
+    /// as where a referenced step is defined. Usually empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<RelatedLocation>,
+}
+
+/// A secondary location attached to a diagnostic, which editors can show as
+/// a clickable link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedLocation {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A replacement of the source text in `span` with `new_text`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+    /// Id of the rule that proposed the edit.
+    pub rule_id: String,
+}
+
+/// Edits that fix problems mechanically, as returned by [`TrussEngine::fix`].
+///
+/// Replacements are sorted by start offset. Each rule keeps its own edits
+/// from overlapping, but two rules may still propose overlapping ones, so
+/// check before applying them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Fix {
+    pub replacements: Vec<TextEdit>,
 }
 
 /// Severity level of a diagnostic.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
@@ -241,6 +691,25 @@ pub enum Severity {
     Info,
 }
 
+/// Broad category of a validation rule, used to group and filter diagnostics.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleCategory {
+    /// YAML syntax and document-level problems.
+    Syntax,
+    /// Structure and allowed values of workflow and action files.
+    #[default]
+    Schema,
+    /// `${{ }}` expressions and the contexts they reference.
+    Expression,
+    /// Injection, permissions, and secret handling.
+    Security,
+    /// Naming and best-practice suggestions.
+    Style,
+}
+
 /// Text span associated with a diagnostic.
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct Span {
@@ -248,6 +717,54 @@ pub struct Span {
     pub end: usize,
 }
 
+impl Span {
+    /// The zero-based (line, character) position of the span's start, with
+    /// characters in UTF-16 code units. See [`LineIndex::line_col`].
+    pub fn to_line_col(&self, index: &LineIndex) -> (u32, u32) {
+        index.line_col(self.start)
+    }
+}
+
+/// Convert a byte offset in `text` to a zero-based (line, character) position.
+///
+/// Characters are counted in UTF-16 code units, as LSP clients and browser
+/// editors expect, so a line containing `é` or an emoji maps correctly. For
+/// ASCII text this equals the byte offset within the line. Offsets past the
+/// end of `text` or inside a multi-byte character are clamped to the
+/// preceding character boundary.
+pub fn byte_to_utf16_position(byte_offset: usize, text: &str) -> (u32, u32) {
+    let mut clamped = byte_offset.min(text.len());
+    while !text.is_char_boundary(clamped) {
+        clamped -= 1;
+    }
+    let bytes_before = &text[..clamped];
+    let line = bytes_before.matches('\n').count() as u32;
+    let last_newline = bytes_before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    let character = text[last_newline..clamped]
+        .chars()
+        .map(|c| c.len_utf16() as u32)
+        .sum::<u32>();
+
+    (line, character)
+}
+
+impl Diagnostic {
+    /// The message with case, quoting, runs of whitespace, and a trailing
+    /// period normalized away, for comparing diagnostics across runs and rules.
+    pub fn normalized_message(&self) -> String {
+        self.message
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_end_matches('.')
+            .chars()
+            .filter(|c| !matches!(c, '\'' | '"' | '`'))
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+}
+
 impl fmt::Display for Diagnostic {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.rule_id.is_empty() {
@@ -311,4 +828,368 @@ mod tests {
         assert!(first.is_ok());
         assert!(second.is_ok());
     }
+
+    #[test]
+    fn cloned_engine_shares_rules_but_not_added_ones() {
+        let engine = TrussEngine::new();
+        let mut clone = engine.clone();
+        assert_eq!(clone.rules().count(), engine.rules().count());
+
+        clone.add_rule(SyntaxRule);
+        assert_eq!(clone.rules().count(), engine.rules().count() + 1);
+
+        let input =
+            "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo hi\n";
+        assert_eq!(
+            clone.analyze(input).diagnostics.len(),
+            TrussEngine::new().analyze(input).diagnostics.len()
+        );
+    }
+
+    #[test]
+    fn builder_composes_rule_subsets() {
+        let full = TrussEngine::new();
+        let rebuilt = TrussEngine::builder()
+            .with_defaults()
+            .with_defaults()
+            .build();
+        let ids = |engine: &TrussEngine| {
+            engine
+                .rules()
+                .map(|r| r.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&rebuilt), ids(&full));
+
+        let mut cheap = TrussEngine::builder()
+            .with_category(RuleCategory::Syntax)
+            .with_category(RuleCategory::Schema)
+            .without("runs_on_required")
+            .build();
+        assert!(cheap
+            .rules()
+            .all(|r| matches!(r.category(), RuleCategory::Syntax | RuleCategory::Schema)));
+        assert!(cheap.rule("non_empty").is_some());
+        assert!(cheap.rule("runs_on_required").is_none());
+        assert!(cheap.rule("script_injection").is_none());
+
+        let result = cheap.analyze("");
+        assert!(result.diagnostics.iter().any(|d| d.rule_id == "non_empty"));
+
+        let custom = TrussEngine::builder()
+            .with_rule(SyntaxRule)
+            .without("no_such_rule")
+            .build();
+        assert_eq!(ids(&custom), ["syntax"]);
+    }
+
+    #[test]
+    fn analyze_rule_runs_only_that_rule() {
+        let mut engine = TrussEngine::new();
+        let input =
+            "on: push\njobs:\n  a:\n    timeout-minutes: 0\n    steps:\n      - run: echo hi\n";
+
+        let timeout = engine.analyze_rule(input, "timeout").unwrap();
+        assert!(!timeout.is_empty());
+        assert!(timeout.iter().all(|d| d.rule_id == "timeout"));
+        let expected: Vec<_> = engine
+            .analyze(input)
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.rule_id == "timeout")
+            .map(|d| (d.span.start, d.span.end))
+            .collect();
+        let spans: Vec<_> = timeout.iter().map(|d| (d.span.start, d.span.end)).collect();
+        assert_eq!(spans, expected);
+
+        assert!(engine.analyze_rule(input, "no_such_rule").is_none());
+        // Workflow-only rules stay quiet on other YAML files
+        assert!(engine
+            .analyze_rule("key: value\n", "runs_on_required")
+            .is_some_and(|diagnostics| diagnostics.is_empty()));
+    }
+
+    #[test]
+    fn utf16_position_counts_code_units() {
+        let text = "a: é\nb: 😀 x\n";
+        assert_eq!(byte_to_utf16_position(0, text), (0, 0));
+        // `é` is 2 bytes but 1 UTF-16 unit
+        assert_eq!(byte_to_utf16_position(5, text), (0, 4));
+        // `😀` is 4 bytes and 2 UTF-16 units
+        assert_eq!(byte_to_utf16_position(13, text), (1, 5));
+        // Inside `😀`, and past the end
+        assert_eq!(byte_to_utf16_position(10, text), (1, 3));
+        assert_eq!(byte_to_utf16_position(100, text), (2, 0));
+    }
+
+    #[test]
+    fn analyze_timed_matches_analyze_and_times_each_rule() {
+        let mut engine = TrussEngine::new();
+        let input = "on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n    timeout-minutes: 0\n    steps:\n      - run: echo hi\n";
+
+        let (timed, timings) = engine.analyze_timed(input);
+        let plain = engine.analyze(input);
+        let spans = |result: &TrussResult| -> Vec<_> {
+            result
+                .diagnostics
+                .iter()
+                .map(|d| (d.span.start, d.rule_id.clone()))
+                .collect()
+        };
+        assert_eq!(spans(&timed), spans(&plain));
+        let workflow_rules = engine
+            .rules()
+            .filter(|rule| rule.applies_to().includes(FileKind::Workflow))
+            .count();
+        assert_eq!(timings.len(), workflow_rules);
+        assert!(timings.iter().any(|(rule, _)| rule == "timeout"));
+        assert!(!timings.iter().any(|(rule, _)| rule == "composite_action"));
+
+        // Only rules that apply to any YAML run on a non-workflow document
+        let (_, timings) = engine.analyze_timed("key: value\n");
+        assert!(timings.len() < workflow_rules);
+        assert!(timings.iter().any(|(rule, _)| rule == "syntax"));
+        assert!(!timings.iter().any(|(rule, _)| rule == "composite_action"));
+    }
+
+    /// Reports a fixed message over the first three bytes of any document.
+    struct FixedRule {
+        name: &'static str,
+        category: RuleCategory,
+        message: &'static str,
+    }
+
+    impl ValidationRule for FixedRule {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn category(&self) -> RuleCategory {
+            self.category
+        }
+
+        fn validate(&self, _tree: &tree_sitter::Tree, _source: &str) -> Vec<Diagnostic> {
+            vec![Diagnostic {
+                message: self.message.to_string(),
+                severity: Severity::Error,
+                span: Span { start: 0, end: 3 },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            }]
+        }
+
+        fn requires_workflow(&self) -> bool {
+            false
+        }
+    }
+
+    /// Reports its own name on any document it runs on.
+    struct KindRule(&'static str, FileKind);
+
+    impl ValidationRule for KindRule {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn validate(&self, _tree: &tree_sitter::Tree, _source: &str) -> Vec<Diagnostic> {
+            vec![Diagnostic {
+                message: self.0.to_string(),
+                severity: Severity::Info,
+                span: Span { start: 0, end: 1 },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            }]
+        }
+
+        fn applies_to(&self) -> FileKind {
+            self.1
+        }
+    }
+
+    #[test]
+    fn rules_run_only_on_the_file_kinds_they_apply_to() {
+        let mut engine = TrussEngine::builder()
+            .with_rule(KindRule("any", FileKind::AnyYaml))
+            .with_rule(KindRule("workflow", FileKind::Workflow))
+            .with_rule(KindRule("composite", FileKind::CompositeAction))
+            .build();
+        let mut ran = |source: &str| -> Vec<String> {
+            let mut ids: Vec<_> = engine
+                .analyze(source)
+                .diagnostics
+                .into_iter()
+                .map(|d| d.rule_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            ran("on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n"),
+            ["any", "workflow"]
+        );
+        assert_eq!(
+            ran("name: x\nruns:\n  using: composite\n  steps: []\n"),
+            ["any", "composite"]
+        );
+        // Other action types aren't composite actions
+        assert_eq!(
+            ran("name: x\nruns:\n  using: node20\n  main: index.js\n"),
+            ["any"]
+        );
+        assert_eq!(ran("key: value\n"), ["any"]);
+
+        // Rules that only override `requires_workflow` keep their old routing
+        assert_eq!(
+            FixedRule {
+                name: "fixed",
+                category: RuleCategory::Style,
+                message: "",
+            }
+            .applies_to(),
+            FileKind::AnyYaml
+        );
+    }
+
+    #[test]
+    fn duplicate_diagnostics_across_rules_are_collapsed() {
+        let mut engine = TrussEngine::new();
+        engine.add_rule(FixedRule {
+            name: "z_style",
+            category: RuleCategory::Style,
+            message: "Reference to undefined input 'x'.",
+        });
+        engine.add_rule(FixedRule {
+            name: "b_expression",
+            category: RuleCategory::Expression,
+            message: "reference to  undefined input `x`",
+        });
+        engine.add_rule(FixedRule {
+            name: "a_expression",
+            category: RuleCategory::Expression,
+            message: "Reference to undefined input \"x\"",
+        });
+        engine.add_rule(FixedRule {
+            name: "distinct",
+            category: RuleCategory::Schema,
+            message: "Input 'x' has no type",
+        });
+
+        let result = engine.analyze("key: value\n");
+        let at_start: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.span.start == 0 && d.span.end == 3)
+            .map(|d| d.rule_id.as_str())
+            .collect();
+
+        // Expression beats Style, then `a_expression` wins the tie on rule_id
+        assert_eq!(at_start, ["a_expression", "distinct"]);
+    }
+
+    #[test]
+    fn diagnostics_carry_rule_id_and_category() {
+        let mut engine = TrussEngine::new();
+        let input = "on: pull_request_target\njobs:\n  a:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo \"${{ github.event.pull_request.title }}\"\n";
+
+        let result = engine.analyze(input);
+        let injection = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule_id == "script_injection")
+            .expect("script injection diagnostic");
+
+        assert_eq!(injection.category, RuleCategory::Security);
+    }
+
+    #[test]
+    fn parse_error_result_reports_as_syntax() {
+        let result = TrussEngine::parse_error_result("on: [push\n");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule_id, "syntax");
+        assert_eq!(result.diagnostics[0].category, RuleCategory::Syntax);
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn sources_over_the_size_limit_are_skipped() {
+        let mut engine = TrussEngine::new();
+        let source = "jobs:\n  build:\n    steps: []\n";
+        let options = AnalysisOptions {
+            max_file_size_bytes: Some(16),
+            ..AnalysisOptions::default()
+        };
+
+        let result = engine.analyze_with_options(source, &options);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Info);
+        assert_eq!(result.diagnostics[0].rule_id, "file_size");
+        assert!(result.diagnostics[0]
+            .message
+            .starts_with("File exceeds size limit"));
+        assert!(result.is_ok());
+
+        let (timed, timings) = engine.analyze_timed_with_options(source, &options);
+        assert_eq!(timed.diagnostics.len(), 1);
+        assert!(timings.is_empty());
+
+        let options = AnalysisOptions {
+            max_file_size_bytes: Some(source.len()),
+            ..AnalysisOptions::default()
+        };
+        let result = engine.analyze_with_options(source, &options);
+        assert!(!result
+            .diagnostics
+            .iter()
+            .any(|d| d.message.starts_with("File exceeds size limit")));
+    }
+
+    #[test]
+    fn severity_overrides_rewrite_matching_rules() {
+        let mut engine = TrussEngine::new();
+        let input = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    timeout-minutes: 0\n    steps:\n      - run: make\n";
+        assert!(!engine.analyze(input).is_ok());
+
+        let mut overrides = HashMap::new();
+        overrides.insert("timeout".to_string(), Severity::Info);
+        overrides.insert("no_such_rule".to_string(), Severity::Error);
+        let result = engine.analyze_with_config(input, &overrides);
+
+        let timeout = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule_id == "timeout")
+            .expect("timeout diagnostic");
+        assert_eq!(timeout.severity, Severity::Info);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn severity_override_does_not_hide_other_errors() {
+        let mut engine = TrussEngine::new();
+        let input = "on: push\njobs:\n  build:\n    runs-on: ''\n    timeout-minutes: 0\n    steps:\n      - run: make\n";
+
+        let mut overrides = HashMap::new();
+        overrides.insert("timeout".to_string(), Severity::Warning);
+        let result = engine.analyze_with_config(input, &overrides);
+
+        assert!(result
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule_id == "timeout")
+            .all(|d| d.severity == Severity::Warning));
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn diagnostic_json_includes_rule_id_and_category() {
+        let mut engine = TrussEngine::new();
+        let result = engine.analyze("");
+
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"rule_id\":\"non_empty\""));
+        assert!(json.contains("\"category\":\"syntax\""));
+    }
 }
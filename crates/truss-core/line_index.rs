@@ -0,0 +1,151 @@
+//! Byte offset to line and column conversion in O(log n).
+//!
+//! [`byte_to_utf16_position`](crate::byte_to_utf16_position) rescans the
+//! text up to the offset on every call, which adds up when an editor or
+//! exporter converts every diagnostic of a large workflow. A [`LineIndex`]
+//! is built once per source, in the same pass as the analysis, and answers
+//! each conversion with a binary search.
+
+/// The start of every line in a source, plus where its non-ASCII
+/// characters are, so columns can be counted in UTF-16 code units or in
+/// characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Byte offset at which each line starts. The first is always 0.
+    line_starts: Vec<usize>,
+    /// Byte range of each non-ASCII character, in order, with how many more
+    /// bytes than UTF-16 code units, and than characters, the characters up
+    /// to and including it take.
+    wide_chars: Vec<WideChar>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WideChar {
+    start: usize,
+    end: usize,
+    extra_bytes: usize,
+    extra_chars: usize,
+}
+
+impl LineIndex {
+    /// Index the lines of `source`.
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = Vec::new();
+        let mut extra_bytes = 0;
+        let mut extra_chars = 0;
+        for (offset, c) in source.char_indices() {
+            if c == '\n' {
+                line_starts.push(offset + 1);
+            } else if !c.is_ascii() {
+                extra_bytes += c.len_utf8() - c.len_utf16();
+                extra_chars += c.len_utf8() - 1;
+                wide_chars.push(WideChar {
+                    start: offset,
+                    end: offset + c.len_utf8(),
+                    extra_bytes,
+                    extra_chars,
+                });
+            }
+        }
+        Self {
+            line_starts,
+            wide_chars,
+            len: source.len(),
+        }
+    }
+
+    /// The number of lines, counting the one after a trailing newline.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The zero-based (line, character) position of a byte offset, with
+    /// characters counted in UTF-16 code units as LSP clients expect.
+    ///
+    /// Gives the same answer as
+    /// [`byte_to_utf16_position`](crate::byte_to_utf16_position) on the
+    /// indexed source: offsets past the end, or inside a multi-byte
+    /// character, are clamped to the preceding character boundary.
+    pub fn line_col(&self, offset: usize) -> (u32, u32) {
+        let (line, character) = self.position(offset, |c| c.extra_bytes);
+        (line as u32, character as u32)
+    }
+
+    /// The zero-based (line, column) position of a byte offset, with the
+    /// column counted in characters, as terminals and CI annotations show it.
+    ///
+    /// Offsets are clamped the same way as in [`line_col`](Self::line_col).
+    pub fn line_col_chars(&self, offset: usize) -> (usize, usize) {
+        self.position(offset, |c| c.extra_chars)
+    }
+
+    /// Line and column of `offset`, where `extra` gives how many more bytes
+    /// than columns the characters up to and including a wide one take.
+    fn position(&self, offset: usize, extra: impl Fn(&WideChar) -> usize) -> (usize, usize) {
+        let mut offset = offset.min(self.len);
+        // Characters before `offset`, and whether it falls inside one
+        let before = self.wide_chars.partition_point(|c| c.start < offset);
+        if let Some(c) = before.checked_sub(1).map(|i| self.wide_chars[i]) {
+            if offset < c.end {
+                offset = c.start;
+            }
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let extra = |offset: usize| {
+            let count = self.wide_chars.partition_point(|c| c.start < offset);
+            count
+                .checked_sub(1)
+                .map_or(0, |i| extra(&self.wide_chars[i]))
+        };
+        (
+            line,
+            offset - line_start - (extra(offset) - extra(line_start)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{byte_to_utf16_position, Span};
+
+    #[test]
+    fn matches_byte_to_utf16_position_at_every_offset() {
+        let text = "on: push\njobs:\n  büild: 🚀 é\n\n    steps: []";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 5);
+        for offset in 0..=text.len() + 2 {
+            assert_eq!(
+                index.line_col(offset),
+                byte_to_utf16_position(offset, text),
+                "offset {}",
+                offset
+            );
+        }
+    }
+
+    #[test]
+    fn line_col_chars_counts_characters() {
+        let text = "büild: 🚀 é\nx";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_col_chars(text.find(':').unwrap()), (0, 5));
+        // '🚀' is two UTF-16 code units but one character
+        let after_rocket = text.find(" é").unwrap();
+        assert_eq!(index.line_col(after_rocket), (0, 9));
+        assert_eq!(index.line_col_chars(after_rocket), (0, 8));
+        assert_eq!(index.line_col_chars(after_rocket - 1), (0, 7));
+        assert_eq!(index.line_col_chars(usize::MAX), (1, 1));
+    }
+
+    #[test]
+    fn span_converts_its_start() {
+        let text = "a: 1\nb: 2\n";
+        let span = Span { start: 5, end: 9 };
+        assert_eq!(span.to_line_col(&LineIndex::new(text)), (1, 0));
+        assert_eq!(LineIndex::new("").line_col(3), (0, 0));
+    }
+}
@@ -0,0 +1,228 @@
+//! Rendering diagnostics as text for terminals, logs, and CI.
+//!
+//! Three forms are available:
+//! - [`text`]: the `[Error] [rule_id] message (start..end)` lines the CLI
+//!   prints, optionally colored by severity
+//! - [`compact`]: one `file:line:column: severity[rule_id]: message` line per
+//!   diagnostic, the format editors and `grep` understand
+//! - [`github`]: GitHub Actions workflow commands (`::error file=...::message`),
+//!   which show up as inline annotations when printed inside a workflow run
+//!
+//! Each takes a slice of diagnostics, so pass `&result.diagnostics` for a
+//! [`TrussResult`](crate::TrussResult) or a filtered list of your own. The
+//! positioned forms also take the result's [`LineIndex`], so each span is
+//! converted without rescanning the source.
+
+use crate::{Diagnostic, LineIndex, Severity};
+use std::fmt::Write;
+
+const RED: &str = "31";
+const YELLOW: &str = "33";
+const BLUE: &str = "34";
+
+/// Options for [`text`] and [`diagnostic`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Wrap each diagnostic in ANSI colors: red for errors, yellow for
+    /// warnings, blue for info.
+    pub color: bool,
+}
+
+/// One diagnostic as a single line of text, without a trailing newline.
+///
+/// Without color this is the diagnostic's `Display` output.
+pub fn diagnostic(diagnostic: &Diagnostic, options: &RenderOptions) -> String {
+    if !options.color {
+        return diagnostic.to_string();
+    }
+    let color = match diagnostic.severity {
+        Severity::Error => RED,
+        Severity::Warning => YELLOW,
+        Severity::Info => BLUE,
+    };
+    format!("\x1b[{}m{}\x1b[0m", color, diagnostic)
+}
+
+/// Every diagnostic on its own line, as rendered by [`diagnostic`].
+pub fn text(diagnostics: &[Diagnostic], options: &RenderOptions) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        let _ = writeln!(out, "{}", self::diagnostic(d, options));
+    }
+    out
+}
+
+/// Every diagnostic as `file:line:column: severity[rule_id]: message`.
+///
+/// `lines` indexes the analyzed text, and turns byte spans into 1-based
+/// lines and columns. Multi-line messages are joined with spaces to keep one
+/// line per diagnostic.
+pub fn compact(diagnostics: &[Diagnostic], file: &str, lines: &LineIndex) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        let (line, column) = line_col(lines, d.span.start);
+        let rule = if d.rule_id.is_empty() {
+            String::new()
+        } else {
+            format!("[{}]", d.rule_id)
+        };
+        let _ = writeln!(
+            out,
+            "{}:{}:{}: {}{}: {}",
+            file,
+            line,
+            column,
+            severity_name(d.severity),
+            rule,
+            d.message.lines().collect::<Vec<_>>().join(" ")
+        );
+    }
+    out
+}
+
+/// Every diagnostic as a GitHub Actions workflow command, such as
+/// `::error file=ci.yml,line=4,col=7,endLine=4,endColumn=12,title=rule_id::message`.
+///
+/// Info diagnostics become `::notice`. `file` should be relative to the
+/// repository root for the annotation to attach to the file.
+pub fn github(diagnostics: &[Diagnostic], file: &str, lines: &LineIndex) -> String {
+    let mut out = String::new();
+    for d in diagnostics {
+        let command = match d.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+        let (line, column) = line_col(lines, d.span.start);
+        let (end_line, end_column) = line_col(lines, d.span.end);
+        let _ = write!(
+            out,
+            "::{} file={},line={},col={},endLine={},endColumn={}",
+            command,
+            escape_property(file),
+            line,
+            column,
+            end_line,
+            end_column
+        );
+        if !d.rule_id.is_empty() {
+            let _ = write!(out, ",title={}", escape_property(&d.rule_id));
+        }
+        let _ = writeln!(out, "::{}", escape_data(&d.message));
+    }
+    out
+}
+
+/// The 1-based line and column (in characters) of a byte offset in the
+/// source `lines` indexes.
+///
+/// Offsets past the end, or inside a multi-byte character, are clamped to
+/// the nearest preceding character boundary.
+pub fn line_col(lines: &LineIndex, offset: usize) -> (usize, usize) {
+    let (line, column) = lines.line_col_chars(offset);
+    (line + 1, column + 1)
+}
+
+fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+/// Escape a workflow command message: `%`, `\r`, and `\n` are percent-encoded.
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property, which additionally can't contain `:` or `,`.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RuleCategory, Span};
+
+    const SOURCE: &str = "on: push\njobs:\n  büild:\n    steps: []\n";
+
+    fn diagnostics() -> Vec<Diagnostic> {
+        let start = SOURCE.find("büild").unwrap();
+        vec![
+            Diagnostic {
+                message: "Job 'büild' is missing runs-on\nAdd runs-on: 100%".to_string(),
+                severity: Severity::Error,
+                span: Span {
+                    start,
+                    end: start + "büild".len(),
+                },
+                rule_id: "runs_on_required".to_string(),
+                category: RuleCategory::Schema,
+                related: Vec::new(),
+            },
+            Diagnostic {
+                message: "Workflow has no name".to_string(),
+                severity: Severity::Info,
+                span: Span { start: 0, end: 2 },
+                rule_id: String::new(),
+                category: RuleCategory::Style,
+                related: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn line_col_counts_characters() {
+        let lines = LineIndex::new(SOURCE);
+        assert_eq!(line_col(&lines, 0), (1, 1));
+        assert_eq!(line_col(&lines, SOURCE.find("jobs").unwrap()), (2, 1));
+        // 'ü' is two bytes but one column
+        let after_u = SOURCE.find("ild").unwrap();
+        assert_eq!(line_col(&lines, after_u), (3, 5));
+        assert_eq!(line_col(&lines, after_u - 1), (3, 4));
+        assert_eq!(line_col(&lines, usize::MAX), (5, 1));
+    }
+
+    #[test]
+    fn text_matches_display_and_colors_by_severity() {
+        let diagnostics = diagnostics();
+        let plain = text(&diagnostics, &RenderOptions::default());
+        assert_eq!(plain, format!("{}\n{}\n", diagnostics[0], diagnostics[1]));
+
+        let color = RenderOptions { color: true };
+        assert!(text(&diagnostics, &color).starts_with("\x1b[31m[Error] [runs_on_required]"));
+        assert_eq!(
+            diagnostic(&diagnostics[1], &color),
+            format!("\x1b[34m{}\x1b[0m", diagnostics[1])
+        );
+    }
+
+    #[test]
+    fn compact_is_one_line_per_diagnostic() {
+        let out = compact(&diagnostics(), "ci.yml", &LineIndex::new(SOURCE));
+        assert_eq!(
+            out,
+            "ci.yml:3:3: error[runs_on_required]: Job 'büild' is missing runs-on Add runs-on: 100%\n\
+             ci.yml:1:1: info: Workflow has no name\n"
+        );
+    }
+
+    #[test]
+    fn github_escapes_workflow_commands() {
+        let out = github(
+            &diagnostics(),
+            ".github/workflows/ci,main.yml",
+            &LineIndex::new(SOURCE),
+        );
+        assert_eq!(
+            out,
+            "::error file=.github/workflows/ci%2Cmain.yml,line=3,col=3,endLine=3,endColumn=8,title=runs_on_required::Job 'büild' is missing runs-on%0AAdd runs-on: 100%25\n\
+             ::notice file=.github/workflows/ci%2Cmain.yml,line=1,col=1,endLine=1,endColumn=3::Workflow has no name\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Tests for inline suppression comments
+//!
+//! `# truss-disable-line <rule_id>` and `# truss-disable-next-line <rule_id>`
+//! drop diagnostics on a single line without turning a rule off globally.
+
+use truss_core::{AnalysisOptions, TrussEngine};
+
+fn rule_ids(yaml: &str) -> Vec<String> {
+    TrussEngine::new()
+        .analyze(yaml)
+        .diagnostics
+        .into_iter()
+        .map(|d| d.rule_id)
+        .collect()
+}
+
+#[test]
+fn test_disable_line_and_next_line_by_rule_id() {
+    let yaml = r#"
+on: push
+jobs:
+  # truss-disable-next-line runs_on_required
+  build:
+    timeout-minutes: 0 # truss-disable-line timeout
+    steps:
+      # truss-disable-next-line step_name, script_injection
+      - run: echo ${{ github.event.issue.title }}
+"#;
+
+    let ids = rule_ids(yaml);
+    assert!(ids.is_empty(), "{:?}", ids);
+}
+
+#[test]
+fn test_disable_only_named_rules() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    timeout-minutes: 0 # truss-disable-line script_injection
+    steps:
+      - run: echo ${{ github.event.issue.title }} # truss-disable-line timeout
+"#;
+
+    let ids = rule_ids(yaml);
+    assert_eq!(ids, ["timeout", "script_injection"]);
+}
+
+#[test]
+fn test_bare_disable_next_line_suppresses_every_rule() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    # truss-disable-next-line
+    timeout-minutes: 0
+    steps:
+      - run: make
+"#;
+
+    let ids = rule_ids(yaml);
+    assert!(ids.is_empty(), "{:?}", ids);
+
+    // Diagnostics added after the rules run honor the comments too
+    let options = AnalysisOptions {
+        allowed_environments: vec!["production".to_string()],
+        ..AnalysisOptions::default()
+    };
+    let environment = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    # truss-disable-next-line
+    environment: staging
+    steps:
+      - run: make
+"#;
+    let result = TrussEngine::new().analyze_with_options(environment, &options);
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+}
+
+#[test]
+fn test_directives_in_scalars_are_not_comments() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      NOTE: |
+        # truss-disable-next-line timeout
+    timeout-minutes: 0
+    steps:
+      - run: echo '# truss-disable-line' ${{ github.event.issue.title }}
+"#;
+
+    let ids = rule_ids(yaml);
+    assert_eq!(ids, ["timeout", "script_injection"]);
+}
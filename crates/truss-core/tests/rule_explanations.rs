@@ -0,0 +1,120 @@
+//! Tests for rule explanations
+//!
+//! Every built-in rule documents itself for `truss explain`. The bad example
+//! must trigger the rule and the good example must not.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_every_rule_has_explanation() {
+    let engine = TrussEngine::new();
+
+    let missing: Vec<_> = engine
+        .rules()
+        .filter(|rule| {
+            let explanation = rule.explanation();
+            explanation.summary.is_empty()
+                || explanation.bad_example.is_empty()
+                || explanation.good_example.is_empty()
+        })
+        .map(|rule| rule.name().to_string())
+        .collect();
+
+    assert!(
+        missing.is_empty(),
+        "Rules without a complete explanation: {:?}",
+        missing
+    );
+}
+
+#[test]
+fn test_explanation_examples_match_rule() {
+    let engine = TrussEngine::new();
+    let mut analyzer = TrussEngine::new();
+    let mut failures = Vec::new();
+
+    for rule in engine.rules() {
+        let explanation = rule.explanation();
+
+        let bad = analyzer.analyze(explanation.bad_example);
+        if !bad.diagnostics.iter().any(|d| d.rule_id == rule.name()) {
+            failures.push(format!(
+                "{}: bad example does not trigger the rule",
+                rule.name()
+            ));
+        }
+
+        let good = analyzer.analyze(explanation.good_example);
+        let good_hits: Vec<_> = good
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule_id == rule.name())
+            .map(|d| d.message.as_str())
+            .collect();
+        if !good_hits.is_empty() {
+            failures.push(format!(
+                "{}: good example triggers the rule: {:?}",
+                rule.name(),
+                good_hits
+            ));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_rule_lookup_by_id() {
+    let engine = TrussEngine::new();
+
+    assert!(engine.rule("step_output_reference").is_some());
+    assert!(engine.rule("no_such_rule").is_none());
+}
+
+#[test]
+fn test_bad_example_stays_within_default_severity() {
+    let engine = TrussEngine::new();
+    let mut analyzer = TrussEngine::new();
+    let mut failures = Vec::new();
+
+    for rule in engine.rules() {
+        let bad = analyzer.analyze(rule.explanation().bad_example);
+        // Severity orders Error < Warning < Info
+        for d in bad.diagnostics.iter().filter(|d| d.rule_id == rule.name()) {
+            if d.severity < rule.default_severity() {
+                failures.push(format!(
+                    "{}: reports {:?} but its default severity is {:?}",
+                    rule.name(),
+                    d.severity,
+                    rule.default_severity()
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_rule_metadata_uses_first_sentence() {
+    let engine = TrussEngine::new();
+
+    let (_, _, severity, description) = engine
+        .rule_metadata()
+        .find(|(id, ..)| *id == "deploy_job_timeout")
+        .unwrap();
+    assert_eq!(severity, Severity::Info);
+    assert!(description.ends_with('.'));
+    assert!(
+        description.len()
+            < engine
+                .rule("deploy_job_timeout")
+                .unwrap()
+                .explanation()
+                .summary
+                .len()
+    );
+    // "e.g." doesn't end the first sentence
+    assert!(description.contains("e.g."));
+}
@@ -0,0 +1,91 @@
+//! Snapshot tests: the exact diagnostics for a set of fixture workflows.
+//!
+//! Each `tests/snapshots/<name>.yml` fixture is analyzed with every rule and
+//! the diagnostics (message, severity, span, rule id, category, related
+//! locations) are compared against the JSON in `<name>.snapshot` next to it.
+//! Any change in wording, severity, or position fails the test, so refactors
+//! can't change output by accident.
+//!
+//! After an intended change, regenerate the snapshots and review the diff:
+//!
+//! ```sh
+//! UPDATE_SNAPSHOTS=1 cargo test -p truss-core --test snapshot_fixtures
+//! ```
+//!
+//! To add a fixture, drop a `.yml` file in `tests/snapshots/`, add a test
+//! below, and run the command above to write its snapshot.
+
+use std::fs;
+use std::path::PathBuf;
+use truss_core::TrussEngine;
+
+/// Analyze `tests/snapshots/<name>.yml` and compare the diagnostics with
+/// `<name>.snapshot`, or rewrite the snapshot when `UPDATE_SNAPSHOTS=1`.
+fn assert_snapshot(name: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots");
+    let fixture = dir.join(format!("{}.yml", name));
+    let snapshot = dir.join(format!("{}.snapshot", name));
+
+    let source = fs::read_to_string(&fixture)
+        .unwrap_or_else(|e| panic!("failed to read {}: {}", fixture.display(), e));
+    let result = TrussEngine::new().analyze(&source);
+    let actual = serde_json::to_string_pretty(&result.diagnostics).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some_and(|v| v == "1") {
+        fs::write(&snapshot, &actual)
+            .unwrap_or_else(|e| panic!("failed to write {}: {}", snapshot.display(), e));
+        return;
+    }
+
+    let expected = fs::read_to_string(&snapshot).unwrap_or_else(|_| {
+        panic!(
+            "{} is missing. Run `UPDATE_SNAPSHOTS=1 cargo test -p truss-core --test snapshot_fixtures` to create it.",
+            snapshot.display()
+        )
+    });
+    if actual != expected {
+        panic!(
+            "Diagnostics for {}.yml differ from {}.snapshot:\n{}\n\
+             If the change is intended, rerun with UPDATE_SNAPSHOTS=1 and review the diff.",
+            name,
+            name,
+            line_diff(&expected, &actual)
+        );
+    }
+}
+
+/// The lines that differ between two texts, as `-expected` / `+actual`,
+/// each with its 1-based line number.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(i), actual.get(i));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            diff.push_str(&format!("{:>5} - {}\n", i + 1, old));
+        }
+        if let Some(new) = new {
+            diff.push_str(&format!("{:>5} + {}\n", i + 1, new));
+        }
+    }
+    diff
+}
+
+#[test]
+fn snapshot_matrix() {
+    assert_snapshot("matrix");
+}
+
+#[test]
+fn snapshot_reusable_workflow() {
+    assert_snapshot("reusable_workflow");
+}
+
+#[test]
+fn snapshot_expressions() {
+    assert_snapshot("expressions");
+}
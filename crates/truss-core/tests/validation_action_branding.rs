@@ -0,0 +1,128 @@
+//! Tests for ActionBrandingRule
+//!
+//! Validates `branding.icon` and `branding.color` in action metadata files.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_action_branding_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Deployer
+description: Deploys the site
+branding:
+  icon: upload-cloud
+  color: gray-dark
+runs:
+  using: composite
+  steps:
+    - run: ./deploy.sh
+      shell: bash
+"#;
+
+    let result = engine.analyze(yaml);
+    let branding_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_branding")
+        .collect();
+
+    assert!(
+        branding_errors.is_empty(),
+        "Valid branding should not produce errors, got: {:?}",
+        branding_errors
+    );
+}
+
+#[test]
+fn test_action_branding_invalid_color() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Deployer
+description: Deploys the site
+branding:
+  icon: upload-cloud
+  color: pink
+runs:
+  using: node20
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let branding_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "action_branding"
+                && d.message.contains("color 'pink'")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        branding_errors.len(),
+        1,
+        "Invalid branding color should produce one error"
+    );
+
+    let span = branding_errors[0].span;
+    assert_eq!(&yaml[span.start..span.end], "pink");
+}
+
+#[test]
+fn test_action_branding_invalid_icon() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Deployer
+description: Deploys the site
+branding:
+  icon: rocket
+  color: blue
+runs:
+  using: docker
+  image: Dockerfile
+"#;
+
+    let result = engine.analyze(yaml);
+    let branding_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_branding" && d.message.contains("icon 'rocket'"))
+        .collect();
+
+    assert_eq!(
+        branding_errors.len(),
+        1,
+        "Icon outside the Feather set should produce one error"
+    );
+}
+
+#[test]
+fn test_action_branding_excluded_feather_icon() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Deployer
+description: Deploys the site
+branding:
+  icon: coffee
+  color: "orange"
+runs:
+  using: node20
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let branding_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_branding")
+        .collect();
+
+    assert_eq!(
+        branding_errors.len(),
+        1,
+        "Feather icons excluded by GitHub should produce an error, got: {:?}",
+        branding_errors
+    );
+}
@@ -0,0 +1,140 @@
+//! Tests for ActionInputNameRule
+//!
+//! Flags `with:` keys on well-known actions that differ from a declared input
+//! only by `_` vs `-`.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_action_input_name_underscore_typo() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch_depth: 0
+"#;
+
+    let result = engine.analyze(yaml);
+    let input_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "action_input_name"
+                && d.message.contains("fetch_depth")
+                && d.message.contains("Did you mean 'fetch-depth'")
+                && d.severity == Severity::Warning
+        })
+        .collect();
+
+    assert_eq!(
+        input_warnings.len(),
+        1,
+        "'fetch_depth' should produce a warning suggesting 'fetch-depth'"
+    );
+
+    let span = input_warnings[0].span;
+    assert_eq!(&yaml[span.start..span.end], "fetch_depth");
+}
+
+#[test]
+fn test_action_input_name_hyphen_typo() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/cache@v4
+        with:
+          path: ~/.cargo
+          key: cargo-${{ hashFiles('Cargo.lock') }}
+          restore_keys: cargo-
+      - uses: actions/upload-artifact@v4
+        with: { name: dist, path: dist/, retention_days: 5 }
+"#;
+
+    let result = engine.analyze(yaml);
+    let input_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_input_name")
+        .collect();
+
+    assert_eq!(
+        input_warnings.len(),
+        2,
+        "Both misspelled inputs should be flagged, got: {:?}",
+        input_warnings
+    );
+}
+
+#[test]
+fn test_action_input_name_correct_inputs_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+          Persist-Credentials: false
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          some_unknown_input: true
+"#;
+
+    let result = engine.analyze(yaml);
+    let input_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_input_name")
+        .collect();
+
+    assert!(
+        input_warnings.is_empty(),
+        "Correct, case-different, or unrelated inputs should not be flagged, got: {:?}",
+        input_warnings
+    );
+}
+
+#[test]
+fn test_action_input_name_unknown_action_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: some-org/custom-action@v1
+        with:
+          fetch_depth: 0
+      - uses: actions/checkout/subdir@v4
+        with:
+          fetch_depth: 0
+"#;
+
+    let result = engine.analyze(yaml);
+    let input_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_input_name")
+        .collect();
+
+    assert!(
+        input_warnings.is_empty(),
+        "Actions outside the known-inputs table should be skipped, got: {:?}",
+        input_warnings
+    );
+}
@@ -0,0 +1,114 @@
+//! Tests for ActionPinningRule
+//!
+//! Requires remote actions to be pinned to a full commit SHA.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_action_pinning_tag_and_branch() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@main
+      - uses: actions/cache@1bd1e32
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_pinning")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        3,
+        "Tags, branches and short SHAs should all warn. Got: {:?}",
+        warnings
+    );
+    assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+    assert!(warnings[0].message.contains("'v4'"));
+}
+
+#[test]
+fn test_action_pinning_full_sha() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683 # v4.2.2
+      - uses: github/codeql-action/init@4e828ff8d448a8a6e532957b1811f387a63867e8
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_pinning")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Full commit SHAs should not warn. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_action_pinning_local_and_docker_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: ./.github/actions/setup
+      - uses: docker://alpine:3.19
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_pinning")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Local and docker:// references should be skipped. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_action_pinning_severity_override() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+"#;
+
+    let mut overrides = std::collections::HashMap::new();
+    overrides.insert("action_pinning".to_string(), Severity::Error);
+    let result = engine.analyze_with_config(yaml, &overrides);
+
+    assert!(result
+        .diagnostics
+        .iter()
+        .any(|d| d.rule_id == "action_pinning" && d.severity == Severity::Error));
+    assert!(!result.is_ok());
+}
@@ -571,3 +571,33 @@ jobs:
         action_errors.iter().map(|d| &d.message).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_action_reference_latest_ref() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@latest
+      - uses: docker://alpine:latest
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_reference")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Only actions/checkout@latest should warn, not the docker image tag. Got: {:?}",
+        warnings
+    );
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert!(warnings[0].message.contains("@latest"));
+}
@@ -0,0 +1,142 @@
+//! Tests for ActionRunsUsingRule
+//!
+//! Validates `runs.using` in action metadata files.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_action_runs_using_node20_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Greeter
+description: Says hello
+runs:
+  using: node20
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let using_diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_runs_using")
+        .collect();
+
+    assert!(
+        using_diagnostics.is_empty(),
+        "'node20' is a supported runtime, got: {:?}",
+        using_diagnostics
+    );
+}
+
+#[test]
+fn test_action_runs_using_node16_deprecated() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Greeter
+description: Says hello
+runs:
+  using: 'node16'
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let using_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "action_runs_using"
+                && d.message.contains("node16")
+                && d.severity == Severity::Warning
+        })
+        .collect();
+
+    assert_eq!(
+        using_warnings.len(),
+        1,
+        "'node16' should produce a deprecation warning"
+    );
+
+    let span = using_warnings[0].span;
+    assert_eq!(&yaml[span.start..span.end], "'node16'");
+}
+
+#[test]
+fn test_action_runs_using_unknown_runtime() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Greeter
+description: Says hello
+runs:
+  using: python3
+  main: main.py
+"#;
+
+    let result = engine.analyze(yaml);
+    let using_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "action_runs_using"
+                && d.message.contains("python3")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        using_errors.len(),
+        1,
+        "Unknown runtime should produce an error"
+    );
+}
+
+#[test]
+fn test_action_runs_using_missing() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Greeter
+description: Says hello
+runs:
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let using_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_runs_using" && d.message.contains("missing"))
+        .collect();
+
+    assert_eq!(
+        using_errors.len(),
+        1,
+        "'runs' without 'using' should produce an error"
+    );
+}
+
+#[test]
+fn test_action_runs_using_workflow_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let using_diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "action_runs_using")
+        .collect();
+
+    assert!(
+        using_diagnostics.is_empty(),
+        "Workflows should not be checked, got: {:?}",
+        using_diagnostics
+    );
+}
@@ -163,8 +163,5 @@ jobs:
 
     // Note: This may produce a warning or be valid depending on implementation
     // Basic format validation should pass, but potentially invalid paths might warn
-    assert!(
-        true,
-        "Potentially invalid artifact path may produce warning"
-    );
+    // Potentially invalid artifact path may produce warning
 }
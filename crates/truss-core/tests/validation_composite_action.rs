@@ -0,0 +1,155 @@
+//! Tests for CompositeActionRule
+//!
+//! Validates the `runs:` section, steps, and input references of composite actions.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_composite_action_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Setup
+description: Installs the toolchain
+inputs:
+  Version:
+    description: Toolchain version
+    default: stable
+runs:
+  using: composite
+  steps:
+    - run: ./install.sh "${{ inputs.version }}"
+      shell: bash
+"#;
+
+    let result = engine.analyze(yaml);
+    assert!(
+        result.diagnostics.is_empty(),
+        "A valid composite action should produce no diagnostics from any rule. Got: {:?}",
+        result.diagnostics
+    );
+}
+
+#[test]
+fn test_composite_action_undeclared_input() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Setup
+description: Installs the toolchain
+inputs:
+  version:
+    description: Toolchain version
+runs:
+  using: composite
+  steps:
+    - run: ./install.sh "${{ inputs.version }}" "${{ inputs.target }}"
+      shell: bash
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action")
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "Only the undeclared input should be reported. Got: {:?}",
+        errors
+    );
+    assert_eq!(errors[0].severity, Severity::Error);
+    assert!(errors[0].message.contains("'target'"));
+    assert_eq!(
+        &yaml[errors[0].span.start..errors[0].span.end],
+        "inputs.target"
+    );
+}
+
+#[test]
+fn test_composite_action_missing_steps() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Setup
+description: Installs the toolchain
+runs:
+  using: composite
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action")
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "Composite action without steps should error. Got: {:?}",
+        errors
+    );
+    assert!(errors[0].message.contains("runs.steps"));
+}
+
+#[test]
+fn test_composite_action_rule_skips_other_files() {
+    let mut engine = TrussEngine::new();
+    let node_action = r#"
+name: Node action
+description: Runs JavaScript
+runs:
+  using: node20
+  main: index.js
+"#;
+    let workflow = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ inputs.anything }}"
+"#;
+
+    for yaml in [node_action, workflow] {
+        let result = engine.analyze(yaml);
+        let diagnostics: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule_id == "composite_action")
+            .collect();
+        assert!(
+            diagnostics.is_empty(),
+            "composite_action should only run on composite actions. Got: {:?}",
+            diagnostics
+        );
+    }
+}
+
+#[test]
+fn test_composite_action_step_needs_exactly_one_of_uses_and_run() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"name: Setup
+description: Sets up the toolchain
+runs:
+  using: composite
+  steps:
+    - uses: actions/setup-node@v4
+      run: npm ci
+      shell: bash
+    - name: Nothing to do
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(errors.len(), 2, "Expected two step errors: {:?}", errors);
+    assert!(errors[0].message.contains("cannot have both"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "run");
+    assert!(errors[1].message.contains("either 'uses' or 'run'"));
+}
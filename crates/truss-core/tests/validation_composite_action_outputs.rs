@@ -0,0 +1,148 @@
+//! Tests for CompositeActionOutputsRule
+//!
+//! Validates that `outputs.<name>.value` in a composite `action.yml` references
+//! step IDs and outputs that exist in `runs.steps`.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_composite_action_outputs_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Compute version
+description: Computes the next release version
+outputs:
+  version:
+    description: The computed version
+    value: ${{ steps.compute.outputs.version }}
+runs:
+  using: composite
+  steps:
+    - id: compute
+      run: echo "version=1.2.3" >> "$GITHUB_OUTPUT"
+      shell: bash
+"#;
+
+    let result = engine.analyze(yaml);
+    let output_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action_outputs")
+        .collect();
+
+    assert!(
+        output_errors.is_empty(),
+        "Outputs referencing existing steps should not produce errors, got: {:?}",
+        output_errors
+    );
+}
+
+#[test]
+fn test_composite_action_outputs_missing_step() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Compute version
+description: Computes the next release version
+outputs:
+  version:
+    description: The computed version
+    value: ${{ steps.calculate.outputs.version }}
+runs:
+  using: composite
+  steps:
+    - id: compute
+      run: echo "version=1.2.3" >> "$GITHUB_OUTPUT"
+      shell: bash
+"#;
+
+    let result = engine.analyze(yaml);
+    let output_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "composite_action_outputs"
+                && d.message.contains("'calculate'")
+                && d.message.contains("Available step IDs: compute")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        output_errors.len(),
+        1,
+        "Output referencing a missing step should produce one error"
+    );
+
+    let span = output_errors[0].span;
+    assert_eq!(&yaml[span.start..span.end], "calculate");
+}
+
+#[test]
+fn test_composite_action_outputs_missing_output() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Compute version
+description: Computes the next release version
+outputs:
+  version:
+    description: The computed version
+    value: ${{ steps.compute.outputs.tag }}
+  artifact:
+    description: Uploaded artifact ID
+    value: ${{ steps.upload.outputs.artifact-id }}
+runs:
+  using: composite
+  steps:
+    - id: compute
+      run: echo "version=1.2.3" >> "$GITHUB_OUTPUT"
+      shell: bash
+    - id: upload
+      uses: actions/upload-artifact@v4
+      with:
+        path: dist/
+"#;
+
+    let result = engine.analyze(yaml);
+    let output_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action_outputs")
+        .collect();
+
+    assert_eq!(
+        output_errors.len(),
+        1,
+        "Only the output missing from a 'run' step should be flagged, got: {:?}",
+        output_errors
+    );
+    assert!(output_errors[0].message.contains("'tag'"));
+}
+
+#[test]
+fn test_composite_action_outputs_non_composite_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+name: Node action
+description: A JavaScript action
+outputs:
+  version:
+    description: The computed version
+runs:
+  using: node20
+  main: dist/index.js
+"#;
+
+    let result = engine.analyze(yaml);
+    let output_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "composite_action_outputs")
+        .collect();
+
+    assert!(
+        output_errors.is_empty(),
+        "Non-composite actions should be skipped, got: {:?}",
+        output_errors
+    );
+}
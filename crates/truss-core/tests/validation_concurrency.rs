@@ -403,3 +403,282 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_concurrency_pull_request_shared_group_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request
+concurrency:
+  group: ${{ github.workflow }}
+  cancel-in-progress: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let group_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "concurrency"
+                && d.message.contains("shared by all pull requests")
+                && d.severity == Severity::Warning
+        })
+        .collect();
+
+    assert_eq!(
+        group_warnings.len(),
+        1,
+        "A PR workflow with a group of only github.workflow should warn"
+    );
+}
+
+#[test]
+fn test_concurrency_pull_request_string_form_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: [push, pull_request_target]
+concurrency: ci-${{ github.workflow }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let group_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("shared by all pull requests"))
+        .collect();
+
+    assert_eq!(
+        group_warnings.len(),
+        1,
+        "The string form of concurrency should also be checked on PR workflows"
+    );
+}
+
+#[test]
+fn test_concurrency_pull_request_quoted_group_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request
+concurrency: "${{ github.workflow }}"
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    concurrency: 'ci-${{ github.workflow }}'
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let group_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("shared by all pull requests"))
+        .collect();
+
+    assert_eq!(
+        group_warnings.len(),
+        1,
+        "A quoted workflow-level group should be checked like a plain one: {:?}",
+        result.diagnostics
+    );
+    assert!(
+        group_warnings[0]
+            .message
+            .contains("'${{ github.workflow }}'"),
+        "The group should be reported without its quotes: {}",
+        group_warnings[0].message
+    );
+}
+
+#[test]
+fn test_concurrency_pull_request_specific_group_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  pull_request:
+    branches: [main]
+concurrency:
+  group: ${{ github.workflow }}-${{ github.head_ref || github.run_id }}
+  cancel-in-progress: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    concurrency:
+      group: ${{ github.workflow }}
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let group_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("shared by all pull requests"))
+        .collect();
+
+    assert!(
+        group_warnings.is_empty(),
+        "PR-specific workflow-level groups should not warn, got: {:?}",
+        group_warnings
+    );
+}
+
+#[test]
+fn test_concurrency_push_only_shared_group_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+concurrency:
+  group: ${{ github.workflow }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let group_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("shared by all pull requests"))
+        .collect();
+
+    assert!(
+        group_warnings.is_empty(),
+        "Workflows not triggered by pull requests should not warn, got: {:?}",
+        group_warnings
+    );
+}
+
+#[test]
+fn test_concurrency_group_unknown_github_property() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+concurrency:
+  group: ${{ github.workflow }}-${{ github.reff }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "concurrency" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(warnings.len(), 1, "Expected one warning: {:?}", warnings);
+    assert!(warnings[0].message.contains("'github.reff'"));
+    assert_eq!(
+        &yaml[warnings[0].span.start..warnings[0].span.end],
+        "github.reff"
+    );
+}
+
+#[test]
+fn test_concurrency_group_unknown_context() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+concurrency: ci-${{ gihub.ref }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    assert!(
+        result.diagnostics.iter().any(|d| d.rule_id == "concurrency"
+            && d.severity == Severity::Warning
+            && d.message.contains("Unknown context 'gihub'")),
+        "Typoed context in concurrency group should warn: {:?}",
+        result.diagnostics
+    );
+}
+
+#[test]
+fn test_concurrency_quoted_string_group() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request
+concurrency: "${{ github.workflow }}-${{ github.head_ref }}"
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    concurrency: 'build-${{ gihub.ref }}'
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let concurrency: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "concurrency")
+        .collect();
+
+    assert!(
+        !concurrency
+            .iter()
+            .any(|d| d.message.contains("missing required 'group'")),
+        "A quoted string is the group itself: {:?}",
+        concurrency
+    );
+    assert_eq!(
+        concurrency.len(),
+        1,
+        "Only the typoed context should be reported: {:?}",
+        concurrency
+    );
+    assert!(concurrency[0].message.contains("Unknown context 'gihub'"));
+    assert_eq!(concurrency[0].severity, Severity::Warning);
+}
+
+#[test]
+fn test_concurrency_group_context_not_available() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+concurrency:
+  group: deploy-${{ secrets.ENVIRONMENT }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    concurrency:
+      group: build-${{ matrix.os }}-${{ needs.setup.outputs.key }}-${{ steps.x.outputs.y }}
+    steps:
+      - run: echo "Building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "concurrency"
+                && d.severity == Severity::Error
+                && d.message.contains("is not available in concurrency")
+        })
+        .collect();
+
+    assert_eq!(errors.len(), 2, "Expected secrets and steps: {:?}", errors);
+    assert!(errors[0].message.contains("'secrets'"));
+    assert!(errors[1].message.contains("'steps'") && errors[1].message.contains("job 'build'"));
+}
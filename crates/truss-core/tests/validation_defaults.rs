@@ -201,3 +201,72 @@ jobs:
         "Valid defaults inheritance should not produce errors"
     );
 }
+
+#[test]
+fn test_defaults_absolute_working_directory_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+defaults:
+  run:
+    working-directory: /opt/app
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    defaults:
+      run:
+        working-directory: /srv/build
+    steps:
+      - run: make
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "defaults" && d.message.contains("absolute path"))
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "Absolute working-directory at workflow and job level should each warn"
+    );
+    assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+    assert!(warnings[0].message.starts_with("workflow"));
+    assert!(warnings[1].message.contains("job 'build'"));
+}
+
+#[test]
+fn test_defaults_custom_shell_template() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+defaults:
+  run:
+    shell: bash --noprofile --norc -eo pipefail {0}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    defaults:
+      run:
+        shell: "{0}"
+    steps:
+      - run: echo "Test"
+"#;
+
+    let result = engine.analyze(yaml);
+    let shell_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "defaults" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(
+        shell_errors.len(),
+        1,
+        "Only the bare {{0}} template should be rejected: {:?}",
+        shell_errors
+    );
+    assert!(shell_errors[0].message.contains("{0}"));
+}
@@ -0,0 +1,123 @@
+//! Tests for DeployJobTimeoutRule
+//!
+//! Recommends `timeout-minutes` on jobs that deploy or publish.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_deploy_job_timeout_run_command_without_timeout() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: |
+          terraform init
+          terraform apply -auto-approve
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deploy_job_timeout")
+        .collect();
+
+    assert_eq!(
+        infos.len(),
+        1,
+        "terraform apply without timeout-minutes should be reported. Got: {:?}",
+        infos
+    );
+    assert_eq!(infos[0].severity, Severity::Info);
+    assert!(infos[0].message.contains("deploy"));
+}
+
+#[test]
+fn test_deploy_job_timeout_action_without_timeout() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  pages:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/deploy-pages@v4
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: pypa/gh-action-pypi-publish@release/v1
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deploy_job_timeout")
+        .collect();
+
+    assert_eq!(
+        infos.len(),
+        2,
+        "Deploy and publish actions should be reported. Got: {:?}",
+        infos
+    );
+}
+
+#[test]
+fn test_deploy_job_timeout_with_timeout() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    timeout-minutes: 20
+    steps:
+      - run: kubectl apply -f k8s/
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deploy_job_timeout")
+        .collect();
+
+    assert!(
+        infos.is_empty(),
+        "Deploy job with timeout-minutes should not be reported. Got: {:?}",
+        infos
+    );
+}
+
+#[test]
+fn test_deploy_job_timeout_ordinary_job() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deploy_job_timeout")
+        .collect();
+
+    assert!(
+        infos.is_empty(),
+        "A job without deploy steps should not be reported. Got: {:?}",
+        infos
+    );
+}
@@ -0,0 +1,124 @@
+//! Tests for DeprecatedActionRule
+//!
+//! Detects `uses:` references to archived or renamed actions.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_deprecated_action_create_release() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/create-release@v1
+        with:
+          tag_name: v1.0.0
+"#;
+
+    let result = engine.analyze(yaml);
+    let deprecated_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "deprecated_action"
+                && d.message.contains("actions/create-release")
+                && d.message.contains("softprops/action-gh-release")
+                && d.severity == Severity::Warning
+        })
+        .collect();
+
+    assert_eq!(
+        deprecated_warnings.len(),
+        1,
+        "actions/create-release should produce a warning suggesting its replacement"
+    );
+}
+
+#[test]
+fn test_deprecated_action_setup_ruby_moved() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: "actions/setup-ruby@v1"
+"#;
+
+    let result = engine.analyze(yaml);
+    let deprecated_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deprecated_action" && d.message.contains("ruby/setup-ruby"))
+        .collect();
+
+    assert_eq!(
+        deprecated_warnings.len(),
+        1,
+        "actions/setup-ruby should produce a warning suggesting ruby/setup-ruby"
+    );
+}
+
+#[test]
+fn test_deprecated_action_current_actions_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: ruby/setup-ruby@v1
+      - uses: softprops/action-gh-release@v2
+      - uses: actions/create-release-notes@v1
+      - uses: ./.github/actions/create-release
+"#;
+
+    let result = engine.analyze(yaml);
+    let deprecated_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "deprecated_action")
+        .collect();
+
+    assert!(
+        deprecated_warnings.is_empty(),
+        "Maintained actions should not produce warnings, got: {:?}",
+        deprecated_warnings
+    );
+}
+
+#[test]
+fn test_deprecated_action_case_insensitive() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: Actions-RS/Toolchain@v1
+"#;
+
+    let result = engine.analyze(yaml);
+    let deprecated_warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "deprecated_action" && d.message.contains("dtolnay/rust-toolchain")
+        })
+        .collect();
+
+    assert_eq!(
+        deprecated_warnings.len(),
+        1,
+        "Action names are case-insensitive on GitHub"
+    );
+}
@@ -0,0 +1,82 @@
+//! Tests for DuplicateKeyRule
+//!
+//! Detects keys that appear more than once in the same mapping.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn duplicate_key(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "duplicate_key")
+        .unwrap()
+}
+
+#[test]
+fn test_duplicate_key_in_nested_mappings() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        env: { MODE: debug, MODE: release }
+    runs-on: windows-latest
+"#;
+
+    let diagnostics = duplicate_key(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    assert!(diagnostics[0].message.contains("Duplicate key 'MODE'"));
+    assert!(diagnostics[1].message.contains("Duplicate key 'runs-on'"));
+    // The span is the second occurrence, the related location the first
+    let second = &diagnostics[1];
+    assert_eq!(second.span.start, yaml.rfind("runs-on").unwrap());
+    assert_eq!(second.related[0].span.start, yaml.find("runs-on").unwrap());
+}
+
+#[test]
+fn test_duplicate_key_sibling_mappings() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: one
+        run: make
+      - name: two
+        run: make test
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make test
+"#;
+
+    let diagnostics = duplicate_key(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "The same key in different mappings is not a duplicate: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_duplicate_key_leaves_job_ids_to_job_name() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+  build:
+    runs-on: ubuntu-latest
+"#;
+
+    let mut engine = TrussEngine::new();
+    assert!(engine
+        .analyze_rule(yaml, "duplicate_key")
+        .unwrap()
+        .is_empty());
+    let job_name = engine.analyze_rule(yaml, "job_name").unwrap();
+    assert!(job_name.iter().any(|d| d.message.contains("duplicate job")));
+}
@@ -4,6 +4,7 @@
 //!
 //! Validates environment references in GitHub Actions workflows.
 
+use truss_core::AnalysisOptions;
 use truss_core::Severity;
 use truss_core::TrussEngine;
 
@@ -82,6 +83,45 @@ jobs:
     );
 }
 
+#[test]
+fn test_environment_url_not_absolute() {
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    environment:
+      name: production
+      url: /dashboard
+  preview:
+    runs-on: ubuntu-latest
+    environment: { name: preview, url: 'example.com' }
+  staging:
+    runs-on: ubuntu-latest
+    environment:
+      name: staging
+      url: ${{ steps.deploy.outputs.url }}
+  docs:
+    runs-on: ubuntu-latest
+    environment:
+      name: docs
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "environment")
+        .unwrap();
+
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    assert!(diagnostics[0]
+        .message
+        .contains("Environment url '/dashboard' is not an http(s) URL"));
+    assert_eq!(
+        &yaml[diagnostics[1].span.start..diagnostics[1].span.end],
+        "'example.com'"
+    );
+}
+
 #[test]
 fn test_environment_valid_workflow_level() {
     let mut engine = TrussEngine::new();
@@ -193,3 +233,145 @@ jobs:
         "Environment protection rules should be validated"
     );
 }
+
+#[test]
+fn test_environment_same_name_from_matrix() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        region: [us-east-1, eu-west-1]
+    environment:
+      name: production
+    steps:
+      - run: ./deploy.sh ${{ matrix.region }}
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "environment" && d.severity == Severity::Info)
+        .collect();
+
+    assert_eq!(
+        infos.len(),
+        1,
+        "Matrix job deploying to one environment should produce an info. Got: {:?}",
+        infos
+    );
+    assert!(infos[0].message.contains("'production'"));
+}
+
+#[test]
+fn test_environment_per_leg_from_matrix() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        env: [staging, production]
+    environment: ${{ matrix.env }}
+    steps:
+      - run: ./deploy.sh
+  release:
+    runs-on: ubuntu-latest
+    environment: production
+    steps:
+      - run: ./release.sh
+"#;
+
+    let result = engine.analyze(yaml);
+    let infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "environment")
+        .collect();
+
+    assert!(
+        infos.is_empty(),
+        "Per-leg environments and non-matrix jobs should not be reported. Got: {:?}",
+        infos
+    );
+}
+
+#[test]
+fn test_environment_not_in_allowed_list() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    environment: prodution
+    steps:
+      - run: ./deploy.sh
+  release:
+    runs-on: ubuntu-latest
+    environment:
+      name: Staging
+    steps:
+      - run: ./release.sh
+  preview:
+    runs-on: ubuntu-latest
+    environment: ${{ github.head_ref }}
+    steps:
+      - run: ./preview.sh
+"#;
+
+    let options = AnalysisOptions {
+        allowed_environments: vec!["staging".to_string(), "production".to_string()],
+        ..AnalysisOptions::default()
+    };
+    let result = engine.analyze_with_options(yaml, &options);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "environment" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Only the misspelled environment should warn, got: {:?}",
+        warnings
+    );
+    assert_eq!(
+        &yaml[warnings[0].span.start..warnings[0].span.end],
+        "prodution"
+    );
+}
+
+#[test]
+fn test_environment_any_name_without_allowed_list() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    environment: anything-goes
+    steps:
+      - run: ./deploy.sh
+"#;
+
+    let result = engine.analyze_with_options(yaml, &AnalysisOptions::default());
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "environment")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "An empty allowed_environments list should accept any name, got: {:?}",
+        warnings
+    );
+}
@@ -521,3 +521,134 @@ on:
         type_errors.iter().map(|d| &d.message).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_workflow_run_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_run:
+    workflows: [CI, "Build docs"]
+    types: [requested, completed, in_progress]
+    branches: [main]
+"#;
+
+    let result = engine.analyze(yaml);
+    let workflow_run_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("workflow_run") && d.severity == Severity::Error)
+        .collect();
+
+    assert!(
+        workflow_run_errors.is_empty(),
+        "Valid workflow_run trigger should not produce errors: {:?}",
+        workflow_run_errors
+            .iter()
+            .map(|d| &d.message)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_workflow_run_missing_workflows() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_run:
+    types: [completed]
+    branches: [main]
+    branches-ignore: [dev]
+"#;
+
+    let result = engine.analyze(yaml);
+    let missing: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("requires 'workflows'"))
+        .collect();
+
+    assert_eq!(missing.len(), 1, "{:?}", result.diagnostics);
+    assert_eq!(missing[0].severity, Severity::Error);
+    assert!(result.diagnostics.iter().any(|d| {
+        d.message.contains("mutually exclusive") && &yaml[d.span.start..d.span.end] == "[dev]"
+    }));
+}
+
+#[test]
+fn test_workflow_run_without_value_missing_workflows() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  push:
+  workflow_run:
+"#;
+
+    let result = engine.analyze(yaml);
+    let missing: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("requires 'workflows'"))
+        .collect();
+
+    assert_eq!(missing.len(), 1, "{:?}", result.diagnostics);
+    assert_eq!(
+        &yaml[missing[0].span.start..missing[0].span.end],
+        "workflow_run"
+    );
+}
+
+#[test]
+fn test_workflow_run_invalid_type_and_field() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_run:
+    workflows: [CI]
+    types: [completed, finished]
+    paths: ['src/**']
+"#;
+
+    let result = engine.analyze(yaml);
+    let type_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("Invalid workflow_run activity type"))
+        .collect();
+
+    assert_eq!(type_errors.len(), 1, "{:?}", result.diagnostics);
+    assert_eq!(type_errors[0].severity, Severity::Error);
+    assert_eq!(
+        &yaml[type_errors[0].span.start..type_errors[0].span.end],
+        "finished"
+    );
+    assert!(result.diagnostics.iter().any(|d| d
+        .message
+        .contains("Invalid field 'paths' for workflow_run event")));
+}
+
+#[test]
+fn test_workflow_run_quoted_types() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_run:
+    workflows: [CI]
+    types: ["completed", 'requested', "complted", 'reqested']
+"#;
+
+    let result = engine.analyze(yaml);
+    let type_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("Invalid workflow_run activity type"))
+        .map(|d| &yaml[d.span.start..d.span.end])
+        .collect();
+
+    assert_eq!(
+        type_errors,
+        vec!["\"complted\"", "'reqested'"],
+        "{:?}",
+        result.diagnostics
+    );
+}
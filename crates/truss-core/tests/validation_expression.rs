@@ -317,3 +317,206 @@ jobs:
         expr_errors.iter().map(|d| &d.message).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_expression_format_placeholder_out_of_range() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ format('{0} {2}', github.ref, github.sha) }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "expression" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "format() with '{{2}}' and two arguments should error. Got: {:?}",
+        errors
+    );
+    assert!(errors[0].message.contains("'{2}'"));
+}
+
+#[test]
+fn test_expression_format_skipped_placeholder() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ format('{0}-{2}', github.ref, github.sha, github.actor) }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "expression" && d.message.contains("skips placeholder '{1}'"))
+        .collect();
+
+    assert_eq!(errors.len(), 1, "Skipped placeholder should error");
+}
+
+#[test]
+fn test_expression_format_valid_calls() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ format('{0}-{1}', github.ref, github.sha) }}"
+      - run: echo "${{ format('{{0}} {0}, {1}', join(github.event.commits.*.id, ', '), github.sha) }}"
+      - run: echo "${{ format(env.TEMPLATE, github.sha) }}"
+      - run: echo "${{ format('{0}/{0}', format('{0}-{1}', github.ref, github.sha)) }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "expression")
+        .collect();
+
+    assert!(
+        errors.is_empty(),
+        "Matching placeholders, escaped braces, computed templates and nested calls should not error. Got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_expression_single_equals_comparison() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ github.ref = 'refs/heads/main' }}
+        run: echo "main"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "expression"
+                && d.severity == Severity::Error
+                && d.message.contains("assignment operator")
+        })
+        .collect();
+
+    assert_eq!(errors.len(), 1, "Single '=' should be an error");
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "=");
+}
+
+#[test]
+fn test_expression_equals_in_strings_and_operators_not_flagged() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ github.ref == 'refs/heads/a=b' && github.run_number >= 2 }}
+        run: echo ${{ format('--tag={0} --name={1}', github.sha, github.ref_name) }}
+      - if: ${{ github.run_attempt <= 3 || github.actor != 'bot=it''s' }}
+        run: echo ok
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("assignment operator"))
+        .collect();
+
+    assert!(
+        errors.is_empty(),
+        "'=' inside string literals or comparison operators should not be flagged, got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_expression_function_wrong_argument_count() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ contains(github.event.pull_request.labels.*.name) }}
+        run: echo ${{ join(github.event.commits.*.id, ', ', 'x') }}
+      - if: ${{ StartsWith(github.ref, 'refs/tags/', 'v') || success('x') }}
+        run: echo ${{ hashFiles() }} ${{ toJSON() }}
+"#;
+
+    let warnings: Vec<_> = TrussEngine::new()
+        .analyze_rule(yaml, "expression")
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.message.contains("() takes"))
+        .collect();
+
+    let messages: Vec<&str> = warnings.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "contains() takes 2 arguments, but 1 was given",
+            "join() takes 1 to 2 arguments, but 3 were given",
+            "startsWith() takes 2 arguments, but 3 were given",
+            "success() takes 0 arguments, but 1 was given",
+            "hashFiles() takes at least 1 argument, but 0 were given",
+            "toJSON() takes 1 argument, but 0 were given",
+        ]
+    );
+    assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+    assert_eq!(
+        &yaml[warnings[0].span.start..warnings[0].span.end],
+        "contains"
+    );
+}
+
+#[test]
+fn test_expression_function_argument_count_respects_nesting_and_quotes() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ contains(fromJSON('["a, b", "c"]'), format('{0},{1}', github.ref, github.sha)) }}
+        run: echo ${{ format('{0}-{1}', github.ref, github.sha) }}
+      - if: ${{ always() && startsWith(github.ref, 'refs/tags/(v1, v2)') }}
+        run: echo ${{ hashFiles('**/package-lock.json', '**/yarn.lock') }} ${{ join(matrix.os) }}
+"#;
+
+    let warnings: Vec<_> = TrussEngine::new()
+        .analyze_rule(yaml, "expression")
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.message.contains("() takes"))
+        .collect();
+    assert!(
+        warnings.is_empty(),
+        "Commas inside strings and nested calls should not count as arguments: {:?}",
+        warnings
+    );
+}
@@ -0,0 +1,176 @@
+//! Tests for FilterPatternRule
+//!
+//! Warns on `branches`, `tags`, and `paths` filter patterns that likely don't
+//! match what was meant in GitHub's glob dialect.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn filter_pattern(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "filter_pattern")
+        .unwrap()
+}
+
+#[test]
+fn test_filter_pattern_single_star() {
+    let yaml = r#"
+on:
+  push:
+    branches: ['*']
+    paths:
+      - 'docs/*'
+      - '*.md'
+  pull_request:
+    paths-ignore: ['*']
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = filter_pattern(yaml);
+    assert_eq!(diagnostics.len(), 4, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    assert!(diagnostics[0]
+        .message
+        .contains("Filter pattern '*' in 'on.push.branches'"));
+    assert!(diagnostics[0]
+        .message
+        .contains("Use '**' to match all branches"));
+    assert!(diagnostics[1].message.contains("Use 'docs/**'"));
+    assert!(diagnostics[2].message.contains("Use '**.md'"));
+    assert!(diagnostics[3]
+        .message
+        .contains("only matches files at the repository root"));
+    assert_eq!(
+        &yaml[diagnostics[1].span.start..diagnostics[1].span.end],
+        "'docs/*'"
+    );
+}
+
+#[test]
+fn test_filter_pattern_special_characters_and_empty_entries() {
+    let yaml = r#"
+on:
+  push:
+    tags:
+      - 'v1.?'
+      - 'release+hotfix'
+      - 'docs!draft'
+      - 'build[x86'
+      - ''
+      -
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = filter_pattern(yaml);
+    assert_eq!(diagnostics.len(), 6, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("'?' matches zero or one"));
+    assert!(diagnostics[1].message.contains("'+' matches one or more"));
+    assert!(diagnostics[2].message.contains("'!' only negates"));
+    assert!(diagnostics[3].message.contains("never closed"));
+    assert!(diagnostics[4].message.contains("It is empty"));
+    assert!(diagnostics[5].message.contains("It is empty"));
+}
+
+#[test]
+fn test_filter_pattern_valid_patterns() {
+    let yaml = r#"
+on:
+  push:
+    branches:
+      - main
+      - 'releases/**'
+      - 'feature/*'
+      - '!releases/**-alpha'
+    tags:
+      - 'v[0-9]+.[0-9]+.[0-9]+'
+      - 'v1.0\+build'
+    paths:
+      - '**.js'
+      - 'src/**'
+      - 'docs/*.md'
+      - '!docs/**'
+      - ${{ github.event.inputs.path }}
+  workflow_run:
+    workflows: ['*']
+    branches: [main]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = filter_pattern(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Patterns that use GitHub's glob syntax as intended should not warn: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_filter_pattern_backslash_paths() {
+    let yaml = r#"
+on:
+  push:
+    paths:
+      - 'src\\**'
+      - 'docs\**'
+      - 'app\main.rs'
+      - 'docs/\[draft\].md'
+    tags: ['v1.0\+build']
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = filter_pattern(yaml);
+    assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.message.contains("matched with '/' separators")));
+    assert!(diagnostics[0].message.contains("Use 'src/**'"));
+    assert!(diagnostics[1].message.contains("Use 'docs/**'"));
+    assert!(diagnostics[2].message.contains("Use 'app/main.rs'"));
+}
+
+#[test]
+fn test_filter_pattern_lone_double_star_paths() {
+    let yaml = r#"
+on:
+  push:
+    paths: ['**']
+  pull_request:
+    paths-ignore:
+      - '**'
+  workflow_dispatch:
+  release:
+    types: [published]
+  pull_request_target:
+    paths:
+      - '**'
+      - '!docs/**'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = filter_pattern(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("doesn't narrow anything"));
+    assert!(diagnostics[1]
+        .message
+        .contains("never triggers the workflow"));
+}
@@ -305,3 +305,194 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_job_container_shorthand_matches_object() {
+    let forms = [
+        ("container: node:18", "container: { image: node:18 }"),
+        (
+            "container: \"node:18\"",
+            "container:\n      image: \"node:18\"",
+        ),
+        ("container: ''", "container:\n      image: ''"),
+    ];
+    for (shorthand, object) in forms {
+        let diagnostics = |form: &str| {
+            let yaml = format!(
+                "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    {}\n    steps:\n      - run: make\n",
+                form
+            );
+            TrussEngine::new()
+                .analyze(&yaml)
+                .diagnostics
+                .into_iter()
+                .filter(|d| d.rule_id == "job_container")
+                .map(|d| (d.severity, d.message))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            diagnostics(shorthand),
+            diagnostics(object),
+            "{} vs {}",
+            shorthand,
+            object
+        );
+    }
+
+    let yaml = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    container: \"\"\n    steps:\n      - run: make\n";
+    let result = TrussEngine::new().analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_container")
+        .collect();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].severity, Severity::Error);
+    assert!(errors[0].message.contains("container has empty image"));
+}
+
+fn container_diagnostics(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "job_container")
+        .unwrap()
+}
+
+#[test]
+fn test_job_container_image_references() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    container: ghcr.io/owner/image:1.2.3@sha256:0123abcd
+    services:
+      registry:
+        image: localhost:5000/cache
+      db:
+        image: ${{ matrix.db }}
+      redis:
+        image: Redis:7
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = container_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .contains("Job 'build' service 'redis' image 'Redis:7' is not a valid image reference"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "Redis:7"
+    );
+}
+
+#[test]
+fn test_job_container_latest_tag_warning() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    container:
+      image: node:latest
+    services:
+      db:
+        image: postgres
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = container_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].message.contains("uses the 'latest' tag"));
+}
+
+#[test]
+fn test_job_container_latest_tag_shorthand_matches_object() {
+    let forms = [
+        "container: node:latest",
+        "container: \"node:latest\"",
+        "container:\n      image: node:latest",
+        "container: { image: node:latest }",
+    ];
+    for form in forms {
+        let yaml = format!(
+            "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    {}\n    steps:\n      - run: make\n",
+            form
+        );
+
+        let diagnostics = container_diagnostics(&yaml);
+        assert_eq!(diagnostics.len(), 1, "{}: {:?}", form, diagnostics);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert_eq!(
+            diagnostics[0].message,
+            "Job 'build' container image 'node:latest' uses the 'latest' tag, so the image can change between runs. Pin a version tag or digest.",
+            "{}",
+            form
+        );
+        let image = &yaml[diagnostics[0].span.start..diagnostics[0].span.end];
+        assert_eq!(image.trim_matches('"'), "node:latest", "{}", form);
+    }
+}
+
+#[test]
+fn test_job_container_service_missing_image() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    services:
+      postgres:
+        env:
+          POSTGRES_PASSWORD: postgres
+    steps:
+      - uses: some/action@v1
+        with:
+          container: ""
+"#;
+
+    let diagnostics = container_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert!(diagnostics[0]
+        .message
+        .contains("Job 'build' service 'postgres' is missing required 'image' field"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "postgres"
+    );
+}
+
+#[test]
+fn test_job_container_port_specs() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    services:
+      db:
+        image: postgres:16
+        ports:
+          - 5432
+          - 8080:80
+          - 53/udp
+          - ${{ matrix.port }}
+          - 70000:80
+          - db:5432
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = container_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("'70000:80'"));
+    assert_eq!(
+        &yaml[diagnostics[1].span.start..diagnostics[1].span.end],
+        "db:5432"
+    );
+}
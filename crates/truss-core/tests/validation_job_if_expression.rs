@@ -293,3 +293,248 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_job_if_expression_secrets_not_allowed() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    if: secrets['DEPLOY_TOKEN'] != ''
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Deploy"
+"#;
+
+    let result = engine.analyze(yaml);
+    let secrets_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "job_if_expression"
+                && d.message
+                    .contains("The secrets context is not available in if conditions")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        secrets_errors.len(),
+        1,
+        "secrets in a job 'if' should produce one error"
+    );
+}
+
+#[test]
+fn test_job_if_expression_single_equals() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    if: github.event_name = 'push'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Deploy"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_if_expression" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(errors.len(), 1, "Bare single '=' in job if should error");
+    assert!(errors[0].message.contains("'=' instead of '=='"));
+}
+
+#[test]
+fn test_job_if_expression_always_false_literal() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  disabled:
+    if: ${{ false }}
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Never"
+  also_disabled:
+    if: github.ref == 'refs/heads/main' && false
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Never"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_if_expression" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "Always-false job conditions should warn, got: {:?}",
+        warnings
+    );
+    assert!(warnings.iter().all(|d| d.message.contains("never runs")));
+}
+
+#[test]
+fn test_job_if_expression_contradictory_equality() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: [push, pull_request]
+jobs:
+  unreachable:
+    if: github.event_name == 'push' && github.event_name == 'pull_request'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Never"
+  negated:
+    if: (matrix.os == 'linux') && matrix.os != 'Linux'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Never"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "job_if_expression" && d.message.contains("always evaluate to false")
+        })
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "Contradictory equality checks should warn, got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_job_if_expression_satisfiable_conditions_no_false_warning() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: [push, pull_request]
+jobs:
+  either:
+    if: github.event_name == 'push' || github.event_name == 'pull_request'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Either"
+  different_operands:
+    if: github.event_name == 'push' && github.ref == 'refs/heads/main'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Both"
+  same_value_different_case:
+    if: github.event_name == 'push' && github.event_name == 'Push'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Case-insensitive"
+  coerced:
+    if: inputs.count == 1 && inputs.count == '1'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Coerced"
+  in_string:
+    if: contains(github.event.head_commit.message, 'a && b') && github.ref == 'refs/heads/main'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "String"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_if_expression" && d.message.contains("always evaluate"))
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Satisfiable conditions should not warn, got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_job_if_expression_text_outside_braces() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "build"
+  deploy:
+    needs: build
+    if: github.event_name == 'push' && ${{ needs.build.result == 'success' }}
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "deploy"
+  notify:
+    needs: build
+    if: ${{ always() }}
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "notify"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "job_if_expression")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0]
+        .message
+        .contains("Job 'deploy' 'if' condition has"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "github.event_name == 'push' &&"
+    );
+}
+
+#[test]
+fn test_job_if_expression_event_name_not_in_triggers() {
+    let yaml = r#"
+on: [pull_request]
+jobs:
+  deploy:
+    if: github.event_name == 'push'
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "deploy"
+  review:
+    if: ${{ 'Pull_Request' == github.event_name || github.event_name != 'schedule' }}
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "review"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "job_if_expression")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].message.contains("isn't triggered by 'push'"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "push"
+    );
+
+    // A called workflow sees its caller's event
+    let reusable = yaml.replace("[pull_request]", "[pull_request, workflow_call]");
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(&reusable, "job_if_expression")
+        .unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
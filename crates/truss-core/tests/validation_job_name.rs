@@ -138,3 +138,109 @@ jobs:
         "Reserved job names should produce error"
     );
 }
+
+#[test]
+fn test_job_name_matches_field_name() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+  steps:
+    - run: make
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_name" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Job ID 'steps' should warn about mis-indentation. Got: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("mis-indented"));
+    let span_text = &yaml[warnings[0].span.start..warnings[0].span.end];
+    assert_eq!(span_text, "steps");
+}
+
+#[test]
+fn test_job_name_similar_to_field_name_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  run-tests:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_name")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Job IDs that only contain a field name should not warn. Got: {:?}",
+        warnings
+    );
+}
+
+fn job_name_diagnostics(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new().analyze_rule(yaml, "job_name").unwrap()
+}
+
+#[test]
+fn test_job_name_must_start_with_letter_or_underscore() {
+    let yaml = r#"
+on: push
+jobs:
+  1-build:
+    runs-on: ubuntu-latest
+  _lint:
+    runs-on: ubuntu-latest
+  -deploy:
+    runs-on: ubuntu-latest
+  tést:
+    runs-on: ubuntu-latest
+"#;
+
+    let diagnostics = job_name_diagnostics(yaml);
+    let flagged: Vec<_> = diagnostics
+        .iter()
+        .map(|d| &yaml[d.span.start..d.span.end])
+        .collect();
+    assert_eq!(flagged, ["1-build", "-deploy", "tést"], "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    assert!(diagnostics[0]
+        .message
+        .contains("must start with a letter or '_'"));
+}
+
+#[test]
+fn test_job_name_length_limit() {
+    let at_limit = "a".repeat(100);
+    let too_long = "b".repeat(101);
+    let yaml = format!(
+        "on: push\njobs:\n  {}:\n    runs-on: ubuntu-latest\n  {}:\n    runs-on: ubuntu-latest\n",
+        at_limit, too_long
+    );
+
+    let diagnostics = job_name_diagnostics(&yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].message.contains("at most 100 characters"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        too_long
+    );
+}
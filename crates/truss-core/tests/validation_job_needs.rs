@@ -112,7 +112,7 @@ jobs:
     let circular_errors: Vec<_> = result
         .diagnostics
         .iter()
-        .filter(|d| d.message.contains("circular") || d.message.contains("dependency"))
+        .filter(|d| d.message.contains("Circular") || d.message.contains("dependency"))
         .collect();
 
     assert!(
@@ -120,10 +120,10 @@ jobs:
         "Circular dependency should produce error"
     );
     assert!(
-        circular_errors
-            .iter()
-            .any(|d| d.message.contains("circular")),
-        "Error message should mention 'circular'"
+        circular_errors.iter().any(|d| d
+            .message
+            .contains("Circular dependency detected: job1 → job2 → job1")),
+        "Error message should name the cycle"
     );
 }
 
@@ -157,3 +157,68 @@ jobs:
         "Error message should mention 'self'"
     );
 }
+
+#[test]
+fn test_job_needs_longer_cycle_path() {
+    let yaml = r#"
+on: push
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+  a:
+    needs: [lint, c]
+    runs-on: ubuntu-latest
+  b:
+    needs: a
+    runs-on: ubuntu-latest
+  c:
+    needs: b
+    runs-on: ubuntu-latest
+"#;
+
+    let diagnostics = TrussEngine::new().analyze_rule(yaml, "job_needs").unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .starts_with("Circular dependency detected: a → c → b → a"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "[lint, c]"
+    );
+    let related: Vec<&str> = diagnostics[0]
+        .related
+        .iter()
+        .map(|r| r.message.as_str())
+        .collect();
+    assert_eq!(
+        related,
+        ["Job 'c' needs 'b' here", "Job 'b' needs 'a' here"]
+    );
+}
+
+#[test]
+fn test_job_needs_diamond_is_not_a_cycle() {
+    let yaml = r#"
+on: push
+jobs:
+  a:
+    needs: [b, c]
+    runs-on: ubuntu-latest
+  b:
+    needs: d
+    runs-on: ubuntu-latest
+  c:
+    needs: d
+    runs-on: ubuntu-latest
+  d:
+    runs-on: ubuntu-latest
+"#;
+
+    let diagnostics = TrussEngine::new().analyze_rule(yaml, "job_needs").unwrap();
+    assert!(
+        diagnostics.is_empty(),
+        "Diamond dependencies should not be reported: {:?}",
+        diagnostics
+    );
+}
@@ -335,3 +335,74 @@ jobs:
         "Job output with conditional expression referencing valid step IDs should not produce errors"
     );
 }
+
+#[test]
+fn test_job_outputs_output_not_set_by_step() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.ver.outputs.version }}
+    steps:
+      - id: ver
+        run: echo "tag=1.0" >> "$GITHUB_OUTPUT"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error && d.message.contains("never sets"))
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "Job output reading an output its step never sets should produce exactly one error, got: {:?}",
+        result.diagnostics
+    );
+    assert_eq!(errors[0].rule_id, "job_outputs");
+    assert!(errors[0].message.contains("Outputs it sets: tag"));
+}
+
+#[test]
+fn test_job_outputs_non_step_contexts_and_unknown_outputs_pass() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  setup:
+    runs-on: ubuntu-latest
+    outputs:
+      sha: ${{ steps.meta.outputs.sha }}
+    steps:
+      - id: meta
+        uses: docker/metadata-action@v5
+  build:
+    needs: setup
+    runs-on: ubuntu-latest
+    env:
+      TARGET: linux
+    outputs:
+      target: ${{ env.TARGET }}
+      sha: ${{ needs.setup.outputs.sha }}
+    steps:
+      - run: echo "building"
+"#;
+
+    let result = engine.analyze(yaml);
+    let output_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "job_outputs")
+        .collect();
+
+    assert!(
+        output_errors.is_empty(),
+        "Outputs from actions, env, or needs can't be checked and should not produce errors, got: {:?}",
+        output_errors
+    );
+}
@@ -264,3 +264,102 @@ jobs:
         "Number fail-fast value should produce error"
     );
 }
+
+#[test]
+fn test_job_strategy_max_parallel_exceeds_matrix_size() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      max-parallel: 10
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        node: [18, 20]
+"#;
+
+    let result = engine.analyze(yaml);
+    let size_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.message.contains("max-parallel")
+                && d.message.contains("4 job(s)")
+                && d.severity == Severity::Info
+        })
+        .collect();
+
+    assert_eq!(
+        size_infos.len(),
+        1,
+        "max-parallel larger than the 4-job matrix should produce an info diagnostic"
+    );
+    let span = size_infos[0].span;
+    assert_eq!(&yaml[span.start..span.end], "10");
+}
+
+#[test]
+fn test_job_strategy_max_parallel_within_matrix_size() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      max-parallel: 4
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        node: [18, 20]
+        exclude:
+          - os: windows-latest
+            node: 18
+        include:
+          - os: macos-latest
+            node: 20
+          - os: ubuntu-latest
+            experimental: true
+"#;
+
+    let result = engine.analyze(yaml);
+    let size_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("max-parallel") && d.severity == Severity::Info)
+        .collect();
+
+    assert!(
+        size_infos.is_empty(),
+        "Matrix expands to 4 jobs (4 - 1 excluded + 1 new include), so max-parallel: 4 is fine, got: {:?}",
+        size_infos
+    );
+}
+
+#[test]
+fn test_job_strategy_max_parallel_dynamic_matrix_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      max-parallel: 50
+      matrix:
+        os: ${{ fromJSON(needs.setup.outputs.os) }}
+"#;
+
+    let result = engine.analyze(yaml);
+    let size_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("max-parallel") && d.severity == Severity::Info)
+        .collect();
+
+    assert!(
+        size_infos.is_empty(),
+        "Dynamic matrices have an unknown size and should be skipped"
+    );
+}
@@ -2,6 +2,7 @@
 //!
 //! Validates matrix strategy syntax in GitHub Actions workflows.
 
+use truss_core::AnalysisOptions;
 use truss_core::Severity;
 use truss_core::TrussEngine;
 
@@ -210,3 +211,239 @@ jobs:
         "Invalid exclude syntax (not an array) should produce error"
     );
 }
+
+#[test]
+fn test_matrix_exclude_value_not_in_base() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        node: [18, 20]
+        exclude:
+          - os: macos-latest
+            node: 18
+    steps:
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_strategy" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Exclude value absent from the base list should warn once. Got: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("macos-latest"));
+}
+
+#[test]
+fn test_matrix_exclude_key_not_in_base() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        node: [18, 20]
+        include:
+          - node: 20
+            experimental: true
+        exclude:
+          - experimental: true
+    steps:
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_strategy" && d.message.contains("experimental"))
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Exclude key that only comes from include should warn. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_matrix_exclude_key_typo_with_computed_dimension() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: ${{ fromJSON(vars.OSES) }}
+        node: [18, 20]
+        exclude:
+          - os: windows-latest
+            nod: 20
+    steps:
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_strategy")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Only the misspelled exclude key should warn. Got: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("'nod'"));
+    let start = yaml.find("nod:").unwrap();
+    assert_eq!(warnings[0].span.start, start);
+    assert_eq!(warnings[0].span.end, start + "nod".len());
+}
+
+#[test]
+fn test_matrix_exclude_matching_values_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        node: [18, "20"]
+        exclude:
+          - os: windows-latest
+            node: "18"
+          - node: 20
+    steps:
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_strategy")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Exclude entries drawn from the base lists should not warn. Got: {:?}",
+        warnings
+    );
+}
+
+/// A workflow whose matrix has `a` and `b` dimensions of the given sizes,
+/// followed by `extra` lines inside the matrix.
+fn matrix_workflow(a: usize, b: usize, extra: &str) -> String {
+    let values = |n: usize| {
+        (1..=n)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    format!(
+        "on: push\njobs:\n  test:\n    runs-on: ubuntu-latest\n    strategy:\n      matrix:\n        a: [{}]\n        b: [{}]\n{}    steps:\n      - run: make test\n",
+        values(a),
+        values(b),
+        extra
+    )
+}
+
+#[test]
+fn test_matrix_over_github_job_limit() {
+    let mut engine = TrussEngine::new();
+
+    let too_big = matrix_workflow(17, 16, "");
+    let diagnostics = engine.analyze_rule(&too_big, "matrix_strategy").unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0].message.starts_with(
+        "Job 'test' has a matrix that expands to 272 jobs, but GitHub allows at most 256"
+    ));
+    assert!(too_big[diagnostics[0].span.start..].starts_with("matrix:"));
+
+    // Excluding one value of `a` brings it down to exactly 256
+    let excluded = matrix_workflow(17, 16, "        exclude:\n          - a: 17\n");
+    let diagnostics = engine.analyze_rule(&excluded, "matrix_strategy").unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    // An include that matches no combination adds a job
+    let included = matrix_workflow(
+        16,
+        16,
+        "        include:\n          - a: 99\n            b: 99\n",
+    );
+    let diagnostics = engine.analyze_rule(&included, "matrix_strategy").unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("expands to 257 jobs"));
+
+    // The size of a computed dimension is unknown
+    let dynamic = matrix_workflow(
+        17,
+        16,
+        "        c: ${{ fromJSON(needs.setup.outputs.c) }}\n",
+    );
+    let diagnostics = engine.analyze_rule(&dynamic, "matrix_strategy").unwrap();
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_matrix_over_configured_limit() {
+    let mut engine = TrussEngine::new();
+    let yaml = matrix_workflow(3, 4, "");
+
+    let options = AnalysisOptions {
+        max_matrix_combinations: Some(10),
+        ..AnalysisOptions::default()
+    };
+    let result = engine.analyze_with_options(&yaml, &options);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_strategy")
+        .collect();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].severity, Severity::Error);
+    assert!(errors[0].message.starts_with(
+        "Job 'test' has a matrix that expands to 12 jobs, over the limit of 10 set by max_matrix_combinations"
+    ));
+
+    // A limit above the matrix size, or above GitHub's own, adds nothing
+    for limit in [12, 1000] {
+        let options = AnalysisOptions {
+            max_matrix_combinations: Some(limit),
+            ..AnalysisOptions::default()
+        };
+        let result = engine.analyze_with_options(&yaml, &options);
+        assert!(
+            !result
+                .diagnostics
+                .iter()
+                .any(|d| d.rule_id == "matrix_strategy"),
+            "{:?}",
+            result.diagnostics
+        );
+    }
+}
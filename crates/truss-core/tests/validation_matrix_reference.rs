@@ -0,0 +1,196 @@
+//! Tests for MatrixReferenceRule
+//!
+//! Validates that `matrix.<key>` references correspond to declared matrix keys.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_matrix_reference_valid_base_key() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+    steps:
+      - run: echo "Running on ${{ matrix.os }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_reference")
+        .collect();
+
+    assert!(
+        reference_errors.is_empty(),
+        "References to declared matrix keys should not produce errors, got: {:?}",
+        reference_errors
+    );
+}
+
+#[test]
+fn test_matrix_reference_valid_include_key() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+        include:
+          - os: ubuntu-latest
+            experimental: true
+    steps:
+      - run: echo "${{ matrix.experimental }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_reference")
+        .collect();
+
+    assert!(
+        reference_errors.is_empty(),
+        "Keys introduced by include entries should be considered declared, got: {:?}",
+        reference_errors
+    );
+}
+
+#[test]
+fn test_matrix_reference_undeclared_key() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+    steps:
+      - run: echo "Version ${{ matrix.version }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "matrix_reference"
+                && d.message.contains("matrix.version")
+                && d.message.contains("Available keys: os")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        reference_errors.len(),
+        1,
+        "Reference to undeclared matrix key 'version' should produce one error"
+    );
+
+    let span = reference_errors[0].span;
+    assert_eq!(&yaml[span.start..span.end], "matrix.version");
+}
+
+#[test]
+fn test_matrix_reference_exclude_keys_not_declared() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+        exclude:
+          - os: windows-latest
+    steps:
+      - run: echo "${{ matrix.exclude }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_reference" && d.message.contains("matrix.exclude"))
+        .collect();
+
+    assert!(
+        !reference_errors.is_empty(),
+        "'exclude' is a matrix modifier, not a key available in the matrix context"
+    );
+}
+
+#[test]
+fn test_matrix_reference_dynamic_matrix_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix: ${{ fromJSON(needs.setup.outputs.matrix) }}
+    steps:
+      - run: echo "${{ matrix.anything }}"
+  partial:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+        include: ${{ fromJSON(needs.setup.outputs.extra) }}
+    steps:
+      - run: echo "${{ matrix.extra }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_reference")
+        .collect();
+
+    assert!(
+        reference_errors.is_empty(),
+        "Dynamic matrices should be skipped, got: {:?}",
+        reference_errors
+    );
+}
+
+#[test]
+fn test_matrix_reference_job_without_matrix_skipped() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ github.event.matrix.os }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let reference_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "matrix_reference")
+        .collect();
+
+    assert!(
+        reference_errors.is_empty(),
+        "Jobs without a matrix should not be checked, got: {:?}",
+        reference_errors
+    );
+}
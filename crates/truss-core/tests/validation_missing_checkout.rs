@@ -0,0 +1,109 @@
+//! Tests for MissingCheckoutRule
+//!
+//! Detects jobs that use repository files before checking the repository out.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn missing_checkout(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "missing_checkout")
+        .unwrap()
+}
+
+#[test]
+fn test_missing_checkout_script_before_checkout() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+      - run: |
+          npm --version
+          ./scripts/build.sh --release
+      - run: bash scripts/test.sh
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: cat VERSION
+  action:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: ./.github/actions/setup
+"#;
+
+    let diagnostics = missing_checkout(yaml);
+    assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Info));
+    assert!(diagnostics[0]
+        .message
+        .contains("Job 'build' uses './scripts/build.sh' before any 'actions/checkout' step"));
+    assert!(diagnostics[1].message.contains("'VERSION'"));
+    assert_eq!(
+        &yaml[diagnostics[2].span.start..diagnostics[2].span.end],
+        "./.github/actions/setup"
+    );
+}
+
+#[test]
+fn test_missing_checkout_after_checkout_valid() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+      - run: ./gradlew build
+      - uses: ./.github/actions/publish
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/download-artifact@v4
+        with:
+          name: dist
+      - run: ./dist/deploy.sh
+"#;
+
+    let diagnostics = missing_checkout(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Files used after a checkout or artifact download should not be flagged: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_missing_checkout_inline_commands_valid() {
+    let yaml = r#"
+on: push
+jobs:
+  notify:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Deployed ${{ github.sha }}" >> "$GITHUB_STEP_SUMMARY"
+      - run: |
+          cat <<EOF > message.txt
+          done
+          EOF
+          python -c "print('hi')"
+          bash -c 'echo ok'
+          cat /etc/os-release
+      - run: ./tool --version
+        working-directory: /opt/tool
+      - uses: actions/github-script@v7
+        with:
+          script: console.log('./not-a-file')
+"#;
+
+    let diagnostics = missing_checkout(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Jobs without repository file references should not be flagged: {:?}",
+        diagnostics
+    );
+}
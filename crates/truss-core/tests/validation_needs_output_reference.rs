@@ -0,0 +1,156 @@
+//! Tests for NeedsOutputReferenceRule
+//!
+//! Validates that `needs.<job>` references name a job the referencing job
+//! depends on, and an output that job declares.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn needs_diagnostics(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze(yaml)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.rule_id == "needs_output_reference")
+        .collect()
+}
+
+#[test]
+fn test_needs_reference_to_declared_dependency_is_valid() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+  deploy:
+    needs: [build]
+    if: needs.build.result == 'success'
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+      - run: echo "${{ toJSON(needs.*.result) }}"
+"#;
+
+    let diagnostics = needs_diagnostics(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "References to a listed dependency should be valid: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_needs_reference_without_dependency() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm run lint
+  deploy:
+    needs: lint
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+"#;
+
+    let diagnostics = needs_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .contains("does not list 'build' in its needs"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "needs.build"
+    );
+}
+
+#[test]
+fn test_needs_reference_to_missing_job() {
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    if: needs.setup.result == 'success'
+    steps:
+      - run: ./deploy.sh ${{ needs.bulid.outputs.version }}
+"#;
+
+    let diagnostics = needs_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("there is no job 'setup'"));
+    assert!(diagnostics[1].message.contains("there is no job 'bulid'"));
+}
+
+#[test]
+fn test_needs_reference_to_undeclared_output() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm test
+  deploy:
+    needs: [build, test]
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.tag }}
+      - run: echo ${{ needs.test.outputs.coverage }}
+"#;
+
+    let diagnostics = needs_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics[0]
+        .message
+        .contains("does not declare output 'tag'. Available outputs: version"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "tag"
+    );
+    assert!(diagnostics[1].message.contains("Available outputs: none"));
+}
+
+#[test]
+fn test_needs_reference_to_reusable_workflow_job_outputs() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    uses: ./.github/workflows/build.yml
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+"#;
+
+    let diagnostics = needs_diagnostics(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Outputs of reusable workflow jobs can't be checked: {:?}",
+        diagnostics
+    );
+}
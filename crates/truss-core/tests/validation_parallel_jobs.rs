@@ -0,0 +1,91 @@
+//! Tests for ParallelJobsRule
+//!
+//! Notes that jobs run in parallel when a workflow has several jobs and none
+//! of them declares `needs`.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn parallel_jobs(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "parallel_jobs")
+        .unwrap()
+}
+
+#[test]
+fn test_parallel_jobs_without_needs() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make test
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make deploy
+"#;
+
+    let diagnostics = parallel_jobs(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Info);
+    assert!(diagnostics[0]
+        .message
+        .contains("has 3 jobs and none of them declares 'needs'"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "jobs"
+    );
+}
+
+#[test]
+fn test_parallel_jobs_with_needs() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make lint
+  deploy:
+    needs: [build]
+    runs-on: ubuntu-latest
+    steps:
+      - run: make deploy
+"#;
+
+    let diagnostics = parallel_jobs(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "One job declaring needs shows ordering was considered: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_parallel_jobs_single_job() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let diagnostics = parallel_jobs(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "A single job has nothing to order: {:?}",
+        diagnostics
+    );
+}
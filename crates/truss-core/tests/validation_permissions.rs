@@ -297,3 +297,221 @@ jobs:
         "'none' is valid as a top-level value but not as a mapping key"
     );
 }
+
+#[test]
+fn test_permissions_empty_produces_info() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+permissions: {}
+jobs:
+  test:
+    runs-on: ubuntu-latest
+"#;
+
+    let result = engine.analyze(yaml);
+    let empty_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "permissions"
+                && d.message.contains("revokes all")
+                && d.severity == Severity::Info
+        })
+        .collect();
+
+    assert_eq!(
+        empty_infos.len(),
+        1,
+        "Empty permissions should produce an info diagnostic"
+    );
+}
+
+#[test]
+fn test_permissions_empty_job_level_produces_info() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    permissions: {}
+"#;
+
+    let result = engine.analyze(yaml);
+    let empty_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("revokes all") && d.severity == Severity::Info)
+        .collect();
+
+    assert_eq!(
+        empty_infos.len(),
+        1,
+        "Empty job-level permissions should produce an info diagnostic"
+    );
+}
+
+#[test]
+fn test_permissions_non_empty_no_info() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+permissions:
+  contents: read
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    permissions: { pull-requests: write }
+"#;
+
+    let result = engine.analyze(yaml);
+    let perm_diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "permissions")
+        .collect();
+
+    assert!(
+        perm_diagnostics.is_empty(),
+        "Non-empty permissions should not produce diagnostics, got: {:?}",
+        perm_diagnostics
+    );
+}
+
+#[test]
+fn test_permissions_invalid_job_level_scope() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    permissions:
+      invalid-scope: read
+"#;
+
+    let result = engine.analyze(yaml);
+    let perm_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("Invalid permission scope") && d.severity == Severity::Error)
+        .collect();
+
+    assert!(
+        !perm_errors.is_empty(),
+        "Invalid job-level permission scope should produce error"
+    );
+}
+
+#[test]
+fn test_permissions_scope_typo_suggests_fix() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+permissions:
+  content: write
+  pull_request: read
+  models: read
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#;
+
+    let result = engine.analyze(yaml);
+    let messages: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "permissions" && d.severity == Severity::Error)
+        .map(|d| d.message.as_str())
+        .collect();
+
+    assert_eq!(
+        messages.len(),
+        2,
+        "Expected two scope errors: {:?}",
+        messages
+    );
+    assert!(messages[0].contains("Did you mean 'contents'?"));
+    assert!(messages[1].contains("Did you mean 'pull-requests'?"));
+}
+
+#[test]
+fn test_permissions_id_token_write_without_oidc_step_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+permissions:
+  contents: read
+  id-token: write
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    permissions:
+      id-token: write
+    steps:
+      - uses: actions/checkout@v4
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "permissions" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "Expected workflow and job warnings: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("workflow level"));
+    assert!(warnings[1].message.contains("job 'test'"));
+}
+
+#[test]
+fn test_permissions_id_token_write_with_oidc_step_is_fine() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+permissions:
+  id-token: write
+jobs:
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make lint
+  deploy:
+    runs-on: ubuntu-latest
+    permissions:
+      id-token: write
+      contents: read
+    steps:
+      - uses: aws-actions/configure-aws-credentials@v4
+        with:
+          role-to-assume: arn:aws:iam::123456789012:role/deploy
+  publish:
+    runs-on: ubuntu-latest
+    permissions:
+      id-token: write
+    steps:
+      - run: npm publish --provenance
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "permissions" && d.severity == Severity::Warning)
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "OIDC-consuming steps should not warn: {:?}",
+        warnings
+    );
+}
@@ -0,0 +1,133 @@
+//! Tests for PullRequestTargetRule
+//!
+//! Flags `pull_request_target` workflows that check out and run code from the pull request.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_pull_request_target_checkout_and_build() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request_target
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          ref: ${{ github.event.pull_request.head.sha }}
+      - run: |
+          npm install
+          npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "pull_request_target")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "PR head checkout and the build step should both warn. Got: {:?}",
+        warnings
+    );
+    assert!(warnings.iter().all(|d| d.severity == Severity::Warning));
+    assert!(warnings[0].message.contains("checks out the pull request"));
+    assert!(warnings[1].message.contains("npm install"));
+    assert!(warnings[1].message.contains("securitylab.github.com"));
+}
+
+#[test]
+fn test_pull_request_target_merge_ref_and_local_action() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  pull_request_target:
+    types: [opened, synchronize]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          ref: refs/pull/${{ github.event.pull_request.number }}/merge
+      - uses: ./.github/actions/build
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "pull_request_target")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        2,
+        "Merge-ref checkout and the local action should both warn. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_pull_request_target_event_data_only() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request_target
+jobs:
+  label:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: make lint-config
+      - run: gh pr edit "$PR" --add-label triage
+        env:
+          PR: ${{ github.event.pull_request.number }}
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "pull_request_target")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Base-branch checkout and github.event data should not warn. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_pull_request_trigger_not_checked() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: pull_request
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          ref: ${{ github.event.pull_request.head.sha }}
+      - run: npm ci && npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "pull_request_target")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "pull_request workflows run without elevated privileges. Got: {:?}",
+        warnings
+    );
+}
@@ -5,7 +5,7 @@
 //! Validates uses: workflow calls reference valid reusable workflows in GitHub Actions workflows.
 
 use truss_core::Severity;
-use truss_core::TrussEngine;
+use truss_core::{AnalysisOptions, TrussEngine};
 
 #[test]
 fn test_reusable_workflow_call_valid_format() {
@@ -207,3 +207,214 @@ jobs:
         "Valid local reusable workflow call should not produce errors"
     );
 }
+
+const CALLED_WORKFLOW: &str = r#"
+on:
+  workflow_call:
+    inputs:
+      environment:
+        type: string
+        required: true
+      region:
+        type: string
+        required: true
+        default: us-east-1
+      dry-run:
+        type: boolean
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#;
+
+const RELEASE_WORKFLOW: &str = r#"
+on:
+  workflow_call:
+    secrets:
+      npm-token:
+        required: true
+      slack-webhook:
+        required: false
+jobs:
+  release:
+    runs-on: ubuntu-latest
+    steps:
+      - run: npm publish
+"#;
+
+fn resolve(path: &str) -> Option<String> {
+    match path {
+        "./.github/workflows/deploy.yml" => Some(CALLED_WORKFLOW.to_string()),
+        "./.github/workflows/ci.yml" => Some("on: push\njobs: {}\n".to_string()),
+        "./.github/workflows/release.yml" => Some(RELEASE_WORKFLOW.to_string()),
+        _ => None,
+    }
+}
+
+fn call_diagnostics(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_with_resolver(yaml, &AnalysisOptions::default(), resolve)
+        .diagnostics
+        .into_iter()
+        .filter(|d| d.rule_id == "reusable_workflow_call")
+        .collect()
+}
+
+#[test]
+fn test_reusable_workflow_call_inputs_match_called_workflow() {
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    uses: ./.github/workflows/deploy.yml
+    with:
+      Environment: production
+      dry-run: true
+  other:
+    uses: ./.github/workflows/unknown.yml
+    with:
+      anything: 1
+"#;
+
+    let diagnostics = call_diagnostics(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Declared inputs (any case) and unresolvable workflows should pass: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_reusable_workflow_call_missing_and_unknown_inputs() {
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    uses: ./.github/workflows/deploy.yml
+    with:
+      dryrun: true
+"#;
+
+    let diagnostics = call_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    assert!(diagnostics[0]
+        .message
+        .contains("without its required input 'environment'"));
+    assert!(diagnostics[1].message.contains(
+        "passes input 'dryrun', but './.github/workflows/deploy.yml' does not declare it. Declared inputs: dry-run, environment, region"
+    ));
+
+    // Plain analysis never resolves other files
+    let result = TrussEngine::new().analyze(yaml);
+    assert!(!result
+        .diagnostics
+        .iter()
+        .any(|d| d.message.contains("required input")));
+}
+
+#[test]
+fn test_reusable_workflow_call_to_workflow_without_workflow_call() {
+    let yaml = r#"
+on: push
+jobs:
+  ci:
+    uses: ./.github/workflows/ci.yml
+"#;
+
+    let diagnostics = call_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert!(diagnostics[0]
+        .message
+        .contains("has no workflow_call trigger"));
+}
+
+#[test]
+fn test_reusable_workflow_call_secrets_match_called_workflow() {
+    let yaml = r#"
+on: push
+jobs:
+  release:
+    uses: ./.github/workflows/release.yml
+    secrets:
+      NPM-TOKEN: ${{ secrets.NPM_TOKEN }}
+  inherited:
+    uses: ./.github/workflows/release.yml
+    secrets: inherit
+"#;
+
+    let diagnostics = call_diagnostics(yaml);
+    assert!(
+        diagnostics.is_empty(),
+        "Declared secrets (any case) and `secrets: inherit` should pass: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_reusable_workflow_call_missing_and_unknown_secrets() {
+    let yaml = r#"
+on: push
+jobs:
+  release:
+    uses: ./.github/workflows/release.yml
+    secrets:
+      npm_token: ${{ secrets.NPM_TOKEN }}
+"#;
+
+    let diagnostics = call_diagnostics(yaml);
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .contains("without its required secret 'npm-token'"));
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert!(diagnostics[1].message.contains(
+        "passes secret 'npm_token', but './.github/workflows/release.yml' does not declare it. Declared secrets: npm-token, slack-webhook"
+    ));
+}
+
+#[test]
+fn test_reusable_workflow_call_with_job_keys() {
+    let yaml = r#"
+on: push
+jobs:
+  call:
+    uses: ./.github/workflows/build.yml
+    runs-on: ubuntu-latest
+    container: node:20
+    services:
+      redis:
+        image: redis
+    steps:
+      - run: make
+  lint:
+    uses: ./.github/workflows/lint.yml
+    steps:
+      - run: make lint
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+"#;
+
+    let mut engine = TrussEngine::new();
+    let diagnostics = engine.analyze_rule(yaml, "reusable_workflow_call").unwrap();
+    assert_eq!(diagnostics.len(), 5, "{:?}", diagnostics);
+    let keys: Vec<&str> = diagnostics
+        .iter()
+        .map(|d| &yaml[d.span.start..d.span.end])
+        .collect();
+    assert_eq!(keys, ["runs-on", "container", "services", "steps", "steps"]);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    assert!(diagnostics[3].message.contains(
+        "Job 'call' calls a reusable workflow with 'uses', so it can't also set 'steps'"
+    ));
+    let related = &diagnostics[0].related[0];
+    assert_eq!(&yaml[related.span.start..related.span.end], "uses");
+
+    // Calls are exempt from runs-on, so 'lint' is only reported for its steps
+    let runs_on = engine.analyze_rule(yaml, "runs_on_required").unwrap();
+    assert!(runs_on.is_empty(), "{:?}", runs_on);
+}
@@ -0,0 +1,101 @@
+//! Tests for RunBlockScalarRule
+//!
+//! Detects `run: >` scripts where folding joins separate commands onto one line.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_run_block_scalar_folded_multiple_commands() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Build
+        run: >
+          npm ci
+          npm run build
+          npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "run_block_scalar")
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Folded run script with several commands should warn once. Got: {:?}",
+        warnings
+    );
+    assert_eq!(warnings[0].severity, Severity::Warning);
+    assert!(warnings[0].message.contains("'npm ci' and 'npm run build'"));
+    assert_eq!(&yaml[warnings[0].span.start..warnings[0].span.end], ">");
+}
+
+#[test]
+fn test_run_block_scalar_literal_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: |
+          npm ci
+          npm test
+      - run: npm test
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "run_block_scalar")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Literal and plain run scripts should not warn. Got: {:?}",
+        warnings
+    );
+}
+
+#[test]
+fn test_run_block_scalar_folded_wrapped_command_valid() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: >-
+          cargo build
+          --release
+          --all-features
+      - run: >
+          docker build . &&
+          docker push example/app
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "run_block_scalar")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "A single command wrapped across lines is what '>' is for. Got: {:?}",
+        warnings
+    );
+}
@@ -146,7 +146,7 @@ jobs:
 
     // Note: This may produce a warning or be valid depending on implementation
     // Basic format validation should pass, but unknown labels might warn
-    assert!(true, "Unknown runner label may produce warning");
+    // Unknown runner label may produce warning
 }
 
 #[test]
@@ -176,3 +176,121 @@ jobs:
         "Valid runner with labels should not produce errors"
     );
 }
+
+#[test]
+fn test_runner_label_valid_group_object() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on:
+      group: ubuntu-runners
+      labels: [self-hosted, linux]
+    steps:
+      - run: echo "Test"
+  larger:
+    runs-on:
+      group: ubuntu-runners
+      labels: ubuntu-22.04-16core
+    steps:
+      - run: echo "Test"
+  flow:
+    runs-on: { group: my-group }
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "runner_label")
+        .unwrap();
+    assert!(
+        diagnostics.is_empty(),
+        "runs-on objects with group and labels should not produce errors: {:?}",
+        diagnostics
+    );
+}
+
+#[test]
+fn test_runner_label_invalid_group_object() {
+    let yaml = r#"
+on: push
+jobs:
+  unknown:
+    runs-on:
+      group: ubuntu-runners
+      label: linux
+    steps:
+      - run: echo "Test"
+  labels-map:
+    runs-on:
+      labels:
+        os: linux
+    steps:
+      - run: echo "Test"
+  empty:
+    runs-on: {}
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "runner_label")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    assert!(diagnostics[0]
+        .message
+        .contains("unknown key 'label' in 'runs-on'"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "label"
+    );
+    assert!(diagnostics[1]
+        .message
+        .contains("Job 'labels-map' has invalid 'runs-on.labels'"));
+    assert!(diagnostics[2]
+        .message
+        .contains("Job 'empty' has a 'runs-on' object without 'group' or 'labels'"));
+}
+
+#[test]
+fn test_runner_label_empty_group() {
+    let yaml = r#"
+on: push
+jobs:
+  quoted:
+    runs-on:
+      group: ''
+      labels: [self-hosted]
+    steps:
+      - run: echo "Test"
+  blank:
+    runs-on: { group: "  " }
+    steps:
+      - run: echo "Test"
+  missing:
+    runs-on:
+      group:
+    steps:
+      - run: echo "Test"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "runner_label")
+        .unwrap();
+    let found: Vec<(&str, &str)> = diagnostics
+        .iter()
+        .map(|d| {
+            assert_eq!(d.severity, Severity::Error);
+            (d.message.as_str(), &yaml[d.span.start..d.span.end])
+        })
+        .collect();
+    assert_eq!(
+        found,
+        [
+            ("Job 'quoted' has empty runs-on group.", "''"),
+            ("Job 'blank' has empty runs-on group.", r#""  ""#),
+            ("Job 'missing' has empty runs-on group.", "group"),
+        ]
+    );
+}
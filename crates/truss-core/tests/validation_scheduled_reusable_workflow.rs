@@ -0,0 +1,122 @@
+//! Tests for ScheduledReusableWorkflowRule
+//!
+//! Flags reusable (`workflow_call`) workflows that also trigger on `schedule`.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+#[test]
+fn test_scheduled_reusable_workflow_call_only() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_call:
+    inputs:
+      env:
+        type: string
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Test"
+"#;
+
+    let result = engine.analyze(yaml);
+    let schedule_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "scheduled_reusable_workflow")
+        .collect();
+
+    assert!(
+        schedule_infos.is_empty(),
+        "A workflow_call-only workflow should not produce a diagnostic"
+    );
+}
+
+#[test]
+fn test_scheduled_reusable_workflow_schedule_only() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  schedule:
+    - cron: '0 0 * * *'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Test"
+"#;
+
+    let result = engine.analyze(yaml);
+    let schedule_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "scheduled_reusable_workflow")
+        .collect();
+
+    assert!(
+        schedule_infos.is_empty(),
+        "A scheduled workflow without workflow_call should not produce a diagnostic"
+    );
+}
+
+#[test]
+fn test_scheduled_reusable_workflow_both_triggers() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_call:
+  schedule:
+    - cron: '0 0 * * *'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Test"
+"#;
+
+    let result = engine.analyze(yaml);
+    let schedule_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "scheduled_reusable_workflow"
+                && d.message.contains("schedule")
+                && d.severity == Severity::Info
+        })
+        .collect();
+
+    assert_eq!(
+        schedule_infos.len(),
+        1,
+        "workflow_call combined with schedule should produce one info diagnostic"
+    );
+    let span = schedule_infos[0].span;
+    assert_eq!(&yaml[span.start..span.end], "schedule");
+}
+
+#[test]
+fn test_scheduled_reusable_workflow_sequence_form() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: [workflow_call, schedule]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "Test"
+"#;
+
+    let result = engine.analyze(yaml);
+    let schedule_infos: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "scheduled_reusable_workflow" && d.severity == Severity::Info)
+        .collect();
+
+    assert!(
+        !schedule_infos.is_empty(),
+        "Sequence form with workflow_call and schedule should produce an info diagnostic"
+    );
+}
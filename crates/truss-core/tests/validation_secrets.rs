@@ -181,8 +181,10 @@ on: push
 jobs:
   build:
     runs-on: ubuntu-latest
+    env:
+      DEPLOY_ENABLED: ${{ secrets.DEPLOY_ENABLED }}
     steps:
-      - if: ${{ secrets.DEPLOY_ENABLED == 'true' }}
+      - if: ${{ env.DEPLOY_ENABLED == 'true' }}
         run: echo "Deploying"
 "#;
 
@@ -198,3 +200,92 @@ jobs:
         "Valid secret reference in conditional should not produce errors"
     );
 }
+
+#[test]
+fn test_secrets_hyphenated_name_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TOKEN: ${{ secrets.my-secret }}
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "secrets_validation" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "secrets.my-secret can never match a repository secret and should warn"
+    );
+    assert!(warnings[0].message.contains("my-secret"));
+}
+
+#[test]
+fn test_secrets_reserved_github_prefix_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ secrets.GITHUB_DEPLOY_KEY }} ${{ secrets.github_token }} ${{ secrets.npm_token }}"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "secrets_validation" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Only GITHUB_DEPLOY_KEY should warn; secret names are case-insensitive, got: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("GITHUB_DEPLOY_KEY"));
+}
+
+#[test]
+fn test_secrets_hyphenated_name_in_reusable_workflow() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  workflow_call:
+    secrets:
+      deploy-key:
+        required: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          KEY: ${{ secrets.deploy-key }}
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "secrets_validation")
+        .collect();
+
+    assert!(
+        warnings.is_empty(),
+        "Secrets declared under workflow_call are not repository secrets, got: {:?}",
+        warnings
+    );
+}
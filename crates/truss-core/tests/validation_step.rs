@@ -234,3 +234,30 @@ jobs:
         "Step with only 'run' should not trigger mutual exclusion error"
     );
 }
+
+#[test]
+fn test_step_both_uses_and_run_points_at_second_key() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      # Checkout first
+      - run: echo "Building"
+        name: Build
+        uses: actions/checkout@v4
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "step" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(errors.len(), 1, "Expected one step error: {:?}", errors);
+    assert!(errors[0].message.contains("cannot have both"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "uses");
+}
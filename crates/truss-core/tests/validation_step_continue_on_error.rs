@@ -163,3 +163,77 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_job_continue_on_error_true_warns() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    continue-on-error: true
+    steps:
+      - run: npm test
+  deploy:
+    needs: test
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#;
+
+    let result = engine.analyze(yaml);
+    let diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "step_continue_on_error")
+        .collect();
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].message.contains("always reports success"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "continue-on-error"
+    );
+}
+
+#[test]
+fn test_job_continue_on_error_values() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  experimental:
+    runs-on: ubuntu-latest
+    continue-on-error: ${{ matrix.experimental }}
+    strategy:
+      matrix:
+        experimental: [true, false]
+    steps:
+      - run: npm test
+  strict:
+    runs-on: ubuntu-latest
+    continue-on-error: false
+    steps:
+      - run: npm test
+  lint:
+    runs-on: ubuntu-latest
+    continue-on-error: "yes"
+    steps:
+      - run: npm run lint
+"#;
+
+    let result = engine.analyze(yaml);
+    let diagnostics: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "step_continue_on_error")
+        .collect();
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert!(diagnostics[0]
+        .message
+        .starts_with("Job 'lint' has invalid continue-on-error: 'yes'"));
+}
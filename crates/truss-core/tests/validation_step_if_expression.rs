@@ -323,3 +323,192 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_step_if_expression_secrets_not_allowed() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ secrets.DEPLOY == 'yes' }}
+        run: ./deploy.sh
+"#;
+
+    let result = engine.analyze(yaml);
+    let secrets_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| {
+            d.rule_id == "step_if_expression"
+                && d.message
+                    .contains("The secrets context is not available in if conditions")
+                && d.severity == Severity::Error
+        })
+        .collect();
+
+    assert_eq!(
+        secrets_errors.len(),
+        1,
+        "secrets in a step 'if' should produce one error"
+    );
+
+    let span = secrets_errors[0].span;
+    assert_eq!(&yaml[span.start..span.end], "secrets.DEPLOY");
+}
+
+#[test]
+fn test_step_if_expression_secrets_like_property_allowed() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - if: env.HAS_SECRETS == 'true' && inputs.secrets.enabled
+        run: ./deploy.sh
+"#;
+
+    let result = engine.analyze(yaml);
+    let secrets_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("secrets context is not available"))
+        .collect();
+
+    assert!(
+        secrets_errors.is_empty(),
+        "Names that merely contain 'secrets' should not be flagged, got: {:?}",
+        secrets_errors
+    );
+}
+
+#[test]
+fn test_step_if_expression_secrets_in_string_literal_allowed() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: issue_comment
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - if: github.event.comment.body == 'secrets.TOKEN'
+        run: ./deploy.sh
+      - if: ${{ contains(github.event.comment.body, 'it''s secrets.TOKEN') && secrets.TOKEN != '' }}
+        run: ./deploy.sh
+"#;
+
+    let result = engine.analyze(yaml);
+    let secrets_errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("secrets context is not available"))
+        .collect();
+
+    assert_eq!(
+        secrets_errors.len(),
+        1,
+        "Only the reference outside a string literal should be flagged, got: {:?}",
+        secrets_errors
+    );
+    let span = secrets_errors[0].span;
+    assert_eq!(&yaml[span.start..span.end], "secrets.TOKEN");
+    assert!(span.start > yaml.find("') &&").unwrap());
+}
+
+#[test]
+fn test_step_if_expression_single_equals() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: github.ref = 'refs/heads/main'
+        run: echo "main"
+      - if: github.ref == 'refs/heads/main' && format('a={0}', github.sha) != ''
+        run: echo "ok"
+"#;
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "step_if_expression" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "Only the bare single '=' condition should error, got: {:?}",
+        errors
+    );
+    assert!(errors[0].message.contains("'=' instead of '=='"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "=");
+}
+
+#[test]
+fn test_step_if_expression_text_outside_braces() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ success() }} && github.ref == 'refs/heads/main'
+        run: echo "mixed"
+      - if: "${{ github.event_name == 'push' }} || always()"
+        run: echo "quoted"
+      - if: "${{ success() && github.ref == 'refs/heads/main' }}"
+        run: echo "wrapped"
+      - if: success() && github.ref == 'refs/heads/main'
+        run: echo "bare"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "step_if_expression")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    assert!(diagnostics[0].message.contains("always true"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "&& github.ref == 'refs/heads/main'"
+    );
+    assert_eq!(
+        &yaml[diagnostics[1].span.start..diagnostics[1].span.end],
+        "|| always()"
+    );
+}
+
+#[test]
+fn test_step_if_expression_event_name_not_in_triggers() {
+    let yaml = r#"
+on:
+  push:
+    branches: [main]
+  workflow_dispatch:
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: "${{ github.event_name == 'pull_request' }}"
+        run: echo "pr"
+      - if: github.event_name == 'workflow_dispatch'
+        run: echo "manual"
+"#;
+
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(yaml, "step_if_expression")
+        .unwrap();
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "pull_request"
+    );
+}
@@ -147,7 +147,7 @@ jobs:
         .collect();
 
     // Note: This may produce a warning or be valid depending on implementation
-    assert!(true, "Very long step name may produce warning");
+    // Very long step name may produce warning
 }
 
 #[test]
@@ -210,3 +210,51 @@ jobs:
         name_errors.iter().map(|d| &d.message).collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_step_name_duplicate_in_job() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Install
+        run: npm ci
+      - name: Test
+        run: npm test
+      - name: 'Install'
+        run: npm ci --prefix docs
+      - name: Build ${{ matrix.os }}
+        run: make
+      - name: Build ${{ matrix.os }}
+        run: make docs
+  lint:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Install
+        run: npm ci
+"#;
+
+    let diagnostics = TrussEngine::new().analyze_rule(yaml, "step_name").unwrap();
+
+    assert_eq!(
+        diagnostics.len(),
+        1,
+        "Only the repeated name within 'build' should warn, got: {:?}",
+        diagnostics
+    );
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0]
+        .message
+        .contains("Job 'build' has more than one step named 'Install'"));
+    assert_eq!(
+        &yaml[diagnostics[0].span.start..diagnostics[0].span.end],
+        "'Install'"
+    );
+    assert_eq!(diagnostics[0].related.len(), 1);
+    assert_eq!(
+        diagnostics[0].related[0].span.start,
+        yaml.find("Install").unwrap()
+    );
+}
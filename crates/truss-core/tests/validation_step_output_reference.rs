@@ -277,6 +277,13 @@ jobs:
         !output_errors.is_empty(),
         "Reference to step output from different job should produce error"
     );
+
+    // The diagnostic links to the step's definition in the other job
+    let related = &output_errors[0].related;
+    assert_eq!(related.len(), 1);
+    assert_eq!(&yaml[related[0].span.start..related[0].span.end], "build");
+    assert_eq!(related[0].span.start, yaml.find("id: build").unwrap() + 4);
+    assert!(related[0].message.contains("in job 'build'"));
 }
 
 #[test]
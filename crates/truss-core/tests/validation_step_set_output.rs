@@ -0,0 +1,167 @@
+//! Tests for StepSetOutputRule
+//!
+//! **Status:** Rule implemented and tested
+//!
+//! Warns on the deprecated `::set-output` and `::save-state` workflow commands.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn step_set_output(yaml: &str) -> Vec<truss_core::Diagnostic> {
+    TrussEngine::new()
+        .analyze_rule(yaml, "step_set_output")
+        .unwrap()
+}
+
+fn spans<'a>(yaml: &'a str, diagnostics: &[truss_core::Diagnostic]) -> Vec<&'a str> {
+    diagnostics
+        .iter()
+        .map(|d| &yaml[d.span.start..d.span.end])
+        .collect()
+}
+
+#[test]
+fn test_step_set_output_spans_each_echoed_command() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: v
+        run: echo "::set-output name=version::1.0"
+      - run: |
+          VERSION=$(cat VERSION)
+          echo '::set-output name=version::'"$VERSION" && echo "::save-state name=pid::$$"
+          printf '%s\n' "::set-output name=raw::x"
+"#;
+
+    let diagnostics = step_set_output(yaml);
+    assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    assert_eq!(
+        spans(yaml, &diagnostics),
+        [
+            r#"echo "::set-output name=version::1.0""#,
+            "echo '::set-output name=version::'",
+            r#"echo "::save-state name=pid::$$""#,
+            r#""::set-output name=raw::x""#,
+        ]
+    );
+    assert!(diagnostics[0].message.contains("$GITHUB_OUTPUT"));
+    assert!(diagnostics[2].message.contains("$GITHUB_STATE"));
+}
+
+#[test]
+fn test_step_set_output_unquoted_command_stops_at_shell_operator() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ::set-output name=dir::$(pwd) ; ls
+"#;
+
+    let diagnostics = step_set_output(yaml);
+    assert_eq!(
+        spans(yaml, &diagnostics),
+        ["echo ::set-output name=dir::$(pwd)"]
+    );
+}
+
+#[test]
+fn test_step_set_output_github_output_no_warning() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: |
+          echo "version=1.0" >> "$GITHUB_OUTPUT"
+          echo "pid=$$" >> "$GITHUB_STATE"
+"#;
+
+    let diagnostics = step_set_output(yaml);
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_step_set_output_fixes_rewrite_echoed_commands() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: |
+          echo "::set-output name=version::1.0"
+          echo '::save-state name=pid::123' # keep
+          echo ::set-output name=dir::$(pwd) && ls
+          echo '::set-output name=sha::'"$SHA"
+          printf '%s\n' "::set-output name=raw::x"
+"#;
+
+    let fix = TrussEngine::new().fix(yaml);
+    let edits: Vec<(&str, &str)> = fix
+        .replacements
+        .iter()
+        .map(|edit| {
+            assert_eq!(edit.rule_id, "step_set_output");
+            (
+                &yaml[edit.span.start..edit.span.end],
+                edit.new_text.as_str(),
+            )
+        })
+        .collect();
+    // Commands that continue past their span, or aren't echoed, are left alone
+    assert_eq!(
+        edits,
+        [
+            (
+                r#"echo "::set-output name=version::1.0""#,
+                r#"echo "version=1.0" >> "$GITHUB_OUTPUT""#
+            ),
+            (
+                "echo '::save-state name=pid::123'",
+                r#"echo 'pid=123' >> "$GITHUB_STATE""#
+            ),
+            (
+                "echo ::set-output name=dir::$(pwd)",
+                r#"echo dir=$(pwd) >> "$GITHUB_OUTPUT""#
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_step_set_output_fixes_skip_suppressed_lines() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "::set-output name=a::1" # truss-disable-line step_set_output
+      # truss-disable-next-line
+      - run: echo "::set-output name=b::2"
+      - run: echo "::set-output name=c::3" # truss-disable-line other_rule
+"#;
+
+    let mut engine = TrussEngine::new();
+    let reported: Vec<&str> = engine
+        .analyze(yaml)
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "step_set_output")
+        .map(|d| &yaml[d.span.start..d.span.end])
+        .collect();
+    assert_eq!(reported, [r#"echo "::set-output name=c::3""#]);
+    let fixed: Vec<&str> = engine
+        .fix(yaml)
+        .replacements
+        .iter()
+        .map(|edit| &yaml[edit.span.start..edit.span.end])
+        .collect();
+    assert_eq!(fixed, [r#"echo "::set-output name=c::3""#]);
+}
@@ -122,8 +122,5 @@ jobs:
 
     // Note: This may produce a warning or be valid depending on implementation
     // Basic format validation should pass, but potentially invalid paths might warn
-    assert!(
-        true,
-        "Potentially invalid working-directory path may produce warning"
-    );
+    // Potentially invalid working-directory path may produce warning
 }
@@ -81,3 +81,57 @@ fn test_syntax_rule_deterministic() {
         "Syntax rule should be deterministic"
     );
 }
+
+#[test]
+fn test_syntax_rule_points_at_unclosed_bracket() {
+    let mut engine = TrussEngine::new();
+    let yaml = "on: push\njobs:\n  build:\n    runs-on: [ubuntu-latest\n";
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "syntax")
+        .collect();
+
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert!(errors[0].message.contains("unclosed '['"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "[");
+}
+
+#[test]
+fn test_syntax_rule_points_at_misindented_line() {
+    let mut engine = TrussEngine::new();
+    let yaml = "a:\n  b: 1\n c: 2\n";
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "syntax")
+        .collect();
+
+    assert_eq!(errors.len(), 1, "got: {:?}", errors);
+    assert!(errors[0].message.contains("unexpected"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "c: 2");
+}
+
+#[test]
+fn test_syntax_rule_reports_nested_error_span() {
+    let mut engine = TrussEngine::new();
+    let yaml = "name: test\nenv:\n  A: \"unclosed\n  B: 1\n";
+
+    let result = engine.analyze(yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "syntax")
+        .collect();
+
+    assert!(!errors.is_empty(), "Unclosed quote should be reported");
+    assert!(
+        errors.iter().all(|d| d.span.start > 10),
+        "Error should point inside env:, not at the start of the file: {:?}",
+        errors
+    );
+}
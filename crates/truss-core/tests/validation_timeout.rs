@@ -235,3 +235,39 @@ jobs:
         "Decimal timeout-minutes should be valid (GitHub Actions accepts decimals)"
     );
 }
+
+#[test]
+fn test_timeout_warning_above_hosted_limit() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    timeout-minutes: 99999
+    steps:
+      - run: echo "Building"
+  soak:
+    runs-on: [self-hosted, linux]
+    timeout-minutes: 1440
+    steps:
+      - run: echo "Soaking"
+"#;
+
+    let result = engine.analyze(yaml);
+    let warnings: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "timeout" && d.severity == Severity::Warning)
+        .collect();
+
+    assert_eq!(
+        warnings.len(),
+        1,
+        "Only the GitHub-hosted job should warn: {:?}",
+        warnings
+    );
+    assert!(warnings[0].message.contains("'build'"));
+    assert!(warnings[0].message.contains("360 minutes"));
+    assert_eq!(&yaml[warnings[0].span.start..warnings[0].span.end], "99999");
+}
@@ -0,0 +1,85 @@
+//! Tests for WorkflowDispatchInputMaxRule
+//!
+//! Validates that workflow_dispatch declares no more inputs than GitHub accepts.
+
+use truss_core::Severity;
+use truss_core::TrussEngine;
+
+fn workflow_with_inputs(count: usize) -> String {
+    let mut yaml = String::from("on:\n  workflow_dispatch:\n    inputs:\n");
+    for i in 1..=count {
+        yaml.push_str(&format!(
+            "      input{}:\n        description: Input {}\n        type: string\n",
+            i, i
+        ));
+    }
+    yaml.push_str(
+        "jobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo hi\n",
+    );
+    yaml
+}
+
+#[test]
+fn test_dispatch_inputs_over_limit() {
+    let mut engine = TrussEngine::new();
+    let yaml = workflow_with_inputs(26);
+
+    let result = engine.analyze(&yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "workflow_dispatch_input_max" && d.severity == Severity::Error)
+        .collect();
+
+    assert_eq!(
+        errors.len(),
+        1,
+        "26 dispatch inputs should produce one error"
+    );
+    assert!(errors[0].message.contains("26 inputs"));
+    assert_eq!(&yaml[errors[0].span.start..errors[0].span.end], "inputs");
+}
+
+#[test]
+fn test_dispatch_inputs_at_limit() {
+    let mut engine = TrussEngine::new();
+    let yaml = workflow_with_inputs(25);
+
+    let result = engine.analyze(&yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "workflow_dispatch_input_max")
+        .collect();
+
+    assert!(
+        errors.is_empty(),
+        "25 dispatch inputs is within the limit, got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn test_workflow_call_inputs_not_counted() {
+    let mut engine = TrussEngine::new();
+    let mut yaml = String::from("on:\n  workflow_dispatch:\n  workflow_call:\n    inputs:\n");
+    for i in 1..=30 {
+        yaml.push_str(&format!("      input{}:\n        type: string\n", i));
+    }
+    yaml.push_str(
+        "jobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: echo hi\n",
+    );
+
+    let result = engine.analyze(&yaml);
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule_id == "workflow_dispatch_input_max")
+        .collect();
+
+    assert!(
+        errors.is_empty(),
+        "The dispatch limit doesn't apply to workflow_call inputs, got: {:?}",
+        errors
+    );
+}
@@ -375,3 +375,43 @@ jobs:
             .collect::<Vec<_>>()
     );
 }
+
+#[test]
+fn test_workflow_trigger_duplicate_push() {
+    let mut engine = TrussEngine::new();
+    let yaml = r#"
+on:
+  push:
+    branches: [main]
+  pull_request:
+  push:
+    tags: ['v*']
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#;
+
+    let result = engine.analyze(yaml);
+    let duplicates: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.message.contains("Duplicate"))
+        .collect();
+
+    assert_eq!(
+        duplicates.len(),
+        1,
+        "Only the trigger rule should report the repeated push. Got: {:?}",
+        duplicates
+    );
+    let duplicate = duplicates[0];
+    assert_eq!(duplicate.rule_id, "workflow_trigger");
+    assert_eq!(
+        duplicate.message,
+        "Duplicate trigger 'push'; only the last definition is used."
+    );
+    assert_eq!(duplicate.span.start, yaml.rfind("push").unwrap());
+    assert_eq!(duplicate.related[0].span.start, yaml.find("push").unwrap());
+}
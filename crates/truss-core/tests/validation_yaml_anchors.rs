@@ -0,0 +1,99 @@
+//! Regression tests for YAML anchors (`&name`) and aliases (`*name`).
+//!
+//! GitHub Actions resolves aliases before running a workflow, so an aliased
+//! `env:`, `runs-on:`, or step must validate like the anchored content it
+//! refers to, without false positives for missing keys.
+
+use truss_core::{Severity, TrussEngine};
+
+const SHARED_ENV: &str = r#"
+name: CI
+on: push
+jobs:
+  build:
+    runs-on: &runner ubuntu-latest
+    env: &shared-env
+      NODE_ENV: production
+      HOME: /tmp/build
+    steps:
+      - &checkout
+        uses: actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683
+      - run: echo "$NODE_ENV"
+  test:
+    runs-on: *runner
+    env: *shared-env
+    steps:
+      - *checkout
+      - run: npm test
+"#;
+
+#[test]
+fn aliased_env_and_steps_have_no_false_positives() {
+    let mut engine = TrussEngine::new();
+    let result = engine.analyze(SHARED_ENV);
+
+    let errors: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect();
+    assert!(
+        errors.is_empty(),
+        "Aliased runs-on, env, and steps should resolve to the anchored content. Got: {:?}",
+        errors
+    );
+}
+
+#[test]
+fn aliased_env_is_checked_once_at_the_anchor() {
+    let diagnostics = TrussEngine::new()
+        .analyze_rule(SHARED_ENV, "step_env")
+        .unwrap();
+
+    assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+    assert!(diagnostics[0].message.contains("'HOME' in env:"));
+    let anchored_home = SHARED_ENV.find("HOME").unwrap();
+    assert_eq!(diagnostics[0].span.start, anchored_home);
+}
+
+#[test]
+fn aliased_step_without_uses_or_run_is_reported() {
+    let yaml = r#"
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - &named
+        name: Only a name
+      - *named
+"#;
+
+    let diagnostics = TrussEngine::new().analyze_rule(yaml, "step").unwrap();
+    assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+}
+
+#[test]
+fn self_referencing_anchor_terminates() {
+    let yaml = r#"
+on: push
+jobs:
+  build: &loop
+    runs-on: ubuntu-latest
+    env: &env
+      NESTED: *env
+    steps:
+      - &step
+        run: echo
+        with: *step
+      - *loop
+      - *undefined
+"#;
+
+    // Only needs to finish: an alias inside its own anchor is left unresolved
+    let mut engine = TrussEngine::new();
+    let result = engine.analyze(yaml);
+    assert!(result.diagnostics.iter().any(|d| d
+        .message
+        .contains("Step must have either 'uses' or 'run' field")));
+}
@@ -0,0 +1,245 @@
+//! Shared pre-pass over a workflow's AST.
+//!
+//! Most rules start by locating `jobs:` and walking every job's `steps:`.
+//! `RuleSet::validate_parallel` builds a `WorkflowIndex` once per analysis and
+//! hands it to each rule, so rules that opt in via
+//! `ValidationRule::validate_with_index` skip that walk.
+
+use super::utils;
+use std::collections::HashMap;
+use tree_sitter::{Node, Tree};
+
+/// A job under `jobs:` with its steps already collected.
+#[derive(Debug, Clone)]
+pub struct IndexedJob<'a> {
+    /// The job ID.
+    pub name: &'a str,
+    /// The key node naming the job, useful as a diagnostic span.
+    pub key: Node<'a>,
+    /// The job's unwrapped mapping.
+    pub value: Node<'a>,
+    /// The unwrapped mapping of every step, in order.
+    pub steps: Vec<Node<'a>>,
+}
+
+/// Lightweight index of a workflow's top-level keys, jobs, and steps.
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowIndex<'a> {
+    top_level: Vec<(&'a str, Node<'a>)>,
+    jobs_node: Option<Node<'a>>,
+    jobs: Vec<IndexedJob<'a>>,
+    step_ids: HashMap<(&'a str, &'a str), Node<'a>>,
+}
+
+impl<'a> WorkflowIndex<'a> {
+    /// Walk the tree once and index its top-level keys, jobs, and steps.
+    pub fn new(tree: &'a Tree, source: &'a str) -> Self {
+        let mut index = Self {
+            top_level: top_level_pairs(tree.root_node(), source),
+            ..Self::default()
+        };
+
+        // Same lookup as `utils::get_jobs_node`, so indexed rules see the same jobs
+        let jobs_node = match utils::get_jobs_node(tree, source) {
+            Some(n) => n,
+            None => return index,
+        };
+        index.jobs_node = Some(jobs_node);
+
+        for pair in utils::mapping_pairs(jobs_node) {
+            let (key, value) = match (pair.child(0), utils::get_pair_value(pair)) {
+                (Some(k), Some(v)) => (k, utils::unwrap_node(v)),
+                _ => continue,
+            };
+            if !matches!(value.kind(), "block_mapping" | "flow_mapping") {
+                continue;
+            }
+            let name = utils::clean_key(key, source);
+            let steps = utils::collect_steps(value, source);
+            for step in &steps {
+                if let Some(id) = utils::find_value_for_key(*step, source, "id") {
+                    index
+                        .step_ids
+                        .entry((name, utils::scalar_value(id, source)))
+                        .or_insert(*step);
+                }
+            }
+            index.jobs.push(IndexedJob {
+                name,
+                key,
+                value,
+                steps,
+            });
+        }
+
+        index
+    }
+
+    /// The unwrapped value of a top-level key such as `on`, `env`, or `jobs`.
+    pub fn top_level(&self, key: &str) -> Option<Node<'a>> {
+        self.top_level
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// The unwrapped `jobs:` mapping, if the document has one.
+    pub fn jobs_node(&self) -> Option<Node<'a>> {
+        self.jobs_node
+    }
+
+    /// Every job whose value is a mapping, in document order.
+    pub fn jobs(&self) -> &[IndexedJob<'a>] {
+        &self.jobs
+    }
+
+    /// Look up a job by ID.
+    pub fn job(&self, name: &str) -> Option<&IndexedJob<'a>> {
+        self.jobs.iter().find(|job| job.name == name)
+    }
+
+    /// Look up a step by its `id:` within a job. The first step wins on duplicates.
+    pub fn step_by_id(&self, job: &str, id: &str) -> Option<Node<'a>> {
+        self.step_ids.get(&(job, id)).copied()
+    }
+}
+
+/// Collect the pairs of the document's root mapping as `(key, unwrapped value)`.
+fn top_level_pairs<'a>(root: Node<'a>, source: &'a str) -> Vec<(&'a str, Node<'a>)> {
+    // stream > document > block_node > block_mapping
+    let mut node = root;
+    for _ in 0..4 {
+        if matches!(node.kind(), "block_mapping" | "flow_mapping") {
+            break;
+        }
+        let mut cursor = node.walk();
+        let next = node.children(&mut cursor).find(|child| {
+            matches!(
+                child.kind(),
+                "document" | "block_node" | "flow_node" | "block_mapping" | "flow_mapping"
+            )
+        });
+        match next {
+            Some(child) => node = utils::unwrap_node(child),
+            None => return Vec::new(),
+        }
+    }
+    if !matches!(node.kind(), "block_mapping" | "flow_mapping") {
+        return Vec::new();
+    }
+
+    utils::mapping_pairs(node)
+        .into_iter()
+        .filter_map(|pair| {
+            let key = pair.child(0)?;
+            let value = utils::get_pair_value(pair)?;
+            Some((utils::clean_key(key, source), utils::unwrap_node(value)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::YamlParser;
+    use crate::validation::ValidationRule;
+
+    const WORKFLOW: &str = r#"# CI
+name: CI
+on: pull_request_target
+env:
+  CI: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: checkout
+        uses: actions/checkout@v4
+        with:
+          ref: ${{ github.event.pull_request.head.sha }}
+      - run: npm ci
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - id: checkout
+        uses: actions/create-release@v1
+      - run: >
+          kubectl apply -f k8s
+          helm upgrade app chart
+"#;
+
+    #[test]
+    fn indexes_top_level_keys_jobs_and_steps() {
+        let tree = YamlParser::new().parse(WORKFLOW).unwrap();
+        let index = WorkflowIndex::new(&tree, WORKFLOW);
+
+        assert!(index.top_level("on").is_some());
+        assert_eq!(
+            index.top_level("env").map(|n| n.kind()),
+            Some("block_mapping")
+        );
+        assert!(index.top_level("permissions").is_none());
+
+        let names: Vec<_> = index.jobs().iter().map(|job| job.name).collect();
+        assert_eq!(names, ["build", "deploy"]);
+        assert_eq!(index.job("deploy").map(|job| job.steps.len()), Some(2));
+
+        let checkout = index.step_by_id("deploy", "checkout").unwrap();
+        assert!(utils::node_text(checkout, WORKFLOW).contains("create-release"));
+        assert!(index.step_by_id("build", "missing").is_none());
+    }
+
+    #[test]
+    fn non_workflow_document_has_empty_index() {
+        let source = "- a\n- b\n";
+        let tree = YamlParser::new().parse(source).unwrap();
+        let index = WorkflowIndex::new(&tree, source);
+
+        assert!(index.jobs_node().is_none());
+        assert!(index.jobs().is_empty());
+        assert!(index.top_level("on").is_none());
+    }
+
+    #[test]
+    fn analysis_looks_up_jobs_once() {
+        utils::JOBS_LOOKUPS.with(|count| count.set(0));
+        // The timed analysis runs rules on this thread, so each lookup is counted
+        let (result, timings) = crate::TrussEngine::new().analyze_timed(WORKFLOW);
+        assert!(!result.diagnostics.is_empty());
+        assert!(timings.len() > 50);
+        assert_eq!(utils::JOBS_LOOKUPS.with(|count| count.get()), 1);
+    }
+
+    #[test]
+    fn indexed_rules_match_unindexed_results() {
+        use crate::validation::rules::*;
+
+        let tree = YamlParser::new().parse(WORKFLOW).unwrap();
+        let index = WorkflowIndex::new(&tree, WORKFLOW);
+        let rules: Vec<Box<dyn ValidationRule>> = vec![
+            Box::new(ActionPinningRule),
+            Box::new(ActionInputNameRule),
+            Box::new(DeprecatedActionRule),
+            Box::new(RunBlockScalarRule),
+            Box::new(PullRequestTargetRule),
+            Box::new(DeployJobTimeoutRule),
+            Box::new(PermissionsRule),
+            Box::new(NeedsOutputReferenceRule),
+            Box::new(StepEnvValidationRule),
+            Box::new(MissingCheckoutRule),
+        ];
+
+        for rule in rules {
+            let spans = |diagnostics: Vec<crate::Diagnostic>| -> Vec<_> {
+                diagnostics
+                    .into_iter()
+                    .map(|d| (d.span.start, d.span.end, d.message))
+                    .collect()
+            };
+            let plain = spans(rule.validate(&tree, WORKFLOW));
+            let indexed = spans(rule.validate_with_index(&tree, WORKFLOW, &index));
+            assert_eq!(plain, indexed, "{} differs with the index", rule.name());
+        }
+    }
+}
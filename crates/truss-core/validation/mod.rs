@@ -1,12 +1,80 @@
 //! Validation rule framework.
 //! Rules are independent and can run in parallel.
 
-use crate::{Diagnostic, TrussResult};
+use crate::{Diagnostic, LineIndex, RuleCategory, Severity, TextEdit, TrussResult};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tree_sitter::Tree;
 
+mod index;
 pub mod rules;
+mod suppress;
 pub mod utils;
 
+pub use index::{IndexedJob, WorkflowIndex};
+pub(crate) use suppress::{apply_inline_suppressions, drop_suppressed_edits};
+
+/// Longer, user-facing description of a rule, shown by `truss explain`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleExplanation {
+    /// What the rule checks and why it matters.
+    pub summary: &'static str,
+    /// YAML that triggers the rule.
+    pub bad_example: &'static str,
+    /// The same YAML with the problem fixed.
+    pub good_example: &'static str,
+}
+
+impl RuleExplanation {
+    /// The first sentence of `summary`, for lists where the full text is too long.
+    pub fn short_summary(&self) -> &'static str {
+        let summary = self.summary;
+        let mut search_from = 0;
+        while let Some(pos) = summary[search_from..].find(". ") {
+            let end = search_from + pos + 1;
+            // Abbreviations like "e.g." don't end a sentence
+            if !summary[..end].ends_with("e.g.") && !summary[..end].ends_with("i.e.") {
+                return &summary[..end];
+            }
+            search_from = end;
+        }
+        summary
+    }
+}
+
+/// Kind of YAML document, used to route each rule to the files it checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    /// Any YAML document. Rules targeting it run on every file.
+    AnyYaml,
+    /// A GitHub Actions workflow.
+    Workflow,
+    /// A composite action's metadata file (`runs.using: composite`).
+    CompositeAction,
+}
+
+impl FileKind {
+    /// Detect the kind of a parsed document.
+    ///
+    /// Documents that are neither a workflow nor a composite action,
+    /// including other action types, are `AnyYaml`.
+    pub fn detect(tree: &Tree, source: &str) -> Self {
+        if utils::is_github_actions_workflow(tree, source) {
+            FileKind::Workflow
+        } else if utils::is_composite_action(tree, source) {
+            FileKind::CompositeAction
+        } else {
+            FileKind::AnyYaml
+        }
+    }
+
+    /// Whether a rule that applies to `self` runs on a document of kind `file`.
+    pub fn includes(self, file: FileKind) -> bool {
+        self == FileKind::AnyYaml || self == file
+    }
+}
+
 /// A validation rule that checks the AST.
 ///
 /// Rules must be:
@@ -25,19 +93,79 @@ pub trait ValidationRule: Send + Sync {
     /// - Independent (doesn't depend on other rules)
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic>;
 
+    /// Validate using the shared `WorkflowIndex` built once per analysis.
+    ///
+    /// Defaults to `validate`. Rules that walk `jobs:` or `steps:` override
+    /// this to read them from the index instead.
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let _ = index;
+        self.validate(tree, source)
+    }
+
     /// Whether this rule only applies to GitHub Actions workflow files.
     ///
-    /// Returns `true` by default. Rules that apply to all YAML files
-    /// (e.g., syntax checking, empty document detection) should override
-    /// this to return `false`.
+    /// Returns `true` by default. Only read by the default `applies_to`,
+    /// which maps `false` to `FileKind::AnyYaml`; new rules should override
+    /// `applies_to` instead.
     fn requires_workflow(&self) -> bool {
         true
     }
+
+    /// Kind of document this rule checks. Other documents skip it.
+    ///
+    /// Defaults to `FileKind::Workflow`, or `FileKind::AnyYaml` when
+    /// `requires_workflow` returns `false`.
+    fn applies_to(&self) -> FileKind {
+        if self.requires_workflow() {
+            FileKind::Workflow
+        } else {
+            FileKind::AnyYaml
+        }
+    }
+
+    /// Category used to group and filter this rule's diagnostics.
+    ///
+    /// Defaults to `RuleCategory::Schema`.
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Schema
+    }
+
+    /// Most severe level this rule reports, before any severity overrides.
+    ///
+    /// Defaults to `Severity::Error`. Rules that only warn or inform override it.
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    /// Edits that fix this rule's problems mechanically, for `truss fix`.
+    ///
+    /// Only implemented by rules whose fix is always safe. The edits must not
+    /// overlap each other. Defaults to none.
+    fn fixes(&self, tree: &Tree, source: &str) -> Vec<TextEdit> {
+        let _ = (tree, source);
+        Vec::new()
+    }
+
+    /// Description and examples for `truss explain`.
+    ///
+    /// Defaults to an empty explanation for rules that don't provide one.
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation::default()
+    }
 }
 
 /// Collection of validation rules.
+///
+/// Rules are stateless and held behind `Arc`, so cloning a `RuleSet` only
+/// copies pointers.
+#[derive(Clone)]
 pub struct RuleSet {
-    rules: Vec<Box<dyn ValidationRule>>,
+    rules: Vec<Arc<dyn ValidationRule>>,
 }
 
 impl RuleSet {
@@ -48,7 +176,48 @@ impl RuleSet {
 
     /// Add a validation rule.
     pub fn add_rule<R: ValidationRule + 'static>(&mut self, rule: R) {
-        self.rules.push(Box::new(rule));
+        self.rules.push(Arc::new(rule));
+    }
+
+    /// Add the rules of `other` that `keep` accepts and whose id isn't registered yet.
+    pub fn merge(&mut self, other: &RuleSet, keep: impl Fn(&dyn ValidationRule) -> bool) {
+        for rule in &other.rules {
+            if keep(rule.as_ref()) && self.find(rule.name()).is_none() {
+                self.rules.push(Arc::clone(rule));
+            }
+        }
+    }
+
+    /// Remove the rule with id `rule_id`. Returns whether one was registered.
+    pub fn remove(&mut self, rule_id: &str) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|rule| rule.name() != rule_id);
+        self.rules.len() != before
+    }
+
+    /// Iterate over the rules in registration order.
+    pub fn rules(&self) -> impl Iterator<Item = &dyn ValidationRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Id, category, default severity, and one-sentence description of each rule,
+    /// in registration order.
+    pub fn metadata(
+        &self,
+    ) -> impl Iterator<Item = (&str, RuleCategory, Severity, &'static str)> + '_ {
+        self.rules().map(|rule| {
+            (
+                rule.name(),
+                rule.category(),
+                rule.default_severity(),
+                rule.explanation().short_summary(),
+            )
+        })
+    }
+
+    /// Find a rule by its id (the value returned by `ValidationRule::name()`).
+    pub fn find(&self, rule_id: &str) -> Option<&dyn ValidationRule> {
+        self.rules().find(|rule| rule.name() == rule_id)
     }
 
     /// Run all validation rules in parallel.
@@ -56,34 +225,170 @@ impl RuleSet {
     /// Rules are independent and can run concurrently.
     /// Results are merged deterministically.
     ///
-    /// The `is_github_actions_workflow` check is performed once here
-    /// rather than in each rule, eliminating redundant tree walks.
+    /// The `FileKind` and the `WorkflowIndex` are detected and built once
+    /// here rather than in each rule, eliminating redundant tree walks. Only
+    /// rules whose `applies_to` includes the document's kind run.
     pub fn validate_parallel(&self, tree: &Tree, source: &str) -> TrussResult {
         use rayon::prelude::*;
 
-        let is_workflow = utils::is_github_actions_workflow(tree, source);
+        let kind = FileKind::detect(tree, source);
+        let index = workflow_index(kind, tree, source);
 
         let all_diagnostics: Vec<Diagnostic> = self
             .rules
             .par_iter()
-            .filter(|rule| is_workflow || !rule.requires_workflow())
+            .filter(|rule| rule.applies_to().includes(kind))
+            .flat_map(|rule| run_rule(rule.as_ref(), tree, source, &index))
+            .collect();
+
+        merge_diagnostics(all_diagnostics, tree, source)
+    }
+
+    /// Run only the rule with id `rule_id`, or return `None` if there isn't one.
+    ///
+    /// Diagnostics are stamped and sorted as in `validate_parallel`. A rule
+    /// reports nothing for documents outside its `applies_to` kind.
+    pub fn validate_rule(&self, rule_id: &str, tree: &Tree, source: &str) -> Option<TrussResult> {
+        let rule = self.find(rule_id)?;
+        let kind = FileKind::detect(tree, source);
+        if !rule.applies_to().includes(kind) {
+            return Some(TrussResult {
+                diagnostics: Vec::new(),
+                line_index: Some(LineIndex::new(source)),
+            });
+        }
+        let index = workflow_index(kind, tree, source);
+        Some(merge_diagnostics(
+            run_rule(rule, tree, source, &index),
+            tree,
+            source,
+        ))
+    }
+
+    /// Run the rules one at a time and record each rule's wall time.
+    ///
+    /// Produces the same result as `validate_parallel`. Running sequentially
+    /// keeps one rule's time from being inflated by others competing for cores.
+    /// Rules skipped for the document's `FileKind` aren't listed, and
+    /// building the shared `WorkflowIndex` isn't attributed to any rule.
+    pub fn validate_timed(
+        &self,
+        tree: &Tree,
+        source: &str,
+    ) -> (TrussResult, Vec<(String, Duration)>) {
+        let kind = FileKind::detect(tree, source);
+        let index = workflow_index(kind, tree, source);
+
+        let mut all_diagnostics = Vec::new();
+        let mut timings = Vec::new();
+        for rule in self.rules() {
+            if !rule.applies_to().includes(kind) {
+                continue;
+            }
+            let start = Instant::now();
+            all_diagnostics.extend(run_rule(rule, tree, source, &index));
+            timings.push((rule.name().to_string(), start.elapsed()));
+        }
+
+        (merge_diagnostics(all_diagnostics, tree, source), timings)
+    }
+
+    /// The fixes of every rule that applies to the document, stamped with
+    /// the rule's id and sorted by start offset. Fixes on a line where an
+    /// inline suppression comment disables their rule are dropped.
+    pub fn fixes(&self, tree: &Tree, source: &str) -> Vec<TextEdit> {
+        let kind = FileKind::detect(tree, source);
+        let mut edits: Vec<TextEdit> = self
+            .rules()
+            .filter(|rule| rule.applies_to().includes(kind))
             .flat_map(|rule| {
-                let rule_name = rule.name().to_string();
-                rule.validate(tree, source)
-                    .into_iter()
-                    .map(move |mut d| {
-                        d.rule_id = rule_name.clone();
-                        d
-                    })
-                    .collect::<Vec<_>>()
+                rule.fixes(tree, source).into_iter().map(|mut edit| {
+                    edit.rule_id = rule.name().to_string();
+                    edit
+                })
             })
             .collect();
+        drop_suppressed_edits(tree, source, &mut edits);
+        edits.sort_by_key(|edit| (edit.span.start, edit.span.end));
+        edits
+    }
+}
+
+/// The shared index for a document of `kind`; empty unless it's a workflow.
+fn workflow_index<'a>(kind: FileKind, tree: &'a Tree, source: &'a str) -> WorkflowIndex<'a> {
+    if kind == FileKind::Workflow {
+        WorkflowIndex::new(tree, source)
+    } else {
+        WorkflowIndex::default()
+    }
+}
+
+/// Run one rule and stamp its diagnostics with the rule's id and category.
+fn run_rule(
+    rule: &dyn ValidationRule,
+    tree: &Tree,
+    source: &str,
+    index: &WorkflowIndex,
+) -> Vec<Diagnostic> {
+    let rule_name = rule.name().to_string();
+    let category = rule.category();
+    rule.validate_with_index(tree, source, index)
+        .into_iter()
+        .map(|mut d| {
+            d.rule_id = rule_name.clone();
+            d.category = category;
+            d
+        })
+        .collect()
+}
+
+/// De-duplicate and sort the diagnostics of all rules into the final result,
+/// dropping those silenced by `# truss-disable-line` comments.
+fn merge_diagnostics(all_diagnostics: Vec<Diagnostic>, tree: &Tree, source: &str) -> TrussResult {
+    let mut diagnostics = dedup_diagnostics(all_diagnostics);
+    apply_inline_suppressions(tree, source, &mut diagnostics);
+    diagnostics.sort_by_key(|d| (d.span.start, d.severity));
+    TrussResult {
+        diagnostics,
+        line_index: Some(LineIndex::new(source)),
+    }
+}
 
-        let mut diagnostics = all_diagnostics;
-        diagnostics.sort_by_key(|d| (d.span.start, d.severity));
+/// Collapse diagnostics that report the same problem from different rules.
+///
+/// Two diagnostics are duplicates when they share a span, a severity, and a
+/// message that only differs in case, quoting, whitespace, or a trailing
+/// period. The one kept comes from the rule with the lowest `RuleCategory`
+/// (syntax before schema before expression, and so on), ties broken by the
+/// lower `rule_id`. Everything else keeps its original order.
+fn dedup_diagnostics(diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    let mut kept: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    let mut seen: HashMap<(usize, usize, Severity, String), usize> = HashMap::new();
 
-        TrussResult { diagnostics }
+    for diagnostic in diagnostics {
+        let key = (
+            diagnostic.span.start,
+            diagnostic.span.end,
+            diagnostic.severity,
+            diagnostic.normalized_message(),
+        );
+        match seen.get(&key) {
+            Some(&i) => {
+                let existing = &kept[i];
+                if (diagnostic.category, &diagnostic.rule_id)
+                    < (existing.category, &existing.rule_id)
+                {
+                    kept[i] = diagnostic;
+                }
+            }
+            None => {
+                seen.insert(key, kept.len());
+                kept.push(diagnostic);
+            }
+        }
     }
+
+    kept
 }
 
 impl Default for RuleSet {
@@ -0,0 +1,389 @@
+use super::super::utils;
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Colors accepted by `branding.color`.
+const VALID_COLORS: &[&str] = &[
+    "white",
+    "yellow",
+    "blue",
+    "green",
+    "orange",
+    "red",
+    "purple",
+    "gray-dark",
+];
+
+/// Icons accepted by `branding.icon`: Feather v4.28.0, minus the icons
+/// GitHub excludes (coffee, columns, divide*, frown, hexagon, key, meh,
+/// mouse-pointer, smile, tool, x-octagon).
+const VALID_ICONS: &[&str] = &[
+    "activity",
+    "airplay",
+    "alert-circle",
+    "alert-octagon",
+    "alert-triangle",
+    "align-center",
+    "align-justify",
+    "align-left",
+    "align-right",
+    "anchor",
+    "aperture",
+    "archive",
+    "arrow-down",
+    "arrow-down-circle",
+    "arrow-down-left",
+    "arrow-down-right",
+    "arrow-left",
+    "arrow-left-circle",
+    "arrow-right",
+    "arrow-right-circle",
+    "arrow-up",
+    "arrow-up-circle",
+    "arrow-up-left",
+    "arrow-up-right",
+    "at-sign",
+    "award",
+    "bar-chart",
+    "bar-chart-2",
+    "battery",
+    "battery-charging",
+    "bell",
+    "bell-off",
+    "bluetooth",
+    "bold",
+    "book",
+    "book-open",
+    "bookmark",
+    "box",
+    "briefcase",
+    "calendar",
+    "camera",
+    "camera-off",
+    "cast",
+    "check",
+    "check-circle",
+    "check-square",
+    "chevron-down",
+    "chevron-left",
+    "chevron-right",
+    "chevron-up",
+    "chevrons-down",
+    "chevrons-left",
+    "chevrons-right",
+    "chevrons-up",
+    "chrome",
+    "circle",
+    "clipboard",
+    "clock",
+    "cloud",
+    "cloud-drizzle",
+    "cloud-lightning",
+    "cloud-off",
+    "cloud-rain",
+    "cloud-snow",
+    "code",
+    "codepen",
+    "codesandbox",
+    "command",
+    "compass",
+    "copy",
+    "corner-down-left",
+    "corner-down-right",
+    "corner-left-down",
+    "corner-left-up",
+    "corner-right-down",
+    "corner-right-up",
+    "corner-up-left",
+    "corner-up-right",
+    "cpu",
+    "credit-card",
+    "crop",
+    "crosshair",
+    "database",
+    "delete",
+    "disc",
+    "dollar-sign",
+    "download",
+    "download-cloud",
+    "dribbble",
+    "droplet",
+    "edit",
+    "edit-2",
+    "edit-3",
+    "external-link",
+    "eye",
+    "eye-off",
+    "facebook",
+    "fast-forward",
+    "feather",
+    "figma",
+    "file",
+    "file-minus",
+    "file-plus",
+    "file-text",
+    "film",
+    "filter",
+    "flag",
+    "folder",
+    "folder-minus",
+    "folder-plus",
+    "framer",
+    "gift",
+    "git-branch",
+    "git-commit",
+    "git-merge",
+    "git-pull-request",
+    "github",
+    "gitlab",
+    "globe",
+    "grid",
+    "hard-drive",
+    "hash",
+    "headphones",
+    "heart",
+    "help-circle",
+    "home",
+    "image",
+    "inbox",
+    "info",
+    "instagram",
+    "italic",
+    "layers",
+    "layout",
+    "life-buoy",
+    "link",
+    "link-2",
+    "linkedin",
+    "list",
+    "loader",
+    "lock",
+    "log-in",
+    "log-out",
+    "mail",
+    "map",
+    "map-pin",
+    "maximize",
+    "maximize-2",
+    "menu",
+    "message-circle",
+    "message-square",
+    "mic",
+    "mic-off",
+    "minimize",
+    "minimize-2",
+    "minus",
+    "minus-circle",
+    "minus-square",
+    "monitor",
+    "moon",
+    "more-horizontal",
+    "more-vertical",
+    "move",
+    "music",
+    "navigation",
+    "navigation-2",
+    "octagon",
+    "package",
+    "paperclip",
+    "pause",
+    "pause-circle",
+    "pen-tool",
+    "percent",
+    "phone",
+    "phone-call",
+    "phone-forwarded",
+    "phone-incoming",
+    "phone-missed",
+    "phone-off",
+    "phone-outgoing",
+    "pie-chart",
+    "play",
+    "play-circle",
+    "plus",
+    "plus-circle",
+    "plus-square",
+    "pocket",
+    "power",
+    "printer",
+    "radio",
+    "refresh-ccw",
+    "refresh-cw",
+    "repeat",
+    "rewind",
+    "rotate-ccw",
+    "rotate-cw",
+    "rss",
+    "save",
+    "scissors",
+    "search",
+    "send",
+    "server",
+    "settings",
+    "share",
+    "share-2",
+    "shield",
+    "shield-off",
+    "shopping-bag",
+    "shopping-cart",
+    "shuffle",
+    "sidebar",
+    "skip-back",
+    "skip-forward",
+    "slack",
+    "slash",
+    "sliders",
+    "smartphone",
+    "speaker",
+    "square",
+    "star",
+    "stop-circle",
+    "sun",
+    "sunrise",
+    "sunset",
+    "table",
+    "tablet",
+    "tag",
+    "target",
+    "terminal",
+    "thermometer",
+    "thumbs-down",
+    "thumbs-up",
+    "toggle-left",
+    "toggle-right",
+    "trash",
+    "trash-2",
+    "trello",
+    "trending-down",
+    "trending-up",
+    "triangle",
+    "truck",
+    "tv",
+    "twitch",
+    "twitter",
+    "type",
+    "umbrella",
+    "underline",
+    "unlock",
+    "upload",
+    "upload-cloud",
+    "user",
+    "user-check",
+    "user-minus",
+    "user-plus",
+    "user-x",
+    "users",
+    "video",
+    "video-off",
+    "voicemail",
+    "volume",
+    "volume-1",
+    "volume-2",
+    "volume-x",
+    "watch",
+    "wifi",
+    "wifi-off",
+    "wind",
+    "x",
+    "x-circle",
+    "x-square",
+    "youtube",
+    "zap",
+    "zap-off",
+    "zoom-in",
+    "zoom-out",
+];
+
+/// Validates `branding.icon` and `branding.color` in action metadata files.
+pub struct ActionBrandingRule;
+
+impl ValidationRule for ActionBrandingRule {
+    fn name(&self) -> &str {
+        "action_branding"
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "In an `action.yml`, `branding.icon` must be a Feather icon GitHub supports and `branding.color` one of the Marketplace colors. The Marketplace rejects anything else.",
+            bad_example: r#"name: Greeter
+description: Says hello
+branding:
+  icon: rocket
+  color: pink
+runs:
+  using: node20
+  main: dist/index.js
+"#,
+            good_example: r#"name: Greeter
+description: Says hello
+branding:
+  icon: send
+  color: purple
+runs:
+  using: node20
+  main: dist/index.js
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        if utils::get_action_runs_node(tree, source).is_none() {
+            return diagnostics;
+        }
+
+        let branding_node = match utils::find_value_for_key(tree.root_node(), source, "branding") {
+            Some(n) => utils::unwrap_node(n),
+            None => return diagnostics,
+        };
+
+        if let Some(icon_node) = utils::find_value_for_key(branding_node, source, "icon") {
+            check_branding_value(icon_node, source, "icon", VALID_ICONS, &mut diagnostics);
+        }
+        if let Some(color_node) = utils::find_value_for_key(branding_node, source, "color") {
+            check_branding_value(color_node, source, "color", VALID_COLORS, &mut diagnostics);
+        }
+
+        diagnostics
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::AnyYaml
+    }
+}
+
+fn check_branding_value(
+    value_node: Node,
+    source: &str,
+    field: &str,
+    allowed: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let value_node = utils::unwrap_node(value_node);
+    let value = utils::scalar_value(value_node, source);
+    if allowed.contains(&value) {
+        return;
+    }
+
+    let hint = if field == "color" {
+        format!("Expected one of: {}", VALID_COLORS.join(", "))
+    } else {
+        "See https://feathericons.com for the available icon names".to_string()
+    };
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "Invalid branding {} '{}'. The Marketplace rejects actions with invalid branding. {}",
+            field, value, hint
+        ),
+        severity: Severity::Error,
+        span: Span {
+            start: value_node.start_byte(),
+            end: value_node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
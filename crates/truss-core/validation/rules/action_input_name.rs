@@ -0,0 +1,266 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Declared inputs of widely used actions, keyed by `owner/repo`.
+///
+/// Only the latest major version's inputs are listed; the table is meant for
+/// fuzzy spelling checks, not as an exhaustive schema.
+pub(crate) const KNOWN_ACTION_INPUTS: &[(&str, &[&str])] = &[
+    (
+        "actions/checkout",
+        &[
+            "repository",
+            "ref",
+            "token",
+            "ssh-key",
+            "ssh-known-hosts",
+            "ssh-strict",
+            "ssh-user",
+            "persist-credentials",
+            "path",
+            "clean",
+            "filter",
+            "sparse-checkout",
+            "sparse-checkout-cone-mode",
+            "fetch-depth",
+            "fetch-tags",
+            "show-progress",
+            "lfs",
+            "submodules",
+            "set-safe-directory",
+            "github-server-url",
+        ],
+    ),
+    (
+        "actions/setup-node",
+        &[
+            "always-auth",
+            "node-version",
+            "node-version-file",
+            "architecture",
+            "check-latest",
+            "registry-url",
+            "scope",
+            "token",
+            "cache",
+            "cache-dependency-path",
+        ],
+    ),
+    (
+        "actions/setup-python",
+        &[
+            "python-version",
+            "python-version-file",
+            "cache",
+            "architecture",
+            "check-latest",
+            "token",
+            "cache-dependency-path",
+            "update-environment",
+            "allow-prereleases",
+        ],
+    ),
+    (
+        "actions/setup-go",
+        &[
+            "go-version",
+            "go-version-file",
+            "check-latest",
+            "token",
+            "cache",
+            "cache-dependency-path",
+            "architecture",
+        ],
+    ),
+    (
+        "actions/setup-java",
+        &[
+            "java-version",
+            "java-version-file",
+            "distribution",
+            "java-package",
+            "architecture",
+            "jdkFile",
+            "check-latest",
+            "server-id",
+            "server-username",
+            "server-password",
+            "settings-path",
+            "overwrite-settings",
+            "gpg-private-key",
+            "gpg-passphrase",
+            "cache",
+            "cache-dependency-path",
+            "token",
+        ],
+    ),
+    (
+        "actions/cache",
+        &[
+            "path",
+            "key",
+            "restore-keys",
+            "upload-chunk-size",
+            "enableCrossOsArchive",
+            "fail-on-cache-miss",
+            "lookup-only",
+        ],
+    ),
+    (
+        "actions/upload-artifact",
+        &[
+            "name",
+            "path",
+            "if-no-files-found",
+            "retention-days",
+            "compression-level",
+            "overwrite",
+            "include-hidden-files",
+        ],
+    ),
+    (
+        "actions/download-artifact",
+        &[
+            "name",
+            "path",
+            "pattern",
+            "merge-multiple",
+            "github-token",
+            "repository",
+            "run-id",
+        ],
+    ),
+];
+
+/// Flags `with:` keys that only differ from a known input by `_` vs `-`.
+pub struct ActionInputNameRule;
+
+impl ValidationRule for ActionInputNameRule {
+    fn name(&self) -> &str {
+        "action_input_name"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags `with:` keys on well-known actions that only differ from a real input by `_` vs `-`. Unknown inputs are silently ignored, so the typo has no effect.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch_depth: 0
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          fetch-depth: 0
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            for &step in &job.steps {
+                let uses_node = match utils::find_value_for_key(step, source, "uses") {
+                    Some(n) => n,
+                    None => continue,
+                };
+                let inputs = match known_inputs(utils::scalar_value(uses_node, source)) {
+                    Some(inputs) => inputs,
+                    None => continue,
+                };
+                if let Some(with_node) = utils::find_value_for_key(step, source, "with") {
+                    check_with_keys(
+                        utils::unwrap_node(with_node),
+                        uses_node,
+                        inputs,
+                        source,
+                        &mut diagnostics,
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Looks up the known inputs for a `uses:` value. Sub-path actions
+/// (`owner/repo/path@ref`) are different actions and are not matched.
+fn known_inputs(uses: &str) -> Option<&'static [&'static str]> {
+    let action = uses.split('@').next().unwrap_or("");
+    KNOWN_ACTION_INPUTS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(action))
+        .map(|(_, inputs)| *inputs)
+}
+
+/// Input names are case-insensitive; `_` and `-` are not interchangeable, but
+/// treating them as equal here is what surfaces the typo.
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase().replace('_', "-")
+}
+
+fn check_with_keys(
+    with_node: Node,
+    uses_node: Node,
+    inputs: &[&str],
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let action = utils::scalar_value(uses_node, source);
+    let action = action.split('@').next().unwrap_or(action);
+
+    for pair in utils::mapping_pairs(with_node) {
+        let key_node = match pair.child(0) {
+            Some(k) => k,
+            None => continue,
+        };
+        let key = utils::clean_key(key_node, source);
+
+        if inputs.iter().any(|input| input.eq_ignore_ascii_case(key)) {
+            continue;
+        }
+
+        let normalized = normalize(key);
+        if let Some(expected) = inputs.iter().find(|input| normalize(input) == normalized) {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "'{}' is not an input of '{}' and will be ignored. Did you mean '{}'?",
+                    key, action, expected
+                ),
+                severity: Severity::Warning,
+                span: Span {
+                    start: key_node.start_byte(),
+                    end: key_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Requires remote actions to be pinned to a full commit SHA.
+pub struct ActionPinningRule;
+
+impl ValidationRule for ActionPinningRule {
+    fn name(&self) -> &str {
+        "action_pinning"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Remote actions should be pinned to a full 40-character commit SHA. Tags like `@v4` and branches like `@main` can be moved to point at different code. Disable the rule or change its severity in `.truss.yml` to match your policy.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@11bd71901bbe5b1630ceea73d27597364c9af683 # v4.2.2
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            for &step in &job.steps {
+                if let Some(uses_node) = utils::find_value_for_key(step, source, "uses") {
+                    check_action_pinning(uses_node, source, &mut diagnostics);
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn check_action_pinning(uses_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let uses = utils::scalar_value(uses_node, source);
+
+    // Local actions live in the same commit, and Docker images are pinned by digest, not ref
+    if uses.starts_with("./") || uses.starts_with("docker://") || uses.contains("${{") {
+        return;
+    }
+
+    // A missing ref is reported by ActionReferenceRule
+    let (action, git_ref) = match uses.rsplit_once('@') {
+        Some((action, git_ref)) if !git_ref.is_empty() => (action, git_ref),
+        _ => return,
+    };
+
+    if is_full_commit_sha(git_ref) {
+        return;
+    }
+
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "Action '{}' is pinned to '{}', which can be moved to different code. Pin it to a full 40-character commit SHA.",
+            action, git_ref
+        ),
+        severity: Severity::Warning,
+        span: Span {
+            start: uses_node.start_byte(),
+            end: uses_node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
+
+fn is_full_commit_sha(git_ref: &str) -> bool {
+    git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates action reference format (owner/repo@ref).
@@ -11,10 +11,39 @@ impl ValidationRule for ActionReferenceRule {
         "action_reference"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks the format of `uses:` action references. Remote actions must be `owner/repo@ref` or `owner/repo/path@ref`, and `@latest` is flagged because actions don't publish a `latest` ref.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -114,6 +143,8 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     }
@@ -131,12 +162,14 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     }
 
     let owner_repo = parts[0];
-    let _ref = parts[1];
+    let git_ref = parts[1];
 
     if !owner_repo.contains('/') {
         diagnostics.push(Diagnostic {
@@ -150,6 +183,8 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     }
@@ -168,6 +203,8 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     }
@@ -187,6 +224,8 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
     }
 
@@ -202,6 +241,26 @@ fn validate_action_reference(uses_node: Node, source: &str, diagnostics: &mut Ve
                 end: uses_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+
+    // `latest` is a Docker tag convention; action refs are git tags, branches, or SHAs
+    if git_ref.eq_ignore_ascii_case("latest") {
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "action reference '{}' uses '@latest', which is not a git ref actions publish. Use a release tag (e.g., {}@v4) or a commit SHA.",
+                uses_cleaned, owner_repo
+            ),
+            severity: Severity::Warning,
+            span: Span {
+                start: uses_node.start_byte(),
+                end: uses_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
     }
 }
@@ -0,0 +1,103 @@
+use super::super::utils;
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::Tree;
+
+/// Runtimes accepted by `runs.using` in an action metadata file.
+const VALID_RUNTIMES: &[&str] = &["composite", "docker", "node20", "node24"];
+
+/// Node runtimes GitHub has deprecated and is removing from runners.
+const DEPRECATED_RUNTIMES: &[&str] = &["node12", "node16"];
+
+/// Validates `runs.using` in action metadata files.
+pub struct ActionRunsUsingRule;
+
+impl ValidationRule for ActionRunsUsingRule {
+    fn name(&self) -> &str {
+        "action_runs_using"
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "In an `action.yml`, `runs.using` must be `composite`, `docker`, `node20`, or `node24`. `node12` and `node16` are deprecated.",
+            bad_example: r#"name: Greeter
+description: Says hello
+runs:
+  using: node16
+  main: dist/index.js
+"#,
+            good_example: r#"name: Greeter
+description: Says hello
+runs:
+  using: node20
+  main: dist/index.js
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let runs_node = match utils::get_action_runs_node(tree, source) {
+            Some(n) => n,
+            None => return diagnostics,
+        };
+
+        let using_node = match utils::find_value_for_key(runs_node, source, "using") {
+            Some(n) => utils::unwrap_node(n),
+            None => {
+                diagnostics.push(Diagnostic {
+                    message: "Action 'runs' is missing required 'using' field. Expected one of: composite, docker, node20, node24".to_string(),
+                    severity: Severity::Error,
+                    span: Span {
+                        start: runs_node.start_byte(),
+                        end: runs_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+                return diagnostics;
+            }
+        };
+
+        let using = utils::scalar_value(using_node, source);
+        let span = Span {
+            start: using_node.start_byte(),
+            end: using_node.end_byte(),
+        };
+
+        if DEPRECATED_RUNTIMES.contains(&using) {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Action runtime '{}' is deprecated and no longer supported on GitHub-hosted runners. Use 'node20' instead.",
+                    using
+                ),
+                severity: Severity::Warning,
+                span,
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        } else if !VALID_RUNTIMES.contains(&using) {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Invalid action runtime 'runs.using: {}'. Expected one of: {}",
+                    using,
+                    VALID_RUNTIMES.join(", ")
+                ),
+                severity: Severity::Error,
+                span,
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+
+        diagnostics
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::AnyYaml
+    }
+}
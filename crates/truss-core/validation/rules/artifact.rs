@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates actions/upload-artifact and actions/download-artifact usage.
@@ -11,10 +11,45 @@ impl ValidationRule for ArtifactValidationRule {
         "artifact"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `actions/upload-artifact` and `actions/download-artifact` inputs, such as empty artifact names and malformed paths.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/upload-artifact@v4
+        with:
+          name: ""
+          path: dist/
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/upload-artifact@v4
+        with:
+          name: dist
+          path: dist/
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -129,6 +164,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                 end: name_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     } else {
                                         // Validate artifact name format (basic validation)
@@ -144,6 +181,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                     end: name_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -172,6 +211,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                 end: path_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
                                 }
@@ -201,6 +242,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                         end: retention_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -216,6 +259,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                     end: retention_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -253,6 +298,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                             end: compression_node.end_byte(),
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 }
                                             }
@@ -268,6 +315,8 @@ impl ValidationRule for ArtifactValidationRule {
                                                         end: compression_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -0,0 +1,130 @@
+use super::super::utils;
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use super::step::check_step_kind;
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::collections::HashSet;
+use tree_sitter::Tree;
+
+/// Validates the `runs:` section, steps, and input references of composite actions.
+///
+/// `runs.using` itself is checked by `ActionRunsUsingRule` for every action type.
+pub struct CompositeActionRule;
+
+impl ValidationRule for CompositeActionRule {
+    fn name(&self) -> &str {
+        "composite_action"
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A composite `action.yml` (`runs.using: composite`) must list its steps under `runs.steps`, each with exactly one of `uses:` or `run:`, and every `inputs.<name>` used in those steps must be declared under `inputs:`.",
+            bad_example: r#"name: Greet
+description: Says hello
+inputs:
+  who:
+    description: Who to greet
+runs:
+  using: composite
+  steps:
+    - run: echo "Hello ${{ inputs.name }}"
+      shell: bash
+"#,
+            good_example: r#"name: Greet
+description: Says hello
+inputs:
+  who:
+    description: Who to greet
+runs:
+  using: composite
+  steps:
+    - run: echo "Hello ${{ inputs.who }}"
+      shell: bash
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let runs_node = match utils::get_action_runs_node(tree, source) {
+            Some(n) => n,
+            None => return diagnostics,
+        };
+
+        let steps = utils::find_value_for_key(runs_node, source, "steps").map(utils::unwrap_node);
+        let has_steps = steps.is_some_and(|s| {
+            matches!(s.kind(), "block_sequence" | "flow_sequence")
+                && !utils::sequence_items(s).is_empty()
+        });
+        if !has_steps {
+            diagnostics.push(Diagnostic {
+                message: "Composite action has no steps. 'runs.steps' must list at least one step when 'using: composite'.".to_string(),
+                severity: Severity::Error,
+                span: Span {
+                    start: runs_node.start_byte(),
+                    end: runs_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+            return diagnostics;
+        }
+
+        for step in steps.map(utils::sequence_items).unwrap_or_default() {
+            check_step_kind(step, source, &mut diagnostics);
+        }
+
+        // Input names are case-insensitive
+        let mut declared: Vec<String> = Vec::new();
+        if let Some(inputs) = utils::find_value_for_key(tree.root_node(), source, "inputs") {
+            for pair in utils::mapping_pairs(utils::unwrap_node(inputs)) {
+                if let Some(key) = pair.child(0) {
+                    declared.push(utils::clean_key(key, source).to_string());
+                }
+            }
+        }
+        let declared_lower: HashSet<String> = declared.iter().map(|d| d.to_lowercase()).collect();
+
+        let runs_range = runs_node.start_byte()..runs_node.end_byte();
+        for expr in utils::find_expressions(source) {
+            if !runs_range.contains(&expr.start) {
+                continue;
+            }
+            // `inner` starts right after the `${{`
+            let inner_start = expr.start + 3;
+            for range in utils::find_context_references(expr.inner, "inputs") {
+                let reference = &expr.inner[range.clone()];
+                let name = match reference.get("inputs.".len()..) {
+                    Some(name) if reference.as_bytes()[6] == b'.' && !name.is_empty() => name,
+                    _ => continue,
+                };
+                if declared_lower.contains(&name.to_lowercase()) {
+                    continue;
+                }
+                let available = if declared.is_empty() {
+                    "the action declares no inputs".to_string()
+                } else {
+                    format!("Available inputs: {}", declared.join(", "))
+                };
+                diagnostics.push(Diagnostic {
+                    message: format!("Reference to undefined input '{}'. {}", name, available),
+                    severity: Severity::Error,
+                    span: Span {
+                        start: inner_start + range.start,
+                        end: inner_start + range.end,
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::CompositeAction
+    }
+}
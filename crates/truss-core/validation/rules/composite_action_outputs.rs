@@ -0,0 +1,156 @@
+use super::super::utils;
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use super::step_output_reference::{collect_step_ids, find_step_output_references_recursive};
+use crate::{Diagnostic, RuleCategory, Severity};
+use std::collections::{HashMap, HashSet};
+use tree_sitter::{Node, Tree};
+
+/// Validates that composite action outputs reference steps and outputs that
+/// exist in `runs.steps`.
+pub struct CompositeActionOutputsRule;
+
+impl ValidationRule for CompositeActionOutputsRule {
+    fn name(&self) -> &str {
+        "composite_action_outputs"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "In a composite `action.yml`, `outputs.<name>.value` must reference a step `id` in `runs.steps`, and an output that step sets.",
+            bad_example: r#"name: Version
+description: Computes a version
+outputs:
+  version:
+    description: The version
+    value: ${{ steps.calc.outputs.version }}
+runs:
+  using: composite
+  steps:
+    - id: compute
+      run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+      shell: bash
+"#,
+            good_example: r#"name: Version
+description: Computes a version
+outputs:
+  version:
+    description: The version
+    value: ${{ steps.compute.outputs.version }}
+runs:
+  using: composite
+  steps:
+    - id: compute
+      run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+      shell: bash
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let runs_node = match utils::get_action_runs_node(tree, source) {
+            Some(n) => n,
+            None => return diagnostics,
+        };
+
+        let outputs_node = match utils::find_value_for_key(tree.root_node(), source, "outputs") {
+            Some(n) => utils::unwrap_node(n),
+            None => return diagnostics,
+        };
+
+        let mut step_ids: Vec<String> = collect_step_ids(runs_node, source)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        step_ids.sort();
+        step_ids.dedup();
+        let step_outputs = utils::collect_step_outputs(runs_node, source);
+
+        for pair in utils::mapping_pairs(outputs_node) {
+            let output_name = match pair.child(0) {
+                Some(k) => utils::clean_key(k, source),
+                None => continue,
+            };
+            let value_node = match utils::get_pair_value(pair)
+                .and_then(|v| utils::find_value_for_key(utils::unwrap_node(v), source, "value"))
+            {
+                Some(v) => v,
+                None => continue,
+            };
+
+            check_output_value(
+                output_name,
+                value_node,
+                source,
+                &step_ids,
+                &step_outputs,
+                &mut diagnostics,
+            );
+        }
+
+        diagnostics
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::CompositeAction
+    }
+}
+
+fn check_output_value(
+    output_name: &str,
+    value_node: Node,
+    source: &str,
+    step_ids: &[String],
+    step_outputs: &HashMap<String, HashSet<String>>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (step_id, step_output, span) in find_step_output_references_recursive(value_node, source) {
+        if !step_ids.contains(&step_id) {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Composite action output '{}' references step '{}' which does not exist in runs.steps. Available step IDs: {}",
+                    output_name,
+                    step_id,
+                    if step_ids.is_empty() {
+                        "none".to_string()
+                    } else {
+                        step_ids.join(", ")
+                    }
+                ),
+                severity: Severity::Error,
+                span,
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+            continue;
+        }
+
+        // Outputs are only known for `run` steps that write to $GITHUB_OUTPUT
+        if let Some(outputs) = step_outputs.get(&step_id) {
+            if !outputs.contains(&step_output) {
+                let mut available: Vec<_> = outputs.iter().map(String::as_str).collect();
+                available.sort_unstable();
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Composite action output '{}' references output '{}' of step '{}', but that step does not set it. Available outputs: {}",
+                        output_name,
+                        step_output,
+                        step_id,
+                        available.join(", ")
+                    ),
+                    severity: Severity::Error,
+                    span,
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates concurrency syntax.
@@ -11,7 +11,42 @@ impl ValidationRule for ConcurrencyRule {
         "concurrency"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `concurrency:`. The object form needs a `group`, `cancel-in-progress` must be a boolean, the group may only reference contexts GitHub allows there, and PR-triggered workflows should not share one group across all PRs.",
+            bad_example: r#"on: pull_request
+concurrency:
+  group: ${{ github.workflow }}
+  cancel-in-progress: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: pull_request
+concurrency:
+  group: ${{ github.workflow }}-${{ github.head_ref || github.ref }}
+  cancel-in-progress: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         let root = tree.root_node();
@@ -19,11 +54,24 @@ impl ValidationRule for ConcurrencyRule {
         // Check workflow-level concurrency
         let workflow_concurrency = utils::find_value_for_key(root, source, "concurrency");
         if let Some(concurrency_node) = workflow_concurrency {
-            validate_concurrency_node(concurrency_node, source, "workflow", &mut diagnostics);
+            validate_concurrency_node(
+                concurrency_node,
+                source,
+                "workflow",
+                WORKFLOW_CONCURRENCY_CONTEXTS,
+                &mut diagnostics,
+            );
+
+            let pr_triggered = utils::collect_triggers(tree, source)
+                .iter()
+                .any(|(event, _)| *event == "pull_request" || *event == "pull_request_target");
+            if pr_triggered {
+                check_pull_request_group(concurrency_node, source, &mut diagnostics);
+            }
         }
 
         // Check job-level concurrency
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -58,6 +106,7 @@ impl ValidationRule for ConcurrencyRule {
                                         concurrency,
                                         source,
                                         &format!("job '{}'", job_name),
+                                        JOB_CONCURRENCY_CONTEXTS,
                                         diagnostics,
                                     );
                                 }
@@ -80,10 +129,65 @@ impl ValidationRule for ConcurrencyRule {
     }
 }
 
+/// Contexts GitHub allows in a workflow-level `concurrency:`.
+const WORKFLOW_CONCURRENCY_CONTEXTS: &[&str] = &["github", "inputs", "vars"];
+
+/// Contexts GitHub allows in `jobs.<job_id>.concurrency`.
+const JOB_CONCURRENCY_CONTEXTS: &[&str] =
+    &["github", "inputs", "vars", "needs", "strategy", "matrix"];
+
+/// Context references that make a concurrency group specific to one PR or ref.
+const PR_SPECIFIC_CONTEXTS: &[&str] = &[
+    "github.ref",
+    "github.head_ref",
+    "github.event.number",
+    "github.event.pull_request.",
+    "github.run_id",
+];
+
+/// On PR-triggered workflows, a workflow-level group without a PR/ref component
+/// is shared by every PR, so each new run cancels runs of unrelated PRs.
+fn check_pull_request_group(
+    concurrency_node: Node,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let concurrency = utils::unwrap_node(concurrency_node);
+    let group_node = match concurrency.kind() {
+        "plain_scalar" | "double_quote_scalar" | "single_quote_scalar" => concurrency,
+        _ => match utils::find_value_for_key(concurrency, source, "group") {
+            Some(n) => utils::unwrap_node(n),
+            None => return,
+        },
+    };
+
+    let group = utils::scalar_value(group_node, source);
+    if group.is_empty() || PR_SPECIFIC_CONTEXTS.iter().any(|ctx| group.contains(ctx)) {
+        return;
+    }
+
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "Concurrency group '{}' is shared by all pull requests, so a run for one PR will cancel runs for unrelated PRs. \
+             Include a PR-specific component such as '${{{{ github.head_ref || github.ref }}}}' or '${{{{ github.event.number }}}}'.",
+            group
+        ),
+        severity: Severity::Warning,
+        span: Span {
+            start: group_node.start_byte(),
+            end: group_node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
+
 fn validate_concurrency_node(
     concurrency_node: Node,
     source: &str,
     context: &str,
+    allowed_contexts: &[&str],
     diagnostics: &mut Vec<Diagnostic>,
 ) {
     let concurrency_to_check = utils::unwrap_node(concurrency_node);
@@ -91,7 +195,7 @@ fn validate_concurrency_node(
     // Concurrency can be a simple string (group name) or a mapping with group/cancel-in-progress.
     // String form: `concurrency: my-group` or `concurrency: ${{ github.ref }}`
     match concurrency_to_check.kind() {
-        "plain_scalar" | "double_quoted_scalar" | "single_quoted_scalar" => {
+        "plain_scalar" | "double_quote_scalar" | "single_quote_scalar" => {
             // String form is valid — the string IS the group name
             let text = utils::node_text(concurrency_to_check, source);
             let cleaned = text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
@@ -104,8 +208,17 @@ fn validate_concurrency_node(
                         end: concurrency_to_check.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
+            check_group_expressions(
+                concurrency_to_check,
+                source,
+                context,
+                allowed_contexts,
+                diagnostics,
+            );
             return;
         }
         _ => {}
@@ -126,6 +239,8 @@ fn validate_concurrency_node(
                 end: concurrency_to_check.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     };
@@ -142,9 +257,13 @@ fn validate_concurrency_node(
                 end: group_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
     }
 
+    check_group_expressions(group_node, source, context, allowed_contexts, diagnostics);
+
     // Note: bare numbers like `group: 1` are valid — GitHub Actions coerces
     // them to strings at runtime. No need to flag them as errors.
 
@@ -152,15 +271,14 @@ fn validate_concurrency_node(
     let cancel_value =
         utils::find_value_for_key(concurrency_to_check, source, "cancel-in-progress");
 
-    if let Some(cancel_node) = cancel_value {
+    if let Some(cancel_node) = cancel_value.map(utils::unwrap_node) {
         let cancel_text = utils::node_text(cancel_node, source);
 
-        // Check if it's a quoted scalar (string) - this is invalid
-        // Check both node kind and if text starts/ends with quotes
-        let is_quoted_string = cancel_node.kind() == "double_quoted_scalar"
-            || cancel_node.kind() == "single_quoted_scalar"
-            || (cancel_text.trim().starts_with('"') && cancel_text.trim().ends_with('"'))
-            || (cancel_text.trim().starts_with('\'') && cancel_text.trim().ends_with('\''));
+        // A quoted scalar is a string, which is invalid
+        let is_quoted_string = matches!(
+            cancel_node.kind(),
+            "double_quote_scalar" | "single_quote_scalar"
+        );
 
         if is_quoted_string {
             diagnostics.push(Diagnostic {
@@ -174,6 +292,8 @@ fn validate_concurrency_node(
                     end: cancel_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         } else {
             // Check the actual value
@@ -197,9 +317,91 @@ fn validate_concurrency_node(
                             end: cancel_node.end_byte(),
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
         }
     }
 }
+
+/// Check the context references in a concurrency group's expressions.
+///
+/// Unknown contexts and unknown `github` properties are likely typos and get a
+/// warning. Known contexts that GitHub doesn't allow in `concurrency:` (such as
+/// `secrets` or `env`) make the workflow invalid.
+fn check_group_expressions(
+    group_node: Node,
+    source: &str,
+    context: &str,
+    allowed_contexts: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let group_start = group_node.start_byte();
+    for expr in utils::find_expressions(utils::node_text(group_node, source)) {
+        // `inner` starts right after the `${{`
+        let inner_start = group_start + expr.start + 3;
+        for range in utils::find_all_context_references(expr.inner) {
+            let reference = &expr.inner[range.clone()];
+            let (name, property) = match reference.split_once('.') {
+                Some((name, property)) => (name, Some(property)),
+                None => (reference, None),
+            };
+            let name_lower = name.to_ascii_lowercase();
+
+            let problem = if !utils::KNOWN_CONTEXTS.contains(&name_lower.as_str()) {
+                Some((
+                    Severity::Warning,
+                    format!(
+                        "Unknown context '{}' in concurrency group at {} level. Available contexts: {}.",
+                        name,
+                        context,
+                        allowed_contexts.join(", ")
+                    ),
+                ))
+            } else if !allowed_contexts.contains(&name_lower.as_str()) {
+                Some((
+                    Severity::Error,
+                    format!(
+                        "Context '{}' is not available in concurrency at {} level. Available contexts: {}.",
+                        name,
+                        context,
+                        allowed_contexts.join(", ")
+                    ),
+                ))
+            } else {
+                match property {
+                    Some(property)
+                        if name_lower == "github"
+                            && !utils::GITHUB_CONTEXT_PROPERTIES
+                                .contains(&property.to_ascii_lowercase().as_str()) =>
+                    {
+                        Some((
+                            Severity::Warning,
+                            format!(
+                                "'{}' is not a property of the github context, so the concurrency group at {} level gets an empty value in its place.",
+                                reference, context
+                            ),
+                        ))
+                    }
+                    _ => None,
+                }
+            };
+
+            if let Some((severity, message)) = problem {
+                diagnostics.push(Diagnostic {
+                    message,
+                    severity,
+                    span: Span {
+                        start: inner_start + range.start,
+                        end: inner_start + range.end,
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+    }
+}
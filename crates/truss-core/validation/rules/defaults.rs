@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates defaults configuration at workflow and job levels.
@@ -11,7 +11,42 @@ impl ValidationRule for DefaultsValidationRule {
         "defaults"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `defaults.run` at workflow and job level: `shell` must be a known shell or a `{command} {0}` template, and `working-directory` a non-empty path, preferably relative to the workspace.",
+            bad_example: r#"on: push
+defaults:
+  run:
+    shell: fish
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+defaults:
+  run:
+    shell: bash
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         let root = tree.root_node();
@@ -23,7 +58,7 @@ impl ValidationRule for DefaultsValidationRule {
         }
 
         // Check job-level defaults
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -88,27 +123,24 @@ fn validate_defaults(
             let shell_cleaned =
                 shell_text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
 
-            if !shell_cleaned.starts_with("${{") {
-                let known_shells = ["bash", "pwsh", "python", "sh", "cmd", "powershell"];
-                let is_known = known_shells
-                    .iter()
-                    .any(|s| s.eq_ignore_ascii_case(shell_cleaned));
-                let is_custom = shell_cleaned.contains("{0}");
-
-                if !is_known && !is_custom && !shell_cleaned.is_empty() {
-                    diagnostics.push(Diagnostic {
-                        message: format!(
-                            "{} defaults.run.shell has invalid value: '{}'. Valid shells are: bash, pwsh, python, sh, cmd, powershell, or a custom command with {{0}} placeholder.",
-                            context, shell_cleaned
-                        ),
-                        severity: Severity::Error,
-                        span: Span {
-                            start: shell_node.start_byte(),
-                            end: shell_node.end_byte(),
-                        },
-                        rule_id: String::new(),
-                    });
-                }
+            if !shell_cleaned.starts_with("${{")
+                && !shell_cleaned.is_empty()
+                && !utils::is_valid_shell(shell_cleaned)
+            {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "{} defaults.run.shell has invalid value: '{}'. Valid shells are: bash, pwsh, python, sh, cmd, powershell, or a custom command followed by a {{0}} placeholder (e.g. 'perl {{0}}').",
+                        context, shell_cleaned
+                    ),
+                    severity: Severity::Error,
+                    span: Span {
+                        start: shell_node.start_byte(),
+                        end: shell_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
             }
         }
 
@@ -119,7 +151,7 @@ fn validate_defaults(
             let working_dir_cleaned =
                 working_dir_text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
 
-            if !working_dir_cleaned.starts_with("${{") && working_dir_cleaned.is_empty() {
+            if working_dir_cleaned.is_empty() {
                 diagnostics.push(Diagnostic {
                     message: format!(
                         "{} defaults.run.working-directory is empty. working-directory must be a valid path.",
@@ -131,6 +163,24 @@ fn validate_defaults(
                         end: working_dir_node.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            } else if working_dir_cleaned.starts_with('/') {
+                // Relative paths resolve against $GITHUB_WORKSPACE on every runner
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "{} defaults.run.working-directory '{}' is an absolute path, which rarely exists on every runner. Use a path relative to the workspace instead.",
+                        context, working_dir_cleaned
+                    ),
+                    severity: Severity::Warning,
+                    span: Span {
+                        start: working_dir_node.start_byte(),
+                        end: working_dir_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -0,0 +1,120 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Fragments of an action name (`owner/repo`) that suggest a deploy or publish step.
+const DEPLOY_ACTION_MARKERS: &[&str] = &["deploy", "publish"];
+
+/// Commands in a `run:` script that suggest a deploy or publish step.
+const DEPLOY_COMMANDS: &[&str] = &[
+    "terraform apply",
+    "kubectl ",
+    "helm upgrade",
+    "npm publish",
+    "cargo publish",
+];
+
+/// Recommends `timeout-minutes` on jobs that deploy or publish.
+pub struct DeployJobTimeoutRule;
+
+impl ValidationRule for DeployJobTimeoutRule {
+    fn name(&self) -> &str {
+        "deploy_job_timeout"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Jobs that deploy or publish (e.g. `terraform apply`, `kubectl`, `npm publish`, or a `*deploy*` action) should set `timeout-minutes`. Without it a hung job runs for the 360-minute default.",
+            bad_example: r#"on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: terraform apply -auto-approve
+"#,
+            good_example: r#"on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    timeout-minutes: 30
+    steps:
+      - run: terraform apply -auto-approve
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            if utils::find_value_for_key(job.value, source, "timeout-minutes").is_some() {
+                continue;
+            }
+
+            let (job_name, key_node) = (job.name, job.key);
+            let marker = job
+                .steps
+                .iter()
+                .find_map(|&step| deploy_marker(step, source));
+
+            // Informational only: plenty of deploys are fast and never hang.
+            if let Some(marker) = marker {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Job '{}' looks like a deploy job ({}) but has no timeout-minutes. \
+                         Set one so a hung run doesn't hold the runner for the 360-minute default.",
+                        job_name, marker
+                    ),
+                    severity: Severity::Info,
+                    span: Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Describe why a step looks like a deploy step, or `None` if it doesn't.
+fn deploy_marker(step: Node, source: &str) -> Option<String> {
+    if let Some(uses) = utils::find_value_for_key(step, source, "uses") {
+        let uses_text = utils::scalar_value(utils::unwrap_node(uses), source);
+        let action = uses_text.split('@').next().unwrap_or("").to_lowercase();
+        if DEPLOY_ACTION_MARKERS.iter().any(|m| action.contains(m)) {
+            return Some(format!("uses '{}'", uses_text));
+        }
+    }
+
+    if let Some(run) = utils::find_value_for_key(step, source, "run") {
+        let script = utils::node_text(utils::unwrap_node(run), source);
+        if let Some(command) = DEPLOY_COMMANDS.iter().find(|c| script.contains(*c)) {
+            return Some(format!("runs '{}'", command.trim()));
+        }
+    }
+
+    None
+}
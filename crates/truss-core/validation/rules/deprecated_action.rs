@@ -0,0 +1,134 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Archived or moved actions and their recommended replacements.
+///
+/// Keyed by `owner/repo` (matched case-insensitively). Each entry carries a
+/// short reason so the message explains why the migration is needed.
+const DEPRECATED_ACTIONS: &[(&str, &str, &str)] = &[
+    (
+        "actions/create-release",
+        "softprops/action-gh-release",
+        "is archived and no longer maintained",
+    ),
+    (
+        "actions/upload-release-asset",
+        "softprops/action-gh-release",
+        "is archived and no longer maintained",
+    ),
+    (
+        "actions/setup-ruby",
+        "ruby/setup-ruby",
+        "is archived; Ruby setup moved to the Ruby organization",
+    ),
+    (
+        "actions/setup-elixir",
+        "erlef/setup-beam",
+        "is archived; Elixir/Erlang setup moved to the Erlang Ecosystem Foundation",
+    ),
+    (
+        "actions/setup-haskell",
+        "haskell-actions/setup",
+        "is archived; Haskell setup moved to the haskell-actions organization",
+    ),
+    (
+        "actions-rs/toolchain",
+        "dtolnay/rust-toolchain",
+        "is archived and no longer maintained",
+    ),
+];
+
+/// Detects `uses:` references to archived or renamed actions.
+pub struct DeprecatedActionRule;
+
+impl ValidationRule for DeprecatedActionRule {
+    fn name(&self) -> &str {
+        "deprecated_action"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags actions that have been archived or moved and names the maintained replacement.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/create-release@v1
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: softprops/action-gh-release@v2
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            for &step in &job.steps {
+                if let Some(uses_node) = utils::find_value_for_key(step, source, "uses") {
+                    check_deprecated_action(uses_node, source, &mut diagnostics);
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn check_deprecated_action(uses_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let uses = utils::scalar_value(uses_node, source);
+    let action = uses.split('@').next().unwrap_or("");
+
+    // Match on owner/repo so sub-path actions (owner/repo/path@ref) are covered too
+    let mut segments = action.splitn(3, '/');
+    let owner_repo_len = match (segments.next(), segments.next()) {
+        (Some(owner), Some(repo)) => owner.len() + 1 + repo.len(),
+        _ => return,
+    };
+    let owner_repo = &action[..owner_repo_len];
+
+    if let Some((name, replacement, reason)) = DEPRECATED_ACTIONS
+        .iter()
+        .find(|(name, _, _)| name.eq_ignore_ascii_case(owner_repo))
+    {
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Action '{}' {}. Migrate to '{}' instead.",
+                name, reason, replacement
+            ),
+            severity: Severity::Warning,
+            span: Span {
+                start: uses_node.start_byte(),
+                end: uses_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
@@ -1,18 +1,13 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Deprecated workflow commands and their replacements.
+///
+/// `::set-output` and `::save-state` are reported by `StepSetOutputRule`, once
+/// per command, so that editors can offer a fix for each.
 const DEPRECATED_COMMANDS: &[(&str, &str)] = &[
-    (
-        "::set-output",
-        "Use `echo \"name=value\" >> $GITHUB_OUTPUT` instead",
-    ),
-    (
-        "::save-state",
-        "Use `echo \"name=value\" >> $GITHUB_STATE` instead",
-    ),
     (
         "::set-env",
         "Use `echo \"name=value\" >> $GITHUB_ENV` instead",
@@ -28,10 +23,47 @@ impl ValidationRule for DeprecatedCommandsRule {
         "deprecated_commands"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags the deprecated `::set-env` and `::add-path` workflow commands. GitHub disabled them for security reasons; write to `$GITHUB_ENV` or `$GITHUB_PATH` instead. `::set-output` and `::save-state` are covered by `step_set_output`.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "::set-env name=VERSION::1.0"
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "VERSION=1.0" >> "$GITHUB_ENV"
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -91,6 +123,8 @@ fn check_deprecated_commands(
                     end: end_byte,
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -0,0 +1,118 @@
+use super::super::utils;
+use super::super::{FileKind, RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
+use std::collections::HashMap;
+use tree_sitter::{Node, Tree};
+
+/// Detects keys that appear more than once in the same mapping.
+pub struct DuplicateKeyRule;
+
+impl ValidationRule for DuplicateKeyRule {
+    fn name(&self) -> &str {
+        "duplicate_key"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Syntax
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A key may appear only once in a mapping. YAML parsers disagree on duplicates, and most silently keep the last one, so a whole block such as a step's `env:` can be dropped without notice.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      MODE: debug
+    env:
+      MODE: release
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      MODE: release
+    steps:
+      - run: make
+"#,
+        }
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::AnyYaml
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        // Duplicate job IDs are reported by JobNameRule, and duplicate
+        // triggers by WorkflowTriggerRule
+        let mut skip: Vec<usize> = index.jobs_node().map(|n| n.id()).into_iter().collect();
+        if utils::is_github_actions_workflow(tree, source) {
+            if let Some(on) = utils::find_value_for_key(tree.root_node(), source, "on") {
+                skip.push(utils::unwrap_node(on).id());
+            }
+        }
+        check_mappings(tree.root_node(), source, &skip, &mut diagnostics);
+        diagnostics
+    }
+}
+
+/// Report repeated keys in every mapping under `node`, at each repeat.
+fn check_mappings(node: Node, source: &str, skip: &[usize], diagnostics: &mut Vec<Diagnostic>) {
+    if matches!(node.kind(), "block_mapping" | "flow_mapping") && !skip.contains(&node.id()) {
+        let mut first_keys: HashMap<&str, Node> = HashMap::new();
+        for key_node in utils::mapping_pairs(node)
+            .into_iter()
+            .filter_map(|pair| pair.child(0))
+        {
+            let key = utils::clean_key(key_node, source);
+            // `<<` merge keys may repeat
+            if key.is_empty() || key == "<<" {
+                continue;
+            }
+            match first_keys.get(key) {
+                Some(first) => diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Duplicate key '{}'. Only one of them takes effect; merge them into a single '{}'.",
+                        key, key
+                    ),
+                    severity: Severity::Error,
+                    span: Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: vec![RelatedLocation {
+                        span: Span {
+                            start: first.start_byte(),
+                            end: first.end_byte(),
+                        },
+                        message: format!("'{}' is first defined here", key),
+                    }],
+                }),
+                None => {
+                    first_keys.insert(key, key_node);
+                }
+            }
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        check_mappings(child, source, skip, diagnostics);
+    }
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates environment references.
@@ -11,11 +11,43 @@ impl ValidationRule for EnvironmentRule {
         "environment"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks job `environment:` names and URLs. A name must not be empty or contain spaces, and a `url` must be an `http(s)://` URL or an expression. Matrix jobs that deploy every leg to the same environment are noted, since each leg needs its own approval.",
+            bad_example: r#"on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    environment:
+      name: prod env
+    steps:
+      - run: ./deploy.sh
+"#,
+            good_example: r#"on: push
+jobs:
+  deploy:
+    runs-on: ubuntu-latest
+    environment:
+      name: production
+    steps:
+      - run: ./deploy.sh
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let root = tree.root_node();
-        let jobs_value = match utils::find_value_for_key(root, source, "jobs") {
+        let jobs_value = match index.jobs_node() {
             Some(v) => v,
             None => return diagnostics,
         };
@@ -51,6 +83,8 @@ impl ValidationRule for EnvironmentRule {
                                                         end: actual_env_value.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -86,9 +120,23 @@ impl ValidationRule for EnvironmentRule {
                                                                     end: field_key_node.end_byte(),
                                                                 },
                                                                 rule_id: String::new(),
+                                                                category: RuleCategory::default(),
+                                                                related: Vec::new(),
                                                             });
                                                         }
 
+                                                        if field_cleaned == "url" {
+                                                            if let Some(url_value) =
+                                                                field_value_node
+                                                            {
+                                                                check_environment_url(
+                                                                    utils::unwrap_node(url_value),
+                                                                    source,
+                                                                    diagnostics,
+                                                                );
+                                                            }
+                                                        }
+
                                                         if field_cleaned == "name" {
                                                             if let Some(name_value) =
                                                                 field_value_node
@@ -113,6 +161,8 @@ impl ValidationRule for EnvironmentRule {
                                                                             end: name_value.end_byte(),
                                                                         },
                                                                         rule_id: String::new(),
+                                                                        category: RuleCategory::default(),
+                                                                        related: Vec::new(),
                                                                     });
                                                                 }
                                                             }
@@ -147,6 +197,142 @@ impl ValidationRule for EnvironmentRule {
 
         find_environment_refs(jobs_value, source, &mut diagnostics);
 
+        if let Some(jobs_node) = index.jobs_node() {
+            check_matrix_deployments(jobs_node, source, &mut diagnostics);
+        }
+
         diagnostics
     }
 }
+
+/// Warns on an `environment.url` that is neither an `http(s)://` URL nor an
+/// expression. GitHub shows it as the deployment link, so relative paths and
+/// bare hostnames produce a broken link.
+fn check_environment_url(url_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let url = utils::scalar_value(url_node, source);
+    let lower = url.to_ascii_lowercase();
+    if url.contains("${{") || lower.starts_with("http://") || lower.starts_with("https://") {
+        return;
+    }
+
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "Environment url '{}' is not an http(s) URL or expression. GitHub links to it from the deployment, so use an absolute URL such as 'https://example.com'.",
+            url
+        ),
+        severity: Severity::Warning,
+        span: Span {
+            start: url_node.start_byte(),
+            end: url_node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
+
+/// Notes jobs that deploy every matrix combination to the same environment.
+///
+/// Each matrix leg is a separate deployment, so required reviewers have to
+/// approve every leg. Names that reference `matrix` target a different
+/// environment per leg and are skipped.
+fn check_matrix_deployments(jobs_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        if utils::get_job_matrix(job_value, source).is_none() {
+            continue;
+        }
+        let env_value = match utils::find_value_for_key(job_value, source, "environment") {
+            Some(v) => utils::unwrap_node(v),
+            None => continue,
+        };
+        let name_node = if matches!(env_value.kind(), "block_mapping" | "flow_mapping") {
+            match utils::find_value_for_key(env_value, source, "name") {
+                Some(v) => utils::unwrap_node(v),
+                None => continue,
+            }
+        } else {
+            env_value
+        };
+
+        let env_name = utils::scalar_value(name_node, source);
+        if env_name.is_empty() || env_name.contains("matrix.") {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Job '{}' deploys to environment '{}' from every matrix combination. Each leg creates a separate deployment that needs its own approval; consider deploying once from a job that needs the matrix job.",
+                job_name, env_name
+            ),
+            severity: Severity::Info,
+            span: Span {
+                start: name_node.start_byte(),
+                end: name_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// Warn on job environments that aren't in the configured `allowed_environments`.
+///
+/// Not part of `EnvironmentRule::validate`, since rules don't see the config;
+/// `TrussEngine::analyze_with_options` runs it when the list is non-empty.
+/// Names are compared case-insensitively, as GitHub does, and names built
+/// from expressions are skipped.
+pub(crate) fn check_allowed_environments(
+    tree: &Tree,
+    source: &str,
+    allowed: &[String],
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let jobs_node = match utils::get_jobs_node(tree, source) {
+        Some(n) => n,
+        None => return diagnostics,
+    };
+
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        let env_value = match utils::find_value_for_key(job_value, source, "environment") {
+            Some(v) => utils::unwrap_node(v),
+            None => continue,
+        };
+        let name_node = if matches!(env_value.kind(), "block_mapping" | "flow_mapping") {
+            match utils::find_value_for_key(env_value, source, "name") {
+                Some(v) => utils::unwrap_node(v),
+                None => continue,
+            }
+        } else {
+            env_value
+        };
+
+        let env_name = utils::scalar_value(name_node, source);
+        if env_name.is_empty()
+            || env_name.contains("${{")
+            || allowed.iter().any(|a| a.eq_ignore_ascii_case(env_name))
+        {
+            continue;
+        }
+
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Job '{}' uses environment '{}', which is not listed in allowed_environments ({}).",
+                job_name,
+                env_name,
+                allowed.join(", ")
+            ),
+            severity: Severity::Warning,
+            span: Span {
+                start: name_node.start_byte(),
+                end: name_node.end_byte(),
+            },
+            rule_id: "environment".to_string(),
+            category: EnvironmentRule.category(),
+            related: Vec::new(),
+        });
+    }
+
+    diagnostics
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates event-specific fields in on: triggers.
@@ -11,6 +11,31 @@ impl ValidationRule for EventPayloadValidationRule {
         "event_payload"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates event-specific fields under `on:`, such as activity `types` and whether a filter like `branches` is supported by that event. A filter can't be combined with its `-ignore` counterpart.",
+            bad_example: r#"on:
+  push:
+    branches: [main]
+    branches-ignore: [dev]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+            good_example: r#"on:
+  push:
+    branches: [main]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -59,6 +84,20 @@ impl ValidationRule for EventPayloadValidationRule {
             validate_issues_event(issues_node, source, &mut diagnostics);
         }
 
+        // Validate workflow_run event fields
+        let workflow_run_value = utils::find_value_for_key(on_to_check, source, "workflow_run");
+        if let Some(wr_node) = workflow_run_value {
+            validate_workflow_run_event(wr_node, source, &mut diagnostics);
+        } else if let Some(key_node) = utils::mapping_pairs(on_to_check)
+            .into_iter()
+            .filter(|pair| utils::get_pair_value(*pair).is_none())
+            .filter_map(|pair| pair.child(0))
+            .find(|key| utils::clean_key(*key, source) == "workflow_run")
+        {
+            // `workflow_run:` with nothing after it lists no workflows either
+            diagnostics.push(missing_workflows(key_node));
+        }
+
         diagnostics
     }
 }
@@ -89,6 +128,8 @@ fn validate_push_event(push_node: Node, source: &str, diagnostics: &mut Vec<Diag
                     end: bi_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -105,6 +146,8 @@ fn validate_push_event(push_node: Node, source: &str, diagnostics: &mut Vec<Diag
                     end: ti_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -121,6 +164,8 @@ fn validate_push_event(push_node: Node, source: &str, diagnostics: &mut Vec<Diag
                     end: pi_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -149,6 +194,8 @@ fn validate_push_event(push_node: Node, source: &str, diagnostics: &mut Vec<Diag
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -181,6 +228,8 @@ fn validate_pull_request_event(pr_node: Node, source: &str, diagnostics: &mut Ve
                     end: bi_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -197,6 +246,8 @@ fn validate_pull_request_event(pr_node: Node, source: &str, diagnostics: &mut Ve
                     end: pi_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -234,6 +285,8 @@ fn validate_pull_request_event(pr_node: Node, source: &str, diagnostics: &mut Ve
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
 
@@ -306,6 +359,8 @@ fn validate_pr_types(types_node: Node, source: &str, diagnostics: &mut Vec<Diagn
                             end: node.end_byte(),
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -343,6 +398,8 @@ fn validate_cron_expression(
                 end: cron_node.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
         return;
     }
@@ -369,6 +426,8 @@ fn validate_cron_expression(
                     end: cron_node.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
     }
@@ -513,6 +572,8 @@ fn validate_schedule_event(schedule_node: Node, source: &str, diagnostics: &mut
                 end: schedule_to_check.end_byte(),
             },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
     } else if let Some(cron_node) = cron_value {
         let cron_text = utils::node_text(cron_node, source);
@@ -547,6 +608,8 @@ fn validate_schedule_event(schedule_node: Node, source: &str, diagnostics: &mut
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -596,6 +659,8 @@ fn validate_workflow_dispatch_event(
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -641,6 +706,8 @@ fn validate_workflow_call_event(wc_node: Node, source: &str, diagnostics: &mut V
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -687,6 +754,8 @@ fn validate_issues_event(issues_node: Node, source: &str, diagnostics: &mut Vec<
                                 end: key_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
 
@@ -754,6 +823,148 @@ fn validate_issues_types(types_node: Node, source: &str, diagnostics: &mut Vec<D
                             end: node.end_byte(),
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    check_type(child, source, valid_types, diagnostics);
+                }
+            }
+        }
+    }
+
+    check_type(types_node, source, &valid_types, diagnostics);
+}
+
+fn validate_workflow_run_event(wr_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let wr_to_check = utils::unwrap_node(wr_node);
+
+    // Valid fields for workflow_run: workflows, types, branches, branches-ignore
+    let valid_fields = ["workflows", "types", "branches", "branches-ignore"];
+
+    // workflows names the workflows whose runs trigger this one; without it
+    // the event never fires
+    if !utils::key_exists(wr_to_check, source, "workflows") {
+        diagnostics.push(missing_workflows(wr_node));
+    }
+
+    let branches_node = utils::find_value_for_key(wr_to_check, source, "branches");
+    let branches_ignore_node = utils::find_value_for_key(wr_to_check, source, "branches-ignore");
+    if branches_node.is_some() {
+        if let Some(bi_node) = branches_ignore_node {
+            diagnostics.push(Diagnostic {
+                message: "Cannot use both 'branches' and 'branches-ignore' on the same event. They are mutually exclusive.".to_string(),
+                severity: Severity::Error,
+                span: Span {
+                    start: bi_node.start_byte(),
+                    end: bi_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+
+    fn check_fields(
+        node: Node,
+        source: &str,
+        valid_fields: &[&str],
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match node.kind() {
+            "block_mapping_pair" | "flow_pair" => {
+                if let Some(key_node) = node.child(0) {
+                    let key_cleaned = utils::clean_key(key_node, source);
+
+                    if !valid_fields.contains(&key_cleaned) {
+                        diagnostics.push(Diagnostic {
+                            message: format!(
+                                "Invalid field '{}' for workflow_run event. Valid fields are: {}",
+                                key_cleaned,
+                                valid_fields.join(", ")
+                            ),
+                            severity: Severity::Error,
+                            span: Span {
+                                start: key_node.start_byte(),
+                                end: key_node.end_byte(),
+                            },
+                            rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
+                        });
+                    }
+
+                    // Validate types field values
+                    if key_cleaned == "types" {
+                        if let Some(value_node) = utils::get_pair_value(node) {
+                            validate_workflow_run_types(value_node, source, diagnostics);
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    check_fields(child, source, valid_fields, diagnostics);
+                }
+            }
+        }
+    }
+
+    check_fields(wr_to_check, source, &valid_fields, diagnostics);
+}
+
+/// Error for a `workflow_run` trigger without `workflows`, spanning `node`.
+fn missing_workflows(node: Node) -> Diagnostic {
+    Diagnostic {
+        message:
+            "workflow_run event requires 'workflows', the list of workflow names that trigger it."
+                .to_string(),
+        severity: Severity::Error,
+        span: Span {
+            start: node.start_byte(),
+            end: node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    }
+}
+
+fn validate_workflow_run_types(types_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let valid_types = ["requested", "completed", "in_progress"];
+
+    fn check_type(
+        node: Node,
+        source: &str,
+        valid_types: &[&str],
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        match node.kind() {
+            "plain_scalar" | "double_quote_scalar" | "single_quote_scalar" => {
+                let type_text = utils::node_text(node, source);
+                let type_cleaned =
+                    type_text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+                if !valid_types.contains(&type_cleaned) {
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Invalid workflow_run activity type: '{}'. Valid types are: {}",
+                            type_cleaned,
+                            valid_types.join(", ")
+                        ),
+                        severity: Severity::Error,
+                        span: Span {
+                            start: node.start_byte(),
+                            end: node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::Tree;
 
 /// Validates GitHub Actions expressions.
@@ -11,6 +11,32 @@ impl ValidationRule for ExpressionValidationRule {
         "expression"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks `${{ }}` expressions for unclosed or empty delimiters, JavaScript-style operators like `===`, unknown functions, and calls with the wrong number of arguments.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ github.ref === 'refs/heads/main' }}
+        run: ./deploy.sh
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ github.ref == 'refs/heads/main' }}
+        run: ./deploy.sh
+"#,
+        }
+    }
+
     fn validate(&self, _tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -29,6 +55,8 @@ impl ValidationRule for ExpressionValidationRule {
                         end: source.len().min(expr.start + 50),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 continue;
             }
@@ -42,6 +70,8 @@ impl ValidationRule for ExpressionValidationRule {
                         end: expr.end,
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             } else if !utils::is_valid_expression_syntax(inner) {
                 diagnostics.push(Diagnostic {
@@ -52,14 +82,27 @@ impl ValidationRule for ExpressionValidationRule {
                         end: expr.end,
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
 
             // Validate operators
-            validate_expression_operators(inner, expr.start, expr.end, &mut diagnostics);
+            let inner_start = expr.start + 3 + (expr.inner.len() - expr.inner.trim_start().len());
+            validate_expression_operators(
+                inner,
+                expr.start,
+                expr.end,
+                inner_start,
+                &mut diagnostics,
+            );
 
             // Validate function calls
             validate_expression_functions(inner, expr.start, expr.end, &mut diagnostics);
+            validate_function_arity(inner, inner_start, &mut diagnostics);
+
+            // Validate format() placeholders against the argument count
+            validate_format_calls(inner, expr.start, expr.end, &mut diagnostics);
         }
 
         diagnostics
@@ -67,10 +110,13 @@ impl ValidationRule for ExpressionValidationRule {
 }
 
 /// Validates expression operators
+///
+/// `inner_start` is the byte offset of the trimmed `expr` in the source.
 fn validate_expression_operators(
     expr: &str,
     start: usize,
     end: usize,
+    inner_start: usize,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
     // Check for invalid operator combinations
@@ -83,43 +129,27 @@ fn validate_expression_operators(
             severity: Severity::Error,
             span: Span { start, end },
             rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
         });
     }
 
-    // Check for invalid assignment operators (expressions are read-only)
-    // Skip if `=` appears inside single-quoted string literals (e.g., format('--tag={0}', ...))
-    if expr.contains('=')
-        && !expr.contains("==")
-        && !expr.contains("!=")
-        && !expr.contains("<=")
-        && !expr.contains(">=")
-    {
-        // Check if the `=` is inside a single-quoted string
-        let eq_in_string = {
-            let mut in_string = false;
-            let mut found_outside = false;
-            for ch in expr.chars() {
-                if ch == '\'' {
-                    in_string = !in_string;
-                } else if ch == '=' && !in_string {
-                    found_outside = true;
-                    break;
-                }
-            }
-            !found_outside
-        };
-
-        if !eq_in_string && expr.matches('=').count() == 1 && !expr.contains("${{") {
-            diagnostics.push(Diagnostic {
-                message: format!(
-                    "Potentially invalid operator in expression: '{}'. Expressions are read-only and cannot use assignment operators.",
-                    expr
-                ),
-                severity: Severity::Warning,
-                span: Span { start, end },
-                rule_id: String::new(),
-            });
-        }
+    // A lone `=` is almost always a typo for `==`; expressions can't assign
+    if let Some(offset) = utils::find_assignment_operator(expr) {
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Invalid assignment operator in expression: '{}'. Expressions are read-only; use '==' to compare values.",
+                expr
+            ),
+            severity: Severity::Error,
+            span: Span {
+                start: inner_start + offset,
+                end: inner_start + offset + 1,
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
     }
 }
 
@@ -187,6 +217,8 @@ fn validate_expression_functions(
                             end: start + actual_pos,
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -195,3 +227,216 @@ fn validate_expression_functions(
         search_pos = actual_pos + 1;
     }
 }
+
+/// Argument counts the built-in functions accept, as `(name, min, max)`.
+const FUNCTION_ARITY: &[(&str, usize, Option<usize>)] = &[
+    ("contains", 2, Some(2)),
+    ("startsWith", 2, Some(2)),
+    ("endsWith", 2, Some(2)),
+    ("format", 1, None),
+    ("join", 1, Some(2)),
+    ("toJSON", 1, Some(1)),
+    ("fromJSON", 1, Some(1)),
+    ("hashFiles", 1, None),
+    ("success", 0, Some(0)),
+    ("failure", 0, Some(0)),
+    ("cancelled", 0, Some(0)),
+    ("always", 0, Some(0)),
+];
+
+/// Warns on calls to built-in functions with the wrong number of arguments.
+///
+/// `inner_start` is the byte offset of the trimmed `expr` in the source.
+fn validate_function_arity(expr: &str, inner_start: usize, diagnostics: &mut Vec<Diagnostic>) {
+    for call in find_function_calls(expr) {
+        let Some(&(name, min, max)) = FUNCTION_ARITY
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(call.name))
+        else {
+            continue;
+        };
+        let count = call.args.len();
+        if (min..=max.unwrap_or(usize::MAX)).contains(&count) {
+            continue;
+        }
+
+        let plural = |n: usize| if n == 1 { "argument" } else { "arguments" };
+        let expected = match max {
+            Some(max) if max == min => format!("{} {}", min, plural(min)),
+            Some(max) => format!("{} to {} {}", min, max, plural(max)),
+            None => format!("at least {} {}", min, plural(min)),
+        };
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "{}() takes {}, but {} {} given",
+                name,
+                expected,
+                count,
+                if count == 1 { "was" } else { "were" }
+            ),
+            severity: Severity::Warning,
+            span: Span {
+                start: inner_start + call.offset,
+                end: inner_start + call.offset + call.name.len(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// Validates that `format()` placeholders have matching arguments.
+///
+/// Only calls whose template is a string literal are checked, since a computed
+/// template's placeholders aren't known until runtime.
+fn validate_format_calls(expr: &str, start: usize, end: usize, diagnostics: &mut Vec<Diagnostic>) {
+    let format_calls = find_function_calls(expr)
+        .into_iter()
+        .filter(|call| call.name.eq_ignore_ascii_case("format"));
+    for FunctionCall { args, .. } in format_calls {
+        let template = match args.first().map(|a| a.trim()) {
+            Some(t) if t.len() >= 2 && t.starts_with('\'') && t.ends_with('\'') => {
+                &t[1..t.len() - 1]
+            }
+            _ => continue,
+        };
+        let indices = format_placeholder_indices(template);
+        let max_index = match indices.iter().max() {
+            Some(max) => *max,
+            None => continue,
+        };
+        let arg_count = args.len() - 1;
+
+        if max_index >= arg_count {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "format() placeholder '{{{}}}' has no matching argument: the template is followed by {} argument(s), so the highest valid placeholder is {}.",
+                    max_index,
+                    arg_count,
+                    match arg_count {
+                        0 => "none".to_string(),
+                        n => format!("'{{{}}}'", n - 1),
+                    }
+                ),
+                severity: Severity::Error,
+                span: Span { start, end },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        } else if let Some(missing) = (0..max_index).find(|i| !indices.contains(i)) {
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "format() template skips placeholder '{{{}}}' but uses '{{{}}}'. Placeholders must be numbered consecutively from '{{0}}'.",
+                    missing, max_index
+                ),
+                severity: Severity::Error,
+                span: Span { start, end },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// A function call in an expression.
+struct FunctionCall<'a> {
+    name: &'a str,
+    /// Byte offset of `name` in the expression.
+    offset: usize,
+    /// Top-level arguments, untrimmed; empty for `f()`.
+    args: Vec<&'a str>,
+}
+
+/// Find every function call in an expression, including nested ones, and
+/// split it into its top-level arguments.
+///
+/// Commas inside string literals and nested calls don't split arguments.
+/// Calls inside string literals and unterminated calls are ignored.
+fn find_function_calls(expr: &str) -> Vec<FunctionCall<'_>> {
+    let bytes = expr.as_bytes();
+    let is_name_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut calls = Vec::new();
+    let mut in_string = false;
+
+    for i in 0..bytes.len() {
+        if bytes[i] == b'\'' {
+            in_string = !in_string;
+            continue;
+        }
+        // A name starts here, and isn't a property like `github.event`
+        if in_string
+            || !bytes[i].is_ascii_alphabetic()
+            || (i > 0 && (is_name_byte(bytes[i - 1]) || bytes[i - 1] == b'.'))
+        {
+            continue;
+        }
+        let name_end = bytes[i..]
+            .iter()
+            .position(|&b| !is_name_byte(b))
+            .map_or(bytes.len(), |len| i + len);
+        if bytes.get(name_end) != Some(&b'(') {
+            continue;
+        }
+
+        let args_start = name_end + 1;
+        let mut args = Vec::new();
+        let mut depth = 0;
+        let mut arg_start = args_start;
+        let mut in_arg_string = false;
+        for (j, &b) in bytes.iter().enumerate().skip(args_start) {
+            match b {
+                b'\'' => in_arg_string = !in_arg_string,
+                _ if in_arg_string => {}
+                b'(' | b'[' => depth += 1,
+                b')' | b']' if depth > 0 => depth -= 1,
+                b',' if depth == 0 => {
+                    args.push(&expr[arg_start..j]);
+                    arg_start = j + 1;
+                }
+                b')' => {
+                    let last = &expr[arg_start..j];
+                    if !(args.is_empty() && last.trim().is_empty()) {
+                        args.push(last);
+                    }
+                    calls.push(FunctionCall {
+                        name: &expr[i..name_end],
+                        offset: i,
+                        args,
+                    });
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    calls
+}
+
+/// Collect the `{N}` placeholder indices used in a `format()` template.
+///
+/// `{{` and `}}` are escaped braces, not placeholders.
+fn format_placeholder_indices(template: &str) -> Vec<usize> {
+    let mut indices = Vec::new();
+    let mut chars = template.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' if chars.peek().map(|(_, next)| *next) == Some('{') => {
+                chars.next();
+            }
+            '{' => {
+                let rest = &template[i + 1..];
+                if let Some(close) = rest.find('}') {
+                    if let Ok(index) = rest[..close].parse::<usize>() {
+                        indices.push(index);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    indices
+}
@@ -0,0 +1,250 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Warns on `branches`/`tags`/`paths` filter patterns that likely don't match what was meant.
+pub struct FilterPatternRule;
+
+/// Event keys that take glob patterns.
+const FILTER_KEYS: &[&str] = &[
+    "branches",
+    "branches-ignore",
+    "tags",
+    "tags-ignore",
+    "paths",
+    "paths-ignore",
+];
+
+impl ValidationRule for FilterPatternRule {
+    fn name(&self) -> &str {
+        "filter_pattern"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Event filters use GitHub's own glob dialect: `*` stops at `/`, `**` crosses it, `?` and `+` repeat the preceding character, and `!` only negates at the start of a pattern. Patterns that read like shell globs often match less than intended, and empty entries match nothing.",
+            bad_example: r#"on:
+  push:
+    paths:
+      - 'docs/*'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make docs
+"#,
+            good_example: r#"on:
+  push:
+    paths:
+      - 'docs/**'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make docs
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let on_value = match utils::find_value_for_key(tree.root_node(), source, "on") {
+            Some(v) => utils::unwrap_node(v),
+            None => return diagnostics,
+        };
+
+        for event_pair in utils::mapping_pairs(on_value) {
+            let (Some(event_key), Some(event)) =
+                (event_pair.child(0), utils::get_pair_value(event_pair))
+            else {
+                continue;
+            };
+            let event_name = utils::clean_key(event_key, source);
+            let event = utils::resolve_alias(event, source);
+
+            for filter_pair in utils::mapping_pairs(event) {
+                let (Some(filter_key), Some(filter)) =
+                    (filter_pair.child(0), utils::get_pair_value(filter_pair))
+                else {
+                    continue;
+                };
+                let filter_name = utils::clean_key(filter_key, source);
+                if !FILTER_KEYS.contains(&filter_name) {
+                    continue;
+                }
+
+                let entries = filter_entries(utils::resolve_alias(filter, source), source);
+                let has_negations = entries.iter().any(|(_, p)| p.starts_with('!'));
+                for (node, pattern) in entries {
+                    if pattern.contains("${{") {
+                        continue;
+                    }
+                    let problem = if pattern.is_empty() {
+                        "It is empty, so it never matches. Remove it or fill in a pattern."
+                            .to_string()
+                    } else {
+                        match pattern_problem(filter_name, pattern, has_negations) {
+                            Some(problem) => problem,
+                            None => continue,
+                        }
+                    };
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Filter pattern '{}' in 'on.{}.{}': {}",
+                            pattern, event_name, filter_name, problem
+                        ),
+                        severity: Severity::Warning,
+                        span: Span {
+                            start: node.start_byte(),
+                            end: node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Every entry of a filter list with its unquoted text. A lone scalar counts
+/// as one entry, and an item with no value (`-`) as an empty one.
+fn filter_entries<'a>(filter: Node<'a>, source: &'a str) -> Vec<(Node<'a>, &'a str)> {
+    if !matches!(filter.kind(), "block_sequence" | "flow_sequence") {
+        return vec![(filter, utils::scalar_value(filter, source))];
+    }
+
+    let mut entries = Vec::new();
+    let mut cursor = filter.walk();
+    for child in filter.children(&mut cursor) {
+        match child.kind() {
+            "block_sequence_item" => {
+                let mut item_cursor = child.walk();
+                let value = child
+                    .children(&mut item_cursor)
+                    .find(|inner| inner.kind() != "-" && inner.kind() != "comment");
+                match value {
+                    Some(value) => {
+                        let value = utils::unwrap_node(value);
+                        entries.push((value, utils::scalar_value(value, source)));
+                    }
+                    None => entries.push((child, "")),
+                }
+            }
+            "flow_node" => {
+                let value = utils::unwrap_node(child);
+                entries.push((value, utils::scalar_value(value, source)));
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+/// What a pattern likely gets wrong, or `None` if it looks intentional.
+///
+/// `has_negations` is whether any entry of the same list starts with `!`.
+fn pattern_problem(filter: &str, pattern: &str, has_negations: bool) -> Option<String> {
+    let body = pattern.strip_prefix('!').unwrap_or(pattern);
+    let is_path = filter.starts_with("paths");
+
+    if is_path && has_windows_separator(body) {
+        return Some(format!(
+            "Paths are matched with '/' separators, even on Windows, and '\\' escapes the next character instead. Use '{}'.",
+            pattern.replace("\\\\", "/").replace('\\', "/")
+        ));
+    }
+    // `['**', '!docs/**']` is a common way to list exceptions
+    if is_path && pattern == "**" && !has_negations {
+        return Some(if filter == "paths" {
+            "'**' matches every file, so this filter doesn't narrow anything. Remove it or list the paths that should trigger the workflow.".to_string()
+        } else {
+            "'**' matches every file, so every change is ignored and the event never triggers the workflow.".to_string()
+        });
+    }
+
+    if body == "*" {
+        return Some(if is_path {
+            "'*' only matches files at the repository root. Use '**' to match files in every directory.".to_string()
+        } else {
+            format!(
+                "'*' doesn't match names containing '/', such as 'feature/login'. Use '**' to match all {}.",
+                filter.trim_end_matches("-ignore")
+            )
+        });
+    }
+    if is_path && !body.contains('/') && body.contains('*') && !body.contains("**") {
+        return Some(format!(
+            "'*' doesn't match '/', so this only matches files at the repository root. Use '**{}' to match them in every directory.",
+            body.trim_start_matches('*')
+        ));
+    }
+    if is_path {
+        if let Some(dir) = body.strip_suffix("/*") {
+            if !dir.contains('*') {
+                return Some(format!(
+                    "This only matches files directly in '{0}/'. Use '{0}/**' to include its subdirectories.",
+                    dir
+                ));
+            }
+        }
+    }
+
+    let chars: Vec<char> = body.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let after_range = i > 0 && chars[i - 1] == ']';
+        match c {
+            // An escaped character is always literal
+            '\\' => i += 1,
+            '?' | '+' if !after_range => {
+                let meaning = if c == '?' {
+                    "zero or one"
+                } else {
+                    "one or more"
+                };
+                return Some(format!(
+                    "'{0}' matches {1} of the preceding character, unlike a shell glob. Escape it as '\\{0}' to match a literal '{0}'.",
+                    c, meaning
+                ));
+            }
+            '!' => {
+                return Some(
+                    "'!' only negates a pattern at its start. Escape it as '\\!' to match a literal '!'."
+                        .to_string(),
+                );
+            }
+            '[' if !chars[i + 1..].contains(&']') => {
+                return Some(
+                    "'[' starts a character range that is never closed. Escape it as '\\[' to match a literal '['."
+                        .to_string(),
+                );
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Whether a path pattern has a `\` that reads as a Windows directory
+/// separator rather than an escape: one before anything but a single glob
+/// character, as in `src\app` or `src\**`.
+fn has_windows_separator(pattern: &str) -> bool {
+    pattern.match_indices('\\').any(|(i, _)| {
+        let rest = &pattern[i + 1..];
+        !matches!(rest.chars().next(), Some('*' | '?' | '+' | '[' | ']' | '!'))
+            || rest.starts_with("**")
+    })
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates container and services configurations.
@@ -11,234 +11,301 @@ impl ValidationRule for JobContainerRule {
         "job_container"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `container:` and `services:` on jobs: each needs a well-formed image reference, `:latest` tags are flagged as non-reproducible, and `ports:` entries must be `host:container` or bare container port numbers.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    container:
+      options: --cpus 1
+    services:
+      db:
+        image: postgres:latest
+        ports:
+          - db:5432
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    container:
+      image: node:20
+      options: --cpus 1
+    services:
+      db:
+        image: postgres:16
+        ports:
+          - 5432:5432
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
 
-        fn check_job_container(node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
-            match node.kind() {
-                "block_mapping_pair" | "flow_pair" => {
-                    if let Some(key_node) = node.child(0) {
-                        let job_name = utils::clean_key(key_node, source).to_string();
-
-                        let job_value = utils::get_pair_value(node);
-
-                        if let Some(job_value_raw) = job_value {
-                            let job_value = utils::unwrap_node(job_value_raw);
-
-                            if job_value.kind() == "block_mapping"
-                                || job_value.kind() == "flow_mapping"
-                            {
-                                // Check container
-                                let container_value =
-                                    utils::find_value_for_key(job_value, source, "container");
-                                if let Some(container_node) = container_value {
-                                    validate_container(
-                                        container_node,
-                                        source,
-                                        &job_name,
-                                        diagnostics,
-                                    );
-                                }
-
-                                // Check services
-                                let services_value =
-                                    utils::find_value_for_key(job_value, source, "services");
-                                if let Some(services_node) = services_value {
-                                    validate_services(
-                                        services_node,
-                                        source,
-                                        &job_name,
-                                        diagnostics,
-                                    );
-                                }
-                            }
+        for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+            for pair in utils::mapping_pairs(job_value) {
+                let Some(key_node) = pair.child(0) else {
+                    continue;
+                };
+                let value = utils::get_pair_value(pair).map(utils::unwrap_node);
+                match utils::clean_key(key_node, source) {
+                    "container" => validate_container(
+                        &format!("Job '{}' container", job_name),
+                        key_node,
+                        value,
+                        source,
+                        &mut diagnostics,
+                    ),
+                    "services" => {
+                        let services = value.map(utils::mapping_pairs).unwrap_or_default();
+                        for service in services {
+                            let Some(service_key) = service.child(0) else {
+                                continue;
+                            };
+                            let service_value =
+                                utils::get_pair_value(service).map(utils::unwrap_node);
+                            validate_container(
+                                &format!(
+                                    "Job '{}' service '{}'",
+                                    job_name,
+                                    utils::clean_key(service_key, source)
+                                ),
+                                service_key,
+                                service_value,
+                                source,
+                                &mut diagnostics,
+                            );
                         }
                     }
-                }
-                _ => {
-                    let mut cursor = node.walk();
-                    for child in node.children(&mut cursor) {
-                        check_job_container(child, source, diagnostics);
-                    }
+                    _ => {}
                 }
             }
         }
 
-        fn validate_container(
-            container_node: Node,
-            source: &str,
-            job_name: &str,
-            diagnostics: &mut Vec<Diagnostic>,
-        ) {
-            let container_to_check = utils::unwrap_node(container_node);
-
-            // Container can be a plain string (the image name directly) or a mapping with `image` key
-            // e.g., `container: ubuntu:20.04` or `container: ${{ matrix.container }}`
-            match container_to_check.kind() {
-                "plain_scalar" | "double_quoted_scalar" | "single_quoted_scalar" => {
-                    // Container is specified as a plain string — this IS the image, which is valid
-                    let text = utils::node_text(container_to_check, source);
-                    let cleaned =
-                        text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-                    if cleaned.is_empty() {
-                        diagnostics.push(Diagnostic {
-                            message: format!(
-                                "Job '{}' container has empty image. Container image is required.",
-                                job_name
-                            ),
-                            severity: Severity::Error,
-                            span: Span {
-                                start: container_to_check.start_byte(),
-                                end: container_to_check.end_byte(),
-                            },
-                            rule_id: String::new(),
-                        });
-                    }
-                    return;
-                }
-                _ => {}
-            }
+        diagnostics
+    }
+}
 
-            // Check image field (required for mapping-style containers)
-            let image_value = utils::find_value_for_key(container_to_check, source, "image");
-            if image_value.is_none() {
-                diagnostics.push(Diagnostic {
-                    message: format!(
-                        "Job '{}' container is missing required 'image' field. Container must specify an image.",
-                        job_name
-                    ),
-                    severity: Severity::Error,
-                    span: Span {
-                        start: container_to_check.start_byte(),
-                        end: container_to_check.end_byte(),
-                    },
-                    rule_id: String::new(),
-                });
-            } else if let Some(image_node) = image_value {
-                let image_text = utils::node_text(image_node, source);
-                let image_cleaned =
-                    image_text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-
-                if image_cleaned.is_empty() {
-                    diagnostics.push(Diagnostic {
-                        message: format!(
-                            "Job '{}' container has empty image field. Container image is required.",
-                            job_name
-                        ),
-                        severity: Severity::Error,
-                        span: Span {
-                            start: image_node.start_byte(),
-                            end: image_node.end_byte(),
-                        },
-                        rule_id: String::new(),
-                    });
-                }
-            }
+/// Check a `container:` value or one service, which share the same shape.
+///
+/// `owner` describes the container in messages; `key_node` is where a missing
+/// image is reported.
+fn validate_container(
+    owner: &str,
+    key_node: Node,
+    value: Option<Node>,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let image = match value {
+        // `container: node:20` names the image directly
+        Some(value) if value.kind().ends_with("scalar") => {
+            validate_image(owner, value, source, diagnostics);
+            return;
+        }
+        Some(value) if matches!(value.kind(), "block_mapping" | "flow_mapping") => {
+            utils::direct_value(value, source, "image")
+        }
+        Some(_) => return,
+        None => None,
+    };
+
+    match image {
+        Some(image) => validate_image(owner, image, source, diagnostics),
+        None => diagnostics.push(error(
+            format!(
+                "{} is missing required 'image' field. Container must specify an image.",
+                owner
+            ),
+            key_node,
+        )),
+    }
 
-            // Check ports format
-            let ports_value = utils::find_value_for_key(container_to_check, source, "ports");
-            if let Some(ports_node) = ports_value {
-                validate_ports(ports_node, source, job_name, diagnostics);
+    let ports = value.and_then(|value| utils::direct_value(value, source, "ports"));
+    if let Some(ports) = ports {
+        let entries = if matches!(ports.kind(), "block_sequence" | "flow_sequence") {
+            utils::sequence_items(ports)
+        } else {
+            vec![ports]
+        };
+        for entry in entries {
+            let port = utils::scalar_value(entry, source);
+            if port.is_empty() || port.contains("${{") || is_valid_port_mapping(port) {
+                continue;
             }
+            diagnostics.push(error(
+                format!(
+                    "{} has invalid port format: '{}'. Ports should be in format 'host:container' or a bare container port, using port numbers from 1 to 65535.",
+                    owner, port
+                ),
+                entry,
+            ));
         }
+    }
+}
 
-        fn validate_services(
-            services_node: Node,
-            source: &str,
-            job_name: &str,
-            diagnostics: &mut Vec<Diagnostic>,
-        ) {
-            // Services is a mapping of service names to container configs
-            // Validate each service container
-            fn check_service(
-                node: Node,
-                source: &str,
-                job_name: &str,
-                diagnostics: &mut Vec<Diagnostic>,
-            ) {
-                match node.kind() {
-                    "block_mapping_pair" | "flow_pair" => {
-                        if let Some(key_node) = node.child(0) {
-                            let _service_name = utils::node_text(key_node, source);
-
-                            let service_value = utils::get_pair_value(node);
-
-                            if let Some(service_value_raw) = service_value {
-                                let service_value = utils::unwrap_node(service_value_raw);
-
-                                validate_container(service_value, source, job_name, diagnostics);
-                            }
-                        }
-                    }
-                    _ => {
-                        let mut cursor = node.walk();
-                        for child in node.children(&mut cursor) {
-                            check_service(child, source, job_name, diagnostics);
-                        }
-                    }
-                }
-            }
+fn validate_image(owner: &str, image: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let reference = utils::scalar_value(image, source);
+    if reference.contains("${{") {
+        return;
+    }
+    if reference.is_empty() {
+        diagnostics.push(error(
+            format!("{} has empty image. Container image is required.", owner),
+            image,
+        ));
+        return;
+    }
+
+    match parse_image_tag(reference) {
+        None => diagnostics.push(error(
+            format!(
+                "{} image '{}' is not a valid image reference. Expected '[registry/]name[:tag][@digest]'.",
+                owner, reference
+            ),
+            image,
+        )),
+        Some(Some("latest")) => diagnostics.push(Diagnostic {
+            message: format!(
+                "{} image '{}' uses the 'latest' tag, so the image can change between runs. Pin a version tag or digest.",
+                owner, reference
+            ),
+            severity: Severity::Warning,
+            span: span(image),
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        }),
+        Some(_) => {}
+    }
+}
 
-            check_service(services_node, source, job_name, diagnostics);
+/// Parse a Docker image reference, returning its tag (if any) when valid.
+///
+/// Accepts `[registry[:port]/]path[:tag][@algorithm:hex]`, with lowercase
+/// path components and a `docker://` prefix allowed.
+fn parse_image_tag(reference: &str) -> Option<Option<&str>> {
+    let reference = reference.strip_prefix("docker://").unwrap_or(reference);
+    let (name, digest) = match reference.split_once('@') {
+        Some((name, digest)) => (name, Some(digest)),
+        None => (reference, None),
+    };
+    if let Some(digest) = digest {
+        let (algorithm, hex) = digest.split_once(':')?;
+        if algorithm.is_empty()
+            || !algorithm.chars().all(|c| c.is_ascii_alphanumeric())
+            || hex.is_empty()
+            || !hex.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return None;
         }
+    }
 
-        fn validate_ports(
-            ports_node: Node,
-            source: &str,
-            job_name: &str,
-            diagnostics: &mut Vec<Diagnostic>,
-        ) {
-            let _ports_text = utils::node_text(ports_node, source);
-
-            // Ports can be an array of strings in format "host:container"
-            // Basic validation: check if format looks correct
-            fn check_port_format(
-                node: Node,
-                source: &str,
-                job_name: &str,
-                diagnostics: &mut Vec<Diagnostic>,
-            ) {
-                match node.kind() {
-                    "plain_scalar" | "double_quoted_scalar" | "single_quoted_scalar" => {
-                        let port_text = utils::node_text(node, source);
-                        let port_cleaned = port_text
-                            .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-
-                        // Check if it's in format "host:container"
-                        if !port_cleaned.contains(':') && !port_cleaned.is_empty() {
-                            diagnostics.push(Diagnostic {
-                                message: format!(
-                                    "Job '{}' container has invalid port format: '{}'. Ports should be in format 'host:container'.",
-                                    job_name, port_cleaned
-                                ),
-                                severity: Severity::Error,
-                                span: Span {
-                                    start: node.start_byte(),
-                                    end: node.end_byte(),
-                                },
-                                rule_id: String::new(),
-                            });
-                        }
-                    }
-                    _ => {
-                        let mut cursor = node.walk();
-                        for child in node.children(&mut cursor) {
-                            check_port_format(child, source, job_name, diagnostics);
-                        }
-                    }
-                }
-            }
+    // A ':' after the last '/' starts the tag; one before it is a registry port
+    let last_slash = name.rfind('/').map_or(0, |i| i + 1);
+    let (path, tag) = match name[last_slash..].find(':') {
+        Some(i) => (&name[..last_slash + i], Some(&name[last_slash + i + 1..])),
+        None => (name, None),
+    };
+    if let Some(tag) = tag {
+        let valid = tag.len() <= 128
+            && tag
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+            && tag
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+        if !valid {
+            return None;
+        }
+    }
 
-            check_port_format(ports_node, source, job_name, diagnostics);
+    let mut components: Vec<&str> = path.split('/').collect();
+    // `ghcr.io/...`, `localhost:5000/...`: the first component is a registry host
+    if components.len() > 1 && (components[0].contains(['.', ':']) || components[0] == "localhost")
+    {
+        let (host, port) = match components[0].split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (components[0], None),
+        };
+        if host.is_empty()
+            || !host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'))
+            || port.is_some_and(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit()))
+        {
+            return None;
         }
+        components.remove(0);
+    }
+    let valid_component = |component: &str| {
+        let bytes = component.as_bytes();
+        !bytes.is_empty()
+            && bytes[0].is_ascii_alphanumeric()
+            && bytes[bytes.len() - 1].is_ascii_alphanumeric()
+            && component.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '.' | '_' | '-')
+            })
+    };
+    if !components.into_iter().all(valid_component) {
+        return None;
+    }
+    Some(tag)
+}
 
-        check_job_container(jobs_node, source, &mut diagnostics);
+/// `8080:80`, `80`, optionally followed by `/tcp` or `/udp`.
+fn is_valid_port_mapping(port: &str) -> bool {
+    let port = port
+        .strip_suffix("/tcp")
+        .or_else(|| port.strip_suffix("/udp"))
+        .unwrap_or(port);
+    let is_port_number = |p: &str| {
+        !p.is_empty()
+            && p.chars().all(|c| c.is_ascii_digit())
+            && p.parse::<u16>().is_ok_and(|n| n > 0)
+    };
+    match port.split_once(':') {
+        Some((host, container)) => is_port_number(host) && is_port_number(container),
+        None => is_port_number(port),
+    }
+}
 
-        diagnostics
+fn span(node: Node) -> Span {
+    Span {
+        start: node.start_byte(),
+        end: node.end_byte(),
+    }
+}
+
+fn error(message: String, node: Node) -> Diagnostic {
+    Diagnostic {
+        message,
+        severity: Severity::Error,
+        span: span(node),
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
     }
 }
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use std::collections::HashSet;
 use tree_sitter::{Node, Tree};
 
@@ -12,10 +12,45 @@ impl ValidationRule for JobIfExpressionRule {
         "job_if_expression"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates job `if:` conditions: expression syntax, references to jobs that don't exist, `secrets` references, which aren't allowed, conditions that are always false, which leave the job unreachable, `github.event_name` compared to an event the workflow isn't triggered by, and text outside a `${{ }}`, which makes the condition a string that is always true.",
+            bad_example: r#"on: push
+jobs:
+  deploy:
+    if: secrets.DEPLOY_TOKEN != ''
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#,
+            good_example: r#"on: push
+jobs:
+  deploy:
+    if: github.ref == 'refs/heads/main'
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -72,6 +107,8 @@ impl ValidationRule for JobIfExpressionRule {
                                                     end: if_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
 
@@ -88,11 +125,13 @@ impl ValidationRule for JobIfExpressionRule {
                                                     end: if_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         } else if utils::is_potentially_always_false(inner) {
                                             diagnostics.push(Diagnostic {
                                                 message: format!(
-                                                    "Job '{}' 'if' expression may always evaluate to false: '{}'",
+                                                    "Job '{}' 'if' expression may always evaluate to false, so the job never runs: '{}'",
                                                     job_name, inner
                                                 ),
                                                 severity: Severity::Warning,
@@ -101,6 +140,69 @@ impl ValidationRule for JobIfExpressionRule {
                                                     end: if_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
+                                            });
+                                        }
+
+                                        if let Some(outside) =
+                                            utils::text_outside_expressions(if_text)
+                                        {
+                                            diagnostics.push(Diagnostic {
+                                                message: format!(
+                                                    "Job '{}' 'if' condition has '{}' outside its '${{{{ }}}}', so GitHub reads the whole value as a string and the condition is always true. Put the entire condition inside one '${{{{ }}}}', or drop the braces.",
+                                                    job_name, &if_text[outside.clone()]
+                                                ),
+                                                severity: Severity::Warning,
+                                                span: Span {
+                                                    start: if_node.start_byte() + outside.start,
+                                                    end: if_node.start_byte() + outside.end,
+                                                },
+                                                rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
+                                            });
+                                        }
+
+                                        for reference in
+                                            utils::find_context_references(if_text, "secrets")
+                                        {
+                                            diagnostics.push(Diagnostic {
+                                                message: format!(
+                                                    "The secrets context is not available in if conditions (job '{}'). \
+                                                     Check the secret in an earlier job and expose the result as a job output.",
+                                                    job_name
+                                                ),
+                                                severity: Severity::Error,
+                                                span: Span {
+                                                    start: if_node.start_byte() + reference.start,
+                                                    end: if_node.start_byte() + reference.end,
+                                                },
+                                                rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
+                                            });
+                                        }
+
+                                        // `${{ }}`-wrapped conditions are checked by ExpressionValidationRule
+                                        if let Some(offset) = (!if_text.contains("${{"))
+                                            .then(|| utils::find_assignment_operator(if_text))
+                                            .flatten()
+                                        {
+                                            let eq_start = if_node.start_byte() + offset;
+                                            diagnostics.push(Diagnostic {
+                                                message: format!(
+                                                    "Job '{}' 'if' expression uses '=' instead of '==': '{}'. Expressions are read-only; use '==' to compare values.",
+                                                    job_name, inner
+                                                ),
+                                                severity: Severity::Error,
+                                                span: Span {
+                                                    start: eq_start,
+                                                    end: eq_start + 1,
+                                                },
+                                                rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
 
@@ -156,6 +258,8 @@ impl ValidationRule for JobIfExpressionRule {
                                                             end: expr_end,
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 }
 
@@ -179,6 +283,34 @@ impl ValidationRule for JobIfExpressionRule {
 
         check_job_if(jobs_node, source, &job_names, &mut diagnostics);
 
+        let triggers: Vec<&str> = utils::collect_triggers(tree, source)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+            let Some(if_node) = utils::direct_value(job_value, source, "if") else {
+                continue;
+            };
+            let if_text = utils::node_text(if_node, source);
+            for (range, event) in utils::find_untriggered_event_comparisons(if_text, &triggers) {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Job '{}' 'if' compares github.event_name to '{}', but the workflow isn't triggered by '{}', so the comparison is always false. Add '{}' to 'on:' or fix the event name.",
+                        job_name, event, event, event
+                    ),
+                    severity: Severity::Warning,
+                    span: Span {
+                        start: if_node.start_byte() + range.start,
+                        end: if_node.start_byte() + range.end,
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.start);
+
         diagnostics
     }
 }
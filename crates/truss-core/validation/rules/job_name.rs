@@ -1,7 +1,28 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
-use tree_sitter::{Node, Tree};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::Tree;
+
+/// Longest job ID GitHub accepts.
+const MAX_JOB_ID_LENGTH: usize = 100;
+
+/// Job and step fields that are almost never meant as job IDs.
+const STEP_OR_JOB_FIELDS: &[&str] = &[
+    "steps",
+    "runs-on",
+    "uses",
+    "with",
+    "run",
+    "needs",
+    "env",
+    "id",
+    "shell",
+    "working-directory",
+    "timeout-minutes",
+    "continue-on-error",
+    "strategy",
+    "outputs",
+];
 
 /// Validates job names.
 pub struct JobNameRule;
@@ -11,41 +32,57 @@ impl ValidationRule for JobNameRule {
         "job_name"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Job IDs must start with a letter or `_`, contain only ASCII letters, digits, `-`, and `_`, and be at most 100 characters long. IDs must also be unique, and an ID like `steps` or `runs-on` usually means a key was mis-indented.",
+            bad_example: r#"on: push
+jobs:
+  build app:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+            good_example: r#"on: push
+jobs:
+  build-app:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let root = tree.root_node();
-        let jobs_value = match utils::find_value_for_key(root, source, "jobs") {
-            Some(v) => v,
+        let jobs_node = match index.jobs_node() {
+            Some(n) => n,
             None => return diagnostics,
         };
 
-        let mut job_names = Vec::new();
-
-        fn collect_job_names(node: Node, source: &str, names: &mut Vec<(String, Span)>) {
-            match node.kind() {
-                "block_mapping_pair" | "flow_pair" => {
-                    if let Some(key_node) = node.child(0) {
-                        let key_cleaned = utils::clean_key(key_node, source);
-                        names.push((
-                            key_cleaned.to_string(),
-                            Span {
-                                start: key_node.start_byte(),
-                                end: key_node.end_byte(),
-                            },
-                        ));
-                    }
-                }
-                _ => {
-                    let mut cursor = node.walk();
-                    for child in node.children(&mut cursor) {
-                        collect_job_names(child, source, names);
-                    }
-                }
-            }
-        }
-
-        collect_job_names(jobs_value, source, &mut job_names);
+        // Only the direct keys of `jobs:` are job IDs, never `jobs` itself
+        let job_names: Vec<(String, Span)> = utils::mapping_pairs(jobs_node)
+            .into_iter()
+            .filter_map(|pair| pair.child(0))
+            .map(|key_node| {
+                (
+                    utils::clean_key(key_node, source).to_string(),
+                    Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                )
+            })
+            .collect();
 
         let mut seen = std::collections::HashSet::new();
         for (name, span) in &job_names {
@@ -55,6 +92,8 @@ impl ValidationRule for JobNameRule {
                     severity: Severity::Error,
                     span: *span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             } else {
                 seen.insert(name.clone());
@@ -65,29 +104,33 @@ impl ValidationRule for JobNameRule {
         for (name, span) in &job_names {
             let name_trimmed = name.trim();
 
-            // Validate job name length (GitHub Actions has practical limits)
-            if name_trimmed.len() > 100 {
+            if name_trimmed.len() > MAX_JOB_ID_LENGTH {
                 diagnostics.push(Diagnostic {
                     message: format!(
-                        "Job name '{}' is too long ({} characters). Consider using a shorter name (recommended: < 50 characters).",
-                        name_trimmed, name_trimmed.len()
+                        "Job ID '{}' is too long ({} characters). GitHub allows at most {} characters.",
+                        name_trimmed,
+                        name_trimmed.len(),
+                        MAX_JOB_ID_LENGTH
                     ),
-                    severity: Severity::Warning,
+                    severity: Severity::Error,
                     span: *span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
 
-            // Validate job name character set (alphanumeric, hyphens, underscores)
             if !is_valid_job_name_format(name_trimmed) {
                 diagnostics.push(Diagnostic {
                     message: format!(
-                        "Invalid job name: '{}'. Job names must contain only alphanumeric characters, hyphens, and underscores.",
+                        "Invalid job name: '{}'. Job IDs must start with a letter or '_' and contain only alphanumeric characters, hyphens, and underscores.",
                         name_trimmed
                     ),
                     severity: Severity::Error,
                     span: *span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
 
@@ -103,6 +146,20 @@ impl ValidationRule for JobNameRule {
                     severity: Severity::Error,
                     span: *span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            } else if STEP_OR_JOB_FIELDS.contains(&name_trimmed) {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Job ID '{}' matches a job/step field name. This is usually a mis-indented key that ended up directly under 'jobs:'.",
+                        name_trimmed
+                    ),
+                    severity: Severity::Warning,
+                    span: *span,
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -112,13 +169,14 @@ impl ValidationRule for JobNameRule {
 }
 
 /// Validates that a job name follows the correct format.
-/// Job names must contain only alphanumeric characters, hyphens, and underscores.
+/// Job IDs must start with an ASCII letter or `_` and contain only ASCII
+/// alphanumeric characters, hyphens, and underscores.
 fn is_valid_job_name_format(job_name: &str) -> bool {
-    if job_name.is_empty() {
-        return false;
+    let mut chars = job_name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {}
+        _ => return false,
     }
 
-    job_name
-        .chars()
-        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
 }
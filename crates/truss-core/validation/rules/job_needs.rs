@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates job dependencies (needs).
@@ -11,10 +11,49 @@ impl ValidationRule for JobNeedsRule {
         "job_needs"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks the `needs:` graph. Every dependency must name an existing job, and the graph must not contain cycles or self-references.",
+            bad_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo test
+  deploy:
+    needs: tests
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo deploy
+"#,
+            good_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo test
+  deploy:
+    needs: test
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo deploy
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -121,6 +160,8 @@ impl ValidationRule for JobNeedsRule {
                                                 end: value_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
 
@@ -136,6 +177,8 @@ impl ValidationRule for JobNeedsRule {
                                                 end: value_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
                                 }
@@ -208,14 +251,14 @@ impl ValidationRule for JobNeedsRule {
             }
         }
 
-        let mut dependencies: std::collections::HashMap<&str, Vec<&str>> =
+        let mut dependencies: std::collections::HashMap<&str, (Node, Vec<&str>)> =
             std::collections::HashMap::new();
 
         fn collect_dependencies<'a>(
             node: Node<'a>,
             source: &'a str,
             job_name: &'a str,
-            deps: &mut std::collections::HashMap<&'a str, Vec<&'a str>>,
+            deps: &mut std::collections::HashMap<&'a str, (Node<'a>, Vec<&'a str>)>,
         ) {
             match node.kind() {
                 "block_mapping_pair" | "flow_pair" => {
@@ -225,7 +268,7 @@ impl ValidationRule for JobNeedsRule {
                             if let Some(value_node_raw) = utils::get_pair_value(node) {
                                 let value_node = utils::unwrap_node(value_node_raw);
                                 let needs_values = extract_needs_values(value_node, source);
-                                deps.insert(job_name, needs_values);
+                                deps.insert(job_name, (value_node, needs_values));
                             }
                         } else if let Some(value_node) = utils::get_pair_value(node) {
                             collect_dependencies(value_node, source, job_name, deps);
@@ -251,7 +294,7 @@ impl ValidationRule for JobNeedsRule {
             node: Node<'a>,
             source: &'a str,
             all_job_names: &std::collections::HashSet<&'a str>,
-            deps: &mut std::collections::HashMap<&'a str, Vec<&'a str>>,
+            deps: &mut std::collections::HashMap<&'a str, (Node<'a>, Vec<&'a str>)>,
         ) {
             match node.kind() {
                 "block_mapping_pair" | "flow_pair" => {
@@ -276,53 +319,109 @@ impl ValidationRule for JobNeedsRule {
 
         collect_all_dependencies(jobs_node, source, &job_names, &mut dependencies);
 
-        fn has_cycle<'a>(
-            job: &'a str,
-            deps: &std::collections::HashMap<&str, Vec<&'a str>>,
-            visited: &mut std::collections::HashSet<&'a str>,
-            rec_stack: &mut std::collections::HashSet<&'a str>,
-        ) -> bool {
-            visited.insert(job);
-            rec_stack.insert(job);
-
-            if let Some(needs) = deps.get(job) {
-                for need in needs {
-                    if !visited.contains(need) {
-                        if has_cycle(need, deps, visited, rec_stack) {
-                            return true;
-                        }
-                    } else if rec_stack.contains(need) {
-                        return true;
+        let mut sorted_job_names: Vec<&str> = job_names.iter().copied().collect();
+        sorted_job_names.sort_unstable();
+        for cycle in find_cycles(&sorted_job_names, &dependencies) {
+            // Start the path at the job defined first in the file
+            let first = (0..cycle.len())
+                .min_by_key(|&i| dependencies[cycle[i]].0.start_byte())
+                .unwrap_or(0);
+            let mut path: Vec<&str> = cycle[first..]
+                .iter()
+                .chain(&cycle[..first])
+                .copied()
+                .collect();
+            let related = path
+                .iter()
+                .zip(path.iter().skip(1).chain(path.first()))
+                .skip(1)
+                .map(|(job, need)| {
+                    let needs_node = dependencies[job].0;
+                    RelatedLocation {
+                        span: Span {
+                            start: needs_node.start_byte(),
+                            end: needs_node.end_byte(),
+                        },
+                        message: format!("Job '{}' needs '{}' here", job, need),
                     }
-                }
-            }
-
-            rec_stack.remove(job);
-            false
+                })
+                .collect();
+            let needs_node = dependencies[path[0]].0;
+            path.push(path[0]);
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Circular dependency detected: {}. GitHub rejects workflows whose jobs wait on each other.",
+                    path.join(" → ")
+                ),
+                severity: Severity::Error,
+                span: Span {
+                    start: needs_node.start_byte(),
+                    end: needs_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related,
+            });
         }
 
-        let mut visited = std::collections::HashSet::new();
-        let mut sorted_job_names: Vec<_> = job_names.iter().collect();
-        sorted_job_names.sort();
-        for job in &sorted_job_names {
-            if !visited.contains(*job) {
-                let mut rec_stack = std::collections::HashSet::new();
-                if has_cycle(job, &dependencies, &mut visited, &mut rec_stack) {
-                    diagnostics.push(Diagnostic {
-                        message: format!("circular dependency detected involving job '{}'", job),
-                        severity: Severity::Error,
-                        span: Span {
-                            start: 0,
-                            end: source.len().min(100),
-                        },
-                        rule_id: String::new(),
+        process_job(jobs_node, source, &job_names, &mut diagnostics);
+
+        diagnostics
+    }
+}
+
+/// Every cycle in the `needs` graph, each as the jobs on it in dependency
+/// order, without repeating the first job at the end.
+///
+/// A depth-first search from each job in `jobs` order; an edge back to a job
+/// still on the search path closes a cycle. A cycle reachable from several
+/// jobs is reported once, and self-references are left to the `needs`
+/// reference check. Jobs that are reached again through another path, as in
+/// a diamond, are finished and never form a cycle.
+fn find_cycles<'a>(
+    jobs: &[&'a str],
+    deps: &std::collections::HashMap<&'a str, (Node, Vec<&'a str>)>,
+) -> Vec<Vec<&'a str>> {
+    fn visit<'a>(
+        job: &'a str,
+        deps: &std::collections::HashMap<&'a str, (Node, Vec<&'a str>)>,
+        path: &mut Vec<&'a str>,
+        finished: &mut std::collections::HashSet<&'a str>,
+        cycles: &mut Vec<Vec<&'a str>>,
+    ) {
+        path.push(job);
+        if let Some((_, needs)) = deps.get(job) {
+            for &need in needs {
+                if need == job || finished.contains(need) {
+                    continue;
+                }
+                if let Some(pos) = path.iter().position(|&j| j == need) {
+                    let cycle = path[pos..].to_vec();
+                    let mut members = cycle.clone();
+                    members.sort_unstable();
+                    let seen = cycles.iter().any(|c| {
+                        let mut other = c.clone();
+                        other.sort_unstable();
+                        other == members
                     });
+                    if !seen {
+                        cycles.push(cycle);
+                    }
+                } else {
+                    visit(need, deps, path, finished, cycles);
                 }
             }
         }
+        path.pop();
+        finished.insert(job);
+    }
 
-        process_job(jobs_node, source, &job_names, &mut diagnostics);
-
-        diagnostics
+    let mut finished = std::collections::HashSet::new();
+    let mut cycles = Vec::new();
+    for &job in jobs {
+        if !finished.contains(job) {
+            visit(job, deps, &mut Vec::new(), &mut finished, &mut cycles);
+        }
     }
+    cycles
 }
@@ -1,10 +1,11 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
-use std::collections::HashSet;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use super::step_output_reference::find_step_output_references_recursive;
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::collections::{HashMap, HashSet};
 use tree_sitter::{Node, Tree};
 
-/// Validates that job outputs reference valid step IDs.
+/// Validates that job outputs reference valid step IDs and outputs those steps set.
 pub struct JobOutputsRule;
 
 impl ValidationRule for JobOutputsRule {
@@ -12,10 +13,49 @@ impl ValidationRule for JobOutputsRule {
         "job_outputs"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Job `outputs:` must reference step IDs that exist in the same job, and outputs those steps actually set. Renaming a step ID or output without updating the job output silently yields an empty value.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.ver.outputs.version }}
+    steps:
+      - id: version
+        run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.version.outputs.version }}
+    steps:
+      - id: version
+        run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -60,12 +100,16 @@ impl ValidationRule for JobOutputsRule {
                                                     if step_ids.is_empty() {
                                                         "none".to_string()
                                                     } else {
-                                                        step_ids.iter().cloned().collect::<Vec<_>>().join(", ")
+                                                        let mut sorted: Vec<_> = step_ids.iter().map(String::as_str).collect();
+                                                        sorted.sort_unstable();
+                                                        sorted.join(", ")
                                                     }
                                                 ),
                                                 severity: Severity::Error,
                                                 span,
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -81,8 +125,20 @@ impl ValidationRule for JobOutputsRule {
                                             severity: Severity::Error,
                                             span,
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
+
+                                    let step_outputs =
+                                        utils::collect_step_outputs(job_value, source);
+                                    check_step_output_names(
+                                        &job_name,
+                                        outputs_to_check,
+                                        source,
+                                        &step_outputs,
+                                        diagnostics,
+                                    );
                                 }
                             }
                         }
@@ -103,6 +159,54 @@ impl ValidationRule for JobOutputsRule {
     }
 }
 
+/// Report job outputs that read a step output the step never sets.
+///
+/// Only steps whose `run:` writes to `$GITHUB_OUTPUT` have known outputs;
+/// references to other steps, and to contexts like `env` or `needs`, pass.
+fn check_step_output_names(
+    job_name: &str,
+    outputs_node: Node,
+    source: &str,
+    step_outputs: &HashMap<String, HashSet<String>>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for pair in utils::mapping_pairs(outputs_node) {
+        let (key_node, value_node) = match (pair.child(0), utils::get_pair_value(pair)) {
+            (Some(k), Some(v)) => (k, v),
+            _ => continue,
+        };
+        let output_name = utils::clean_key(key_node, source);
+
+        for (step_id, step_output, span) in
+            find_step_output_references_recursive(value_node, source)
+        {
+            let set = match step_outputs.get(&step_id) {
+                Some(set) if !set.contains(&step_output) => set,
+                _ => continue,
+            };
+            let mut available: Vec<&str> = set.iter().map(String::as_str).collect();
+            available.sort_unstable();
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Job '{}' output '{}' reads 'steps.{}.outputs.{}', but step '{}' never sets '{}'. Outputs it sets: {}",
+                    job_name,
+                    output_name,
+                    step_id,
+                    step_output,
+                    step_id,
+                    step_output,
+                    available.join(", ")
+                ),
+                severity: Severity::Error,
+                span,
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
 fn collect_step_ids(job_node: Node, source: &str) -> HashSet<String> {
     let mut step_ids = HashSet::new();
 
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates strategy field syntax and constraints (max-parallel, fail-fast).
@@ -11,10 +11,47 @@ impl ValidationRule for JobStrategyValidationRule {
         "job_strategy"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `strategy` settings besides the matrix: `max-parallel` must be a positive integer and `fail-fast` a boolean.",
+            bad_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: "false"
+      matrix:
+        node: [18, 20]
+    steps:
+      - run: npm test
+"#,
+            good_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      fail-fast: false
+      matrix:
+        node: [18, 20]
+    steps:
+      - run: npm test
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -71,6 +108,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                     end: strategy_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         } else {
                                             // Strategy has max-parallel or fail-fast but no matrix - this is valid but unusual
@@ -86,6 +125,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                     end: strategy_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -121,6 +162,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                     end: max_parallel_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         } else {
                                             match max_parallel_cleaned.parse::<i64>() {
@@ -137,6 +180,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                                 end: max_parallel_node.end_byte(),
                                                             },
                                                             rule_id: String::new(),
+                                                            category: RuleCategory::default(),
+                                                            related: Vec::new(),
                                                         });
                                                     } else if value == 0 {
                                                         diagnostics.push(Diagnostic {
@@ -150,7 +195,31 @@ impl ValidationRule for JobStrategyValidationRule {
                                                                 end: max_parallel_node.end_byte(),
                                                             },
                                                             rule_id: String::new(),
+                                                            category: RuleCategory::default(),
+                                                            related: Vec::new(),
                                                         });
+                                                    } else if let Some(matrix_size) =
+                                                        static_matrix_size(job_value, source)
+                                                    {
+                                                        // Dynamic (fromJSON) matrices have an unknown size and are skipped
+                                                        if matrix_size > 0
+                                                            && value as u64 > matrix_size as u64
+                                                        {
+                                                            diagnostics.push(Diagnostic {
+                                                                message: format!(
+                                                                    "Job '{}' has max-parallel: {} but its matrix only expands to {} job(s), so the limit has no effect.",
+                                                                    job_name, value, matrix_size
+                                                                ),
+                                                                severity: Severity::Info,
+                                                                span: Span {
+                                                                    start: max_parallel_node.start_byte(),
+                                                                    end: max_parallel_node.end_byte(),
+                                                                },
+                                                                rule_id: String::new(),
+                                                                category: RuleCategory::default(),
+                                                                related: Vec::new(),
+                                                            });
+                                                        }
                                                     }
                                                 }
                                                 Err(_) => {
@@ -165,6 +234,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                             end: max_parallel_node.end_byte(),
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 }
                                             }
@@ -202,6 +273,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                     end: fail_fast_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         } else {
                                             // Check if it's a boolean
@@ -221,6 +294,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                             end: fail_fast_node.end_byte(),
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 } else {
                                                     diagnostics.push(Diagnostic {
@@ -234,6 +309,8 @@ impl ValidationRule for JobStrategyValidationRule {
                                                             end: fail_fast_node.end_byte(),
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 }
                                             }
@@ -258,3 +335,10 @@ impl ValidationRule for JobStrategyValidationRule {
         diagnostics
     }
 }
+
+/// Number of jobs a job's matrix expands to, if it can be computed statically.
+fn static_matrix_size(job_value: Node, source: &str) -> Option<usize> {
+    let matrix = utils::get_job_matrix(job_value, source)?;
+    let parsed = utils::parse_static_matrix(matrix, source)?;
+    Some(parsed.combination_count())
+}
@@ -1,25 +1,60 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates matrix strategy syntax in GitHub Actions workflows.
 pub struct MatrixStrategyRule;
 
+/// Most jobs GitHub generates from one matrix in a workflow run.
+pub(crate) const GITHUB_MAX_MATRIX_JOBS: usize = 256;
+
 impl ValidationRule for MatrixStrategyRule {
     fn name(&self) -> &str {
         "matrix_strategy"
     }
 
-    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `strategy.matrix`. Dimensions must be lists, and `include`/`exclude` must be lists of mappings. A matrix can expand to at most 256 jobs; larger ones are rejected when the workflow runs.",
+            bad_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+        include: experimental
+    steps:
+      - run: make test
+"#,
+            good_example: r#"on: push
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        os: [ubuntu-latest]
+        include:
+          - os: ubuntu-latest
+            experimental: true
+    steps:
+      - run: make test
+"#,
+        }
+    }
 
-        let root = tree.root_node();
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
 
-        let jobs_value = match utils::find_value_for_key(root, source, "jobs") {
-            Some(v) => v,
-            None => return diagnostics,
-        };
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
 
         fn find_matrix_nodes<'a>(
             node: Node<'a>,
@@ -75,9 +110,13 @@ impl ValidationRule for MatrixStrategyRule {
         }
 
         let mut matrix_nodes = Vec::new();
-        find_matrix_nodes(jobs_value, source, &mut matrix_nodes, 0);
+        for job in index.jobs() {
+            let mut found = Vec::new();
+            find_matrix_nodes(job.value, source, &mut found, 1);
+            matrix_nodes.extend(found.into_iter().map(|(node, span)| (job.name, node, span)));
+        }
 
-        for (matrix_node, span) in matrix_nodes {
+        for (job_name, matrix_node, span) in matrix_nodes {
             let matrix_to_check = utils::unwrap_node(matrix_node);
 
             let is_empty = {
@@ -121,6 +160,8 @@ impl ValidationRule for MatrixStrategyRule {
                     severity: Severity::Error,
                     span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 continue;
             }
@@ -177,6 +218,8 @@ impl ValidationRule for MatrixStrategyRule {
                                             end: key_node.end_byte(),
                                         },
                                         rule_id: String::new(),
+                                        category: RuleCategory::default(),
+                                        related: Vec::new(),
                                     });
                                 }
 
@@ -216,6 +259,8 @@ impl ValidationRule for MatrixStrategyRule {
                                                     end: value_to_check.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -261,6 +306,8 @@ impl ValidationRule for MatrixStrategyRule {
                     severity: Severity::Error,
                     span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
 
@@ -311,6 +358,8 @@ impl ValidationRule for MatrixStrategyRule {
                                                     end: value_to_check.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -335,12 +384,172 @@ impl ValidationRule for MatrixStrategyRule {
 
             validate_include_exclude(matrix_to_check, source, "include", &mut diagnostics);
             validate_include_exclude(matrix_to_check, source, "exclude", &mut diagnostics);
+            check_exclude_entries(matrix_to_check, source, &mut diagnostics);
+
+            if let Some(count) = static_combination_count(matrix_to_check, source) {
+                if count > GITHUB_MAX_MATRIX_JOBS {
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Job '{}' has a matrix that expands to {} jobs, but GitHub allows at most {} per matrix. Split it across jobs or remove combinations with exclude.",
+                            job_name, count, GITHUB_MAX_MATRIX_JOBS
+                        ),
+                        severity: Severity::Error,
+                        span,
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
         }
 
         diagnostics
     }
 }
 
+/// Number of jobs a matrix expands to after `include` and `exclude`, or
+/// `None` when any part of it is computed at runtime (e.g. `fromJSON`).
+fn static_combination_count(matrix: Node, source: &str) -> Option<usize> {
+    utils::parse_static_matrix(matrix, source).map(|parsed| parsed.combination_count())
+}
+
+/// Error on matrices that expand to more than `limit` jobs, for the
+/// `max_matrix_combinations` setting in `.truss.yml`.
+///
+/// Not part of `MatrixStrategyRule::validate`, since rules don't see the
+/// config; `TrussEngine::analyze_with_options` runs it when the setting is
+/// below GitHub's own limit. Matrices over that limit are already reported
+/// by the rule and are skipped here.
+pub(crate) fn check_matrix_limit(tree: &Tree, source: &str, limit: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let jobs_node = match utils::get_jobs_node(tree, source) {
+        Some(n) => n,
+        None => return diagnostics,
+    };
+
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        let matrix = match utils::get_job_matrix(job_value, source) {
+            Some(m) => m,
+            None => continue,
+        };
+        let count = match static_combination_count(matrix, source) {
+            Some(count) if count > limit && count <= GITHUB_MAX_MATRIX_JOBS => count,
+            _ => continue,
+        };
+        let span = match matrix.parent().and_then(|p| p.parent()) {
+            Some(pair) if matches!(pair.kind(), "block_mapping_pair" | "flow_pair") => Span {
+                start: pair.start_byte(),
+                end: pair.end_byte(),
+            },
+            _ => Span {
+                start: matrix.start_byte(),
+                end: matrix.end_byte(),
+            },
+        };
+
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Job '{}' has a matrix that expands to {} jobs, over the limit of {} set by max_matrix_combinations in .truss.yml.",
+                job_name, count, limit
+            ),
+            severity: Severity::Error,
+            span,
+            rule_id: "matrix_strategy".to_string(),
+            category: MatrixStrategyRule.category(),
+            related: Vec::new(),
+        });
+    }
+
+    diagnostics
+}
+
+/// Warns on `exclude` entries that can never match a base combination.
+///
+/// GitHub applies `exclude` to the base matrix before `include`, so an entry
+/// naming a key that isn't a base dimension, or a value missing from that
+/// dimension's list, removes nothing. Keys are checked against the declared
+/// dimensions even when their values are computed (e.g. `fromJSON`); values
+/// only when the whole matrix is static.
+fn check_exclude_entries(matrix: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if !matches!(matrix.kind(), "block_mapping" | "flow_mapping") {
+        return;
+    }
+    let dimensions: Vec<&str> = utils::mapping_pairs(matrix)
+        .into_iter()
+        .filter_map(|pair| pair.child(0))
+        .map(|key| utils::clean_key(key, source))
+        .filter(|key| !matches!(*key, "include" | "exclude"))
+        .collect();
+    let parsed = utils::parse_static_matrix(matrix, source);
+    let exclude = match utils::find_value_for_key(matrix, source, "exclude") {
+        Some(v) => utils::unwrap_node(v),
+        None => return,
+    };
+
+    for entry in utils::sequence_items(exclude) {
+        for pair in utils::mapping_pairs(entry) {
+            let (key_node, value_node) = match (pair.child(0), utils::get_pair_value(pair)) {
+                (Some(k), Some(v)) => (k, utils::unwrap_node(v)),
+                _ => continue,
+            };
+            let key = utils::clean_key(key_node, source);
+            if !dimensions.contains(&key) {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Matrix exclude key '{}' is not a base matrix dimension, so this entry never matches.",
+                        key
+                    ),
+                    severity: Severity::Warning,
+                    span: Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+                continue;
+            }
+            let values = match parsed
+                .as_ref()
+                .and_then(|parsed| parsed.dimensions.iter().find(|(dim, _)| *dim == key))
+            {
+                Some((_, values)) => values,
+                None => continue,
+            };
+
+            // Object-valued dimensions can't be compared by text.
+            if !matches!(
+                value_node.kind(),
+                "plain_scalar" | "double_quote_scalar" | "single_quote_scalar"
+            ) {
+                continue;
+            }
+            let value = utils::scalar_value(value_node, source);
+            if value.contains("${{") || values.contains(&value) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Matrix exclude value '{}' for '{}' is not in the base list [{}], so this entry never matches.",
+                    value,
+                    key,
+                    values.join(", ")
+                ),
+                severity: Severity::Warning,
+                span: Span {
+                    start: value_node.start_byte(),
+                    end: value_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
 /// Validates that a matrix key name follows the correct format.
 /// Matrix keys must contain only alphanumeric characters, hyphens, and underscores.
 fn is_valid_matrix_key_name(key_name: &str) -> bool {
@@ -0,0 +1,172 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Validates that `matrix.<key>` references in a job match a declared matrix key.
+pub struct MatrixReferenceRule;
+
+impl ValidationRule for MatrixReferenceRule {
+    fn name(&self) -> &str {
+        "matrix_reference"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`matrix.<key>` references must name a key the job's matrix declares, either as a dimension or through `include`. A typo silently expands to an empty string.",
+            bad_example: r#"on: push
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+    steps:
+      - run: echo ${{ matrix.version }}
+"#,
+            good_example: r#"on: push
+jobs:
+  test:
+    runs-on: ${{ matrix.os }}
+    strategy:
+      matrix:
+        os: [ubuntu-latest, windows-latest]
+    steps:
+      - run: echo ${{ matrix.os }}
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let jobs_node = match index.jobs_node() {
+            Some(n) => n,
+            None => return diagnostics,
+        };
+
+        for job_pair in utils::mapping_pairs(jobs_node) {
+            let job_name = match job_pair.child(0) {
+                Some(key_node) => utils::clean_key(key_node, source),
+                None => continue,
+            };
+            let job_value = match utils::get_pair_value(job_pair) {
+                Some(v) => utils::unwrap_node(v),
+                None => continue,
+            };
+            if job_value.kind() != "block_mapping" && job_value.kind() != "flow_mapping" {
+                continue;
+            }
+
+            // Only jobs that declare a matrix are checked; a dynamic matrix
+            // (fromJSON) has keys we can't know statically.
+            let declared_keys = match utils::get_job_matrix(job_value, source)
+                .and_then(|matrix| utils::collect_matrix_keys(matrix, source))
+            {
+                Some(keys) => keys,
+                None => continue,
+            };
+
+            check_job_references(
+                job_name,
+                job_value,
+                source,
+                &declared_keys,
+                &mut diagnostics,
+            );
+        }
+
+        diagnostics
+    }
+}
+
+fn check_job_references(
+    job_name: &str,
+    job_value: Node,
+    source: &str,
+    declared_keys: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let job_text = utils::node_text(job_value, source);
+    let job_start = job_value.start_byte();
+
+    for expr in utils::find_expressions(job_text) {
+        // Offset of the expression's inner text within the source
+        let inner_start = job_start + expr.start + 3;
+
+        for (offset, key) in find_matrix_references(expr.inner) {
+            if declared_keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+                continue;
+            }
+
+            let ref_start = inner_start + offset;
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Job '{}' references 'matrix.{}' but the matrix does not declare '{}'. Available keys: {}",
+                    job_name,
+                    key,
+                    key,
+                    if declared_keys.is_empty() {
+                        "none".to_string()
+                    } else {
+                        declared_keys.join(", ")
+                    }
+                ),
+                severity: Severity::Error,
+                span: Span {
+                    start: ref_start,
+                    end: ref_start + "matrix.".len() + key.len(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+    }
+}
+
+/// Find `matrix.<key>` references in an expression, returning the byte offset
+/// of each `matrix` token along with the referenced key.
+fn find_matrix_references(expr: &str) -> Vec<(usize, &str)> {
+    const PREFIX: &str = "matrix.";
+
+    let mut references = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(pos) = utils::find_ignore_ascii_case(&expr[search_from..], PREFIX) {
+        let start = search_from + pos;
+        search_from = start + PREFIX.len();
+
+        // Skip property accesses that merely end in "matrix" (e.g. `foo.matrix.os`)
+        let preceded_by_ident = expr[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.');
+        if preceded_by_ident {
+            continue;
+        }
+
+        let rest = &expr[search_from..];
+        let key_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len());
+        if key_len > 0 {
+            references.push((start, &rest[..key_len]));
+        }
+    }
+
+    references
+}
@@ -0,0 +1,158 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Detects jobs that use repository files before checking the repository out.
+pub struct MissingCheckoutRule;
+
+/// Commands whose first argument, when it isn't an option, is a file to read.
+const FILE_COMMANDS: &[&str] = &[
+    "bash", "cat", "node", "perl", "python", "python3", "ruby", "sh", "source",
+];
+
+impl ValidationRule for MissingCheckoutRule {
+    fn name(&self) -> &str {
+        "missing_checkout"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A job starts with an empty workspace, so running `./scripts/build.sh`, reading a repository file, or using a local action fails unless an earlier step runs `actions/checkout`. This is a heuristic based on the paths in `run:` scripts; disable it in `.truss.yml` if it doesn't fit your workflows.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./scripts/build.sh
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - run: ./scripts/build.sh
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            for &step in &job.steps {
+                if provides_files(step, source) {
+                    break;
+                }
+                if let Some((node, path)) = repository_reference(step, source) {
+                    // Once per job: later steps usually fail for the same reason
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Job '{}' uses '{}' before any 'actions/checkout' step, so the repository isn't on the runner yet. Add '- uses: actions/checkout@v4' before this step.",
+                            job.name, path
+                        ),
+                        severity: Severity::Info,
+                        span: Span {
+                            start: node.start_byte(),
+                            end: node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                    break;
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Whether a step puts files in the workspace: a checkout (including forks
+/// and wrappers of `actions/checkout`), a downloaded artifact, or a clone.
+fn provides_files(step: Node, source: &str) -> bool {
+    if let Some(uses) = utils::direct_value(step, source, "uses") {
+        let uses = utils::scalar_value(uses, source).to_ascii_lowercase();
+        return uses.contains("checkout") || uses.contains("download-artifact");
+    }
+    utils::direct_value(step, source, "run").is_some_and(|run| {
+        let script = utils::node_text(run, source);
+        ["git clone", "git init", "gh repo clone"]
+            .iter()
+            .any(|command| script.contains(command))
+    })
+}
+
+/// The node and path of the first repository file a step relies on: a local
+/// action in `uses:`, or a relative path in its `run:` script.
+fn repository_reference<'a>(step: Node<'a>, source: &'a str) -> Option<(Node<'a>, &'a str)> {
+    if let Some(uses) = utils::direct_value(step, source, "uses") {
+        let path = utils::scalar_value(uses, source);
+        return path.starts_with("./").then_some((uses, path));
+    }
+
+    // An absolute working directory means relative paths don't point into the workspace
+    let outside_workspace = utils::direct_value(step, source, "working-directory")
+        .is_some_and(|dir| utils::scalar_value(dir, source).starts_with('/'));
+    if outside_workspace {
+        return None;
+    }
+    let run = utils::direct_value(step, source, "run")?;
+    let path = utils::node_text(run, source)
+        .lines()
+        .find_map(|line| relative_path(line.split('#').next().unwrap_or_default()))?;
+    Some((run, path))
+}
+
+/// The first repository-relative path on a script line: a word starting with
+/// `./` (`./gradlew`), or the first argument of a command in `FILE_COMMANDS`
+/// (`bash scripts/ci.sh`, `cat VERSION`).
+fn relative_path(line: &str) -> Option<&str> {
+    let is_separator = |c: char| c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(' | ')');
+    let words: Vec<&str> = line
+        .split(is_separator)
+        .map(|word| word.trim_matches(['"', '\'']))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    for (i, word) in words.iter().enumerate() {
+        if word.len() > 2 && word.starts_with("./") {
+            return Some(word);
+        }
+        if !FILE_COMMANDS.contains(word) {
+            continue;
+        }
+        let Some(argument) = words.get(i + 1) else {
+            continue;
+        };
+        let is_file = !argument.starts_with(['-', '/', '$', '~', '<', '>'])
+            && !argument.contains("://")
+            && !argument.contains("${{")
+            && argument
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '_' | '-'));
+        if is_file {
+            return Some(argument);
+        }
+    }
+    None
+}
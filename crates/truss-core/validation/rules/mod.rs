@@ -1,13 +1,23 @@
 //! Validation rules for GitHub Actions workflows.
 
+pub mod action_branding;
+pub mod action_input_name;
+pub mod action_pinning;
 pub mod action_reference;
+pub mod action_runs_using;
 pub mod artifact;
+pub mod composite_action;
+pub mod composite_action_outputs;
 pub mod concurrency;
 pub mod defaults;
+pub mod deploy_job_timeout;
+pub mod deprecated_action;
 pub mod deprecated_commands;
+pub mod duplicate_key;
 pub mod environment;
 pub mod event_payload;
 pub mod expression;
+pub mod filter_pattern;
 pub mod job_container;
 pub mod job_if_expression;
 pub mod job_name;
@@ -15,11 +25,18 @@ pub mod job_needs;
 pub mod job_outputs;
 pub mod job_strategy;
 pub mod matrix;
+pub mod matrix_reference;
+pub mod missing_checkout;
+pub mod needs_output_reference;
 pub mod non_empty;
+pub mod parallel_jobs;
 pub mod permissions;
+pub mod pull_request_target;
 pub mod reusable_workflow_call;
+pub mod run_block_scalar;
 pub mod runner_label;
 pub mod runs_on;
+pub mod scheduled_reusable_workflow;
 pub mod schema;
 pub mod script_injection;
 pub mod secrets;
@@ -30,6 +47,7 @@ pub mod step_id_uniqueness;
 pub mod step_if_expression;
 pub mod step_name;
 pub mod step_output_reference;
+pub mod step_set_output;
 pub mod step_shell;
 pub mod step_timeout;
 pub mod step_working_directory;
@@ -38,19 +56,30 @@ pub mod timeout;
 pub mod workflow_call_inputs;
 pub mod workflow_call_outputs;
 pub mod workflow_call_secrets;
+pub mod workflow_dispatch_input_max;
 pub mod workflow_inputs;
 pub mod workflow_name;
 pub mod workflow_trigger;
 
 // Re-export all rules for easy importing
+pub use action_branding::ActionBrandingRule;
+pub use action_input_name::ActionInputNameRule;
+pub use action_pinning::ActionPinningRule;
 pub use action_reference::ActionReferenceRule;
+pub use action_runs_using::ActionRunsUsingRule;
 pub use artifact::ArtifactValidationRule;
+pub use composite_action::CompositeActionRule;
+pub use composite_action_outputs::CompositeActionOutputsRule;
 pub use concurrency::ConcurrencyRule;
 pub use defaults::DefaultsValidationRule;
+pub use deploy_job_timeout::DeployJobTimeoutRule;
+pub use deprecated_action::DeprecatedActionRule;
 pub use deprecated_commands::DeprecatedCommandsRule;
+pub use duplicate_key::DuplicateKeyRule;
 pub use environment::EnvironmentRule;
 pub use event_payload::EventPayloadValidationRule;
 pub use expression::ExpressionValidationRule;
+pub use filter_pattern::FilterPatternRule;
 pub use job_container::JobContainerRule;
 pub use job_if_expression::JobIfExpressionRule;
 pub use job_name::JobNameRule;
@@ -58,11 +87,18 @@ pub use job_needs::JobNeedsRule;
 pub use job_outputs::JobOutputsRule;
 pub use job_strategy::JobStrategyValidationRule;
 pub use matrix::MatrixStrategyRule;
+pub use matrix_reference::MatrixReferenceRule;
+pub use missing_checkout::MissingCheckoutRule;
+pub use needs_output_reference::NeedsOutputReferenceRule;
 pub use non_empty::NonEmptyRule;
+pub use parallel_jobs::ParallelJobsRule;
 pub use permissions::PermissionsRule;
+pub use pull_request_target::PullRequestTargetRule;
 pub use reusable_workflow_call::ReusableWorkflowCallRule;
+pub use run_block_scalar::RunBlockScalarRule;
 pub use runner_label::RunnerLabelRule;
 pub use runs_on::RunsOnRequiredRule;
+pub use scheduled_reusable_workflow::ScheduledReusableWorkflowRule;
 pub use schema::GitHubActionsSchemaRule;
 pub use script_injection::ScriptInjectionRule;
 pub use secrets::SecretsValidationRule;
@@ -73,6 +109,7 @@ pub use step_id_uniqueness::StepIdUniquenessRule;
 pub use step_if_expression::StepIfExpressionRule;
 pub use step_name::StepNameRule;
 pub use step_output_reference::StepOutputReferenceRule;
+pub use step_set_output::StepSetOutputRule;
 pub use step_shell::StepShellRule;
 pub use step_timeout::StepTimeoutRule;
 pub use step_working_directory::StepWorkingDirectoryRule;
@@ -81,6 +118,7 @@ pub use timeout::TimeoutRule;
 pub use workflow_call_inputs::WorkflowCallInputsRule;
 pub use workflow_call_outputs::WorkflowCallOutputsRule;
 pub use workflow_call_secrets::WorkflowCallSecretsRule;
+pub use workflow_dispatch_input_max::WorkflowDispatchInputMaxRule;
 pub use workflow_inputs::WorkflowInputsRule;
 pub use workflow_name::WorkflowNameRule;
 pub use workflow_trigger::WorkflowTriggerRule;
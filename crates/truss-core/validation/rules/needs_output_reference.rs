@@ -0,0 +1,210 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::collections::HashSet;
+use tree_sitter::{Node, Tree};
+
+/// Validates `needs.<job>` references against the jobs each job depends on.
+pub struct NeedsOutputReferenceRule;
+
+impl ValidationRule for NeedsOutputReferenceRule {
+    fn name(&self) -> &str {
+        "needs_output_reference"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A job can only read `needs.<job>` for the jobs listed in its own `needs:`. Referencing any other job, or an output that job doesn't declare, evaluates to an empty string at runtime.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh ${{ needs.build.outputs.version }}
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let job_outputs = match index.jobs_node() {
+            Some(jobs_node) => utils::collect_job_outputs(jobs_node, source),
+            None => return diagnostics,
+        };
+
+        for job in index.jobs() {
+            let needs: HashSet<String> = declared_needs(job.value, source)
+                .into_iter()
+                .map(str::to_ascii_lowercase)
+                .collect();
+            for (expr, inner_start) in job_expressions(job.value, &job.steps, source) {
+                for range in utils::find_context_references(expr, "needs") {
+                    // `needs.<job>`; `needs[...]` and `needs.*` name no job statically
+                    let reference = &expr[range.clone()];
+                    let needed = &reference["needs.".len()..];
+                    if needed.is_empty() || reference.as_bytes()["needs".len()] != b'.' {
+                        continue;
+                    }
+                    let span = Span {
+                        start: inner_start + range.start,
+                        end: inner_start + range.end,
+                    };
+
+                    let target = index
+                        .jobs()
+                        .iter()
+                        .find(|other| other.name.eq_ignore_ascii_case(needed));
+                    let Some(target) = target else {
+                        diagnostics.push(error(
+                            format!(
+                                "Job '{}' references 'needs.{}', but there is no job '{}'",
+                                job.name, needed, needed
+                            ),
+                            span,
+                        ));
+                        continue;
+                    };
+                    if !needs.contains(&needed.to_ascii_lowercase()) {
+                        diagnostics.push(error(
+                            format!(
+                                "Job '{}' references 'needs.{}' but does not list '{}' in its needs, so the value is always empty. Add 'needs: {}' to the job.",
+                                job.name, needed, target.name, target.name
+                            ),
+                            span,
+                        ));
+                        continue;
+                    }
+
+                    // A reusable workflow job's outputs come from the called workflow
+                    if utils::direct_value(target.value, source, "uses").is_some() {
+                        continue;
+                    }
+                    let Some(output) = output_name(&expr[range.end..]) else {
+                        continue;
+                    };
+                    let declared = job_outputs.get(target.name);
+                    if declared.is_some_and(|outputs| {
+                        outputs.iter().any(|name| name.eq_ignore_ascii_case(output))
+                    }) {
+                        continue;
+                    }
+                    let available = match declared {
+                        Some(outputs) => {
+                            let mut sorted: Vec<_> = outputs.iter().map(String::as_str).collect();
+                            sorted.sort_unstable();
+                            sorted.join(", ")
+                        }
+                        None => "none".to_string(),
+                    };
+                    let output_start = range.end + ".outputs.".len();
+                    diagnostics.push(error(
+                        format!(
+                            "Job '{}' references 'needs.{}.outputs.{}', but job '{}' does not declare output '{}'. Available outputs: {}",
+                            job.name, needed, output, target.name, output, available
+                        ),
+                        Span {
+                            start: inner_start + output_start,
+                            end: inner_start + output_start + output.len(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn error(message: String, span: Span) -> Diagnostic {
+    Diagnostic {
+        message,
+        severity: Severity::Error,
+        span,
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    }
+}
+
+/// Every expression in a job as `(text, byte offset of text)`: the contents
+/// of each `${{ }}`, plus job and step `if:` conditions written without one.
+fn job_expressions<'a>(job: Node, steps: &[Node], source: &'a str) -> Vec<(&'a str, usize)> {
+    let job_start = job.start_byte();
+    let mut expressions: Vec<_> = utils::find_expressions(utils::node_text(job, source))
+        .into_iter()
+        .map(|expr| (expr.inner, job_start + expr.start + 3))
+        .collect();
+
+    for mapping in std::iter::once(&job).chain(steps) {
+        if let Some(condition) = utils::direct_value(*mapping, source, "if") {
+            let text = utils::node_text(condition, source);
+            if !text.contains("${{") {
+                expressions.push((text, condition.start_byte()));
+            }
+        }
+    }
+    expressions
+}
+
+/// The job IDs listed in a job's `needs:`, as a single ID or a sequence.
+fn declared_needs<'a>(job: Node<'a>, source: &'a str) -> Vec<&'a str> {
+    match utils::direct_value(job, source, "needs") {
+        Some(needs) if matches!(needs.kind(), "block_sequence" | "flow_sequence") => {
+            utils::sequence_items(needs)
+                .into_iter()
+                .map(|item| utils::scalar_value(item, source))
+                .collect()
+        }
+        Some(needs) => vec![utils::scalar_value(needs, source)],
+        None => Vec::new(),
+    }
+}
+
+/// The output name in the text following `needs.<job>`, if it is `.outputs.<name>`.
+fn output_name(rest: &str) -> Option<&str> {
+    let after = rest.get(..".outputs.".len())?;
+    if !after.eq_ignore_ascii_case(".outputs.") {
+        return None;
+    }
+    let name = &rest[after.len()..];
+    let len = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+        .unwrap_or(name.len());
+    (len > 0).then(|| &name[..len])
+}
@@ -1,5 +1,5 @@
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::Tree;
 
 /// Validates that the document is not empty.
@@ -10,8 +10,31 @@ impl ValidationRule for NonEmptyRule {
         "non_empty"
     }
 
-    fn requires_workflow(&self) -> bool {
-        false
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Syntax
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags empty or whitespace-only documents, which usually means a placeholder file was committed by accident.",
+            bad_example: r#"
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::AnyYaml
     }
 
     fn validate(&self, _tree: &Tree, source: &str) -> Vec<Diagnostic> {
@@ -21,6 +44,8 @@ impl ValidationRule for NonEmptyRule {
                 severity: Severity::Warning,
                 span: Span::default(),
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             }]
         } else {
             Vec::new()
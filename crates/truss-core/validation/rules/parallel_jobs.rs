@@ -0,0 +1,99 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::Tree;
+
+/// Notes that jobs run in parallel when a workflow has several jobs and
+/// none of them declares `needs`.
+pub struct ParallelJobsRule;
+
+impl ValidationRule for ParallelJobsRule {
+    fn name(&self) -> &str {
+        "parallel_jobs"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Jobs run in parallel, not in the order they are written. A workflow with several jobs and no `needs:` anywhere often assumes otherwise, e.g. a deploy job listed after the build it relies on. Disable this rule in `.truss.yml` if your jobs are meant to be independent.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make deploy
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+  deploy:
+    needs: build
+    runs-on: ubuntu-latest
+    steps:
+      - run: make deploy
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let jobs = index.jobs();
+        if jobs.len() < 2
+            || jobs
+                .iter()
+                .any(|job| utils::key_exists(job.value, source, "needs"))
+        {
+            return Vec::new();
+        }
+
+        // Once per file, on the `jobs` key
+        let mut jobs_pair = index.jobs_node();
+        while let Some(node) = jobs_pair {
+            if matches!(node.kind(), "block_mapping_pair" | "flow_pair") {
+                break;
+            }
+            jobs_pair = node.parent();
+        }
+        let jobs_key = match jobs_pair.and_then(|pair| pair.child(0)) {
+            Some(key) => key,
+            None => return Vec::new(),
+        };
+
+        vec![Diagnostic {
+            message: format!(
+                "This workflow has {} jobs and none of them declares 'needs', so they all start at once rather than in the order they are written. Add 'needs: <job>' to a job that must wait for another.",
+                jobs.len()
+            ),
+            severity: Severity::Info,
+            span: Span {
+                start: jobs_key.start_byte(),
+                end: jobs_key.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        }]
+    }
+}
@@ -1,8 +1,53 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
+/// `GITHUB_TOKEN` permission scopes GitHub accepts under `permissions:`.
+const PERMISSION_SCOPES: &[&str] = &[
+    "actions",
+    "attestations",
+    "checks",
+    "contents",
+    "deployments",
+    "discussions",
+    "id-token",
+    "issues",
+    "models",
+    "packages",
+    "pages",
+    "pull-requests",
+    "repository-projects",
+    "security-events",
+    "statuses",
+    "workflows",
+];
+
+/// Action prefixes that request an OIDC token, so they need `id-token: write`.
+const OIDC_ACTIONS: &[&str] = &[
+    "actions/attest",
+    "actions/deploy-pages",
+    "aws-actions/configure-aws-credentials",
+    "azure/login",
+    "google-github-actions/auth",
+    "hashicorp/vault-action",
+    "octo-sts/action",
+    "pypa/gh-action-pypi-publish",
+    "sigstore/",
+];
+
+/// Script fragments that request an OIDC token directly or through a tool
+/// that uses one (npm/uv trusted publishing, cosign keyless signing).
+const OIDC_SCRIPT_MARKERS: &[&str] = &[
+    "ACTIONS_ID_TOKEN_REQUEST",
+    "getIDToken",
+    "--provenance",
+    "npm publish",
+    "uv publish",
+    "cosign sign",
+    "cosign attest",
+];
+
 /// Validates permissions configuration.
 pub struct PermissionsRule;
 
@@ -11,29 +56,48 @@ impl ValidationRule for PermissionsRule {
         "permissions"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `permissions:` at workflow and job level. Use `read-all`, `write-all`, or a mapping of known scopes to `read`, `write`, or `none`. `id-token: write` should only be granted where a step actually requests an OIDC token.",
+            bad_example: r#"on: push
+permissions:
+  content: read
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+            good_example: r#"on: push
+permissions:
+  contents: read
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         let root = tree.root_node();
-
-        let valid_scopes = [
-            "actions",
-            "attestations",
-            "checks",
-            "contents",
-            "deployments",
-            "id-token",
-            "issues",
-            "discussions",
-            "packages",
-            "pages",
-            "pull-requests",
-            "repository-projects",
-            "security-events",
-            "statuses",
-            "workflows",
-        ];
-
+        let valid_scopes = PERMISSION_SCOPES;
         let valid_values = ["read", "write", "none"];
 
         fn validate_permissions_node(
@@ -43,6 +107,7 @@ impl ValidationRule for PermissionsRule {
             valid_values: &[&str],
             diagnostics: &mut Vec<Diagnostic>,
         ) {
+            let node = utils::unwrap_node(node);
             match node.kind() {
                 "plain_scalar" | "double_quoted_scalar" | "single_quoted_scalar" => {
                     let text = utils::node_text(node, source);
@@ -57,14 +122,32 @@ impl ValidationRule for PermissionsRule {
                                 end: node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
-                "block_mapping" | "flow_mapping" | "block_node" => {
-                    let node_to_process = utils::unwrap_node(node);
+                "block_mapping" | "flow_mapping" => {
+                    // `permissions: {}` is valid hardening, but it's easy to write by
+                    // accident when a scope was meant to be granted.
+                    if utils::mapping_pairs(node).is_empty() {
+                        diagnostics.push(Diagnostic {
+                            message: "Empty permissions '{}' revokes all GITHUB_TOKEN scopes. \
+                                      This is fine for hardening, but grant the scopes you need if that wasn't intended."
+                                .to_string(),
+                            severity: Severity::Info,
+                            span: Span {
+                                start: node.start_byte(),
+                                end: node.end_byte(),
+                            },
+                            rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
+                        });
+                    }
 
-                    let mut cursor = node_to_process.walk();
-                    for child in node_to_process.children(&mut cursor) {
+                    let mut cursor = node.walk();
+                    for child in node.children(&mut cursor) {
                         if child.kind() == "block_mapping_pair" || child.kind() == "flow_pair" {
                             if let Some(key_node) = child.child(0) {
                                 let scope = utils::node_text(key_node, source);
@@ -74,10 +157,13 @@ impl ValidationRule for PermissionsRule {
                                     })
                                     .trim_end_matches(':');
                                 if !valid_scopes.contains(&scope_cleaned) {
+                                    let hint = suggest_scope(scope_cleaned)
+                                        .map(|s| format!(". Did you mean '{}'?", s))
+                                        .unwrap_or_default();
                                     diagnostics.push(Diagnostic {
                                         message: format!(
-                                            "Invalid permission scope: '{}'",
-                                            scope_cleaned
+                                            "Invalid permission scope: '{}'{}",
+                                            scope_cleaned, hint
                                         ),
                                         severity: Severity::Error,
                                         span: Span {
@@ -85,6 +171,8 @@ impl ValidationRule for PermissionsRule {
                                             end: key_node.end_byte(),
                                         },
                                         rule_id: String::new(),
+                                        category: RuleCategory::default(),
+                                        related: Vec::new(),
                                     });
                                 }
 
@@ -103,6 +191,8 @@ impl ValidationRule for PermissionsRule {
                                                 end: value_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
                                 }
@@ -118,61 +208,106 @@ impl ValidationRule for PermissionsRule {
             validate_permissions_node(
                 permissions_value,
                 source,
-                &valid_scopes,
+                valid_scopes,
                 &valid_values,
                 &mut diagnostics,
             );
+
+            let any_job_needs_token = index
+                .jobs()
+                .iter()
+                .any(|job| job_requests_oidc_token(job.value, &job.steps, source));
+            if !any_job_needs_token {
+                check_unused_id_token(permissions_value, source, "workflow", &mut diagnostics);
+            }
         }
 
-        if let Some(jobs_value) = utils::find_value_for_key(root, source, "jobs") {
-            fn find_job_permissions(
-                node: Node,
-                source: &str,
-                valid_scopes: &[&str],
-                valid_values: &[&str],
-                diagnostics: &mut Vec<Diagnostic>,
-            ) {
-                match node.kind() {
-                    "block_mapping_pair" | "flow_pair" => {
-                        if let Some(key_node) = node.child(0) {
-                            let key_cleaned = utils::clean_key(key_node, source);
-                            if key_cleaned == "permissions" {
-                                if let Some(perm_value) = utils::get_pair_value(node) {
-                                    validate_permissions_node(
-                                        perm_value,
-                                        source,
-                                        valid_scopes,
-                                        valid_values,
-                                        diagnostics,
-                                    );
-                                }
-                            }
-                        }
-                    }
-                    _ => {
-                        let mut cursor = node.walk();
-                        for child in node.children(&mut cursor) {
-                            find_job_permissions(
-                                child,
-                                source,
-                                valid_scopes,
-                                valid_values,
-                                diagnostics,
-                            );
-                        }
-                    }
+        for job in index.jobs() {
+            if let Some(perm_value) = utils::find_value_for_key(job.value, source, "permissions") {
+                validate_permissions_node(
+                    perm_value,
+                    source,
+                    valid_scopes,
+                    &valid_values,
+                    &mut diagnostics,
+                );
+
+                if !job_requests_oidc_token(job.value, &job.steps, source) {
+                    check_unused_id_token(
+                        perm_value,
+                        source,
+                        &format!("job '{}'", job.name),
+                        &mut diagnostics,
+                    );
                 }
             }
-
-            find_job_permissions(
-                jobs_value,
-                source,
-                &valid_scopes,
-                &valid_values,
-                &mut diagnostics,
-            );
         }
 
         diagnostics
     }
 }
+
+/// Suggest the scope a typo was probably meant to be, e.g. `content` → `contents`.
+fn suggest_scope(scope: &str) -> Option<&'static str> {
+    let normalized = scope.to_ascii_lowercase().replace('_', "-");
+    PERMISSION_SCOPES.iter().copied().find(|valid| {
+        *valid == normalized
+            || valid.strip_suffix('s') == Some(normalized.as_str())
+            || normalized.strip_suffix('s') == Some(valid)
+    })
+}
+
+/// Whether a job can use an OIDC token: a step requests one, or the job calls
+/// a reusable workflow that might.
+fn job_requests_oidc_token(job_value: Node, steps: &[Node], source: &str) -> bool {
+    if utils::find_value_for_key(job_value, source, "uses").is_some() {
+        return true;
+    }
+    steps.iter().any(|&step| {
+        let uses_oidc_action =
+            utils::find_value_for_key(step, source, "uses").is_some_and(|uses| {
+                let uses = utils::scalar_value(uses, source).to_ascii_lowercase();
+                OIDC_ACTIONS.iter().any(|action| uses.starts_with(action))
+            });
+        let text = utils::node_text(step, source);
+        uses_oidc_action
+            || OIDC_SCRIPT_MARKERS
+                .iter()
+                .any(|marker| text.contains(marker))
+    })
+}
+
+/// Warn on `id-token: write` in a permissions mapping whose jobs never request a token.
+fn check_unused_id_token(
+    permissions: Node,
+    source: &str,
+    level: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let permissions = utils::unwrap_node(permissions);
+    if !matches!(permissions.kind(), "block_mapping" | "flow_mapping") {
+        return;
+    }
+    let value = match utils::find_value_for_key(permissions, source, "id-token") {
+        Some(v) => utils::unwrap_node(v),
+        None => return,
+    };
+    if utils::scalar_value(value, source) != "write" {
+        return;
+    }
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "'id-token: write' at {} level allows requesting OIDC tokens, but no step uses one. \
+             Remove it, or grant it only to the job that authenticates with OIDC.",
+            level
+        ),
+        severity: Severity::Warning,
+        span: Span {
+            start: value.start_byte(),
+            end: value.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
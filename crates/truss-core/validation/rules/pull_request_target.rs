@@ -0,0 +1,214 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Expression fragments in `actions/checkout` inputs that select the PR's code
+/// instead of the base branch.
+const PR_HEAD_REFS: &[&str] = &[
+    "github.event.pull_request.head",
+    "github.event.pull_request.merge_commit_sha",
+    "github.head_ref",
+    "refs/pull/",
+];
+
+/// Commands that install, build, or test a project and therefore run code
+/// from the checked-out tree (package scripts, build files, test suites).
+const BUILD_COMMANDS: &[&str] = &[
+    "npm install",
+    "npm ci",
+    "npm test",
+    "npm run",
+    "yarn",
+    "pnpm",
+    "bun install",
+    "bun run",
+    "pip install",
+    "poetry install",
+    "python setup.py",
+    "pytest",
+    "tox",
+    "make",
+    "cargo build",
+    "cargo test",
+    "cargo run",
+    "go build",
+    "go test",
+    "go run",
+    "mvn",
+    "gradle",
+    "./gradlew",
+    "bundle install",
+    "bundle exec",
+    "composer install",
+    "dotnet build",
+    "dotnet test",
+];
+
+const PWN_REQUEST_DOCS: &str =
+    "https://securitylab.github.com/resources/github-actions-preventing-pwn-requests/";
+
+/// Flags `pull_request_target` workflows that check out and run code from the pull request.
+pub struct PullRequestTargetRule;
+
+impl ValidationRule for PullRequestTargetRule {
+    fn name(&self) -> &str {
+        "pull_request_target"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`pull_request_target` runs with a write token and access to secrets. Checking out the PR head and then installing, building, or testing it runs attacker-controlled code with those privileges. Use `pull_request` for anything that executes PR code.",
+            bad_example: r#"on: pull_request_target
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+        with:
+          ref: ${{ github.event.pull_request.head.sha }}
+      - run: npm ci && npm test
+"#,
+            good_example: r#"on: pull_request_target
+jobs:
+  label:
+    runs-on: ubuntu-latest
+    steps:
+      - run: gh pr edit "$PR" --add-label triage
+        env:
+          PR: ${{ github.event.pull_request.number }}
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let triggers = utils::collect_triggers(tree, source);
+        if !triggers
+            .iter()
+            .any(|(event, _)| *event == "pull_request_target")
+        {
+            return diagnostics;
+        }
+
+        for job in index.jobs() {
+            // Steps only run PR code once the PR head has been checked out
+            let mut pr_head_checked_out = false;
+
+            for &step in &job.steps {
+                if let Some(uses_node) = utils::find_value_for_key(step, source, "uses") {
+                    let uses = utils::scalar_value(uses_node, source);
+                    if is_pr_head_checkout(step, uses, source) {
+                        pr_head_checked_out = true;
+                        diagnostics.push(warning(
+                            uses_node,
+                            format!(
+                                "pull_request_target workflow checks out the pull request's code. \
+                                 This trigger runs with a write token and secrets, so any PR code \
+                                 that executes here can steal them. Use the pull_request trigger to \
+                                 build untrusted code. See {}",
+                                PWN_REQUEST_DOCS
+                            ),
+                        ));
+                    } else if pr_head_checked_out && uses.starts_with("./") {
+                        diagnostics.push(warning(
+                            uses_node,
+                            format!(
+                                "Local action '{}' comes from the checked-out pull request and runs \
+                                 with pull_request_target privileges. See {}",
+                                uses, PWN_REQUEST_DOCS
+                            ),
+                        ));
+                    }
+                }
+
+                if !pr_head_checked_out {
+                    continue;
+                }
+                if let Some(run_node) = utils::find_value_for_key(step, source, "run") {
+                    let script = utils::node_text(run_node, source);
+                    if let Some(command) = find_build_command(script) {
+                        diagnostics.push(warning(
+                            run_node,
+                            format!(
+                                "'{}' runs code from the checked-out pull request with \
+                                 pull_request_target privileges (write token and secrets). \
+                                 A malicious PR can change what this command executes. See {}",
+                                command, PWN_REQUEST_DOCS
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn warning(node: Node, message: String) -> Diagnostic {
+    Diagnostic {
+        message,
+        severity: Severity::Warning,
+        span: Span {
+            start: node.start_byte(),
+            end: node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    }
+}
+
+/// Whether a step is an `actions/checkout` of the pull request's head or merge ref.
+fn is_pr_head_checkout(step: Node, uses: &str, source: &str) -> bool {
+    let action = uses.split('@').next().unwrap_or("");
+    if !action.eq_ignore_ascii_case("actions/checkout") {
+        return false;
+    }
+    let with = match utils::find_value_for_key(step, source, "with") {
+        Some(v) => utils::unwrap_node(v),
+        None => return false,
+    };
+    ["ref", "repository"].iter().any(|input| {
+        utils::find_value_for_key(with, source, input).is_some_and(|value| {
+            let text = utils::node_text(value, source);
+            PR_HEAD_REFS.iter().any(|r| text.contains(r))
+        })
+    })
+}
+
+/// Find the first install/build/test command in a `run:` script.
+///
+/// The script is split into simple commands on newlines and shell operators,
+/// so `make` matches `make test` but not `mkdir` or `cmake`.
+fn find_build_command(script: &str) -> Option<&'static str> {
+    script
+        .split(['\n', ';', '&', '|'])
+        .map(str::trim)
+        .find_map(|command| {
+            BUILD_COMMANDS.iter().copied().find(|prefix| {
+                command
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+            })
+        })
+}
@@ -1,6 +1,7 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::parser::YamlParser;
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates uses: workflow calls reference valid reusable workflows.
@@ -11,10 +12,35 @@ impl ValidationRule for ReusableWorkflowCallRule {
         "reusable_workflow_call"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates jobs that call a reusable workflow. `uses:` must be a local `./.github/workflows/*.yml` path or `owner/repo/.github/workflows/file.yml@ref`. The calling job can't also set `steps`, `runs-on`, `container`, or `services`.",
+            bad_example: r#"on: push
+jobs:
+  call:
+    uses: octo-org/repo/.github/workflows/ci.yml
+"#,
+            good_example: r#"on: push
+jobs:
+  call:
+    uses: octo-org/repo/.github/workflows/ci.yml@v1
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -66,6 +92,8 @@ impl ValidationRule for ReusableWorkflowCallRule {
                                                         end: uses_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -89,6 +117,8 @@ impl ValidationRule for ReusableWorkflowCallRule {
                                                     end: uses_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         } else {
                                             // Check if path is valid
@@ -108,6 +138,8 @@ impl ValidationRule for ReusableWorkflowCallRule {
                                                             end: uses_node.start_byte() + path.len().min(uses_node.end_byte() - uses_node.start_byte()),
                                                         },
                                                         rule_id: String::new(),
+                                                        category: RuleCategory::default(),
+                                                        related: Vec::new(),
                                                     });
                                                 } else {
                                                     // Validate that with: and secrets: fields are properly structured
@@ -135,6 +167,8 @@ impl ValidationRule for ReusableWorkflowCallRule {
                                                                     end: with_node.end_byte(),
                                                                 },
                                                                 rule_id: String::new(),
+                                                                category: RuleCategory::default(),
+                                                                related: Vec::new(),
                                                             });
                                                         }
                                                     }
@@ -155,15 +189,14 @@ impl ValidationRule for ReusableWorkflowCallRule {
                                                                     end: secrets_node.end_byte(),
                                                                 },
                                                                 rule_id: String::new(),
+                                                                category: RuleCategory::default(),
+                                                                related: Vec::new(),
                                                             });
                                                         }
                                                     }
 
-                                                    // Note: Full validation of required inputs/secrets would require:
-                                                    // 1. Parsing the referenced workflow file
-                                                    // 2. Extracting workflow_call inputs/secrets definitions
-                                                    // 3. Checking if required inputs/secrets are provided
-                                                    // This is beyond the scope of static analysis without filesystem access
+                                                    // `with:` against the called workflow's inputs needs
+                                                    // the other file: see `check_called_workflows`
                                                 }
                                             }
                                         }
@@ -183,7 +216,222 @@ impl ValidationRule for ReusableWorkflowCallRule {
         }
 
         process_jobs(jobs_node, source, &mut diagnostics);
+        check_incompatible_keys(jobs_node, source, &mut diagnostics);
 
         diagnostics
     }
 }
+
+/// Keys a job calling a reusable workflow can't set, since the called
+/// workflow's own jobs pick their runners and run the steps.
+const INCOMPATIBLE_KEYS: [&str; 4] = ["steps", "runs-on", "container", "services"];
+
+/// Error on each key in [`INCOMPATIBLE_KEYS`] set on a job with `uses:`.
+///
+/// `RunsOnRequiredRule` skips `uses:` jobs, so a call without `runs-on`
+/// is only reported here if it sets one of these keys.
+fn check_incompatible_keys(jobs_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        let pairs = utils::mapping_pairs(job_value);
+        let Some(uses_key) = pairs
+            .iter()
+            .filter_map(|pair| pair.child(0))
+            .find(|key| utils::clean_key(*key, source) == "uses")
+        else {
+            continue;
+        };
+        for key in pairs.iter().filter_map(|pair| pair.child(0)) {
+            let key_name = utils::clean_key(key, source);
+            if !INCOMPATIBLE_KEYS.contains(&key_name) {
+                continue;
+            }
+            diagnostics.push(Diagnostic {
+                message: format!(
+                    "Job '{}' calls a reusable workflow with 'uses', so it can't also set '{}'. Set it on the jobs of the called workflow instead.",
+                    job_name, key_name
+                ),
+                severity: Severity::Error,
+                span: Span {
+                    start: key.start_byte(),
+                    end: key.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: vec![RelatedLocation {
+                    span: Span {
+                        start: uses_key.start_byte(),
+                        end: uses_key.end_byte(),
+                    },
+                    message: "Reusable workflow called here".to_string(),
+                }],
+            });
+        }
+    }
+}
+
+/// Check `with:` and `secrets:` on calls to local reusable workflows against
+/// what the called workflow declares under `on.workflow_call`.
+///
+/// Not part of `ReusableWorkflowCallRule::validate`, since rules only see one
+/// file; `TrussEngine::analyze_with_resolver` runs it. `resolve` gets the
+/// `uses:` path as written (e.g. `./.github/workflows/build.yml`) and returns
+/// the file's contents, or `None` to skip the call. Names are compared
+/// case-insensitively, as GitHub does. Unknown inputs are errors, since
+/// GitHub rejects the call, while unknown secrets are only warnings: they
+/// are ignored at run time. `secrets: inherit` passes every secret.
+pub(crate) fn check_called_workflows(
+    tree: &Tree,
+    source: &str,
+    parser: &mut YamlParser,
+    resolve: &dyn Fn(&str) -> Option<String>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let jobs_node = match utils::get_jobs_node(tree, source) {
+        Some(n) => n,
+        None => return diagnostics,
+    };
+
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        let uses_node = match utils::find_value_for_key(job_value, source, "uses") {
+            Some(v) => utils::unwrap_node(v),
+            None => continue,
+        };
+        let uses = utils::scalar_value(uses_node, source);
+        if !uses.starts_with("./") || uses.contains("${{") {
+            continue;
+        }
+        let called_source = match resolve(uses) {
+            Some(content) => content,
+            None => continue,
+        };
+        let called_tree = match parser.parse(&called_source) {
+            Ok(tree) => tree,
+            Err(_) => continue,
+        };
+
+        let mut diagnostic = |message: String, severity: Severity, node: Node| {
+            diagnostics.push(Diagnostic {
+                message,
+                severity,
+                span: Span {
+                    start: node.start_byte(),
+                    end: node.end_byte(),
+                },
+                rule_id: "reusable_workflow_call".to_string(),
+                category: ReusableWorkflowCallRule.category(),
+                related: Vec::new(),
+            });
+        };
+
+        let Some(workflow_call) = called_workflow_call(&called_tree, &called_source) else {
+            diagnostic(
+                format!(
+                    "Job '{}' calls '{}', which has no workflow_call trigger and can't be used as a reusable workflow",
+                    job_name, uses
+                ),
+                Severity::Error,
+                uses_node,
+            );
+            continue;
+        };
+
+        let with = utils::find_value_for_key(job_value, source, "with");
+        let secrets = utils::direct_value(job_value, source, "secrets");
+        let inherits_secrets = secrets.is_some_and(|s| utils::scalar_value(s, source) == "inherit");
+        let mut checks = vec![("input", with, Severity::Error)];
+        if !inherits_secrets {
+            checks.push(("secret", secrets, Severity::Warning));
+        }
+
+        for (kind, passed_node, unknown_severity) in checks {
+            let declared = declared_interface(workflow_call, &called_source, &format!("{}s", kind));
+            let passed: Vec<(&str, Node)> = passed_node
+                .map(|node| utils::mapping_pairs(utils::unwrap_node(node)))
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|pair| {
+                    let key = pair.child(0)?;
+                    Some((utils::clean_key(key, source), key))
+                })
+                .collect();
+
+            for (name, required) in &declared {
+                if *required && !passed.iter().any(|(p, _)| p.eq_ignore_ascii_case(name)) {
+                    diagnostic(
+                        format!(
+                            "Job '{}' calls '{}' without its required {} '{}'",
+                            job_name, uses, kind, name
+                        ),
+                        Severity::Error,
+                        uses_node,
+                    );
+                }
+            }
+            for (name, key) in &passed {
+                if !declared.iter().any(|(d, _)| d.eq_ignore_ascii_case(name)) {
+                    let mut names: Vec<&str> = declared.iter().map(|(d, _)| d.as_str()).collect();
+                    names.sort_unstable();
+                    diagnostic(
+                        format!(
+                            "Job '{}' passes {} '{}', but '{}' does not declare it. Declared {}s: {}",
+                            job_name,
+                            kind,
+                            name,
+                            uses,
+                            kind,
+                            if names.is_empty() {
+                                "none".to_string()
+                            } else {
+                                names.join(", ")
+                            }
+                        ),
+                        unknown_severity,
+                        *key,
+                    );
+                }
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// The `workflow_call` trigger node of a reusable workflow, or `None` if it
+/// has none.
+fn called_workflow_call<'a>(tree: &'a Tree, source: &'a str) -> Option<Node<'a>> {
+    utils::collect_triggers(tree, source)
+        .into_iter()
+        .find(|(event, _)| *event == "workflow_call")
+        .map(|(_, trigger)| trigger)
+}
+
+/// The `inputs` or `secrets` a `workflow_call` trigger declares, as
+/// `(name, required)` in declaration order.
+///
+/// An input with a `default` never has to be passed, even if marked `required`.
+fn declared_interface(trigger: Node, source: &str, section: &str) -> Vec<(String, bool)> {
+    // Only the mapping form (`workflow_call:` with a value) can declare anything
+    let declarations = trigger
+        .parent()
+        .filter(|pair| matches!(pair.kind(), "block_mapping_pair" | "flow_pair"))
+        .and_then(utils::get_pair_value)
+        .and_then(|call| utils::find_value_for_key(utils::unwrap_node(call), source, section));
+    let Some(declarations) = declarations else {
+        return Vec::new();
+    };
+
+    utils::mapping_pairs(utils::unwrap_node(declarations))
+        .into_iter()
+        .filter_map(|pair| {
+            let name = utils::clean_key(pair.child(0)?, source).to_string();
+            let definition = utils::get_pair_value(pair).map(utils::unwrap_node);
+            let required = definition.is_some_and(|d| {
+                utils::find_value_for_key(d, source, "required")
+                    .is_some_and(|r| utils::scalar_value(r, source).eq_ignore_ascii_case("true"))
+                    && utils::find_value_for_key(d, source, "default").is_none()
+            });
+            Some((name, required))
+        })
+        .collect()
+}
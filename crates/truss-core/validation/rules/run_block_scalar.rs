@@ -0,0 +1,126 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
+
+/// Detects `run: >` scripts where folding joins separate commands onto one line.
+pub struct RunBlockScalarRule;
+
+impl ValidationRule for RunBlockScalarRule {
+    fn name(&self) -> &str {
+        "run_block_scalar"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A folded block scalar (`run: >`) joins lines with spaces, so a multi-line script becomes a single command. Use a literal block scalar (`run: |`) to keep one command per line.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: >
+          npm ci
+          npm test
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: |
+          npm ci
+          npm test
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for job in index.jobs() {
+            for &step in &job.steps {
+                if let Some(run_node) = utils::find_value_for_key(step, source, "run") {
+                    check_run_scalar(utils::unwrap_node(run_node), source, &mut diagnostics);
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn check_run_scalar(run_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if run_node.kind() != "block_scalar" {
+        return;
+    }
+    let text = utils::node_text(run_node, source);
+    if !text.starts_with('>') {
+        return;
+    }
+
+    let (first, second) = match find_joined_commands(text) {
+        Some(pair) => pair,
+        None => return,
+    };
+
+    // Point at the `>` header rather than the whole script
+    let header = run_node.child(0).unwrap_or(run_node);
+    diagnostics.push(Diagnostic {
+        message: format!(
+            "run: uses a folded block scalar ('>'), so '{}' and '{}' are joined into one command. Use 'run: |' to keep each line as a separate command.",
+            first, second
+        ),
+        severity: Severity::Warning,
+        span: Span {
+            start: header.start_byte(),
+            end: header.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    });
+}
+
+/// Find two consecutive lines that folding joins although they look like separate commands.
+///
+/// Folding only joins adjacent lines at the base indentation. A line that ends
+/// with `\`, `&&`, `||` or `|`, or a following line that starts with `-`, reads
+/// as one command wrapped for length, which is what `>` is meant for.
+fn find_joined_commands(text: &str) -> Option<(&str, &str)> {
+    let lines: Vec<&str> = text.lines().skip(1).collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()?;
+
+    for pair in lines.windows(2) {
+        let (prev, next) = (pair[0], pair[1]);
+        let folds = [prev, next]
+            .iter()
+            .all(|line| !line.trim().is_empty() && line.len() - line.trim_start().len() == indent);
+        if !folds {
+            continue;
+        }
+        let (prev, next) = (prev.trim(), next.trim());
+        let continues = ["\\", "&&", "||", "|"].iter().any(|op| prev.ends_with(op));
+        if !continues && !next.starts_with('-') {
+            return Some((prev, next));
+        }
+    }
+    None
+}
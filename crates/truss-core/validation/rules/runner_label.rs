@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates runs-on labels are valid GitHub-hosted runners or self-hosted runner groups.
@@ -11,10 +11,39 @@ impl ValidationRule for RunnerLabelRule {
         "runner_label"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`runs-on` labels must not be empty. Custom and self-hosted labels are accepted because they can't be checked statically. The object form, `runs-on: { group: ..., labels: ... }`, takes only `group` and `labels`, and needs at least one of them.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ""
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -62,6 +91,20 @@ impl ValidationRule for RunnerLabelRule {
                                         return;
                                     }
 
+                                    // runs-on can also pick a runner group: runs-on: { group: ..., labels: ... }
+                                    if unwrapped.kind() == "block_mapping"
+                                        || unwrapped.kind() == "flow_mapping"
+                                    {
+                                        check_runner_object(
+                                            &job_name,
+                                            runs_on_node,
+                                            unwrapped,
+                                            source,
+                                            diagnostics,
+                                        );
+                                        return;
+                                    }
+
                                     let runs_on_text = utils::node_text(runs_on_node, source);
                                     let runs_on_cleaned = runs_on_text.trim_matches(|c: char| {
                                         c == '"' || c == '\'' || c.is_whitespace()
@@ -118,6 +161,8 @@ impl ValidationRule for RunnerLabelRule {
                                                     end: runs_on_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                         // Don't warn on unknown labels — they are likely
@@ -142,3 +187,109 @@ impl ValidationRule for RunnerLabelRule {
         diagnostics
     }
 }
+
+/// Check the object form of `runs-on`: only `group` and `labels` are allowed,
+/// at least one of them must be set, `group` can't be empty, and `labels` is
+/// a label or a list of them.
+fn check_runner_object(
+    job_name: &str,
+    runs_on_node: Node,
+    object: Node,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut has_target = false;
+
+    for pair in utils::mapping_pairs(object) {
+        let Some(key_node) = pair.child(0) else {
+            continue;
+        };
+        let key = utils::clean_key(key_node, source);
+        let value = utils::get_pair_value(pair).map(|v| utils::resolve_alias(v, source));
+
+        match key {
+            "group" => {
+                has_target = true;
+                let is_empty = match value {
+                    Some(value) => {
+                        !matches!(
+                            value.kind(),
+                            "block_mapping" | "flow_mapping" | "block_sequence" | "flow_sequence"
+                        ) && utils::scalar_value(value, source).is_empty()
+                    }
+                    None => true,
+                };
+                if is_empty {
+                    let node = value.unwrap_or(key_node);
+                    diagnostics.push(Diagnostic {
+                        message: format!("Job '{}' has empty runs-on group.", job_name),
+                        severity: Severity::Error,
+                        span: Span {
+                            start: node.start_byte(),
+                            end: node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+            "labels" => {
+                has_target = true;
+                let is_label_list = value.is_some_and(|value| match value.kind() {
+                    "block_sequence" | "flow_sequence" => true,
+                    "block_mapping" | "flow_mapping" => false,
+                    _ => !utils::scalar_value(value, source).is_empty(),
+                });
+                if !is_label_list {
+                    let node = value.unwrap_or(key_node);
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Job '{}' has invalid 'runs-on.labels'. Use a label or a list of labels, e.g. 'labels: [self-hosted, linux]'.",
+                            job_name
+                        ),
+                        severity: Severity::Error,
+                        span: Span {
+                            start: node.start_byte(),
+                            end: node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+            _ => diagnostics.push(Diagnostic {
+                message: format!(
+                    "Job '{}' has unknown key '{}' in 'runs-on'. Only 'group' and 'labels' are allowed.",
+                    job_name, key
+                ),
+                severity: Severity::Error,
+                span: Span {
+                    start: key_node.start_byte(),
+                    end: key_node.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            }),
+        }
+    }
+
+    if !has_target {
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "Job '{}' has a 'runs-on' object without 'group' or 'labels'. Set at least one to choose a runner.",
+                job_name
+            ),
+            severity: Severity::Error,
+            span: Span {
+                start: runs_on_node.start_byte(),
+                end: runs_on_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates that `runs-on` is required for all jobs.
@@ -11,10 +11,38 @@ impl ValidationRule for RunsOnRequiredRule {
         "runs_on_required"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Every job that runs steps needs a non-empty `runs-on`. Jobs that call a reusable workflow with `uses:` are exempt.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -61,6 +89,8 @@ impl ValidationRule for RunsOnRequiredRule {
                                                     end: value_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -77,6 +107,8 @@ impl ValidationRule for RunsOnRequiredRule {
                                                 end: job_value.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                     }
                                 }
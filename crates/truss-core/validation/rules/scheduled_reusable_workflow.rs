@@ -0,0 +1,76 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::Tree;
+
+/// Flags reusable (`workflow_call`) workflows that also trigger on `schedule`.
+pub struct ScheduledReusableWorkflowRule;
+
+impl ValidationRule for ScheduledReusableWorkflowRule {
+    fn name(&self) -> &str {
+        "scheduled_reusable_workflow"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A reusable (`workflow_call`) workflow that also runs on `schedule` is usually doing double duty; scheduled runs get none of the caller's inputs or secrets.",
+            bad_example: r#"on:
+  workflow_call:
+  schedule:
+    - cron: '0 0 * * *'
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+            good_example: r#"on:
+  workflow_call:
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let triggers = utils::collect_triggers(tree, source);
+
+        if !triggers.iter().any(|(event, _)| *event == "workflow_call") {
+            return diagnostics;
+        }
+
+        // Dual-purpose files are occasionally intentional, so this stays informational.
+        for (event, node) in &triggers {
+            if *event == "schedule" {
+                diagnostics.push(Diagnostic {
+                    message: "Reusable workflow (workflow_call) is also triggered by 'schedule'. \
+                              Consider moving the schedule into a separate caller workflow so this file has a single purpose."
+                        .to_string(),
+                    severity: Severity::Info,
+                    span: Span {
+                        start: node.start_byte(),
+                        end: node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
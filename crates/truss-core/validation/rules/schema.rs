@@ -1,5 +1,5 @@
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::Tree;
 
 /// Validates that GitHub Actions workflows have required top-level fields.
@@ -10,6 +10,27 @@ impl ValidationRule for GitHubActionsSchemaRule {
         "github_actions_schema"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks the top-level shape of a workflow. A file with `jobs:` but no `on:` never runs.",
+            bad_example: r#"name: CI
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+            good_example: r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -51,6 +72,8 @@ impl ValidationRule for GitHubActionsSchemaRule {
                     end: source.len().min(100),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
 
@@ -1,11 +1,14 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// GitHub event properties that can be controlled by external users and are
 /// therefore untrusted inputs. Using these directly in `run:` scripts via
 /// `${{ }}` creates a script injection vulnerability.
+///
+/// `*` stands for an array index, so `github.event.commits.*.message` also
+/// matches `github.event.commits[0].message`.
 const UNTRUSTED_INPUTS: &[&str] = &[
     "github.event.issue.title",
     "github.event.issue.body",
@@ -13,6 +16,7 @@ const UNTRUSTED_INPUTS: &[&str] = &[
     "github.event.pull_request.body",
     "github.event.pull_request.head.ref",
     "github.event.pull_request.head.label",
+    "github.event.pull_request.head.repo.default_branch",
     "github.event.comment.body",
     "github.event.review.body",
     "github.event.review_comment.body",
@@ -25,11 +29,21 @@ const UNTRUSTED_INPUTS: &[&str] = &[
     "github.event.head_commit.message",
     "github.event.head_commit.author.name",
     "github.event.head_commit.author.email",
+    "github.event.workflow_run.head_branch",
+    "github.event.workflow_run.head_commit.message",
+    "github.event.workflow_run.head_commit.author.name",
+    "github.event.workflow_run.head_commit.author.email",
+    "github.event.workflow_run.pull_requests.*.head.ref",
     "github.head_ref",
 ];
 
+/// `with:` inputs of Docker container actions that end up on the container's
+/// command line, making them injection vectors just like `run:` scripts.
+const CONTAINER_COMMAND_INPUTS: &[&str] = &["args", "entrypoint"];
+
 /// Detects potential script injection vulnerabilities where untrusted inputs
-/// are used directly in `run:` scripts via `${{ }}` expressions.
+/// are used directly in `run:` scripts or container action `with.args` /
+/// `with.entrypoint` via `${{ }}` expressions.
 pub struct ScriptInjectionRule;
 
 impl ValidationRule for ScriptInjectionRule {
@@ -37,10 +51,49 @@ impl ValidationRule for ScriptInjectionRule {
         "script_injection"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags untrusted event data, such as PR titles or issue bodies, interpolated directly into `run:` scripts, where an attacker can inject shell commands. Pass the value through `env:` instead.",
+            bad_example: r#"on: pull_request
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ github.event.pull_request.title }}"
+"#,
+            good_example: r#"on: pull_request
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "$TITLE"
+        env:
+          TITLE: ${{ github.event.pull_request.title }}
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -61,11 +114,16 @@ fn find_run_steps(node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
                         let run_text = utils::node_text(value_node, source);
                         check_script_injection(
                             run_text,
+                            "a 'run' script",
                             value_node.start_byte(),
                             value_node.end_byte(),
                             diagnostics,
                         );
                     }
+                } else if key_cleaned == "with" {
+                    if let Some(value_node) = utils::get_pair_value(node) {
+                        check_container_inputs(utils::unwrap_node(value_node), source, diagnostics);
+                    }
                 }
                 if let Some(value_node) = utils::get_pair_value(node) {
                     find_run_steps(value_node, source, diagnostics);
@@ -81,24 +139,49 @@ fn find_run_steps(node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
     }
 }
 
+/// Check the `args` and `entrypoint` inputs of a step's `with:` mapping.
+fn check_container_inputs(with_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for pair in utils::mapping_pairs(with_node) {
+        let key = match pair.child(0) {
+            Some(key_node) => utils::clean_key(key_node, source),
+            None => continue,
+        };
+        if !CONTAINER_COMMAND_INPUTS.contains(&key) {
+            continue;
+        }
+        if let Some(value_node) = utils::get_pair_value(pair) {
+            check_script_injection(
+                utils::node_text(value_node, source),
+                &format!("'with.{}'", key),
+                value_node.start_byte(),
+                value_node.end_byte(),
+                diagnostics,
+            );
+        }
+    }
+}
+
 fn check_script_injection(
-    run_text: &str,
+    text: &str,
+    location: &str,
     start_byte: usize,
     end_byte: usize,
     diagnostics: &mut Vec<Diagnostic>,
 ) {
-    let expressions = utils::find_expressions(run_text);
+    let expressions = utils::find_expressions(text);
 
     for expr in &expressions {
-        let inner = expr.inner.trim();
+        let raw = expr.inner.trim();
+        let normalized = normalize_indices(raw);
+        let inner = normalized.as_str();
         for untrusted in UNTRUSTED_INPUTS {
             // Check for exact match or property access on the untrusted input
             if inner == *untrusted || inner.starts_with(&format!("{}.", untrusted)) {
                 diagnostics.push(Diagnostic {
                     message: format!(
-                        "Potential script injection: untrusted input '{}' is used directly in a 'run' script. \
+                        "Potential script injection: untrusted input '{}' is used directly in {}. \
                          Use an environment variable instead: env: MY_VAR: ${{{{ {} }}}}",
-                        untrusted, inner
+                        untrusted, location, raw
                     ),
                     severity: Severity::Warning,
                     span: Span {
@@ -106,6 +189,8 @@ fn check_script_injection(
                         end: end_byte,
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 break;
             }
@@ -124,9 +209,31 @@ fn check_script_injection(
                         end: end_byte,
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 break;
             }
         }
     }
 }
+
+/// Rewrite index access (`commits[0]`, `commits['x']`) as `.*` so it lines up
+/// with the wildcard entries in `UNTRUSTED_INPUTS`.
+fn normalize_indices(expr: &str) -> String {
+    let mut out = String::with_capacity(expr.len());
+    let mut chars = expr.chars();
+    while let Some(c) = chars.next() {
+        if c == '[' {
+            for c in chars.by_ref() {
+                if c == ']' {
+                    break;
+                }
+            }
+            out.push_str(".*");
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
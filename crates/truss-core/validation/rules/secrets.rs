@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::Tree;
 
 /// Validates secrets.* references in GitHub Actions workflows.
@@ -11,11 +11,51 @@ impl ValidationRule for SecretsValidationRule {
         "secrets_validation"
     }
 
-    fn validate(&self, _tree: &Tree, source: &str) -> Vec<Diagnostic> {
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Catches malformed `secrets` references, such as the singular `secret.` or a missing secret name.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TOKEN: ${{ secret.DEPLOY_TOKEN }}
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TOKEN: ${{ secrets.DEPLOY_TOKEN }}
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
+        // Outside a reusable workflow every secret must be a repository,
+        // environment, or organization secret, so GitHub's naming rules apply.
+        let repository_secrets = !utils::collect_triggers(tree, source)
+            .iter()
+            .any(|(event, _)| *event == "workflow_call");
+
         for expr in utils::find_expressions(source) {
-            self.check_secret_references(expr.inner, expr.start, expr.end, &mut diagnostics);
+            self.check_secret_references(
+                expr.inner,
+                expr.start,
+                expr.end,
+                repository_secrets,
+                &mut diagnostics,
+            );
         }
 
         diagnostics
@@ -28,6 +68,7 @@ impl SecretsValidationRule {
         expr: &str,
         expr_start: usize,
         _expr_end: usize,
+        repository_secrets: bool,
         diagnostics: &mut Vec<Diagnostic>,
     ) {
         // Look for secret references in the expression
@@ -78,6 +119,8 @@ impl SecretsValidationRule {
                         end: expr_start + 3 + secret_ref_end,
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
 
                 search_pos = secret_ref_end;
@@ -134,6 +177,8 @@ impl SecretsValidationRule {
                             end: expr_start + 3 + actual_pos + 7 + identifier_end,
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
 
                     search_pos = actual_pos + 7 + identifier_end;
@@ -173,6 +218,26 @@ impl SecretsValidationRule {
                                     end: expr_start + 3 + actual_pos + 7 + 1 + name_end,
                                 },
                                 rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
+                            });
+                        } else if let Some(problem) = repository_secrets
+                            .then(|| repository_secret_name_problem(secret_name))
+                            .flatten()
+                        {
+                            diagnostics.push(Diagnostic {
+                                message: format!(
+                                    "Secret '{}' can never match a configured secret: {}.",
+                                    secret_name, problem
+                                ),
+                                severity: Severity::Warning,
+                                span: Span {
+                                    start: expr_start + 3 + actual_pos + 7 + 1,
+                                    end: expr_start + 3 + actual_pos + 7 + 1 + name_end,
+                                },
+                                rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
                             });
                         }
 
@@ -206,3 +271,21 @@ fn is_valid_secret_name_format(secret_name: &str) -> bool {
         .chars()
         .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
 }
+
+/// Check a name against GitHub's rules for repository, environment, and
+/// organization secrets, returning why it can't exist.
+///
+/// Secret names are case-insensitive, so lowercase references are fine.
+fn repository_secret_name_problem(secret_name: &str) -> Option<&'static str> {
+    if !secret_name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Some("secret names may only contain letters, digits, and underscores");
+    }
+    let upper = secret_name.to_ascii_uppercase();
+    if upper.starts_with("GITHUB_") && upper != "GITHUB_TOKEN" {
+        return Some("the 'GITHUB_' prefix is reserved and only GITHUB_TOKEN is provided");
+    }
+    None
+}
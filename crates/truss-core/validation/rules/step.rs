@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates step structure.
@@ -11,10 +11,40 @@ impl ValidationRule for StepValidationRule {
         "step"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Every step needs exactly one of `uses:` or `run:`. A step with neither does nothing; a step with both is rejected.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Build
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Build
+        run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -34,94 +64,16 @@ impl ValidationRule for StepValidationRule {
                                     }
                                     return;
                                 }
-                                fn validate_step(
-                                    step_node: Node,
-                                    source: &str,
-                                    diagnostics: &mut Vec<Diagnostic>,
-                                ) {
-                                    let step_to_check = utils::unwrap_node(step_node);
-
-                                    let mut has_uses = false;
-                                    let mut has_run = false;
-
-                                    fn check_step_keys(
-                                        node: Node,
-                                        source: &str,
-                                        has_uses: &mut bool,
-                                        has_run: &mut bool,
-                                    ) {
-                                        match node.kind() {
-                                            "block_mapping_pair" | "flow_pair" => {
-                                                if let Some(key_node) = node.child(0) {
-                                                    let key_text =
-                                                        utils::node_text(key_node, source);
-                                                    let key_cleaned = key_text
-                                                        .trim_matches(|c: char| {
-                                                            c == '"'
-                                                                || c == '\''
-                                                                || c.is_whitespace()
-                                                        })
-                                                        .trim_end_matches(':');
-                                                    if key_cleaned == "uses" {
-                                                        *has_uses = true;
-                                                    } else if key_cleaned == "run" {
-                                                        *has_run = true;
-                                                    }
-                                                }
-                                            }
-                                            _ => {
-                                                let mut cursor = node.walk();
-                                                for child in node.children(&mut cursor) {
-                                                    check_step_keys(
-                                                        child, source, has_uses, has_run,
-                                                    );
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    check_step_keys(
-                                        step_to_check,
-                                        source,
-                                        &mut has_uses,
-                                        &mut has_run,
-                                    );
-
-                                    if !has_uses && !has_run {
-                                        diagnostics.push(Diagnostic {
-                                            message: "Step must have either 'uses' or 'run' field"
-                                                .to_string(),
-                                            severity: Severity::Error,
-                                            span: Span {
-                                                start: step_node.start_byte(),
-                                                end: step_node.end_byte(),
-                                            },
-                                            rule_id: String::new(),
-                                        });
-                                    } else if has_uses && has_run {
-                                        diagnostics.push(Diagnostic {
-                                            message: "Step cannot have both 'uses' and 'run' fields. Each step must use one or the other."
-                                                .to_string(),
-                                            severity: Severity::Error,
-                                            span: Span {
-                                                start: step_node.start_byte(),
-                                                end: step_node.end_byte(),
-                                            },
-                                            rule_id: String::new(),
-                                        });
-                                    }
-                                }
-
                                 // Handle block_sequence - steps are children of block_sequence
                                 if steps_value.kind() == "block_sequence" {
                                     let mut cursor = steps_value.walk();
                                     for step_node in steps_value.children(&mut cursor) {
                                         // Each step in a block_sequence is a block_node
                                         if step_node.kind() == "block_node" {
-                                            validate_step(step_node, source, diagnostics);
+                                            check_step_kind(step_node, source, diagnostics);
                                         } else {
                                             // Also check if it's directly a block_mapping
-                                            validate_step(step_node, source, diagnostics);
+                                            check_step_kind(step_node, source, diagnostics);
                                         }
                                     }
                                 } else if steps_value.kind() == "flow_sequence" {
@@ -131,7 +83,7 @@ impl ValidationRule for StepValidationRule {
                                         if step_node.kind() == "flow_node"
                                             || step_node.kind() == "block_node"
                                         {
-                                            validate_step(step_node, source, diagnostics);
+                                            check_step_kind(step_node, source, diagnostics);
                                         }
                                     }
                                 } else {
@@ -140,7 +92,7 @@ impl ValidationRule for StepValidationRule {
                                         if step_node.kind() == "block_node"
                                             || step_node.kind() == "flow_node"
                                         {
-                                            validate_step(step_node, source, diagnostics);
+                                            check_step_kind(step_node, source, diagnostics);
                                         }
                                     }
                                 }
@@ -171,3 +123,84 @@ impl ValidationRule for StepValidationRule {
         diagnostics
     }
 }
+
+/// Check that a step has exactly one of `uses:` and `run:`.
+///
+/// Shared with `CompositeActionRule`, whose `runs.steps` follow the same rule.
+/// A step with both keys is reported at whichever of the two comes second.
+pub(crate) fn check_step_kind(step_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let mut uses_key = None;
+    let mut run_key = None;
+
+    fn find_keys<'a>(
+        node: Node<'a>,
+        source: &str,
+        uses_key: &mut Option<Node<'a>>,
+        run_key: &mut Option<Node<'a>>,
+    ) {
+        match node.kind() {
+            "block_mapping_pair" | "flow_pair" => {
+                if let Some(key_node) = node.child(0) {
+                    match utils::clean_key(key_node, source) {
+                        "uses" => *uses_key = Some(key_node),
+                        "run" => *run_key = Some(key_node),
+                        _ => {}
+                    }
+                }
+            }
+            "alias" => {
+                let target = utils::resolve_alias(node, source);
+                if target != node {
+                    find_keys(target, source, uses_key, run_key);
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    find_keys(child, source, uses_key, run_key);
+                }
+            }
+        }
+    }
+
+    find_keys(
+        utils::unwrap_node(step_node),
+        source,
+        &mut uses_key,
+        &mut run_key,
+    );
+
+    match (uses_key, run_key) {
+        (None, None) => diagnostics.push(Diagnostic {
+            message: "Step must have either 'uses' or 'run' field".to_string(),
+            severity: Severity::Error,
+            span: Span {
+                start: step_node.start_byte(),
+                end: step_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        }),
+        (Some(uses), Some(run)) => {
+            let second = if uses.start_byte() > run.start_byte() {
+                uses
+            } else {
+                run
+            };
+            diagnostics.push(Diagnostic {
+                message: "Step cannot have both 'uses' and 'run' fields. Each step must use one or the other."
+                    .to_string(),
+                severity: Severity::Error,
+                span: Span {
+                    start: second.start_byte(),
+                    end: second.end_byte(),
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            });
+        }
+        _ => {}
+    }
+}
@@ -1,9 +1,9 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
-/// Validates continue-on-error is a boolean.
+/// Validates `continue-on-error` on steps and jobs.
 pub struct StepContinueOnErrorRule;
 
 impl ValidationRule for StepContinueOnErrorRule {
@@ -11,10 +11,41 @@ impl ValidationRule for StepContinueOnErrorRule {
         "step_continue_on_error"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`continue-on-error` must be a boolean or an expression. A quoted `\"true\"` is a string, not a boolean. A job with `continue-on-error: true` always reports success, so jobs that need it run and status checks pass even when it fails.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make lint
+        continue-on-error: "true"
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make lint
+        continue-on-error: true
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -133,73 +164,87 @@ impl ValidationRule for StepContinueOnErrorRule {
                     utils::find_value_for_key(step_to_check, source, "continue-on-error");
 
                 if let Some(continue_node) = continue_on_error_value {
-                    let continue_text = utils::node_text(continue_node, source);
-                    let continue_cleaned = continue_text
-                        .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-
-                    // Check if it's an expression
-                    if continue_cleaned.starts_with("${{") {
-                        // Expressions are valid
-                        return;
-                    }
-
-                    // Check if it's a string (quoted) - match the exact pattern from job_strategy.rs and timeout.rs
-                    // Check the original text before any processing
-                    if continue_text.trim().starts_with('"')
-                        || continue_text.trim().starts_with('\'')
-                    {
-                        diagnostics.push(Diagnostic {
-                            message: format!(
-                                "Step has invalid continue-on-error: '{}'. continue-on-error must be a boolean (true or false), not a string.",
-                                continue_cleaned
-                            ),
-                            severity: Severity::Error,
-                            span: Span {
-                                start: continue_node.start_byte(),
-                                end: continue_node.end_byte(),
-                            },
-                            rule_id: String::new(),
-                        });
-                    } else {
-                        // Check if it's a boolean
-                        let is_bool = continue_cleaned == "true" || continue_cleaned == "false";
-                        if !is_bool {
-                            // Check if it's a number
-                            if continue_cleaned.parse::<f64>().is_ok() {
-                                diagnostics.push(Diagnostic {
-                                    message: format!(
-                                        "Step has invalid continue-on-error: '{}'. continue-on-error must be a boolean (true or false), not a number.",
-                                        continue_cleaned
-                                    ),
-                                    severity: Severity::Error,
-                                    span: Span {
-                                        start: continue_node.start_byte(),
-                                        end: continue_node.end_byte(),
-                                    },
-                                    rule_id: String::new(),
-                                });
-                            } else {
-                                diagnostics.push(Diagnostic {
-                                    message: format!(
-                                        "Step has invalid continue-on-error: '{}'. continue-on-error must be a boolean (true or false).",
-                                        continue_cleaned
-                                    ),
-                                    severity: Severity::Error,
-                                    span: Span {
-                                        start: continue_node.start_byte(),
-                                        end: continue_node.end_byte(),
-                                    },
-                                    rule_id: String::new(),
-                                });
-                            }
-                        }
-                    }
+                    diagnostics.extend(check_value("Step", continue_node, source));
                 }
             }
         }
 
         find_steps(jobs_node, source, &mut diagnostics);
 
+        for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+            for pair in utils::mapping_pairs(job_value) {
+                let (key_node, value_node) = match (pair.child(0), utils::get_pair_value(pair)) {
+                    (Some(k), Some(v)) => (k, v),
+                    _ => continue,
+                };
+                if utils::clean_key(key_node, source) != "continue-on-error" {
+                    continue;
+                }
+
+                let owner = format!("Job '{}'", job_name);
+                if let Some(invalid) = check_value(&owner, value_node, source) {
+                    diagnostics.push(invalid);
+                } else if utils::node_text(value_node, source).trim() == "true" {
+                    // An expression like `${{ matrix.experimental }}` is the
+                    // usual way to let only some matrix entries fail
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Job '{}' sets continue-on-error: true, so the job always reports success. Jobs that need it still run and status checks pass even when it fails. Use an expression such as '${{{{ matrix.experimental }}}}' to allow only some runs to fail.",
+                            job_name
+                        ),
+                        severity: Severity::Warning,
+                        span: Span {
+                            start: key_node.start_byte(),
+                            end: key_node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+        }
+
         diagnostics
     }
 }
+
+/// Report a `continue-on-error` value that is neither a boolean nor an expression.
+///
+/// `owner` starts the message, e.g. `Step` or `Job 'build'`.
+fn check_value(owner: &str, continue_node: Node, source: &str) -> Option<Diagnostic> {
+    let continue_text = utils::node_text(continue_node, source);
+    let continue_cleaned =
+        continue_text.trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+
+    // Expressions are valid
+    if continue_cleaned.starts_with("${{") {
+        return None;
+    }
+
+    let reason = if continue_text.trim().starts_with('"') || continue_text.trim().starts_with('\'')
+    {
+        ", not a string"
+    } else if continue_cleaned == "true" || continue_cleaned == "false" {
+        return None;
+    } else if continue_cleaned.parse::<f64>().is_ok() {
+        ", not a number"
+    } else {
+        ""
+    };
+
+    Some(Diagnostic {
+        message: format!(
+            "{} has invalid continue-on-error: '{}'. continue-on-error must be a boolean (true or false){}.",
+            owner, continue_cleaned, reason
+        ),
+        severity: Severity::Error,
+        span: Span {
+            start: continue_node.start_byte(),
+            end: continue_node.end_byte(),
+        },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    })
+}
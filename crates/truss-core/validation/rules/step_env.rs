@@ -1,6 +1,7 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::ops::Range;
 use tree_sitter::{Node, Tree};
 
 /// Validates environment variable names and values at step level.
@@ -11,10 +12,46 @@ impl ValidationRule for StepEnvValidationRule {
         "step_env"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `env:` variables. Step variable names must be valid identifiers, and no `env:` block should give a literal value to a variable the runner provides (`GITHUB_*`, `RUNNER_*`), set `CI` to anything but `true`, or replace `HOME` or `PATH`. Passing a value through with an expression is fine. Env names are case-sensitive, so an `env.X` reference that matches a defined variable only in a different case is flagged too.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    env:
+      GITHUB_REPOSITORY: myrepo
+    steps:
+      - run: make
+        env:
+          MY-VAR: value
+          PATH: $PATH:/opt/tools/bin
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        env:
+          MY_VAR: value
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -112,6 +149,8 @@ impl ValidationRule for StepEnvValidationRule {
                                                     end: key_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
 
@@ -137,6 +176,168 @@ impl ValidationRule for StepEnvValidationRule {
 
         find_steps(jobs_node, source, &mut diagnostics);
 
+        // Workflow, job, and step `env:` can all shadow the runner's variables
+        let mut env_blocks: Vec<Node> = index.top_level("env").into_iter().collect();
+        for job in index.jobs() {
+            env_blocks.extend(utils::direct_value(job.value, source, "env"));
+            for step in &job.steps {
+                env_blocks.extend(utils::direct_value(*step, source, "env"));
+            }
+        }
+        // An anchored block shared through aliases is reported once, at the anchor
+        env_blocks.sort_by_key(|env| env.start_byte());
+        env_blocks.dedup();
+        for env in env_blocks {
+            check_shadowed_variables(env, source, &mut diagnostics);
+        }
+        check_reference_casing(index, source, &mut diagnostics);
+        diagnostics.sort_by_key(|d| d.span.start);
+
         diagnostics
     }
 }
+
+/// Warn on literal values for variables the runner already sets.
+///
+/// Expressions are skipped, since passing a value through
+/// (`GITHUB_TOKEN: ${{ secrets.GITHUB_TOKEN }}`) is a common, valid pattern.
+fn check_shadowed_variables(env: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for pair in utils::mapping_pairs(env) {
+        let (key_node, value) = match (pair.child(0), utils::get_pair_value(pair)) {
+            (Some(k), Some(v)) => (k, utils::unwrap_node(v)),
+            _ => continue,
+        };
+        let value = utils::scalar_value(value, source);
+        if value.contains("${{") {
+            continue;
+        }
+
+        let name = utils::clean_key(key_node, source);
+        let message = if utils::RUNNER_PROVIDED_ENV_VARS.contains(&name) {
+            format!(
+                "'{}' is set by the runner and can't be overridden, so the value '{}' is ignored. Use a different name for your own variable.",
+                name, value
+            )
+        } else if name == "CI" && !value.eq_ignore_ascii_case("true") {
+            format!(
+                "'CI: {}' overrides the runner's CI=true, which test runners, package managers, and build tools use to detect CI. Set it only on the step that needs it, if at all.",
+                value
+            )
+        } else if name == "PATH" {
+            "'PATH' in env: replaces the runner's PATH, and env values aren't shell-expanded, so '$PATH' stays literal. Add directories with 'echo \"<dir>\" >> \"$GITHUB_PATH\"' instead.".to_string()
+        } else if name == "HOME" {
+            "'HOME' in env: replaces the runner's home directory, where tools look for caches, config, and credentials. Use a different variable for your own directory.".to_string()
+        } else {
+            continue;
+        };
+
+        diagnostics.push(Diagnostic {
+            message,
+            severity: Severity::Warning,
+            span: Span {
+                start: key_node.start_byte(),
+                end: key_node.end_byte(),
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// Warn on `env.X` references that match a defined variable only when case
+/// is ignored, such as `env.my_var` with `MY_VAR` defined.
+///
+/// Each job sees the workflow's variables plus those of the job and its
+/// steps. Expressions outside any job see only the workflow's.
+fn check_reference_casing(index: &WorkflowIndex, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let workflow_env = env_names(index.top_level("env"), source);
+    let mut scopes: Vec<(Range<usize>, Vec<&str>)> = Vec::new();
+    for job in index.jobs() {
+        let mut names = workflow_env.clone();
+        names.extend(env_names(
+            utils::direct_value(job.value, source, "env"),
+            source,
+        ));
+        for step in &job.steps {
+            names.extend(env_names(utils::direct_value(*step, source, "env"), source));
+        }
+        scopes.push((job.value.start_byte()..job.value.end_byte(), names));
+    }
+    if workflow_env.is_empty() && scopes.iter().all(|(_, names)| names.is_empty()) {
+        return;
+    }
+
+    for expr in utils::find_expressions(source) {
+        let defined = scopes
+            .iter()
+            .find(|(range, _)| range.contains(&expr.start))
+            .map_or(&workflow_env, |(_, names)| names);
+        check_references(expr.inner, expr.start + 3, defined, diagnostics);
+    }
+    // `if:` conditions are expressions even without `${{ }}`
+    for (job, (_, defined)) in index.jobs().iter().zip(&scopes) {
+        let conditions = std::iter::once(job.value)
+            .chain(job.steps.iter().copied())
+            .filter_map(|mapping| utils::direct_value(mapping, source, "if"));
+        for condition in conditions {
+            let condition = utils::unwrap_node(condition);
+            let text = utils::scalar_value(condition, source);
+            if !text.contains("${{") {
+                let offset = condition.start_byte()
+                    + utils::node_text(condition, source).find(text).unwrap_or(0);
+                check_references(text, offset, defined, diagnostics);
+            }
+        }
+    }
+}
+
+/// Report the `env.X` references in expression text at `offset` whose name
+/// is in `defined` only under a different case.
+fn check_references(
+    text: &str,
+    offset: usize,
+    defined: &[&str],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for reference in utils::find_context_references(text, "env") {
+        // `env.NAME`; the `env['NAME']` form is left alone
+        let name = match text[reference.clone()].get(4..) {
+            Some(name) if text.as_bytes()[reference.start + 3] == b'.' => name,
+            _ => continue,
+        };
+        if name.is_empty() || defined.contains(&name) {
+            continue;
+        }
+        let Some(actual) = defined.iter().find(|d| d.eq_ignore_ascii_case(name)) else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            message: format!(
+                "'env.{}' doesn't match any env variable, but '{}' is defined. Env names are case-sensitive, so use 'env.{}'.",
+                name, actual, actual
+            ),
+            severity: Severity::Warning,
+            span: Span {
+                start: offset + reference.start,
+                end: offset + reference.end,
+            },
+            rule_id: String::new(),
+            category: RuleCategory::default(),
+            related: Vec::new(),
+        });
+    }
+}
+
+/// The variable names an `env:` block defines. An expression in place of a
+/// mapping defines none that can be checked.
+fn env_names<'a>(env: Option<Node<'a>>, source: &'a str) -> Vec<&'a str> {
+    env.map(|env| {
+        utils::mapping_pairs(utils::unwrap_node(env))
+            .into_iter()
+            .filter_map(|pair| pair.child(0))
+            .map(|key| utils::clean_key(key, source))
+            .collect()
+    })
+    .unwrap_or_default()
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates if condition expressions in steps.
@@ -11,10 +11,47 @@ impl ValidationRule for StepIfExpressionRule {
         "step_if_expression"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates step `if:` conditions. The expression must be well-formed, the `secrets` context is not available there, comparing `github.event_name` to an event the workflow isn't triggered by is always false, and text outside a `${{ }}` (`${{ success() }} && always()`) turns the condition into a string that is always true.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ secrets.DEPLOY == 'yes' }}
+        run: ./deploy.sh
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - if: ${{ env.DEPLOY == 'yes' }}
+        run: ./deploy.sh
+        env:
+          DEPLOY: ${{ secrets.DEPLOY }}
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -98,6 +135,8 @@ impl ValidationRule for StepIfExpressionRule {
                                 end: if_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
 
@@ -114,6 +153,8 @@ impl ValidationRule for StepIfExpressionRule {
                                 end: if_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     } else if utils::is_potentially_always_false(inner) {
                         diagnostics.push(Diagnostic {
@@ -127,6 +168,63 @@ impl ValidationRule for StepIfExpressionRule {
                                 end: if_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
+                        });
+                    }
+
+                    if let Some(outside) = utils::text_outside_expressions(if_text) {
+                        diagnostics.push(Diagnostic {
+                            message: format!(
+                                "Step 'if' condition has '{}' outside its '${{{{ }}}}', so GitHub reads the whole value as a string and the condition is always true. Put the entire condition inside one '${{{{ }}}}', or drop the braces.",
+                                &if_text[outside.clone()]
+                            ),
+                            severity: Severity::Warning,
+                            span: Span {
+                                start: if_node.start_byte() + outside.start,
+                                end: if_node.start_byte() + outside.end,
+                            },
+                            rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
+                        });
+                    }
+
+                    for reference in utils::find_context_references(if_text, "secrets") {
+                        diagnostics.push(Diagnostic {
+                            message: "The secrets context is not available in if conditions. \
+                                      Expose the secret through 'env' and check the env variable instead."
+                                .to_string(),
+                            severity: Severity::Error,
+                            span: Span {
+                                start: if_node.start_byte() + reference.start,
+                                end: if_node.start_byte() + reference.end,
+                            },
+                            rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
+                        });
+                    }
+
+                    // `${{ }}`-wrapped conditions are checked by ExpressionValidationRule
+                    if let Some(offset) = (!if_text.contains("${{"))
+                        .then(|| utils::find_assignment_operator(if_text))
+                        .flatten()
+                    {
+                        let eq_start = if_node.start_byte() + offset;
+                        diagnostics.push(Diagnostic {
+                            message: format!(
+                                "Step 'if' expression uses '=' instead of '==': '{}'. Expressions are read-only; use '==' to compare values.",
+                                inner
+                            ),
+                            severity: Severity::Error,
+                            span: Span {
+                                start: eq_start,
+                                end: eq_start + 1,
+                            },
+                            rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -135,6 +233,37 @@ impl ValidationRule for StepIfExpressionRule {
 
         find_steps(jobs_node, source, &mut diagnostics);
 
+        let triggers: Vec<&str> = utils::collect_triggers(tree, source)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        for (_, job_value) in utils::collect_jobs(jobs_node, source) {
+            for step in utils::collect_steps(job_value, source) {
+                let Some(if_node) = utils::direct_value(step, source, "if") else {
+                    continue;
+                };
+                let if_text = utils::node_text(if_node, source);
+                for (range, event) in utils::find_untriggered_event_comparisons(if_text, &triggers)
+                {
+                    diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Step 'if' compares github.event_name to '{}', but the workflow isn't triggered by '{}', so the comparison is always false. Add '{}' to 'on:' or fix the event name.",
+                            event, event, event
+                        ),
+                        severity: Severity::Warning,
+                        span: Span {
+                            start: if_node.start_byte() + range.start,
+                            end: if_node.start_byte() + range.end,
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
+                    });
+                }
+            }
+        }
+        diagnostics.sort_by_key(|d| d.span.start);
+
         diagnostics
     }
 }
@@ -1,9 +1,10 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
+use std::collections::HashMap;
 use tree_sitter::{Node, Tree};
 
-/// Validates step name field format.
+/// Validates step name field format and warns on duplicate names within a job.
 pub struct StepNameRule;
 
 impl ValidationRule for StepNameRule {
@@ -11,10 +12,49 @@ impl ValidationRule for StepNameRule {
         "step_name"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "A step `name:` is optional, but when present it should be non-empty and reasonably short so run logs stay readable. Two steps in the same job shouldn't share a name, or their logs can't be told apart.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: ""
+        run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - name: Build
+        run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -100,6 +140,8 @@ impl ValidationRule for StepNameRule {
                                 end: name_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     } else if name_cleaned.len() > 100 {
                         // Warn if very long
@@ -114,6 +156,8 @@ impl ValidationRule for StepNameRule {
                                 end: name_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -121,7 +165,56 @@ impl ValidationRule for StepNameRule {
         }
 
         find_steps(jobs_node, source, &mut diagnostics);
+        check_duplicate_names(jobs_node, source, &mut diagnostics);
 
         diagnostics
     }
 }
+
+/// Warns on a step that reuses the `name` of an earlier step in the same job.
+///
+/// Names are compared per job, so the same name in different jobs is fine.
+/// Expression names can't be compared statically and are skipped, as is a
+/// step aliased from an earlier one, since its name has no location of its own.
+fn check_duplicate_names(jobs_node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (job_name, job_value) in utils::collect_jobs(jobs_node, source) {
+        let mut seen: HashMap<&str, Node> = HashMap::new();
+        for step in utils::collect_steps(job_value, source) {
+            let name_node = match utils::find_value_for_key(step, source, "name") {
+                Some(v) => utils::unwrap_node(v),
+                None => continue,
+            };
+            let name = utils::scalar_value(name_node, source);
+            if name.is_empty() || name.contains("${{") {
+                continue;
+            }
+
+            match seen.get(name) {
+                Some(first) if first.id() == name_node.id() => {}
+                Some(first) => diagnostics.push(Diagnostic {
+                    message: format!(
+                        "Job '{}' has more than one step named '{}'. Give each step a distinct name so their logs can be told apart.",
+                        job_name, name
+                    ),
+                    severity: Severity::Warning,
+                    span: Span {
+                        start: name_node.start_byte(),
+                        end: name_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: vec![RelatedLocation {
+                        span: Span {
+                            start: first.start_byte(),
+                            end: first.end_byte(),
+                        },
+                        message: format!("Step '{}' is first named here", name),
+                    }],
+                }),
+                None => {
+                    seen.insert(name, name_node);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
 use std::collections::{HashMap, HashSet};
 use tree_sitter::{Node, Tree};
 
@@ -12,23 +12,60 @@ impl ValidationRule for StepOutputReferenceRule {
         "step_output_reference"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Expression
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`steps.<id>.outputs.<name>` references must point at a step with that `id` in the same job, and at an output that step sets.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: meta
+        run: echo "tag=v1" >> "$GITHUB_OUTPUT"
+      - run: echo ${{ steps.metadata.outputs.tag }}
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: meta
+        run: echo "tag=v1" >> "$GITHUB_OUTPUT"
+      - run: echo ${{ steps.meta.outputs.tag }}
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
 
         let mut processed_jobs = HashSet::new();
 
-        // First pass: collect all step IDs with their job names across all jobs
-        let mut all_step_ids_by_job: HashMap<String, HashSet<String>> = HashMap::new();
+        // First pass: collect all step IDs, and where each is defined, by job
+        let mut all_step_ids_by_job: HashMap<String, HashMap<String, Span>> = HashMap::new();
 
         fn collect_all_step_ids(
             node: Node,
             source: &str,
-            step_ids_by_job: &mut HashMap<String, HashSet<String>>,
+            step_ids_by_job: &mut HashMap<String, HashMap<String, Span>>,
         ) {
             match node.kind() {
                 "block_mapping_pair" | "flow_pair" => {
@@ -43,11 +80,10 @@ impl ValidationRule for StepOutputReferenceRule {
                             if job_value.kind() == "block_mapping"
                                 || job_value.kind() == "flow_mapping"
                             {
-                                let step_ids_vec = collect_step_ids(job_value, source);
-                                let step_ids_set: HashSet<String> =
-                                    step_ids_vec.into_iter().map(|(id, _)| id).collect();
-                                if !step_ids_set.is_empty() {
-                                    step_ids_by_job.insert(job_name, step_ids_set);
+                                let step_ids: HashMap<String, Span> =
+                                    collect_step_ids(job_value, source).into_iter().collect();
+                                if !step_ids.is_empty() {
+                                    step_ids_by_job.insert(job_name, step_ids);
                                 }
                             }
                         }
@@ -69,7 +105,7 @@ impl ValidationRule for StepOutputReferenceRule {
             source: &str,
             diagnostics: &mut Vec<Diagnostic>,
             processed_jobs: &mut HashSet<String>,
-            all_step_ids_by_job: &HashMap<String, HashSet<String>>,
+            all_step_ids_by_job: &HashMap<String, HashMap<String, Span>>,
         ) {
             match node.kind() {
                 "block_mapping_pair" | "flow_pair" => {
@@ -101,17 +137,28 @@ impl ValidationRule for StepOutputReferenceRule {
                                     collect_steps_without_ids(job_value, source);
 
                                 // Collect outputs set by each step (for validation)
-                                let step_outputs_map = collect_step_outputs(job_value, source);
+                                let step_outputs_map =
+                                    utils::collect_step_outputs(job_value, source);
 
                                 // Find all step output references in this job (recursively search all fields)
                                 let output_refs =
                                     find_step_output_references_recursive(job_value, source);
 
+                                // Output names in the job's own `outputs:` are checked by JobOutputsRule
+                                let job_outputs_range =
+                                    utils::find_value_for_key(job_value, source, "outputs")
+                                        .map(|n| n.start_byte()..n.end_byte());
+
                                 // Validate each reference
                                 for (step_id, output_name, span) in output_refs {
+                                    let in_job_outputs = job_outputs_range
+                                        .as_ref()
+                                        .is_some_and(|range| range.contains(&span.start));
+
                                     // Direct error production: if step exists and has outputs, check if output name exists
                                     // Produce error immediately if conditions are met
-                                    if step_ids_map.contains(&step_id)
+                                    if !in_job_outputs
+                                        && step_ids_map.contains(&step_id)
                                         && step_outputs_map.contains_key(&step_id)
                                     {
                                         if let Some(outputs) = step_outputs_map.get(&step_id) {
@@ -134,6 +181,8 @@ impl ValidationRule for StepOutputReferenceRule {
                                                     severity: Severity::Error,
                                                     span,
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                                 continue; // Skip rest of validation
                                             }
@@ -148,9 +197,10 @@ impl ValidationRule for StepOutputReferenceRule {
                                         let mut found_in_other_job = false;
                                         for (other_job_name, other_step_ids) in all_step_ids_by_job
                                         {
-                                            if other_job_name != &job_name
-                                                && other_step_ids.contains(&step_id)
-                                            {
+                                            let definition = other_step_ids
+                                                .get(&step_id)
+                                                .filter(|_| other_job_name != &job_name);
+                                            if let Some(&definition) = definition {
                                                 diagnostics.push(Diagnostic {
                                                     message: format!(
                                                         "Job '{}' references step output 'steps.{}.outputs.{}' but step '{}' is in job '{}'. Step outputs can only be referenced within the same job.",
@@ -159,6 +209,14 @@ impl ValidationRule for StepOutputReferenceRule {
                                                     severity: Severity::Error,
                                                     span,
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: vec![RelatedLocation {
+                                                        span: definition,
+                                                        message: format!(
+                                                            "Step '{}' is defined here, in job '{}'",
+                                                            step_id, other_job_name
+                                                        ),
+                                                    }],
                                                 });
                                                 found_in_other_job = true;
                                                 break;
@@ -177,6 +235,8 @@ impl ValidationRule for StepOutputReferenceRule {
                                                     severity: Severity::Error,
                                                     span,
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             } else {
                                                 diagnostics.push(Diagnostic {
@@ -187,12 +247,17 @@ impl ValidationRule for StepOutputReferenceRule {
                                                     severity: Severity::Error,
                                                     span,
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
                                     } else {
                                         // Step exists, validate output name
-                                        if let Some(outputs) = step_outputs_map.get(&step_id) {
+                                        if let Some(outputs) = step_outputs_map
+                                            .get(&step_id)
+                                            .filter(|_| !in_job_outputs)
+                                        {
                                             if !outputs.contains(&output_name) {
                                                 diagnostics.push(Diagnostic {
                                                     message: format!(
@@ -212,6 +277,8 @@ impl ValidationRule for StepOutputReferenceRule {
                                                     severity: Severity::Error,
                                                     span,
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -225,6 +292,8 @@ impl ValidationRule for StepOutputReferenceRule {
                                                 severity: Severity::Warning,
                                                 span,
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -275,7 +344,7 @@ impl ValidationRule for StepOutputReferenceRule {
     }
 }
 
-fn collect_step_ids(job_node: Node, source: &str) -> Vec<(String, Span)> {
+pub(super) fn collect_step_ids(job_node: Node, source: &str) -> Vec<(String, Span)> {
     let mut step_ids = Vec::new();
 
     let steps_value = utils::find_value_for_key(job_node, source, "steps");
@@ -394,7 +463,10 @@ fn is_valid_output_name_format(output_name: &str) -> bool {
 }
 
 /// Recursively finds all step output references in a job node
-fn find_step_output_references_recursive(node: Node, source: &str) -> Vec<(String, String, Span)> {
+pub(super) fn find_step_output_references_recursive(
+    node: Node,
+    source: &str,
+) -> Vec<(String, String, Span)> {
     let mut references = Vec::new();
 
     fn search_node(node: Node, source: &str, references: &mut Vec<(String, String, Span)>) {
@@ -518,148 +590,3 @@ fn find_step_output_references_recursive(node: Node, source: &str) -> Vec<(Strin
 
     references
 }
-
-/// Collects outputs set by each step by parsing run commands
-fn collect_step_outputs(job_node: Node, source: &str) -> HashMap<String, HashSet<String>> {
-    let mut step_outputs = HashMap::new();
-
-    let steps_value = utils::find_value_for_key(job_node, source, "steps");
-
-    if let Some(steps_node_raw) = steps_value {
-        let steps_node = utils::unwrap_node(steps_node_raw);
-
-        fn collect_from_steps(
-            node: Node,
-            source: &str,
-            step_outputs: &mut HashMap<String, HashSet<String>>,
-        ) {
-            match node.kind() {
-                "block_sequence" | "flow_sequence" => {
-                    let mut cursor = node.walk();
-                    for child in node.children(&mut cursor) {
-                        collect_from_steps(child, source, step_outputs);
-                    }
-                }
-                "block_mapping" | "flow_mapping" => {
-                    // This is a step object
-                    let id_value = utils::find_value_for_key(node, source, "id");
-                    if let Some(id_node) = id_value {
-                        let id_text = utils::node_text(id_node, source);
-                        let id_cleaned = id_text
-                            .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-
-                        if !id_cleaned.is_empty() {
-                            let mut outputs = HashSet::new();
-
-                            // Look for outputs set via $GITHUB_OUTPUT
-                            let run_value = utils::find_value_for_key(node, source, "run");
-                            if let Some(run_node) = run_value {
-                                let run_text = utils::node_text(run_node, source);
-
-                                // Look for patterns like: echo "name=value" >> $GITHUB_OUTPUT
-                                // or: echo "name=value" >> "$GITHUB_OUTPUT"
-                                // or: echo name=value >> $GITHUB_OUTPUT
-                                for line in run_text.lines() {
-                                    // Pattern: echo "name=value" >> $GITHUB_OUTPUT or echo name=value >> $GITHUB_OUTPUT
-                                    if line.contains("$GITHUB_OUTPUT")
-                                        || line.contains("\"$GITHUB_OUTPUT\"")
-                                    {
-                                        // Try to extract output name from echo commands
-                                        if let Some(echo_start) = line.find("echo") {
-                                            let after_echo = &line[echo_start + 4..].trim();
-
-                                            // First try quoted strings
-                                            for quote_char in ['"', '\''] {
-                                                if let Some(quote_start) =
-                                                    after_echo.find(quote_char)
-                                                {
-                                                    if let Some(quote_end) = after_echo
-                                                        [quote_start + 1..]
-                                                        .find(quote_char)
-                                                    {
-                                                        let quoted = &after_echo[quote_start + 1
-                                                            ..quote_start + 1 + quote_end];
-                                                        if let Some(equals_pos) = quoted.find('=') {
-                                                            let output_name = &quoted[..equals_pos];
-                                                            if !output_name.is_empty() {
-                                                                outputs.insert(
-                                                                    output_name.to_string(),
-                                                                );
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            }
-
-                                            // Also try unquoted patterns: name=value
-                                            // Find the part before >> or | or && or ; or end of line
-                                            let before_redirect = after_echo
-                                                .split(">>")
-                                                .next()
-                                                .and_then(|s| s.split("|").next())
-                                                .and_then(|s| s.split("&&").next())
-                                                .and_then(|s| s.split(";").next())
-                                                .unwrap_or(after_echo);
-
-                                            // Look for name=value pattern
-                                            for part in before_redirect.split_whitespace() {
-                                                if let Some(equals_pos) = part.find('=') {
-                                                    let output_name = &part[..equals_pos];
-                                                    // Remove quotes if present
-                                                    let output_name =
-                                                        output_name.trim_matches(|c: char| {
-                                                            c == '"' || c == '\''
-                                                        });
-                                                    if !output_name.is_empty()
-                                                        && output_name.chars().all(|c| {
-                                                            c.is_alphanumeric()
-                                                                || c == '-'
-                                                                || c == '_'
-                                                        })
-                                                    {
-                                                        outputs.insert(output_name.to_string());
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-
-                                    // Pattern: ::set-output name=name::value (legacy)
-                                    if line.contains("::set-output") {
-                                        if let Some(name_start) = line.find("name=") {
-                                            let after_name = &line[name_start + 5..];
-                                            if let Some(colon_pos) = after_name.find("::") {
-                                                let output_name = &after_name[..colon_pos];
-                                                let output_name =
-                                                    output_name.trim_matches(|c: char| {
-                                                        c == '"' || c == '\'' || c.is_whitespace()
-                                                    });
-                                                if !output_name.is_empty() {
-                                                    outputs.insert(output_name.to_string());
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-
-                            if !outputs.is_empty() {
-                                step_outputs.insert(id_cleaned.to_string(), outputs.clone());
-                            }
-                        }
-                    }
-                }
-                _ => {
-                    let mut cursor = node.walk();
-                    for child in node.children(&mut cursor) {
-                        collect_from_steps(child, source, step_outputs);
-                    }
-                }
-            }
-        }
-
-        collect_from_steps(steps_node, source, &mut step_outputs);
-    }
-
-    step_outputs
-}
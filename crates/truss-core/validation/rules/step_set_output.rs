@@ -0,0 +1,209 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span, TextEdit};
+use std::ops::Range;
+use tree_sitter::Tree;
+
+/// The legacy commands this rule reports, with the file each one writes to now.
+const LEGACY_COMMANDS: &[(&str, &str)] = &[
+    ("::set-output", "GITHUB_OUTPUT"),
+    ("::save-state", "GITHUB_STATE"),
+];
+
+/// Warns on the `::set-output` and `::save-state` workflow commands in `run:`
+/// scripts, one diagnostic per command so each can be rewritten in place.
+pub struct StepSetOutputRule;
+
+impl ValidationRule for StepSetOutputRule {
+    fn name(&self) -> &str {
+        "step_set_output"
+    }
+
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Security
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Flags `echo \"::set-output name=x::y\"` and `::save-state`, which GitHub deprecated because any log line could set outputs. Write `x=y` to `$GITHUB_OUTPUT` or `$GITHUB_STATE` instead; `truss fix` and the language server's quick fix do the rewrite.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: v
+        run: echo "::set-output name=version::1.0"
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: v
+        run: echo "version=1.0" >> "$GITHUB_OUTPUT"
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
+        find_commands(index, source)
+            .into_iter()
+            .map(|found| Diagnostic {
+                message: format!(
+                    "Deprecated workflow command '{}' detected. Use `echo \"name=value\" >> ${}` instead",
+                    found.command, found.file
+                ),
+                severity: Severity::Warning,
+                span: Span {
+                    start: found.span.start,
+                    end: found.span.end,
+                },
+                rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Rewrite each echoed command to append to `$GITHUB_OUTPUT` or
+    /// `$GITHUB_STATE`, unless its arguments continue past the reported span,
+    /// as in `echo '::set-output name=x::'"$Y"`.
+    fn fixes(&self, tree: &Tree, source: &str) -> Vec<TextEdit> {
+        find_commands(&WorkflowIndex::new(tree, source), source)
+            .into_iter()
+            .filter(|found| {
+                let rest = source[found.span.end..].split('\n').next().unwrap_or("");
+                let rest = rest.trim();
+                rest.is_empty() || rest.starts_with([';', '&', '|', '#'])
+            })
+            .filter_map(|found| {
+                Some(TextEdit {
+                    new_text: rewrite_command(&source[found.span.clone()], found.file)?,
+                    span: Span {
+                        start: found.span.start,
+                        end: found.span.end,
+                    },
+                    rule_id: String::new(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A legacy command in a `run:` script.
+struct LegacyCommand {
+    /// Byte range of the command in the source, as given by `command_span`.
+    span: Range<usize>,
+    command: &'static str,
+    file: &'static str,
+}
+
+/// Every legacy command in the workflow's `run:` scripts, in source order.
+fn find_commands(index: &WorkflowIndex, source: &str) -> Vec<LegacyCommand> {
+    let mut runs: Vec<_> = index
+        .jobs()
+        .iter()
+        .flat_map(|job| job.steps.iter().copied())
+        .filter_map(|step| utils::find_value_for_key(step, source, "run"))
+        .collect();
+    // A step shared through an alias is reported once, at the anchor
+    runs.sort_by_key(|run| run.start_byte());
+    runs.dedup();
+
+    let mut found = Vec::new();
+    for run in runs {
+        let text = utils::node_text(run, source);
+        let mut line_start = run.start_byte();
+        for line in text.split('\n') {
+            for (command, file) in LEGACY_COMMANDS {
+                let mut search = 0;
+                while let Some(pos) = line[search..].find(command) {
+                    let at = search + pos;
+                    search = at + command.len();
+                    let span = command_span(line, at);
+                    found.push(LegacyCommand {
+                        span: line_start + span.start..line_start + span.end,
+                        command,
+                        file,
+                    });
+                }
+            }
+            line_start += line.len() + 1;
+        }
+    }
+    found.sort_by_key(|found| found.span.start);
+    found
+}
+
+/// `echo "::set-output name=x::y"` as `echo "x=y" >> "$GITHUB_OUTPUT"`,
+/// keeping the original quotes. `None` for a command that isn't echoed.
+fn rewrite_command(command: &str, file: &str) -> Option<String> {
+    let args = command.strip_prefix("echo")?;
+    let rest = args.trim_start();
+    if rest.len() == args.len() {
+        return None;
+    }
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'');
+    let rest = &rest[quote.map_or(0, char::len_utf8)..];
+    let rest = rest
+        .strip_prefix("::")?
+        .split_once(' ')?
+        .1
+        .strip_prefix("name=")?;
+    let rest = match quote {
+        Some(quote) => rest.strip_suffix(quote)?,
+        None => rest,
+    };
+    let (name, value) = rest.split_once("::")?;
+    let quote = quote.map(String::from).unwrap_or_default();
+    Some(format!(
+        "echo {}{}={}{} >> \"${}\"",
+        quote, name, value, quote, file
+    ))
+}
+
+/// The part of `line` to report for the command at `at`: the whole
+/// `echo "::set-output ..."` when it is echoed, otherwise the command through
+/// its closing quote or up to the next shell operator.
+fn command_span(line: &str, at: usize) -> Range<usize> {
+    let before = &line[..at];
+    let quote = before
+        .chars()
+        .next_back()
+        .filter(|c| *c == '"' || *c == '\'');
+    let unquoted = &before[..before.len() - quote.map_or(0, char::len_utf8)];
+    let echo = unquoted
+        .strip_suffix(' ')
+        .and_then(|rest| rest.trim_end_matches(' ').strip_suffix("echo"))
+        .filter(|rest| rest.is_empty() || rest.ends_with(|c: char| " \t;&|(".contains(c)));
+
+    let rest = &line[at..];
+    let end = match quote {
+        Some(quote) => rest.find(quote).map_or(line.len(), |i| at + i + 1),
+        None => {
+            let end = rest
+                .find(|c: char| ";&|>".contains(c))
+                .unwrap_or(rest.len());
+            at + rest[..end].trim_end().len()
+        }
+    };
+    let start = match (echo, quote) {
+        (Some(rest), _) => rest.len(),
+        (None, Some(_)) => at - 1,
+        (None, None) => at,
+    };
+    start..end
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates shell field values.
@@ -11,10 +11,41 @@ impl ValidationRule for StepShellRule {
         "step_shell"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "The `shell:` of a `run` step must be a built-in shell (`bash`, `pwsh`, `python`, `sh`, `cmd`, `powershell`) or a custom command containing `{0}`.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        shell: zsh
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        shell: bash
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -133,23 +164,16 @@ impl ValidationRule for StepShellRule {
                         end: shell_node.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 return;
             }
 
-            // Known valid shells
-            let known_shells = ["bash", "pwsh", "python", "sh", "cmd", "powershell"];
-            let is_known = known_shells
-                .iter()
-                .any(|s| s.eq_ignore_ascii_case(shell_cleaned));
-
-            // Check for custom shell format (should contain {0} placeholder)
-            let is_custom = shell_cleaned.contains("{0}");
-
-            if !is_known && !is_custom {
+            if !utils::is_valid_shell(shell_cleaned) {
                 diagnostics.push(Diagnostic {
                     message: format!(
-                        "Step has invalid shell: '{}'. Valid shells are: bash, pwsh, python, sh, cmd, powershell, or a custom command with {{0}} placeholder.",
+                        "Step has invalid shell: '{}'. Valid shells are: bash, pwsh, python, sh, cmd, powershell, or a custom command followed by a {{0}} placeholder (e.g. 'perl {{0}}').",
                         shell_cleaned
                     ),
                     severity: Severity::Error,
@@ -158,6 +182,8 @@ impl ValidationRule for StepShellRule {
                         end: shell_node.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates timeout-minutes at step level.
@@ -11,10 +11,41 @@ impl ValidationRule for StepTimeoutRule {
         "step_timeout"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Step `timeout-minutes` must be a positive number.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        timeout-minutes: -5
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        timeout-minutes: 5
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -145,6 +176,8 @@ impl ValidationRule for StepTimeoutRule {
                         end: timeout_node.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 return;
             }
@@ -164,6 +197,8 @@ impl ValidationRule for StepTimeoutRule {
                                 end: timeout_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     } else if value == 0.0 {
                         diagnostics.push(Diagnostic {
@@ -177,6 +212,8 @@ impl ValidationRule for StepTimeoutRule {
                                 end: timeout_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -192,6 +229,8 @@ impl ValidationRule for StepTimeoutRule {
                             end: timeout_node.end_byte(),
                         },
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
 /// Validates working-directory paths.
@@ -11,10 +11,41 @@ impl ValidationRule for StepWorkingDirectoryRule {
         "step_working_directory"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Checks step `working-directory` values. Empty values are errors; absolute paths outside the runner's home and unusual `..` segments get a warning.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        working-directory: /opt/app
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: make
+        working-directory: ./app
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -60,7 +91,24 @@ impl ValidationRule for StepWorkingDirectoryRule {
             source: &str,
             diagnostics: &mut Vec<Diagnostic>,
         ) {
-            let step_to_check = utils::unwrap_node(step_node);
+            let mut step_to_check = utils::unwrap_node(step_node);
+
+            // Handle block_sequence_item - find the value child (skip dash and comments)
+            if step_to_check.kind() == "block_sequence_item" {
+                let mut found = false;
+                for i in 1..step_to_check.child_count() {
+                    if let Some(child) = step_to_check.child(i) {
+                        if child.kind() != "comment" {
+                            step_to_check = utils::unwrap_node(child);
+                            found = true;
+                            break;
+                        }
+                    }
+                }
+                if !found {
+                    return;
+                }
+            }
 
             if step_to_check.kind() == "block_mapping" || step_to_check.kind() == "flow_mapping" {
                 let working_dir_value =
@@ -87,6 +135,8 @@ impl ValidationRule for StepWorkingDirectoryRule {
                                 end: working_dir_node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     } else {
                         // Basic path format validation
@@ -107,6 +157,8 @@ impl ValidationRule for StepWorkingDirectoryRule {
                                     end: working_dir_node.end_byte(),
                                 },
                                 rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
                             });
                         }
 
@@ -126,6 +178,8 @@ impl ValidationRule for StepWorkingDirectoryRule {
                                     end: working_dir_node.end_byte(),
                                 },
                                 rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
                             });
                         }
                     }
@@ -1,6 +1,6 @@
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
-use tree_sitter::Tree;
+use super::super::{FileKind, RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use tree_sitter::{Node, Tree};
 
 /// Validates YAML syntax using tree-sitter parse errors.
 pub struct SyntaxRule;
@@ -10,8 +10,28 @@ impl ValidationRule for SyntaxRule {
         "syntax"
     }
 
-    fn requires_workflow(&self) -> bool {
-        false
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Syntax
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Reports YAML that can't be parsed. Nothing else can be checked until the file parses, so fix these first.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: [ubuntu-latest
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: [ubuntu-latest]
+"#,
+        }
+    }
+
+    fn applies_to(&self) -> FileKind {
+        FileKind::AnyYaml
     }
 
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
@@ -22,26 +42,7 @@ impl ValidationRule for SyntaxRule {
         }
 
         let mut diagnostics = Vec::new();
-        let mut cursor = root_node.walk();
-
-        for child in root_node.children(&mut cursor) {
-            if child.is_error() || child.is_missing() {
-                let start = child.start_byte();
-                let end = child.end_byte().min(source.len());
-                let error_snippet = if end > start && end <= source.len() {
-                    source[start..end].chars().take(50).collect::<String>()
-                } else {
-                    String::new()
-                };
-
-                diagnostics.push(Diagnostic {
-                    message: format!("Syntax error: {}", error_snippet),
-                    severity: Severity::Error,
-                    span: Span { start, end },
-                    rule_id: String::new(),
-                });
-            }
-        }
+        collect_syntax_errors(root_node, source, &mut diagnostics);
 
         if diagnostics.is_empty() {
             diagnostics.push(Diagnostic {
@@ -52,9 +53,100 @@ impl ValidationRule for SyntaxRule {
                     end: source.len().min(100),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
 
         diagnostics
     }
 }
+
+/// Tokens that leave a flow collection or quoted scalar open when unmatched.
+const OPENING_TOKENS: &[&str] = &["[", "{", "\"", "'"];
+
+/// Report every `ERROR` and `MISSING` node, descending only into subtrees
+/// that contain errors.
+fn collect_syntax_errors(node: Node, source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if node.is_missing() {
+        diagnostics.push(syntax_error(
+            format!("Syntax error: missing '{}'", node.kind()),
+            node.start_byte(),
+            node.start_byte(),
+        ));
+        return;
+    }
+
+    if node.is_error() {
+        diagnostics.push(describe_error_node(node, source));
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.has_error() || child.is_missing() {
+            collect_syntax_errors(child, source, diagnostics);
+        }
+    }
+}
+
+/// Locate the problem inside an `ERROR` node.
+///
+/// tree-sitter often wraps everything it could not fit into one large
+/// `ERROR` node, so its own span can cover most of the file. An unmatched
+/// bracket or quote inside it, or the first line after it, is a better
+/// pointer to what went wrong.
+fn describe_error_node(node: Node, source: &str) -> Diagnostic {
+    let mut cursor = node.walk();
+    let unclosed = node
+        .children(&mut cursor)
+        .filter(|child| OPENING_TOKENS.contains(&child.kind()))
+        .last();
+    if let Some(token) = unclosed {
+        return syntax_error(
+            format!("Syntax error: unclosed '{}'", token.kind()),
+            token.start_byte(),
+            token.end_byte(),
+        );
+    }
+
+    let start = node.start_byte();
+    let end = node.end_byte().min(source.len());
+    let text = &source[start..end];
+
+    // Parsing stopped at the end of the node; point at the next line it couldn't fit
+    if text.contains('\n') {
+        let rest = &source[end..];
+        let offset = rest.len() - rest.trim_start().len();
+        let line = rest.trim_start().lines().next().unwrap_or("").trim_end();
+        if !line.is_empty() {
+            let line_start = end + offset;
+            return syntax_error(
+                format!("Syntax error: unexpected '{}'", snippet(line)),
+                line_start,
+                line_start + line.len(),
+            );
+        }
+    }
+
+    syntax_error(
+        format!("Syntax error: unexpected '{}'", snippet(text.trim())),
+        start,
+        end,
+    )
+}
+
+fn snippet(text: &str) -> String {
+    text.lines().next().unwrap_or("").chars().take(50).collect()
+}
+
+fn syntax_error(message: String, start: usize, end: usize) -> Diagnostic {
+    Diagnostic {
+        message,
+        severity: Severity::Error,
+        span: Span { start, end },
+        rule_id: String::new(),
+        category: RuleCategory::default(),
+        related: Vec::new(),
+    }
+}
@@ -1,8 +1,11 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::{Node, Tree};
 
+/// Longest a job can run on a GitHub-hosted runner.
+const MAX_HOSTED_TIMEOUT_MINUTES: f64 = 360.0;
+
 /// Validates that `timeout-minutes` is a positive number.
 pub struct TimeoutRule;
 
@@ -11,10 +14,41 @@ impl ValidationRule for TimeoutRule {
         "timeout"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Job `timeout-minutes` must be a positive number. Strings and zero are accepted by the parser but don't behave as expected, and GitHub-hosted runners stop every job after 360 minutes regardless of a larger value.",
+            bad_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    timeout-minutes: 0
+    steps:
+      - run: make
+"#,
+            good_example: r#"on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    timeout-minutes: 30
+    steps:
+      - run: make
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        _tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        let jobs_node = match utils::get_jobs_node(tree, source) {
+        let jobs_node = match index.jobs_node() {
             Some(n) => n,
             None => return diagnostics,
         };
@@ -68,6 +102,8 @@ impl ValidationRule for TimeoutRule {
                                                 end: timeout_node.end_byte(),
                                             },
                                             rule_id: String::new(),
+                                            category: RuleCategory::default(),
+                                            related: Vec::new(),
                                         });
                                         return;
                                     }
@@ -88,6 +124,8 @@ impl ValidationRule for TimeoutRule {
                                                         end: timeout_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             } else if value == 0.0 {
                                                 // Zero value
@@ -102,9 +140,28 @@ impl ValidationRule for TimeoutRule {
                                                         end: timeout_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
+                                                });
+                                            } else if value > MAX_HOSTED_TIMEOUT_MINUTES
+                                                && !is_self_hosted(job_value, source)
+                                            {
+                                                diagnostics.push(Diagnostic {
+                                                    message: format!(
+                                                        "Job '{}' has timeout-minutes: {}, but GitHub-hosted runners stop jobs after {} minutes. Self-hosted runners are exempt; add the 'self-hosted' label to runs-on if this job uses one.",
+                                                        key_cleaned, timeout_cleaned, MAX_HOSTED_TIMEOUT_MINUTES
+                                                    ),
+                                                    severity: Severity::Warning,
+                                                    span: Span {
+                                                        start: timeout_node.start_byte(),
+                                                        end: timeout_node.end_byte(),
+                                                    },
+                                                    rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
-                                            // Positive values (including decimals) are valid
+                                            // Other positive values (including decimals) are valid
                                         }
                                         Err(_) => {
                                             // Not a valid number and not an expression
@@ -120,6 +177,8 @@ impl ValidationRule for TimeoutRule {
                                                     end: timeout_node.end_byte(),
                                                 },
                                                 rule_id: String::new(),
+                                                category: RuleCategory::default(),
+                                                related: Vec::new(),
                                             });
                                         }
                                     }
@@ -143,3 +202,9 @@ impl ValidationRule for TimeoutRule {
         diagnostics
     }
 }
+
+/// Whether a job's `runs-on` targets a self-hosted runner, which has no job time limit.
+fn is_self_hosted(job_value: Node, source: &str) -> bool {
+    utils::find_value_for_key(job_value, source, "runs-on")
+        .is_some_and(|runs_on| utils::node_text(runs_on, source).contains("self-hosted"))
+}
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use std::collections::HashMap;
 use tree_sitter::{Node, Tree};
 
@@ -12,6 +12,34 @@ impl ValidationRule for WorkflowCallInputsRule {
         "workflow_call_inputs"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `workflow_call` inputs. Each input needs a valid `type`, and `inputs.<name>` references must point at a declared input.",
+            bad_example: r#"on:
+  workflow_call:
+    inputs:
+      env:
+        type: str
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ inputs.env }}
+"#,
+            good_example: r#"on:
+  workflow_call:
+    inputs:
+      env:
+        type: string
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ inputs.env }}
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -46,6 +74,8 @@ impl ValidationRule for WorkflowCallInputsRule {
                     severity: Severity::Error,
                     span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
             return diagnostics;
@@ -60,7 +90,7 @@ impl ValidationRule for WorkflowCallInputsRule {
         if let Some(inputs_node) = inputs_value {
             let inputs_to_check = utils::unwrap_node(inputs_node);
 
-            self.collect_input_definitions(inputs_to_check, source, &mut defined_inputs);
+            utils::collect_input_definitions(inputs_to_check, source, &mut defined_inputs);
         }
 
         // Also collect inputs from workflow_dispatch if it coexists —
@@ -73,7 +103,7 @@ impl ValidationRule for WorkflowCallInputsRule {
                 utils::find_value_for_key(dispatch_to_check, source, "inputs")
             {
                 let dispatch_inputs_node = utils::unwrap_node(dispatch_inputs);
-                self.collect_input_definitions(dispatch_inputs_node, source, &mut defined_inputs);
+                utils::collect_input_definitions(dispatch_inputs_node, source, &mut defined_inputs);
             }
         }
 
@@ -88,6 +118,8 @@ impl ValidationRule for WorkflowCallInputsRule {
                     severity: Severity::Error,
                     span: *type_span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -101,7 +133,10 @@ impl ValidationRule for WorkflowCallInputsRule {
         // Find all inputs.* references in expressions
         let input_references = self.find_input_references(source);
 
-        // Validate that all referenced inputs are defined
+        // Validate that all referenced inputs are defined. The names are sorted
+        // so the message doesn't depend on HashMap order.
+        let mut available_inputs: Vec<&str> = defined_inputs.keys().map(String::as_str).collect();
+        available_inputs.sort_unstable();
         for (input_name, span) in input_references {
             if !defined_inputs.contains_key(&input_name) {
                 diagnostics.push(Diagnostic {
@@ -111,16 +146,14 @@ impl ValidationRule for WorkflowCallInputsRule {
                         if defined_inputs.is_empty() {
                             "none".to_string()
                         } else {
-                            defined_inputs
-                                .keys()
-                                .cloned()
-                                .collect::<Vec<_>>()
-                                .join(", ")
+                            available_inputs.join(", ")
                         }
                     ),
                     severity: Severity::Error,
                     span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -130,63 +163,6 @@ impl ValidationRule for WorkflowCallInputsRule {
 }
 
 impl WorkflowCallInputsRule {
-    fn collect_input_definitions(
-        &self,
-        node: Node,
-        source: &str,
-        inputs: &mut HashMap<String, (String, Span)>,
-    ) {
-        match node.kind() {
-            "block_mapping_pair" | "flow_pair" => {
-                if let Some(key_node) = node.child(0) {
-                    let input_name = utils::clean_key(key_node, source).to_string();
-
-                    let input_value = utils::get_pair_value(node);
-
-                    if let Some(input_value_raw) = input_value {
-                        let input_value = utils::unwrap_node(input_value_raw);
-
-                        // Find the type field (optional — defaults to "string")
-                        let type_value = utils::find_value_for_key(input_value, source, "type");
-                        if let Some(type_node) = type_value {
-                            let type_text = utils::node_text(type_node, source);
-                            let type_cleaned = type_text
-                                .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-                            inputs.insert(
-                                input_name,
-                                (
-                                    type_cleaned.to_string(),
-                                    Span {
-                                        start: type_node.start_byte(),
-                                        end: type_node.end_byte(),
-                                    },
-                                ),
-                            );
-                        } else {
-                            // Input without explicit type — defaults to "string" in GitHub Actions
-                            inputs.insert(
-                                input_name,
-                                (
-                                    "string".to_string(),
-                                    Span {
-                                        start: key_node.start_byte(),
-                                        end: key_node.end_byte(),
-                                    },
-                                ),
-                            );
-                        }
-                    }
-                }
-            }
-            _ => {
-                let mut cursor = node.walk();
-                for child in node.children(&mut cursor) {
-                    self.collect_input_definitions(child, source, inputs);
-                }
-            }
-        }
-    }
-
     fn is_valid_input_type(&self, input_type: &str) -> bool {
         matches!(
             input_type,
@@ -232,6 +208,8 @@ impl WorkflowCallInputsRule {
                                         end: required_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -270,6 +248,8 @@ impl WorkflowCallInputsRule {
                                                         end: default_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -305,6 +285,8 @@ impl WorkflowCallInputsRule {
                                         end: description_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -1,7 +1,7 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
-use std::collections::{HashMap, HashSet};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::collections::HashSet;
 use tree_sitter::{Node, Tree};
 
 /// Validates workflow_call outputs are properly defined.
@@ -12,7 +12,50 @@ impl ValidationRule for WorkflowCallOutputsRule {
         "workflow_call_outputs"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "`workflow_call` outputs need a `value` that references an existing job and one of its outputs.",
+            bad_example: r#"on:
+  workflow_call:
+    outputs:
+      version:
+        value: ${{ jobs.bulid.outputs.version }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+"#,
+            good_example: r#"on:
+  workflow_call:
+    outputs:
+      version:
+        value: ${{ jobs.build.outputs.version }}
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: v
+        run: echo "version=1" >> "$GITHUB_OUTPUT"
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         let root = tree.root_node();
@@ -32,11 +75,11 @@ impl ValidationRule for WorkflowCallOutputsRule {
         let call_to_check = utils::unwrap_node(workflow_call);
 
         // Collect all job names and their outputs for reference validation
-        let jobs_value = utils::find_value_for_key(root, source, "jobs");
+        let jobs_value = index.jobs_node();
         let (job_names, job_outputs) = if let Some(jobs_node) = jobs_value {
             (
                 collect_job_names(jobs_node, source),
-                collect_job_outputs(jobs_node, source),
+                utils::collect_job_outputs(jobs_node, source),
             )
         } else {
             (HashSet::new(), std::collections::HashMap::new())
@@ -70,6 +113,8 @@ impl ValidationRule for WorkflowCallOutputsRule {
                         severity: Severity::Error,
                         span,
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -85,6 +130,8 @@ impl ValidationRule for WorkflowCallOutputsRule {
                         severity: Severity::Error,
                         span,
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 } else {
                     // Check if the job output exists
@@ -98,12 +145,16 @@ impl ValidationRule for WorkflowCallOutputsRule {
                                     if outputs.is_empty() {
                                         "none".to_string()
                                     } else {
-                                        outputs.iter().cloned().collect::<Vec<_>>().join(", ")
+                                        let mut sorted: Vec<_> = outputs.iter().map(String::as_str).collect();
+                                        sorted.sort_unstable();
+                                        sorted.join(", ")
                                     }
                                 ),
                                 severity: Severity::Error,
                                 span,
                                 rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
                             });
                         }
                     } else {
@@ -116,6 +167,8 @@ impl ValidationRule for WorkflowCallOutputsRule {
                             severity: Severity::Error,
                             span,
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -150,68 +203,6 @@ fn collect_job_names(jobs_node: Node, source: &str) -> HashSet<String> {
     job_names
 }
 
-fn collect_job_outputs(jobs_node: Node, source: &str) -> HashMap<String, HashSet<String>> {
-    let mut job_outputs = HashMap::new();
-
-    fn collect(node: Node, source: &str, outputs: &mut HashMap<String, HashSet<String>>) {
-        match node.kind() {
-            "block_mapping_pair" | "flow_pair" => {
-                if let Some(key_node) = node.child(0) {
-                    let job_name = utils::clean_key(key_node, source).to_string();
-
-                    let job_value = utils::get_pair_value(node);
-
-                    if let Some(job_value_raw) = job_value {
-                        let job_value = utils::unwrap_node(job_value_raw);
-
-                        if job_value.kind() == "block_mapping" || job_value.kind() == "flow_mapping"
-                        {
-                            // Find outputs in this job
-                            let outputs_value =
-                                utils::find_value_for_key(job_value, source, "outputs");
-                            if let Some(outputs_node_raw) = outputs_value {
-                                let outputs_node = utils::unwrap_node(outputs_node_raw);
-
-                                let mut output_names = HashSet::new();
-                                collect_output_names(outputs_node, source, &mut output_names);
-                                if !output_names.is_empty() {
-                                    outputs.insert(job_name, output_names);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            _ => {
-                let mut cursor = node.walk();
-                for child in node.children(&mut cursor) {
-                    collect(child, source, outputs);
-                }
-            }
-        }
-    }
-
-    collect(jobs_node, source, &mut job_outputs);
-    job_outputs
-}
-
-fn collect_output_names(node: Node, source: &str, names: &mut HashSet<String>) {
-    match node.kind() {
-        "block_mapping_pair" | "flow_pair" => {
-            if let Some(key_node) = node.child(0) {
-                let key_cleaned = utils::clean_key(key_node, source);
-                names.insert(key_cleaned.to_string());
-            }
-        }
-        _ => {
-            let mut cursor = node.walk();
-            for child in node.children(&mut cursor) {
-                collect_output_names(child, source, names);
-            }
-        }
-    }
-}
-
 fn find_job_output_references(outputs_node: Node, source: &str) -> Vec<(String, String, Span)> {
     let mut references = Vec::new();
     let node_text = utils::node_text(outputs_node, source);
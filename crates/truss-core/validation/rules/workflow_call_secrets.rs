@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use std::collections::HashSet;
 use tree_sitter::{Node, Tree};
 
@@ -12,6 +12,38 @@ impl ValidationRule for WorkflowCallSecretsRule {
         "workflow_call_secrets"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "In a reusable workflow, `secrets.<name>` references must point at a secret declared under `on.workflow_call.secrets`.",
+            bad_example: r#"on:
+  workflow_call:
+    secrets:
+      token:
+        required: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TOKEN: ${{ secrets.deploy_token }}
+"#,
+            good_example: r#"on:
+  workflow_call:
+    secrets:
+      token:
+        required: true
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: ./deploy.sh
+        env:
+          TOKEN: ${{ secrets.token }}
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -79,16 +111,21 @@ impl ValidationRule for WorkflowCallSecretsRule {
                     message: format!(
                         "Reference to undefined workflow_call secret '{}'. Available secrets: {}",
                         secret_name,
-                        defined_secrets
-                            .iter()
-                            .filter(|s| s.as_str() != "GITHUB_TOKEN")
-                            .cloned()
-                            .collect::<Vec<_>>()
-                            .join(", ")
+                        {
+                            let mut sorted: Vec<_> = defined_secrets
+                                .iter()
+                                .map(String::as_str)
+                                .filter(|s| *s != "GITHUB_TOKEN")
+                                .collect();
+                            sorted.sort_unstable();
+                            sorted.join(", ")
+                        }
                     ),
                     severity: Severity::Error,
                     span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -153,6 +190,8 @@ impl WorkflowCallSecretsRule {
                                         end: required_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -177,6 +216,8 @@ impl WorkflowCallSecretsRule {
                                         end: description_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -0,0 +1,93 @@
+use super::super::utils;
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
+use std::collections::HashMap;
+use tree_sitter::Tree;
+
+/// Most inputs GitHub accepts on a `workflow_dispatch` trigger.
+const MAX_DISPATCH_INPUTS: usize = 25;
+
+/// Enforces GitHub's limit on the number of `workflow_dispatch` inputs.
+pub struct WorkflowDispatchInputMaxRule;
+
+impl ValidationRule for WorkflowDispatchInputMaxRule {
+    fn name(&self) -> &str {
+        "workflow_dispatch_input_max"
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "GitHub accepts at most 25 `workflow_dispatch` inputs. A workflow with more can't be dispatched, and the error only shows up in the Actions UI. Group related settings into a single `choice` or JSON `string` input.",
+            bad_example: r#"on:
+  workflow_dispatch:
+    inputs: {
+      a: {}, b: {}, c: {}, d: {}, e: {}, f: {}, g: {}, h: {}, i: {},
+      j: {}, k: {}, l: {}, m: {}, n: {}, o: {}, p: {}, q: {}, r: {},
+      s: {}, t: {}, u: {}, v: {}, w: {}, x: {}, y: {}, z: {}
+    }
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ inputs.a }}"
+"#,
+            good_example: r#"on:
+  workflow_dispatch:
+    inputs:
+      config:
+        description: JSON settings for the run
+        type: string
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo "${{ fromJSON(inputs.config).target }}"
+"#,
+        }
+    }
+
+    fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let on_value = match utils::find_value_for_key(tree.root_node(), source, "on") {
+            Some(v) => utils::unwrap_node(v),
+            None => return diagnostics,
+        };
+        let dispatch = match utils::find_value_for_key(on_value, source, "workflow_dispatch") {
+            Some(v) => utils::unwrap_node(v),
+            None => return diagnostics,
+        };
+
+        for pair in utils::mapping_pairs(dispatch) {
+            let (key_node, inputs_node) = match (pair.child(0), utils::get_pair_value(pair)) {
+                (Some(k), Some(v)) => (k, utils::unwrap_node(v)),
+                _ => continue,
+            };
+            if utils::clean_key(key_node, source) != "inputs" {
+                continue;
+            }
+
+            let mut inputs = HashMap::new();
+            utils::collect_input_definitions(inputs_node, source, &mut inputs);
+            if inputs.len() > MAX_DISPATCH_INPUTS {
+                diagnostics.push(Diagnostic {
+                    message: format!(
+                        "workflow_dispatch declares {} inputs, but GitHub allows at most {}. The workflow can't be dispatched until inputs are removed or combined.",
+                        inputs.len(),
+                        MAX_DISPATCH_INPUTS
+                    ),
+                    severity: Severity::Error,
+                    span: Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                    rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
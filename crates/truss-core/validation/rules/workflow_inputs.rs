@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule, WorkflowIndex};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use std::collections::HashMap;
 use tree_sitter::{Node, Tree};
 
@@ -12,7 +12,45 @@ impl ValidationRule for WorkflowInputsRule {
         "workflow_inputs"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates `workflow_dispatch` inputs: types must be known, `choice` inputs need options, and defaults must match the type.",
+            bad_example: r#"on:
+  workflow_dispatch:
+    inputs:
+      level:
+        type: enum
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+            good_example: r#"on:
+  workflow_dispatch:
+    inputs:
+      level:
+        type: choice
+        options: [debug, info]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
+        self.validate_with_index(tree, source, &WorkflowIndex::new(tree, source))
+    }
+
+    fn validate_with_index(
+        &self,
+        tree: &Tree,
+        source: &str,
+        index: &WorkflowIndex,
+    ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
         let root = tree.root_node();
@@ -43,7 +81,7 @@ impl ValidationRule for WorkflowInputsRule {
             let inputs_to_check = utils::unwrap_node(inputs_node);
 
             // Collect all input definitions
-            self.collect_input_definitions(inputs_to_check, source, &mut defined_inputs);
+            utils::collect_input_definitions(inputs_to_check, source, &mut defined_inputs);
         }
 
         // Also collect inputs from workflow_call if it coexists —
@@ -52,7 +90,7 @@ impl ValidationRule for WorkflowInputsRule {
             let call_to_check = utils::unwrap_node(call_value);
             if let Some(call_inputs) = utils::find_value_for_key(call_to_check, source, "inputs") {
                 let call_inputs_node = utils::unwrap_node(call_inputs);
-                self.collect_input_definitions(call_inputs_node, source, &mut defined_inputs);
+                utils::collect_input_definitions(call_inputs_node, source, &mut defined_inputs);
             }
         }
 
@@ -67,6 +105,8 @@ impl ValidationRule for WorkflowInputsRule {
                     severity: Severity::Error,
                     span: *type_span,
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
@@ -79,7 +119,7 @@ impl ValidationRule for WorkflowInputsRule {
 
         // Find all inputs.* references in expressions (but exclude the inputs definition section itself)
         // We need to find references in jobs, not in the inputs definition
-        let jobs_value = utils::find_value_for_key(root, source, "jobs");
+        let jobs_value = index.jobs_node();
         let input_references = if let Some(jobs_node) = jobs_value {
             // Only search for input references in the jobs section
             self.find_input_references_in_node(jobs_node, source)
@@ -92,21 +132,23 @@ impl ValidationRule for WorkflowInputsRule {
         // inputs section, GitHub Actions returns empty string for any inputs.* reference,
         // so we should not flag them as errors.
         if !defined_inputs.is_empty() {
+            // Sorted so the message doesn't depend on HashMap order
+            let mut available_inputs: Vec<&str> =
+                defined_inputs.keys().map(String::as_str).collect();
+            available_inputs.sort_unstable();
             for (input_name, span) in input_references {
                 if !defined_inputs.contains_key(&input_name) {
                     diagnostics.push(Diagnostic {
                         message: format!(
                             "Reference to undefined input '{}'. Available inputs: {}",
                             input_name,
-                            defined_inputs
-                                .keys()
-                                .cloned()
-                                .collect::<Vec<_>>()
-                                .join(", ")
+                            available_inputs.join(", ")
                         ),
                         severity: Severity::Error,
                         span,
                         rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: Vec::new(),
                     });
                 }
             }
@@ -117,64 +159,6 @@ impl ValidationRule for WorkflowInputsRule {
 }
 
 impl WorkflowInputsRule {
-    fn collect_input_definitions(
-        &self,
-        node: Node,
-        source: &str,
-        inputs: &mut HashMap<String, (String, Span)>,
-    ) {
-        match node.kind() {
-            "block_mapping_pair" | "flow_pair" => {
-                if let Some(key_node) = node.child(0) {
-                    let input_name = utils::clean_key(key_node, source).trim().to_string();
-
-                    // Get the input value node
-                    let input_value = utils::get_pair_value(node);
-
-                    if let Some(input_value_raw) = input_value {
-                        let input_value = utils::unwrap_node(input_value_raw);
-
-                        // Find the type field (optional — defaults to "string")
-                        let type_value = utils::find_value_for_key(input_value, source, "type");
-                        if let Some(type_node) = type_value {
-                            let type_text = utils::node_text(type_node, source);
-                            let type_cleaned = type_text
-                                .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
-                            inputs.insert(
-                                input_name,
-                                (
-                                    type_cleaned.to_string(),
-                                    Span {
-                                        start: type_node.start_byte(),
-                                        end: type_node.end_byte(),
-                                    },
-                                ),
-                            );
-                        } else {
-                            // Input without explicit type — defaults to "string" in GitHub Actions
-                            inputs.insert(
-                                input_name,
-                                (
-                                    "string".to_string(),
-                                    Span {
-                                        start: key_node.start_byte(),
-                                        end: key_node.end_byte(),
-                                    },
-                                ),
-                            );
-                        }
-                    }
-                }
-            }
-            _ => {
-                let mut cursor = node.walk();
-                for child in node.children(&mut cursor) {
-                    self.collect_input_definitions(child, source, inputs);
-                }
-            }
-        }
-    }
-
     fn is_valid_input_type(&self, input_type: &str) -> bool {
         matches!(
             input_type,
@@ -220,6 +204,8 @@ impl WorkflowInputsRule {
                                         end: required_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -258,6 +244,8 @@ impl WorkflowInputsRule {
                                                         end: default_node.end_byte(),
                                                     },
                                                     rule_id: String::new(),
+                                                    category: RuleCategory::default(),
+                                                    related: Vec::new(),
                                                 });
                                             }
                                         }
@@ -293,6 +281,8 @@ impl WorkflowInputsRule {
                                         end: description_node.end_byte(),
                                     },
                                     rule_id: String::new(),
+                                    category: RuleCategory::default(),
+                                    related: Vec::new(),
                                 });
                             }
                         }
@@ -1,6 +1,6 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RuleCategory, Severity, Span};
 use tree_sitter::Tree;
 
 /// Validates workflow name field.
@@ -11,6 +11,32 @@ impl ValidationRule for WorkflowNameRule {
         "workflow_name"
     }
 
+    fn category(&self) -> RuleCategory {
+        RuleCategory::Style
+    }
+
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "The workflow `name:` is optional, but when present it should be non-empty and at most 255 characters.",
+            bad_example: r#"name: ""
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+            good_example: r#"name: CI
+on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -33,6 +59,8 @@ impl ValidationRule for WorkflowNameRule {
                     end: name_value.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
 
@@ -48,6 +76,8 @@ impl ValidationRule for WorkflowNameRule {
                     end: name_value.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
 
@@ -1,6 +1,7 @@
 use super::super::utils;
-use super::super::ValidationRule;
-use crate::{Diagnostic, Severity, Span};
+use super::super::{RuleExplanation, ValidationRule};
+use crate::{Diagnostic, RelatedLocation, RuleCategory, Severity, Span};
+use std::collections::HashMap;
 use tree_sitter::Tree;
 
 /// All valid GitHub Actions event types.
@@ -50,6 +51,26 @@ impl ValidationRule for WorkflowTriggerRule {
         "workflow_trigger"
     }
 
+    fn explanation(&self) -> RuleExplanation {
+        RuleExplanation {
+            summary: "Validates the `on:` block. Every event must be one GitHub knows about; a misspelled event means the workflow never runs.",
+            bad_example: r#"on: pull-request
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+            good_example: r#"on: pull_request
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo hi
+"#,
+        }
+    }
+
     fn validate(&self, tree: &Tree, source: &str) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -66,6 +87,8 @@ impl ValidationRule for WorkflowTriggerRule {
                         end: source.len().min(100),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
                 return diagnostics;
             }
@@ -83,6 +106,8 @@ impl ValidationRule for WorkflowTriggerRule {
                     end: on_to_check.end_byte(),
                 },
                 rule_id: String::new(),
+                category: RuleCategory::default(),
+                related: Vec::new(),
             });
         }
 
@@ -129,6 +154,8 @@ impl ValidationRule for WorkflowTriggerRule {
                                     end: key_node.end_byte(),
                                 },
                                 rule_id: String::new(),
+                                category: RuleCategory::default(),
+                                related: Vec::new(),
                             });
                         }
                     }
@@ -154,6 +181,8 @@ impl ValidationRule for WorkflowTriggerRule {
                                 end: node.end_byte(),
                             },
                             rule_id: String::new(),
+                            category: RuleCategory::default(),
+                            related: Vec::new(),
                         });
                     }
                 }
@@ -169,6 +198,45 @@ impl ValidationRule for WorkflowTriggerRule {
         // Validate event types in the on: mapping
         validate_event_types(on_to_check, source, &mut diagnostics);
 
+        // A repeated trigger replaces the earlier one, filters and all
+        if matches!(event_node.kind(), "block_mapping" | "flow_mapping") {
+            let mut first_keys: HashMap<&str, tree_sitter::Node> = HashMap::new();
+            for key_node in utils::mapping_pairs(event_node)
+                .into_iter()
+                .filter_map(|pair| pair.child(0))
+            {
+                let event = utils::clean_key(key_node, source);
+                if event.is_empty() {
+                    continue;
+                }
+                match first_keys.get(event) {
+                    Some(first) => diagnostics.push(Diagnostic {
+                        message: format!(
+                            "Duplicate trigger '{}'; only the last definition is used.",
+                            event
+                        ),
+                        severity: Severity::Error,
+                        span: Span {
+                            start: key_node.start_byte(),
+                            end: key_node.end_byte(),
+                        },
+                        rule_id: String::new(),
+                        category: RuleCategory::default(),
+                        related: vec![RelatedLocation {
+                            span: Span {
+                                start: first.start_byte(),
+                                end: first.end_byte(),
+                            },
+                            message: format!("'{}' is first defined here", event),
+                        }],
+                    }),
+                    None => {
+                        first_keys.insert(event, key_node);
+                    }
+                }
+            }
+        }
+
         // Also validate simple event text if present (scalar on: value)
         if let Some(event_text) = event_text {
             if !VALID_EVENTS
@@ -186,6 +254,8 @@ impl ValidationRule for WorkflowTriggerRule {
                         end: event_node.end_byte(),
                     },
                     rule_id: String::new(),
+                    category: RuleCategory::default(),
+                    related: Vec::new(),
                 });
             }
         }
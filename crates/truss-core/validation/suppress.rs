@@ -0,0 +1,110 @@
+//! Inline suppression comments.
+//!
+//! `# truss-disable-line <rule_id>` drops that rule's diagnostics on the
+//! comment's own line, and `# truss-disable-next-line <rule_id>` on the line
+//! below it. Several ids can be listed, separated by commas or spaces; with
+//! no id, every rule is suppressed on that line. Comments are read from the
+//! tree, so text inside a `run: |` script never counts as one.
+
+use crate::{Diagnostic, TextEdit};
+use std::collections::HashMap;
+use tree_sitter::{Node, Tree};
+
+const DISABLE_LINE: &str = "truss-disable-line";
+const DISABLE_NEXT_LINE: &str = "truss-disable-next-line";
+
+/// Drop the diagnostics that a suppression comment in `source` covers.
+///
+/// A diagnostic belongs to the line its span starts on.
+pub(crate) fn apply_inline_suppressions(
+    tree: &Tree,
+    source: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    retain_unsuppressed(tree, source, diagnostics, |d| (d.span.start, &d.rule_id));
+}
+
+/// Drop the fixes that a suppression comment in `source` covers, so `truss
+/// fix` leaves alone the lines `truss validate` reports as clean.
+///
+/// Like a diagnostic, an edit belongs to the line its span starts on.
+pub(crate) fn drop_suppressed_edits(tree: &Tree, source: &str, edits: &mut Vec<TextEdit>) {
+    retain_unsuppressed(tree, source, edits, |e| (e.span.start, &e.rule_id));
+}
+
+/// Keep the items whose `(start offset, rule id)` no suppression comment covers.
+fn retain_unsuppressed<T>(
+    tree: &Tree,
+    source: &str,
+    items: &mut Vec<T>,
+    key: impl Fn(&T) -> (usize, &str),
+) {
+    // Most files have none, so skip the tree walk
+    if items.is_empty() || !source.contains("truss-disable") {
+        return;
+    }
+
+    let mut disabled: HashMap<usize, Option<Vec<&str>>> = HashMap::new();
+    collect_comments(tree.root_node(), source, &mut disabled);
+    if disabled.is_empty() {
+        return;
+    }
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    items.retain(|item| {
+        let (start, rule_id) = key(item);
+        let line = line_starts.partition_point(|&line_start| line_start <= start) - 1;
+        match disabled.get(&line) {
+            Some(Some(rules)) => !rules.contains(&rule_id),
+            Some(None) => false,
+            None => true,
+        }
+    });
+}
+
+/// Record the rules each suppression comment under `node` disables, keyed
+/// by 0-based line. `None` means every rule.
+fn collect_comments<'a>(
+    node: Node,
+    source: &'a str,
+    disabled: &mut HashMap<usize, Option<Vec<&'a str>>>,
+) {
+    if node.kind() == "comment" {
+        let text = source
+            .get(node.start_byte()..node.end_byte())
+            .unwrap_or("")
+            .trim_start_matches('#')
+            .trim();
+        let row = node.start_position().row;
+        let (line, rules) = if let Some(rules) = text.strip_prefix(DISABLE_NEXT_LINE) {
+            (row + 1, rules)
+        } else if let Some(rules) = text.strip_prefix(DISABLE_LINE) {
+            (row, rules)
+        } else {
+            return;
+        };
+        // `truss-disable-lines` isn't a directive
+        if !rules.is_empty() && !rules.starts_with(char::is_whitespace) {
+            return;
+        }
+        let rules: Vec<&str> = rules
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|id| !id.is_empty())
+            .collect();
+        let entry = disabled.entry(line).or_insert_with(|| Some(Vec::new()));
+        // A bare comment wins over any list for the same line
+        if rules.is_empty() {
+            *entry = None;
+        } else if let Some(ids) = entry {
+            ids.extend(rules);
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comments(child, source, disabled);
+    }
+}
@@ -1,5 +1,9 @@
 //! Helper utilities for validation rules.
 
+use crate::Span;
+#[cfg(test)]
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
 use tree_sitter::{Node, Tree};
 
 /// Check if a YAML document is a GitHub Actions workflow by examining top-level keys.
@@ -58,14 +62,18 @@ pub(crate) fn is_github_actions_workflow(tree: &Tree, source: &str) -> bool {
 ///
 /// In tree-sitter-yaml, comments are extras that can appear as children of any node.
 /// When unwrapping block_node/flow_node, we must skip comment children to find the
-/// actual content (block_mapping, block_sequence, block_scalar, etc.).
+/// actual content (block_mapping, block_sequence, block_scalar, etc.). An anchor
+/// (`&name`) or tag (`!!str`) in front of the content is skipped the same way.
+///
+/// Aliases (`*name`) are returned as is, since resolving them needs the source;
+/// see `resolve_alias`.
 pub(crate) fn unwrap_node<'a>(node: Node<'a>) -> Node<'a> {
     let mut current = node;
     while let "block_node" | "flow_node" = current.kind() {
         let mut found_inner = false;
         for i in 0..current.child_count() {
             if let Some(child) = current.child(i) {
-                if child.kind() != "comment" {
+                if !matches!(child.kind(), "comment" | "anchor" | "tag") {
                     current = child;
                     found_inner = true;
                     break;
@@ -79,7 +87,61 @@ pub(crate) fn unwrap_node<'a>(node: Node<'a>) -> Node<'a> {
     current
 }
 
+/// Unwrap `node` and, if it is an alias (`*name`), follow it to the unwrapped
+/// content of the anchor (`&name`) it refers to.
+///
+/// As in YAML, the closest anchor before the alias in the same document wins.
+/// An alias with no such anchor, or inside the node its anchor marks, is
+/// returned unresolved. Every resolved alias therefore points at a node that
+/// ends before it, so following aliases can never loop.
+pub(crate) fn resolve_alias<'a>(node: Node<'a>, source: &str) -> Node<'a> {
+    let node = unwrap_node(node);
+    if node.kind() != "alias" {
+        return node;
+    }
+    let name = node_text(node, source).trim_start_matches('*');
+
+    let mut document = node;
+    while let Some(parent) = document.parent() {
+        document = parent;
+        if document.kind() == "document" {
+            break;
+        }
+    }
+
+    fn find_anchor<'a>(
+        node: Node<'a>,
+        source: &str,
+        name: &str,
+        before: usize,
+        found: &mut Option<Node<'a>>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            if child.start_byte() >= before {
+                break;
+            }
+            if child.kind() == "anchor" {
+                if node_text(child, source).trim_start_matches('&') == name {
+                    *found = Some(node);
+                }
+            } else {
+                find_anchor(child, source, name, before, found);
+            }
+        }
+    }
+
+    let mut anchored = None;
+    find_anchor(document, source, name, node.start_byte(), &mut anchored);
+    match anchored {
+        Some(anchored) if anchored.end_byte() <= node.start_byte() => unwrap_node(anchored),
+        _ => node,
+    }
+}
+
 /// Helper function to find a value node for a given key in the AST
+///
+/// Aliases are followed, both while searching and in the returned value.
 pub(crate) fn find_value_for_key<'a>(
     node: Node<'a>,
     source: &'a str,
@@ -100,6 +162,10 @@ pub(crate) fn find_value_for_key<'a>(
                         if let Some(child) = node.child(i) {
                             let kind = child.kind();
                             if kind != "comment" && kind != ":" {
+                                let value = unwrap_node(child);
+                                if value.kind() == "alias" {
+                                    return Some(resolve_alias(value, source));
+                                }
                                 return Some(child);
                             }
                         }
@@ -107,6 +173,12 @@ pub(crate) fn find_value_for_key<'a>(
                 }
             }
         }
+        "alias" => {
+            let target = resolve_alias(node, source);
+            if target != node {
+                return find_value_for_key(target, source, target_key);
+            }
+        }
         _ => {
             let mut cursor = node.walk();
             for child in node.children(&mut cursor) {
@@ -156,6 +228,10 @@ pub(crate) fn key_exists(node: Node, source: &str, target_key: &str) -> bool {
             }
             false
         }
+        "alias" => {
+            let target = resolve_alias(node, source);
+            target != node && key_exists(target, source, target_key)
+        }
         _ => {
             let mut cursor = node.walk();
             for child in node.children(&mut cursor) {
@@ -178,16 +254,563 @@ pub(crate) fn clean_key<'a>(node: Node, source: &'a str) -> &'a str {
         .trim_end_matches(':')
 }
 
+#[cfg(test)]
+thread_local! {
+    /// How many times this thread has searched a tree for `jobs:`, so tests
+    /// can check an analysis does it once.
+    pub(crate) static JOBS_LOOKUPS: Cell<usize> = const { Cell::new(0) };
+}
+
 /// Get the unwrapped `jobs:` mapping node from a workflow tree.
 ///
-/// Combines `find_value_for_key` + `unwrap_node` for the common pattern
-/// used by 31+ validation rules.
+/// Combines `find_value_for_key` + `unwrap_node`. Rules read the result from
+/// `WorkflowIndex::jobs_node` instead; this is for `WorkflowIndex` itself and
+/// the checks that run outside the rule set.
 pub(crate) fn get_jobs_node<'a>(tree: &'a Tree, source: &'a str) -> Option<Node<'a>> {
+    #[cfg(test)]
+    JOBS_LOOKUPS.with(|count| count.set(count.get() + 1));
     let root = tree.root_node();
     let jobs_value = find_value_for_key(root, source, "jobs")?;
     Some(unwrap_node(jobs_value))
 }
 
+/// Get the `runs:` mapping of an action metadata file (`action.yml`).
+///
+/// Returns `None` for workflows, so action-file rules can run on any YAML
+/// document and bail out early when it isn't an action.
+pub(crate) fn get_action_runs_node<'a>(tree: &'a Tree, source: &'a str) -> Option<Node<'a>> {
+    let root = tree.root_node();
+    if find_value_for_key(root, source, "jobs").is_some() {
+        return None;
+    }
+    let runs = unwrap_node(find_value_for_key(root, source, "runs")?);
+    match runs.kind() {
+        "block_mapping" | "flow_mapping" => Some(runs),
+        _ => None,
+    }
+}
+
+/// Whether the document is a composite action (`runs.using: composite`).
+///
+/// Workflows always return `false`, since `get_action_runs_node` ignores
+/// documents with a top-level `jobs:` key.
+pub(crate) fn is_composite_action(tree: &Tree, source: &str) -> bool {
+    get_action_runs_node(tree, source)
+        .and_then(|runs| find_value_for_key(runs, source, "using"))
+        .is_some_and(|using| scalar_value(using, source).eq_ignore_ascii_case("composite"))
+}
+
+/// Collect the event names declared under the workflow's `on:` key.
+///
+/// Handles the scalar (`on: push`), sequence (`on: [push, pull_request]`),
+/// and mapping forms. Each event name is paired with the node that names it,
+/// which is the key node for the mapping form.
+pub(crate) fn collect_triggers<'a>(tree: &'a Tree, source: &'a str) -> Vec<(&'a str, Node<'a>)> {
+    let mut triggers = Vec::new();
+
+    let on_value = match find_value_for_key(tree.root_node(), source, "on") {
+        Some(v) => unwrap_node(v),
+        None => return triggers,
+    };
+
+    match on_value.kind() {
+        "block_mapping" | "flow_mapping" => {
+            for pair in mapping_pairs(on_value) {
+                if let Some(key_node) = pair.child(0) {
+                    triggers.push((clean_key(key_node, source), key_node));
+                }
+            }
+        }
+        "block_sequence" | "flow_sequence" => {
+            for item in sequence_items(on_value) {
+                triggers.push((clean_key(item, source), item));
+            }
+        }
+        _ => triggers.push((clean_key(on_value, source), on_value)),
+    }
+
+    triggers
+}
+
+/// Collect the inputs declared in an `inputs:` mapping of `workflow_dispatch`
+/// or `workflow_call`, keyed by name.
+///
+/// Each input maps to its `type` and the span of the `type:` value. Inputs
+/// without a `type` default to `string`, spanning the input's key instead.
+pub(crate) fn collect_input_definitions(
+    node: Node,
+    source: &str,
+    inputs: &mut HashMap<String, (String, Span)>,
+) {
+    match node.kind() {
+        "block_mapping_pair" | "flow_pair" => {
+            let (key_node, input_value) = match (node.child(0), get_pair_value(node)) {
+                (Some(k), Some(v)) => (k, unwrap_node(v)),
+                _ => return,
+            };
+            let input_name = clean_key(key_node, source).trim().to_string();
+            let definition = match find_value_for_key(input_value, source, "type") {
+                Some(type_node) => (
+                    node_text(type_node, source)
+                        .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+                        .to_string(),
+                    Span {
+                        start: type_node.start_byte(),
+                        end: type_node.end_byte(),
+                    },
+                ),
+                None => (
+                    "string".to_string(),
+                    Span {
+                        start: key_node.start_byte(),
+                        end: key_node.end_byte(),
+                    },
+                ),
+            };
+            inputs.insert(input_name, definition);
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_input_definitions(child, source, inputs);
+            }
+        }
+    }
+}
+
+/// Collect the `block_mapping_pair` / `flow_pair` children of a mapping node.
+///
+/// Only direct pairs are returned, unlike `find_value_for_key` which searches
+/// recursively. Comments and other extras are skipped.
+pub(crate) fn mapping_pairs<'a>(node: Node<'a>) -> Vec<Node<'a>> {
+    let mut cursor = node.walk();
+    node.children(&mut cursor)
+        .filter(|child| matches!(child.kind(), "block_mapping_pair" | "flow_pair"))
+        .collect()
+}
+
+/// The unwrapped value of `key` in `mapping` itself, not in any nested mapping.
+///
+/// An alias value is resolved to the anchored content.
+pub(crate) fn direct_value<'a>(mapping: Node<'a>, source: &str, key: &str) -> Option<Node<'a>> {
+    mapping_pairs(mapping).into_iter().find_map(|pair| {
+        let key_node = pair.child(0)?;
+        if clean_key(key_node, source) == key {
+            get_pair_value(pair).map(|value| resolve_alias(value, source))
+        } else {
+            None
+        }
+    })
+}
+
+/// Collect the unwrapped items of a `block_sequence` or `flow_sequence` node.
+///
+/// Skips the `-` markers, flow punctuation, and comments, returning the
+/// content of each item (e.g., the `block_mapping` of an `include:` entry).
+pub(crate) fn sequence_items<'a>(node: Node<'a>) -> Vec<Node<'a>> {
+    let mut items = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        match child.kind() {
+            "block_sequence_item" => {
+                for i in 0..child.child_count() {
+                    if let Some(inner) = child.child(i) {
+                        if inner.kind() != "-" && inner.kind() != "comment" {
+                            items.push(unwrap_node(inner));
+                            break;
+                        }
+                    }
+                }
+            }
+            "flow_node" | "flow_pair" => items.push(unwrap_node(child)),
+            _ => {}
+        }
+    }
+    items
+}
+
+/// Collect every job under `jobs:` as its ID paired with its unwrapped mapping.
+///
+/// Jobs whose value isn't a mapping (e.g., incomplete documents) are skipped.
+pub(crate) fn collect_jobs<'a>(jobs_node: Node<'a>, source: &'a str) -> Vec<(&'a str, Node<'a>)> {
+    let mut jobs = Vec::new();
+    for pair in mapping_pairs(jobs_node) {
+        let (key_node, value) = match (pair.child(0), get_pair_value(pair)) {
+            (Some(k), Some(v)) => (k, resolve_alias(v, source)),
+            _ => continue,
+        };
+        if matches!(value.kind(), "block_mapping" | "flow_mapping") {
+            jobs.push((clean_key(key_node, source), value));
+        }
+    }
+    jobs
+}
+
+/// Collect the unwrapped mapping of every step in a job's `steps:` sequence.
+///
+/// Steps written as an alias (`- *checkout`) are resolved to the anchored step.
+pub(crate) fn collect_steps<'a>(job_value: Node<'a>, source: &'a str) -> Vec<Node<'a>> {
+    let steps = match find_value_for_key(job_value, source, "steps") {
+        Some(v) => unwrap_node(v),
+        None => return Vec::new(),
+    };
+    if !matches!(steps.kind(), "block_sequence" | "flow_sequence") {
+        return Vec::new();
+    }
+    sequence_items(steps)
+        .into_iter()
+        .map(|step| resolve_alias(step, source))
+        .filter(|step| matches!(step.kind(), "block_mapping" | "flow_mapping"))
+        .collect()
+}
+
+/// Collect the outputs each step with an `id` sets from its `run:` script.
+///
+/// `node` is a mapping with a `steps:` key (a job, or a composite action's `runs:`).
+/// Outputs are found from `echo "name=value" >> $GITHUB_OUTPUT` and legacy
+/// `::set-output` lines; steps that set none are left out, since their outputs
+/// (e.g. from `uses:` actions or scripts) can't be known statically.
+pub(crate) fn collect_step_outputs(node: Node, source: &str) -> HashMap<String, HashSet<String>> {
+    let mut step_outputs = HashMap::new();
+
+    let steps_value = find_value_for_key(node, source, "steps");
+
+    if let Some(steps_node_raw) = steps_value {
+        let steps_node = unwrap_node(steps_node_raw);
+
+        fn collect_from_steps(
+            node: Node,
+            source: &str,
+            step_outputs: &mut HashMap<String, HashSet<String>>,
+        ) {
+            match node.kind() {
+                "block_sequence" | "flow_sequence" => {
+                    let mut cursor = node.walk();
+                    for child in node.children(&mut cursor) {
+                        collect_from_steps(child, source, step_outputs);
+                    }
+                }
+                "block_mapping" | "flow_mapping" => {
+                    // This is a step object
+                    let id_value = find_value_for_key(node, source, "id");
+                    if let Some(id_node) = id_value {
+                        let id_text = node_text(id_node, source);
+                        let id_cleaned = id_text
+                            .trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace());
+
+                        if !id_cleaned.is_empty() {
+                            let mut outputs = HashSet::new();
+
+                            // Look for outputs set via $GITHUB_OUTPUT
+                            let run_value = find_value_for_key(node, source, "run");
+                            if let Some(run_node) = run_value {
+                                let run_text = node_text(run_node, source);
+
+                                // Look for patterns like: echo "name=value" >> $GITHUB_OUTPUT
+                                // or: echo "name=value" >> "$GITHUB_OUTPUT"
+                                // or: echo name=value >> $GITHUB_OUTPUT
+                                for line in run_text.lines() {
+                                    // Pattern: echo "name=value" >> $GITHUB_OUTPUT or echo name=value >> $GITHUB_OUTPUT
+                                    if line.contains("$GITHUB_OUTPUT")
+                                        || line.contains("\"$GITHUB_OUTPUT\"")
+                                    {
+                                        // Try to extract output name from echo commands
+                                        if let Some(echo_start) = line.find("echo") {
+                                            let after_echo = &line[echo_start + 4..].trim();
+
+                                            // First try quoted strings
+                                            for quote_char in ['"', '\''] {
+                                                if let Some(quote_start) =
+                                                    after_echo.find(quote_char)
+                                                {
+                                                    if let Some(quote_end) = after_echo
+                                                        [quote_start + 1..]
+                                                        .find(quote_char)
+                                                    {
+                                                        let quoted = &after_echo[quote_start + 1
+                                                            ..quote_start + 1 + quote_end];
+                                                        if let Some(equals_pos) = quoted.find('=') {
+                                                            let output_name = &quoted[..equals_pos];
+                                                            if !output_name.is_empty() {
+                                                                outputs.insert(
+                                                                    output_name.to_string(),
+                                                                );
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+
+                                            // Also try unquoted patterns: name=value
+                                            // Find the part before >> or | or && or ; or end of line
+                                            let before_redirect = after_echo
+                                                .split(">>")
+                                                .next()
+                                                .and_then(|s| s.split("|").next())
+                                                .and_then(|s| s.split("&&").next())
+                                                .and_then(|s| s.split(";").next())
+                                                .unwrap_or(after_echo);
+
+                                            // Look for name=value pattern
+                                            for part in before_redirect.split_whitespace() {
+                                                if let Some(equals_pos) = part.find('=') {
+                                                    let output_name = &part[..equals_pos];
+                                                    // Remove quotes if present
+                                                    let output_name =
+                                                        output_name.trim_matches(|c: char| {
+                                                            c == '"' || c == '\''
+                                                        });
+                                                    if !output_name.is_empty()
+                                                        && output_name.chars().all(|c| {
+                                                            c.is_alphanumeric()
+                                                                || c == '-'
+                                                                || c == '_'
+                                                        })
+                                                    {
+                                                        outputs.insert(output_name.to_string());
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+
+                                    // Pattern: ::set-output name=name::value (legacy)
+                                    if line.contains("::set-output") {
+                                        if let Some(name_start) = line.find("name=") {
+                                            let after_name = &line[name_start + 5..];
+                                            if let Some(colon_pos) = after_name.find("::") {
+                                                let output_name = &after_name[..colon_pos];
+                                                let output_name =
+                                                    output_name.trim_matches(|c: char| {
+                                                        c == '"' || c == '\'' || c.is_whitespace()
+                                                    });
+                                                if !output_name.is_empty() {
+                                                    outputs.insert(output_name.to_string());
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            if !outputs.is_empty() {
+                                step_outputs.insert(id_cleaned.to_string(), outputs.clone());
+                            }
+                        }
+                    }
+                }
+                _ => {
+                    let mut cursor = node.walk();
+                    for child in node.children(&mut cursor) {
+                        collect_from_steps(child, source, step_outputs);
+                    }
+                }
+            }
+        }
+
+        collect_from_steps(steps_node, source, &mut step_outputs);
+    }
+
+    step_outputs
+}
+
+/// Get the unwrapped `strategy.matrix` node declared on a job, if any.
+///
+/// `job_value` is the unwrapped mapping of a single job.
+pub(crate) fn get_job_matrix<'a>(job_value: Node<'a>, source: &'a str) -> Option<Node<'a>> {
+    let strategy = find_value_for_key(job_value, source, "strategy")?;
+    let matrix = find_value_for_key(unwrap_node(strategy), source, "matrix")?;
+    Some(unwrap_node(matrix))
+}
+
+/// Collect the keys a matrix makes available through the `matrix` context.
+///
+/// This is the union of the base dimensions and any keys introduced by
+/// `include:` entries. Returns `None` when the keys can't be known statically,
+/// e.g. `matrix: ${{ fromJSON(...) }}` or `include: ${{ fromJSON(...) }}`.
+pub(crate) fn collect_matrix_keys<'a>(matrix: Node<'a>, source: &'a str) -> Option<Vec<&'a str>> {
+    if !matches!(matrix.kind(), "block_mapping" | "flow_mapping") {
+        return None;
+    }
+
+    let mut keys = Vec::new();
+    for pair in mapping_pairs(matrix) {
+        let key = match pair.child(0) {
+            Some(key_node) => clean_key(key_node, source),
+            None => continue,
+        };
+        match key {
+            "exclude" => {}
+            "include" => {
+                let value = unwrap_node(get_pair_value(pair)?);
+                if !matches!(value.kind(), "block_sequence" | "flow_sequence") {
+                    return None;
+                }
+                for entry in sequence_items(value) {
+                    if !matches!(entry.kind(), "block_mapping" | "flow_mapping") {
+                        return None;
+                    }
+                    for entry_pair in mapping_pairs(entry) {
+                        if let Some(entry_key) = entry_pair.child(0) {
+                            keys.push(clean_key(entry_key, source));
+                        }
+                    }
+                }
+            }
+            _ => keys.push(key),
+        }
+    }
+
+    keys.sort_unstable();
+    keys.dedup();
+    Some(keys)
+}
+
+/// A matrix whose dimensions and `include`/`exclude` entries are all literal.
+pub(crate) struct StaticMatrix<'a> {
+    /// Base dimensions with their values, in declaration order.
+    pub dimensions: Vec<(&'a str, Vec<&'a str>)>,
+    /// `include:` entries as key/value pairs.
+    pub include: Vec<Vec<(&'a str, &'a str)>>,
+    /// `exclude:` entries as key/value pairs.
+    pub exclude: Vec<Vec<(&'a str, &'a str)>>,
+}
+
+/// Upper bound on combinations enumerated when applying `include`/`exclude`.
+///
+/// GitHub caps a matrix at 256 jobs, so anything past this is already invalid
+/// and the raw product of dimension sizes is reported instead.
+const MAX_ENUMERATED_COMBINATIONS: usize = 10_000;
+
+impl<'a> StaticMatrix<'a> {
+    /// Number of jobs this matrix expands to, following GitHub's semantics:
+    /// `exclude` entries remove matching combinations, and each `include`
+    /// entry either extends the combinations it doesn't conflict with or,
+    /// if there are none, adds a new combination.
+    pub fn combination_count(&self) -> usize {
+        let product = self
+            .dimensions
+            .iter()
+            .fold(1usize, |acc, (_, values)| acc.saturating_mul(values.len()));
+
+        if self.dimensions.is_empty() {
+            return self.include.len();
+        }
+        if product > MAX_ENUMERATED_COMBINATIONS {
+            return product;
+        }
+
+        let combinations: Vec<Vec<&str>> = self.combinations();
+        let remaining: Vec<&Vec<&str>> = combinations
+            .iter()
+            .filter(|combo| !self.exclude.iter().any(|entry| self.matches(combo, entry)))
+            .collect();
+
+        let added = self
+            .include
+            .iter()
+            .filter(|entry| !remaining.iter().any(|combo| self.matches(combo, entry)))
+            .count();
+
+        remaining.len() + added
+    }
+
+    /// Cartesian product of the base dimension values.
+    fn combinations(&self) -> Vec<Vec<&'a str>> {
+        let mut combinations: Vec<Vec<&str>> = vec![Vec::new()];
+        for (_, values) in &self.dimensions {
+            combinations = combinations
+                .iter()
+                .flat_map(|combo| {
+                    values.iter().map(move |value| {
+                        let mut next = combo.clone();
+                        next.push(*value);
+                        next
+                    })
+                })
+                .collect();
+        }
+        combinations
+    }
+
+    /// Whether every pair in `entry` that names a base dimension agrees with `combo`.
+    fn matches(&self, combo: &[&str], entry: &[(&str, &str)]) -> bool {
+        entry.iter().all(|(key, value)| {
+            match self.dimensions.iter().position(|(dim, _)| dim == key) {
+                Some(index) => combo[index] == *value,
+                None => true,
+            }
+        })
+    }
+}
+
+/// Parse a matrix node into a `StaticMatrix`.
+///
+/// Returns `None` when any part of the matrix is computed at runtime, e.g.
+/// `matrix: ${{ fromJSON(...) }}` or `os: ${{ fromJSON(...) }}`.
+pub(crate) fn parse_static_matrix<'a>(
+    matrix: Node<'a>,
+    source: &'a str,
+) -> Option<StaticMatrix<'a>> {
+    if !matches!(matrix.kind(), "block_mapping" | "flow_mapping") {
+        return None;
+    }
+
+    let mut parsed = StaticMatrix {
+        dimensions: Vec::new(),
+        include: Vec::new(),
+        exclude: Vec::new(),
+    };
+
+    for pair in mapping_pairs(matrix) {
+        let key = clean_key(pair.child(0)?, source);
+        let value = unwrap_node(get_pair_value(pair)?);
+        if !matches!(value.kind(), "block_sequence" | "flow_sequence") {
+            return None;
+        }
+
+        match key {
+            "include" | "exclude" => {
+                let mut entries = Vec::new();
+                for entry in sequence_items(value) {
+                    if !matches!(entry.kind(), "block_mapping" | "flow_mapping") {
+                        return None;
+                    }
+                    let mut entry_pairs = Vec::new();
+                    for entry_pair in mapping_pairs(entry) {
+                        let entry_key = clean_key(entry_pair.child(0)?, source);
+                        let entry_value = scalar_value(get_pair_value(entry_pair)?, source);
+                        entry_pairs.push((entry_key, entry_value));
+                    }
+                    entries.push(entry_pairs);
+                }
+                if key == "include" {
+                    parsed.include = entries;
+                } else {
+                    parsed.exclude = entries;
+                }
+            }
+            _ => {
+                let values: Vec<&str> = sequence_items(value)
+                    .into_iter()
+                    .map(|item| scalar_value(item, source))
+                    .collect();
+                if values.iter().any(|v| v.contains("${{")) {
+                    return None;
+                }
+                parsed.dimensions.push((key, values));
+            }
+        }
+    }
+
+    Some(parsed)
+}
+
+/// Text of a value node with surrounding quotes and whitespace removed.
+pub(crate) fn scalar_value<'a>(node: Node, source: &'a str) -> &'a str {
+    node_text(node, source).trim_matches(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+}
+
 /// Helper to extract text from a node.
 ///
 /// Returns an empty string if the byte offsets fall outside the source
@@ -197,11 +820,54 @@ pub(crate) fn node_text<'a>(node: Node, source: &'a str) -> &'a str {
 }
 
 /// Known GitHub Actions expression context names.
-const KNOWN_CONTEXTS: &[&str] = &[
+pub(crate) const KNOWN_CONTEXTS: &[&str] = &[
     "github", "matrix", "secrets", "vars", "needs", "inputs", "env", "job", "jobs", "steps",
     "runner", "strategy",
 ];
 
+/// Properties of the `github` context.
+pub(crate) const GITHUB_CONTEXT_PROPERTIES: &[&str] = &[
+    "action",
+    "action_path",
+    "action_ref",
+    "action_repository",
+    "action_status",
+    "actor",
+    "actor_id",
+    "api_url",
+    "base_ref",
+    "env",
+    "event",
+    "event_name",
+    "event_path",
+    "graphql_url",
+    "head_ref",
+    "job",
+    "path",
+    "ref",
+    "ref_name",
+    "ref_protected",
+    "ref_type",
+    "repository",
+    "repository_id",
+    "repository_owner",
+    "repository_owner_id",
+    "repositoryurl",
+    "retention_days",
+    "run_attempt",
+    "run_id",
+    "run_number",
+    "secret_source",
+    "server_url",
+    "sha",
+    "token",
+    "triggering_actor",
+    "workflow",
+    "workflow_ref",
+    "workflow_sha",
+    "workspace",
+];
+
 /// Check if an expression has valid GitHub Actions expression syntax.
 ///
 /// Validates that the expression contains recognized contexts, functions,
@@ -285,6 +951,167 @@ pub(crate) fn is_valid_expression_syntax(expr: &str) -> bool {
     true
 }
 
+/// Find a lone `=` used where a comparison was meant, e.g. `github.ref = 'main'`.
+///
+/// Returns the byte offset of the `=`. `==`, `!=`, `<=`, `>=`, and `===` are
+/// not matches, nor is any `=` inside a single-quoted string literal, which
+/// covers `format('--tag={0}', ...)` templates.
+pub(crate) fn find_assignment_operator(expr: &str) -> Option<usize> {
+    let bytes = expr.as_bytes();
+    let mut in_string = false;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            // `''` escapes a quote, which toggles twice and stays in the string
+            b'\'' => in_string = !in_string,
+            b'=' if !in_string => {
+                let prev = i.checked_sub(1).map(|p| bytes[p]);
+                let next = bytes.get(i + 1).copied();
+                let is_operator_part =
+                    matches!(prev, Some(b'=' | b'!' | b'<' | b'>')) || next == Some(b'=');
+                if !is_operator_part {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Collect the output names each job declares under `outputs:`.
+///
+/// Jobs without outputs are left out.
+pub(crate) fn collect_job_outputs(
+    jobs_node: Node,
+    source: &str,
+) -> HashMap<String, HashSet<String>> {
+    let mut job_outputs = HashMap::new();
+
+    fn collect(node: Node, source: &str, outputs: &mut HashMap<String, HashSet<String>>) {
+        match node.kind() {
+            "block_mapping_pair" | "flow_pair" => {
+                if let Some(key_node) = node.child(0) {
+                    let job_name = clean_key(key_node, source).to_string();
+
+                    let job_value = get_pair_value(node);
+
+                    if let Some(job_value_raw) = job_value {
+                        let job_value = unwrap_node(job_value_raw);
+
+                        if job_value.kind() == "block_mapping" || job_value.kind() == "flow_mapping"
+                        {
+                            // Find outputs in this job
+                            let outputs_value = find_value_for_key(job_value, source, "outputs");
+                            if let Some(outputs_node_raw) = outputs_value {
+                                let outputs_node = unwrap_node(outputs_node_raw);
+
+                                let mut output_names = HashSet::new();
+                                collect_output_names(outputs_node, source, &mut output_names);
+                                if !output_names.is_empty() {
+                                    outputs.insert(job_name, output_names);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    collect(child, source, outputs);
+                }
+            }
+        }
+    }
+
+    collect(jobs_node, source, &mut job_outputs);
+    job_outputs
+}
+
+/// Collect the keys of an `outputs:` mapping.
+fn collect_output_names(node: Node, source: &str, names: &mut HashSet<String>) {
+    match node.kind() {
+        "block_mapping_pair" | "flow_pair" => {
+            if let Some(key_node) = node.child(0) {
+                let key_cleaned = clean_key(key_node, source);
+                names.insert(key_cleaned.to_string());
+            }
+        }
+        _ => {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                collect_output_names(child, source, names);
+            }
+        }
+    }
+}
+
+/// Shells `shell:` accepts by name.
+pub(crate) const KNOWN_SHELLS: &[&str] = &["bash", "pwsh", "python", "sh", "cmd", "powershell"];
+
+/// Whether `shell` is a known shell or a custom `{command} [args] {0}` template.
+///
+/// A custom template must name a command before the `{0}` placeholder, which
+/// the runner replaces with the path of the script file.
+pub(crate) fn is_valid_shell(shell: &str) -> bool {
+    if KNOWN_SHELLS.iter().any(|s| s.eq_ignore_ascii_case(shell)) {
+        return true;
+    }
+    let mut words = shell.split_whitespace();
+    words.next().is_some_and(|command| !command.contains("{0}"))
+        && words.any(|word| word.contains("{0}"))
+}
+
+/// Default environment variables the runner sets for every job.
+///
+/// GitHub doesn't let workflows override these: a value set in `env:` is
+/// ignored, so a literal assignment is always a mistake.
+pub(crate) const RUNNER_PROVIDED_ENV_VARS: &[&str] = &[
+    "GITHUB_ACTION",
+    "GITHUB_ACTION_PATH",
+    "GITHUB_ACTION_REPOSITORY",
+    "GITHUB_ACTIONS",
+    "GITHUB_ACTOR",
+    "GITHUB_ACTOR_ID",
+    "GITHUB_API_URL",
+    "GITHUB_BASE_REF",
+    "GITHUB_ENV",
+    "GITHUB_EVENT_NAME",
+    "GITHUB_EVENT_PATH",
+    "GITHUB_GRAPHQL_URL",
+    "GITHUB_HEAD_REF",
+    "GITHUB_JOB",
+    "GITHUB_OUTPUT",
+    "GITHUB_PATH",
+    "GITHUB_REF",
+    "GITHUB_REF_NAME",
+    "GITHUB_REF_PROTECTED",
+    "GITHUB_REF_TYPE",
+    "GITHUB_REPOSITORY",
+    "GITHUB_REPOSITORY_ID",
+    "GITHUB_REPOSITORY_OWNER",
+    "GITHUB_REPOSITORY_OWNER_ID",
+    "GITHUB_RETENTION_DAYS",
+    "GITHUB_RUN_ATTEMPT",
+    "GITHUB_RUN_ID",
+    "GITHUB_RUN_NUMBER",
+    "GITHUB_SERVER_URL",
+    "GITHUB_SHA",
+    "GITHUB_STEP_SUMMARY",
+    "GITHUB_TRIGGERING_ACTOR",
+    "GITHUB_WORKFLOW",
+    "GITHUB_WORKFLOW_REF",
+    "GITHUB_WORKFLOW_SHA",
+    "GITHUB_WORKSPACE",
+    "RUNNER_ARCH",
+    "RUNNER_DEBUG",
+    "RUNNER_ENVIRONMENT",
+    "RUNNER_NAME",
+    "RUNNER_OS",
+    "RUNNER_TEMP",
+    "RUNNER_TOOL_CACHE",
+];
+
 /// Check if expression may always evaluate to true.
 ///
 /// Only matches bare `true` or `!false` — not complex expressions that
@@ -382,12 +1209,331 @@ pub(crate) fn find_expressions(source: &str) -> Vec<Expression<'_>> {
     results
 }
 
+/// The byte range of the first text outside `${{ }}` in an `if:` condition
+/// that mixes the two, such as `${{ success() }} && always()`.
+///
+/// GitHub only evaluates `if:` as one expression when it is bare or entirely
+/// wrapped in a single `${{ }}`. Anything else is a string template whose
+/// result is a non-empty string, so the condition is always true. The quotes
+/// of a quoted scalar and whitespace between expressions don't count as text.
+pub(crate) fn text_outside_expressions(condition: &str) -> Option<std::ops::Range<usize>> {
+    let expressions = find_expressions(condition);
+    if expressions.is_empty() {
+        return None;
+    }
+
+    let value = condition.trim();
+    let mut start = condition.len() - condition.trim_start().len();
+    let mut end = start + value.len();
+    let quoted = value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')));
+    if quoted {
+        start += 1;
+        end -= 1;
+    }
+
+    let mut gaps = Vec::with_capacity(expressions.len() + 1);
+    for expression in &expressions {
+        gaps.push(start..expression.start.max(start));
+        start = expression.end;
+    }
+    gaps.push(start..end.max(start));
+
+    gaps.into_iter().find_map(|gap| {
+        let text = &condition[gap.clone()];
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let offset = gap.start + (text.len() - text.trim_start().len());
+        Some(offset..offset + trimmed.len())
+    })
+}
+
 /// Check if expression may always evaluate to false.
 ///
-/// Only matches bare `false` or `!true` — not complex expressions that
-/// happen to contain the substring "false".
+/// Matches bare `false` or `!true`, and `&&` chains that are trivially
+/// contradictory: one conjunct is `false`, or the same operand is compared
+/// with `==` to two different literals (`github.event_name == 'push' &&
+/// github.event_name == 'pull_request'`), or with both `==` and `!=` to the
+/// same one. Anything with a top-level `||` is left alone.
 pub(crate) fn is_potentially_always_false(expr: &str) -> bool {
-    expr.eq_ignore_ascii_case("false") || expr.eq_ignore_ascii_case("!true")
+    if expr.eq_ignore_ascii_case("false") || expr.eq_ignore_ascii_case("!true") {
+        return true;
+    }
+
+    let conjuncts = match split_conjunction(expr) {
+        Some(c) if c.len() > 1 => c,
+        _ => return false,
+    };
+    let mut equal: HashMap<String, (&str, String)> = HashMap::new();
+    let mut not_equal: Vec<(String, (&str, String))> = Vec::new();
+    for conjunct in conjuncts {
+        let conjunct = strip_outer_parens(conjunct);
+        if is_potentially_always_false(conjunct) {
+            return true;
+        }
+        let (operand, negated, literal) = match parse_literal_comparison(conjunct) {
+            Some(c) => c,
+            None => continue,
+        };
+        if negated {
+            not_equal.push((operand, literal));
+            continue;
+        }
+        match equal.get(&operand) {
+            // Literals of different types may still compare equal after coercion
+            Some(existing) if existing.0 == literal.0 && existing.1 != literal.1 => return true,
+            Some(_) => {}
+            None => {
+                equal.insert(operand, literal);
+            }
+        }
+    }
+    not_equal
+        .iter()
+        .any(|(operand, literal)| equal.get(operand) == Some(literal))
+}
+
+/// Split `expr` on `&&` outside string literals and parentheses.
+///
+/// Returns `None` if it has a top-level `||`, whose alternatives could each hold.
+fn split_conjunction(expr: &str) -> Option<Vec<&str>> {
+    let bytes = expr.as_bytes();
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => in_string = !in_string,
+            _ if in_string => {}
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b'|' if depth == 0 && bytes.get(i + 1) == Some(&b'|') => return None,
+            b'&' if depth == 0 && bytes.get(i + 1) == Some(&b'&') => {
+                parts.push(expr[start..i].trim());
+                start = i + 2;
+                i += 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(expr[start..].trim());
+    Some(parts)
+}
+
+/// Remove parentheses wrapping the whole of `expr`, e.g. `(a == 'b')`.
+fn strip_outer_parens(expr: &str) -> &str {
+    let mut expr = expr.trim();
+    while let Some(inner) = expr.strip_prefix('(').and_then(|e| e.strip_suffix(')')) {
+        // `(a) && (b)` starts and ends with parens but isn't wrapped
+        let mut depth = 0i32;
+        let balanced = inner.bytes().all(|b| {
+            match b {
+                b'(' => depth += 1,
+                b')' => depth -= 1,
+                _ => {}
+            }
+            depth >= 0
+        });
+        if !balanced {
+            break;
+        }
+        expr = inner.trim();
+    }
+    expr
+}
+
+/// Parse `operand == literal` or `operand != literal` (either way round).
+///
+/// Returns the lowercased operand, whether the comparison is `!=`, and the
+/// literal as parsed by `parse_literal`.
+fn parse_literal_comparison(expr: &str) -> Option<(String, bool, (&'static str, String))> {
+    let (pos, negated) = match (expr.find("=="), expr.find("!=")) {
+        (Some(eq), None) => (eq, false),
+        (None, Some(ne)) => (ne, true),
+        _ => return None,
+    };
+    let (left, right) = (expr[..pos].trim(), expr[pos + 2..].trim());
+    let (operand, literal) = match (parse_literal(left), parse_literal(right)) {
+        (None, Some(literal)) => (left, literal),
+        (Some(literal), None) => (right, literal),
+        _ => return None,
+    };
+    let is_path = operand.starts_with(|c: char| c.is_ascii_alphabetic())
+        && operand
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-' | '*'));
+    if !is_path {
+        return None;
+    }
+    Some((operand.to_ascii_lowercase(), negated, literal))
+}
+
+/// Parse a literal into its type and a normalized value.
+///
+/// String comparisons in expressions ignore case, so strings are lowercased;
+/// `1` and `1.0` normalize to the same number.
+fn parse_literal(text: &str) -> Option<(&'static str, String)> {
+    if let Some(inner) = text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+        if inner.replace("''", "").contains('\'') {
+            return None;
+        }
+        return Some(("string", inner.replace("''", "'").to_lowercase()));
+    }
+    let numeric = text.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '.'));
+    if let Some(number) = text.parse::<f64>().ok().filter(|_| numeric) {
+        return Some(("number", number.to_string()));
+    }
+    match text {
+        "true" | "false" => Some(("boolean", text.to_string())),
+        "null" => Some(("null", String::new())),
+        _ => None,
+    }
+}
+
+/// Find every context reference in expression text, whatever the context.
+///
+/// A reference is an identifier followed by `.` or `[`, outside string
+/// literals and not part of a longer property path. Returns byte ranges of the
+/// context name plus its first property (`github.ref`, or just `matrix` for
+/// `matrix['os']`), relative to `expr`.
+pub(crate) fn find_all_context_references(expr: &str) -> Vec<std::ops::Range<usize>> {
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'-';
+    let bytes = expr.as_bytes();
+    let mut references = Vec::new();
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b == b'\'' {
+            in_string = !in_string;
+            i += 1;
+            continue;
+        }
+        let starts_ident = (b.is_ascii_alphabetic() || b == b'_')
+            && !in_string
+            && (i == 0 || !(is_ident(bytes[i - 1]) || bytes[i - 1] == b'.'));
+        if !starts_ident {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        while i < bytes.len() && is_ident(bytes[i]) {
+            i += 1;
+        }
+        match bytes.get(i) {
+            Some(b'.') => {
+                let mut end = i + 1;
+                while end < bytes.len() && (is_ident(bytes[end]) || bytes[end] == b'*') {
+                    end += 1;
+                }
+                references.push(start..end);
+            }
+            Some(b'[') => references.push(start..i),
+            _ => {}
+        }
+    }
+
+    references
+}
+
+/// Find references to a context (e.g. `secrets`) in expression text.
+///
+/// Matches `context.name` and `context[...]` case-insensitively, skipping
+/// occurrences inside `'...'` string literals or that are part of a longer
+/// identifier or property path such as `inputs.secrets.x`. Returns byte
+/// ranges of `context.name` relative to `text`.
+pub(crate) fn find_context_references(text: &str, context: &str) -> Vec<std::ops::Range<usize>> {
+    let mut references = Vec::new();
+    let mut search_pos = 0;
+
+    while let Some(pos) = find_ignore_ascii_case(&text[search_pos..], context) {
+        let start = search_pos + pos;
+        let after = start + context.len();
+        search_pos = after;
+
+        // An odd number of quotes before the match means it's inside a string
+        if text[..start].matches('\'').count() % 2 == 1 {
+            continue;
+        }
+
+        let preceded_by_ident = text[..start]
+            .chars()
+            .next_back()
+            .map(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+            .unwrap_or(false);
+        if preceded_by_ident {
+            continue;
+        }
+
+        let rest = &text[after..];
+        if !(rest.starts_with('.') || rest.starts_with('[')) {
+            continue;
+        }
+        let name_len = rest[1..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(rest.len() - 1);
+        references.push(start..after + 1 + name_len);
+    }
+
+    references
+}
+
+/// Find `github.event_name == '<event>'` comparisons (either way round) in
+/// condition text whose event isn't in `triggers`, the workflow's `on:` events.
+///
+/// Such a comparison is always false. Returns the byte range of each event
+/// name inside its quotes, relative to `condition`, with the name. Nothing is
+/// reported without triggers, or when `workflow_call` is one of them, since
+/// a called workflow sees the caller's event.
+pub(crate) fn find_untriggered_event_comparisons<'a>(
+    condition: &'a str,
+    triggers: &[&str],
+) -> Vec<(std::ops::Range<usize>, &'a str)> {
+    let mut found = Vec::new();
+    if triggers.is_empty() || triggers.contains(&"workflow_call") {
+        return found;
+    }
+
+    for reference in find_context_references(condition, "github") {
+        let property = &condition[reference.start + "github".len()..reference.end];
+        if !property.eq_ignore_ascii_case(".event_name") {
+            continue;
+        }
+        let literal = quoted_operand_after(condition, reference.end)
+            .or_else(|| quoted_operand_before(condition, reference.start));
+        if let Some(range) = literal {
+            let event = &condition[range.clone()];
+            // String comparison in expressions ignores case
+            if !event.is_empty() && !triggers.iter().any(|t| t.eq_ignore_ascii_case(event)) {
+                found.push((range, event));
+            }
+        }
+    }
+
+    found
+}
+
+/// The range inside the quotes of `== 'literal'` starting at `pos`.
+fn quoted_operand_after(text: &str, pos: usize) -> Option<std::ops::Range<usize>> {
+    let rest = text[pos..].trim_start().strip_prefix("==")?.trim_start();
+    let literal = rest.strip_prefix('\'')?;
+    let start = text.len() - literal.len();
+    Some(start..start + literal.find('\'')?)
+}
+
+/// The range inside the quotes of `'literal' ==` ending at `pos`.
+fn quoted_operand_before(text: &str, pos: usize) -> Option<std::ops::Range<usize>> {
+    let rest = text[..pos].trim_end().strip_suffix("==")?.trim_end();
+    let literal = rest.strip_suffix('\'')?;
+    Some(literal.rfind('\'')? + 1..literal.len())
 }
 
 /// Case-insensitive substring search without allocating a new String.
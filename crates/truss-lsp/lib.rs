@@ -7,8 +7,15 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::io::{self, BufRead, BufReader, Read, Write};
-use truss_core::{Diagnostic as CoreDiagnostic, Severity as CoreSeverity, TrussEngine};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use truss_core::config::TrussConfig;
+use truss_core::{
+    byte_to_utf16_position, Diagnostic as CoreDiagnostic, LineIndex, Severity as CoreSeverity,
+    TrussEngine, TrussResult, WorkflowIndex,
+};
 
 /// JSON-RPC message types for LSP communication.
 ///
@@ -90,6 +97,15 @@ struct LspNotification {
 struct LspServer {
     engine: TrussEngine,
     documents: HashMap<String, DocumentState>,
+    /// Documents changed since they were last analyzed, with the version
+    /// waiting to be analyzed. `didChange` only records the new text, and
+    /// [`Self::flush_pending`] analyzes the latest one, so a burst of edits
+    /// costs one analysis.
+    pending: HashMap<String, i32>,
+    /// Workspace folder from `initialize`, where `.truss.yml` discovery starts.
+    workspace_root: Option<PathBuf>,
+    /// The discovered `.truss.yml`, or the default config if there is none.
+    config: TrussConfig,
     initialized: bool,
     shutdown_requested: bool,
     exit_received: bool,
@@ -98,6 +114,7 @@ struct LspServer {
 struct DocumentState {
     text: String,
     version: i32,
+    /// Tree from the last analysis, which may be of an older version.
     tree: Option<tree_sitter::Tree>,
 }
 
@@ -106,6 +123,9 @@ impl LspServer {
         Self {
             engine: TrussEngine::new(),
             documents: HashMap::new(),
+            pending: HashMap::new(),
+            workspace_root: None,
+            config: TrussConfig::default(),
             initialized: false,
             shutdown_requested: false,
             exit_received: false,
@@ -117,6 +137,13 @@ impl LspServer {
 
         match message {
             LspMessage::Request(req) => {
+                // Requests read the document's tree, so bring it up to date
+                // with the latest changes first
+                responses.extend(
+                    self.flush_pending()
+                        .into_iter()
+                        .map(LspOutgoing::Notification),
+                );
                 if let Some(response) = self.handle_request(req) {
                     responses.push(LspOutgoing::Response(response));
                 }
@@ -135,12 +162,22 @@ impl LspServer {
         match req.method.as_str() {
             "initialize" => {
                 self.initialized = true;
+                self.workspace_root = req.params.as_ref().and_then(workspace_root);
+                self.reload_config();
                 let result = serde_json::json!({
                     "capabilities": {
                         "textDocumentSync": {
                             "openClose": true,
                             "change": 1, // TextDocumentSyncKind.Full
                             "save": false
+                        },
+                        "hoverProvider": true,
+                        "completionProvider": {
+                            "triggerCharacters": [".", "{"]
+                        },
+                        "documentSymbolProvider": true,
+                        "codeActionProvider": {
+                            "codeActionKinds": ["quickfix"]
                         }
                     },
                     "serverInfo": {
@@ -164,6 +201,64 @@ impl LspServer {
                     error: None,
                 })
             }
+            "textDocument/hover" if self.initialized => {
+                let result = req
+                    .params
+                    .and_then(|params| serde_json::from_value::<HoverParams>(params).ok())
+                    .and_then(|params| self.handle_hover(params))
+                    .unwrap_or(Value::Null);
+                Some(LspResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: req.id,
+                    result: Some(result),
+                    error: None,
+                })
+            }
+            "textDocument/completion" if self.initialized => {
+                let result = req
+                    .params
+                    .and_then(|params| serde_json::from_value::<CompletionParams>(params).ok())
+                    .and_then(|params| self.handle_completion(params))
+                    .unwrap_or(Value::Null);
+                Some(LspResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: req.id,
+                    result: Some(result),
+                    error: None,
+                })
+            }
+            "textDocument/codeAction" if self.initialized => {
+                let result = req
+                    .params
+                    .and_then(|params| serde_json::from_value::<CodeActionParams>(params).ok())
+                    .map(|params| self.handle_code_action(params))
+                    .unwrap_or(Value::Null);
+                Some(LspResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: req.id,
+                    result: Some(result),
+                    error: None,
+                })
+            }
+            "truss/listRules" if self.initialized => Some(LspResponse {
+                jsonrpc: "2.0".to_string(),
+                id: req.id,
+                result: Some(self.handle_list_rules()),
+                error: None,
+            }),
+            "textDocument/documentSymbol" if self.initialized => {
+                let result = req
+                    .params
+                    .and_then(|params| serde_json::from_value::<DocumentSymbolParams>(params).ok())
+                    .and_then(|params| self.handle_document_symbol(params))
+                    .unwrap_or(Value::Null);
+                Some(LspResponse {
+                    jsonrpc: "2.0".to_string(),
+                    id: req.id,
+                    result: Some(result),
+                    error: None,
+                })
+            }
             _ => {
                 if !self.initialized {
                     return Some(LspResponse {
@@ -218,7 +313,7 @@ impl LspServer {
                     if let Ok(did_change) =
                         serde_json::from_value::<DidChangeTextDocumentParams>(params)
                     {
-                        self.handle_did_change(did_change, &mut notifications);
+                        self.handle_did_change(did_change);
                     }
                 }
             }
@@ -234,6 +329,13 @@ impl LspServer {
                     }
                 }
             }
+            "workspace/didChangeConfiguration" => {
+                if !self.initialized {
+                    return notifications;
+                }
+                self.reload_config();
+                self.republish_all(&mut notifications);
+            }
             "exit" => {
                 self.exit_received = true;
             }
@@ -252,34 +354,23 @@ impl LspServer {
         let text = params.text_document.text;
         let version = params.text_document.version;
 
-        let text_for_diagnostics = text.clone();
         let (result, tree) = self.engine.analyze_with_tree(&text);
+        notifications.push(self.publish_diagnostics(&uri, version, result, &text));
 
+        self.pending.remove(&uri);
         self.documents.insert(
-            uri.clone(),
+            uri,
             DocumentState {
                 text,
                 version,
                 tree,
             },
         );
-
-        let diagnostics = self.convert_diagnostics(&result.diagnostics, &text_for_diagnostics);
-        notifications.push(LspNotification {
-            jsonrpc: "2.0".to_string(),
-            method: "textDocument/publishDiagnostics".to_string(),
-            params: Some(serde_json::json!({
-                "uri": uri,
-                "diagnostics": diagnostics
-            })),
-        });
     }
 
-    fn handle_did_change(
-        &mut self,
-        params: DidChangeTextDocumentParams,
-        notifications: &mut Vec<LspNotification>,
-    ) {
+    /// Record a document's new text. It is analyzed by the next
+    /// [`Self::flush_pending`], once every queued change has been read.
+    fn handle_did_change(&mut self, params: DidChangeTextDocumentParams) {
         let uri = params.text_document.uri;
 
         // We advertise TextDocumentSyncKind.Full (1), so the client always
@@ -291,40 +382,49 @@ impl LspServer {
 
         let version = params.text_document.version;
 
-        let old_tree = self.documents.get(&uri).and_then(|doc| doc.tree.as_ref());
-
-        let (result, tree) = if let Some(old) = old_tree {
-            self.engine
-                .analyze_incremental_with_tree(&new_text, Some(old))
-        } else {
-            self.engine.analyze_with_tree(&new_text)
-        };
-
-        let text_for_diagnostics = new_text.clone();
         if let Some(doc) = self.documents.get_mut(&uri) {
+            // A change older than the text we have is out of date
+            if version < doc.version {
+                return;
+            }
             doc.text = new_text;
             doc.version = version;
-            doc.tree = tree;
         } else {
             self.documents.insert(
                 uri.clone(),
                 DocumentState {
                     text: new_text,
                     version,
-                    tree,
+                    tree: None,
                 },
             );
         }
+        self.pending.insert(uri, version);
+    }
 
-        let diagnostics = self.convert_diagnostics(&result.diagnostics, &text_for_diagnostics);
-        notifications.push(LspNotification {
-            jsonrpc: "2.0".to_string(),
-            method: "textDocument/publishDiagnostics".to_string(),
-            params: Some(serde_json::json!({
-                "uri": uri,
-                "diagnostics": diagnostics
-            })),
-        });
+    /// Analyze the documents changed since their last analysis and publish
+    /// their diagnostics, for the latest version of each.
+    fn flush_pending(&mut self) -> Vec<LspNotification> {
+        let mut pending: Vec<(String, i32)> = self.pending.drain().collect();
+        pending.sort();
+        let mut notifications = Vec::new();
+        for (uri, version) in pending {
+            let doc = match self.documents.get(&uri) {
+                Some(doc) if doc.version == version => doc,
+                _ => continue,
+            };
+            let (result, tree) = match doc.tree.as_ref() {
+                Some(old) => self
+                    .engine
+                    .analyze_incremental_with_tree(&doc.text, Some(old)),
+                None => self.engine.analyze_with_tree(&doc.text),
+            };
+            notifications.push(self.publish_diagnostics(&uri, version, result, &doc.text));
+            if let Some(doc) = self.documents.get_mut(&uri) {
+                doc.tree = tree;
+            }
+        }
+        notifications
     }
 
     fn handle_did_close(
@@ -334,6 +434,7 @@ impl LspServer {
     ) {
         let uri = params.text_document.uri;
         self.documents.remove(&uri);
+        self.pending.remove(&uri);
 
         // Clear diagnostics for the closed document
         notifications.push(LspNotification {
@@ -346,24 +447,320 @@ impl LspServer {
         });
     }
 
-    fn convert_diagnostics(&self, diagnostics: &[CoreDiagnostic], text: &str) -> Vec<Value> {
+    /// Re-discover `.truss.yml` from the workspace root.
+    ///
+    /// A config that fails to parse is reported on stderr and replaced by the
+    /// defaults, as the CLI does for a discovered config.
+    fn reload_config(&mut self) {
+        let path = match self
+            .workspace_root
+            .as_deref()
+            .and_then(TrussConfig::discover)
+        {
+            Some(path) => path,
+            None => {
+                self.config = TrussConfig::default();
+                return;
+            }
+        };
+        self.config = TrussConfig::from_file(&path).unwrap_or_else(|e| {
+            eprintln!("truss-lsp: ignoring {}: {}", path.display(), e);
+            TrussConfig::default()
+        });
+    }
+
+    /// Re-analyze every open document and publish its diagnostics.
+    fn republish_all(&mut self, notifications: &mut Vec<LspNotification>) {
+        self.pending.clear();
+        let mut uris: Vec<String> = self.documents.keys().cloned().collect();
+        uris.sort();
+        for uri in uris {
+            let (text, version) = match self.documents.get(&uri) {
+                Some(doc) => (doc.text.clone(), doc.version),
+                None => continue,
+            };
+            let (result, tree) = self.engine.analyze_with_tree(&text);
+            notifications.push(self.publish_diagnostics(&uri, version, result, &text));
+            if let Some(doc) = self.documents.get_mut(&uri) {
+                doc.tree = tree;
+            }
+        }
+    }
+
+    /// Build the `publishDiagnostics` notification for a version of a
+    /// document, with the workspace config applied.
+    fn publish_diagnostics(
+        &self,
+        uri: &str,
+        version: i32,
+        mut result: TrussResult,
+        text: &str,
+    ) -> LspNotification {
+        // The analysis indexed the document already; results without an
+        // index are rebuilt from the text
+        let lines = result
+            .line_index
+            .take()
+            .unwrap_or_else(|| LineIndex::new(text));
+        let diagnostics = self.apply_config(uri, result);
+        LspNotification {
+            jsonrpc: "2.0".to_string(),
+            method: "textDocument/publishDiagnostics".to_string(),
+            params: Some(serde_json::json!({
+                "uri": uri,
+                "version": version,
+                "diagnostics": self.convert_diagnostics(&diagnostics, uri, &lines)
+            })),
+        }
+    }
+
+    /// Apply `.truss.yml`'s `ignore`, rule enablement, and severity settings,
+    /// matching against the document's path relative to the workspace root.
+    fn apply_config(&self, uri: &str, mut result: TrussResult) -> Vec<CoreDiagnostic> {
+        let path = config_path(uri, self.workspace_root.as_deref());
+        if self.config.is_ignored(&path) {
+            return Vec::new();
+        }
+        result.apply_severity_overrides(&self.config.severity_overrides_for(&path));
+        result
+            .diagnostics
+            .into_iter()
+            .filter(|d| self.config.is_rule_enabled_for(&d.rule_id, &path))
+            .collect()
+    }
+
+    /// Describe the context reference under the cursor, if it's inside an expression.
+    fn handle_hover(&self, params: HoverParams) -> Option<Value> {
+        let doc = self.documents.get(&params.text_document.uri)?;
+        let tree = doc.tree.as_ref()?;
+        let offset =
+            lsp_position_to_byte(params.position.line, params.position.character, &doc.text)?;
+
+        // Expressions live inside a single scalar, so only look at that node's text
+        let node = tree.root_node().descendant_for_byte_range(offset, offset)?;
+        if !node.kind().ends_with("scalar") {
+            return None;
+        }
+        let scalar = &doc.text[node.start_byte()..node.end_byte()];
+        let cursor = offset - node.start_byte();
+        if !is_in_expression(scalar, cursor, is_if_value(node, &doc.text)) {
+            return None;
+        }
+
+        let (start, end) = reference_bounds(scalar, cursor)?;
+        let reference = &scalar[start..end];
+        let markdown = describe_reference(reference)?;
+
+        let (start_line, start_char) = byte_to_utf16_position(node.start_byte() + start, &doc.text);
+        let (end_line, end_char) = byte_to_utf16_position(node.start_byte() + end, &doc.text);
+        Some(serde_json::json!({
+            "contents": {
+                "kind": "markdown",
+                "value": markdown
+            },
+            "range": {
+                "start": { "line": start_line, "character": start_char },
+                "end": { "line": end_line, "character": end_char }
+            }
+        }))
+    }
+
+    /// Suggest context names and properties while typing an expression.
+    ///
+    /// The expression may still be unclosed (`${{ github.` with no `}}` yet),
+    /// so it is found by scanning the current line rather than the parsed
+    /// scalar. The tree supplies the step ids and job names for `steps.` and
+    /// `needs.`.
+    fn handle_completion(&self, params: CompletionParams) -> Option<Value> {
+        let doc = self.documents.get(&params.text_document.uri)?;
+        let offset =
+            lsp_position_to_byte(params.position.line, params.position.character, &doc.text)?;
+        let line_start = doc.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let before = &doc.text[line_start..offset];
+        if !is_in_open_expression(before) {
+            return None;
+        }
+
+        // The dotted reference typed so far, e.g. `steps.build.` or `git`
+        let is_ref_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.');
+        let reference = before
+            .rfind(|c: char| !is_ref_char(c))
+            .map_or(before, |i| &before[i + 1..]);
+        let mut path: Vec<&str> = reference.split('.').collect();
+        path.pop(); // the partial name being typed; the client filters on it
+
+        let job = doc.tree.as_ref().and_then(|tree| {
+            let index = WorkflowIndex::new(tree, &doc.text);
+            let job = index.jobs().iter().find(|job| {
+                let range = enclosing_pair(job.value).unwrap_or(job.value).byte_range();
+                range.start <= offset && offset <= range.end
+            })?;
+            let step_ids = job
+                .steps
+                .iter()
+                .filter(|step| step.end_byte() < offset)
+                .filter_map(|&step| mapping_value(step, "id", &doc.text))
+                .map(|id| scalar_text(id, &doc.text))
+                .collect::<Vec<_>>();
+            let needs = job_needs(job.value, &doc.text)
+                .into_iter()
+                .map(|needed| {
+                    let outputs = index
+                        .jobs()
+                        .iter()
+                        .find(|other| other.name == needed)
+                        .and_then(|other| mapping_value(other.value, "outputs", &doc.text))
+                        .map(|outputs| mapping_keys(outputs, &doc.text))
+                        .unwrap_or_default();
+                    (needed, outputs)
+                })
+                .collect::<Vec<_>>();
+            Some((step_ids, needs))
+        });
+        let (step_ids, needs) = job.unwrap_or_default();
+
+        let items: Vec<Value> = match path.as_slice() {
+            [] => CONTEXT_DOCS
+                .iter()
+                .map(|(name, doc)| completion_item(name, COMPLETION_MODULE, Some(doc)))
+                .collect(),
+            ["github"] => GITHUB_PROPERTY_DOCS
+                .iter()
+                .map(|(name, doc)| completion_item(name, COMPLETION_PROPERTY, Some(doc)))
+                .collect(),
+            ["steps"] => step_ids
+                .iter()
+                .map(|id| completion_item(id, COMPLETION_FIELD, None))
+                .collect(),
+            ["steps", _] => ["outputs", "outcome", "conclusion"]
+                .iter()
+                .map(|name| completion_item(name, COMPLETION_PROPERTY, None))
+                .collect(),
+            ["needs"] => needs
+                .iter()
+                .map(|(job, _)| completion_item(job, COMPLETION_FIELD, None))
+                .collect(),
+            ["needs", _] => ["outputs", "result"]
+                .iter()
+                .map(|name| completion_item(name, COMPLETION_PROPERTY, None))
+                .collect(),
+            ["needs", job, "outputs"] => needs
+                .iter()
+                .find(|(name, _)| name == job)
+                .map(|(_, outputs)| {
+                    outputs
+                        .iter()
+                        .map(|name| completion_item(name, COMPLETION_PROPERTY, None))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        Some(Value::Array(items))
+    }
+
+    /// Quick fixes for the diagnostics the client sends back.
+    ///
+    /// A diagnostic gets a fix when a rule proposes an edit for exactly its
+    /// range, such as `step_set_output` rewriting `echo "::set-output name=x::y"`
+    /// to write `x=y` to `$GITHUB_OUTPUT`. The edits are the ones `truss fix`
+    /// applies.
+    fn handle_code_action(&mut self, params: CodeActionParams) -> Value {
+        let uri = params.text_document.uri;
+        let doc = match self.documents.get(&uri) {
+            Some(doc) => doc,
+            None => return Value::Array(Vec::new()),
+        };
+        let fix = self.engine.fix(&doc.text);
+        if fix.replacements.is_empty() {
+            return Value::Array(Vec::new());
+        }
+        let actions = params
+            .context
+            .diagnostics
+            .into_iter()
+            .filter_map(|d| {
+                let position = |key: &str| {
+                    let position = &d["range"][key];
+                    lsp_position_to_byte(
+                        position["line"].as_u64()? as u32,
+                        position["character"].as_u64()? as u32,
+                        &doc.text,
+                    )
+                };
+                let (start, end) = (position("start")?, position("end")?);
+                let edit = fix.replacements.iter().find(|edit| {
+                    d["code"] == edit.rule_id.as_str()
+                        && edit.span.start == start
+                        && edit.span.end == end
+                })?;
+                Some(serde_json::json!({
+                    "title": format!("Replace with `{}`", edit.new_text),
+                    "kind": "quickfix",
+                    "diagnostics": [d],
+                    "isPreferred": true,
+                    "edit": {
+                        "changes": {
+                            uri.as_str(): [{
+                                "range": d["range"],
+                                "newText": edit.new_text
+                            }]
+                        }
+                    }
+                }))
+            })
+            .collect();
+        Value::Array(actions)
+    }
+
+    /// Outline of a workflow: its name, each trigger under `on`, and each job with its steps.
+    fn handle_document_symbol(&self, params: DocumentSymbolParams) -> Option<Value> {
+        let doc = self.documents.get(&params.text_document.uri)?;
+        let tree = doc.tree.as_ref()?;
+        Some(Value::Array(workflow_symbols(tree, &doc.text)))
+    }
+
+    /// Metadata for every rule, for clients building a settings UI.
+    ///
+    /// Custom `truss/listRules` request; category and severity use the same
+    /// lowercase names as `.truss.yml` and the CLI.
+    fn handle_list_rules(&self) -> Value {
+        let rules: Vec<Value> = self
+            .engine
+            .rule_metadata()
+            .map(|(id, category, severity, description)| {
+                serde_json::json!({
+                    "id": id,
+                    "category": category,
+                    "defaultSeverity": severity,
+                    "description": description
+                })
+            })
+            .collect();
+        Value::Array(rules)
+    }
+
+    /// Convert core diagnostics to LSP ones. Related locations always point
+    /// into the same document, `uri`.
+    fn convert_diagnostics(
+        &self,
+        diagnostics: &[CoreDiagnostic],
+        uri: &str,
+        lines: &LineIndex,
+    ) -> Vec<Value> {
+        let range = |start: usize, end: usize| {
+            let (start_line, start_char) = lines.line_col(start);
+            let (end_line, end_char) = lines.line_col(end);
+            serde_json::json!({
+                "start": { "line": start_line, "character": start_char },
+                "end": { "line": end_line, "character": end_char }
+            })
+        };
         diagnostics
             .iter()
             .map(|d| {
-                let (start_line, start_char) = byte_to_lsp_position(d.span.start, text);
-                let (end_line, end_char) = byte_to_lsp_position(d.span.end, text);
-
-                serde_json::json!({
-                    "range": {
-                        "start": {
-                            "line": start_line,
-                            "character": start_char
-                        },
-                        "end": {
-                            "line": end_line,
-                            "character": end_char
-                        }
-                    },
+                let mut diagnostic = serde_json::json!({
+                    "range": range(d.span.start, d.span.end),
                     "severity": match d.severity {
                         CoreSeverity::Error => 1,
                         CoreSeverity::Warning => 2,
@@ -371,31 +768,618 @@ impl LspServer {
                     },
                     "message": d.message,
                     "source": "truss"
-                })
+                });
+                if !d.rule_id.is_empty() {
+                    diagnostic["code"] = Value::String(d.rule_id.clone());
+                }
+                if !d.related.is_empty() {
+                    let related: Vec<Value> = d
+                        .related
+                        .iter()
+                        .map(|related| {
+                            serde_json::json!({
+                                "location": {
+                                    "uri": uri,
+                                    "range": range(related.span.start, related.span.end)
+                                },
+                                "message": related.message
+                            })
+                        })
+                        .collect();
+                    diagnostic["relatedInformation"] = Value::Array(related);
+                }
+                diagnostic
             })
             .collect()
     }
 }
 
-/// Convert a byte offset in `text` to an LSP position (line, character).
+/// The workspace folder from `initialize` params: `rootUri`, else the first of
+/// `workspaceFolders`, else the deprecated `rootPath`.
+fn workspace_root(params: &Value) -> Option<PathBuf> {
+    let uri = params["rootUri"]
+        .as_str()
+        .or_else(|| params["workspaceFolders"][0]["uri"].as_str());
+    match uri {
+        Some(uri) => uri_to_path(uri),
+        None => params["rootPath"].as_str().map(PathBuf::from),
+    }
+}
+
+/// Convert a `file://` URI to a filesystem path. Other schemes yield `None`.
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    // Skip an authority such as `localhost`
+    let path = &path[path.find('/')?..];
+
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+
+    // `file:///C:/repo` is `C:/repo` on Windows
+    let is_drive = path.len() > 2 && path.as_bytes()[2] == b':';
+    Some(PathBuf::from(if is_drive { &path[1..] } else { &path[..] }))
+}
+
+/// The path `.truss.yml` globs are matched against for a document.
+///
+/// This is the document URI's path relative to the workspace root, as the CLI
+/// sees files when run from the repository root (`.github/workflows/ci.yml`).
+/// Documents outside the workspace use their full path, and non-file URIs the
+/// URI itself.
+fn config_path(uri: &str, workspace_root: Option<&Path>) -> String {
+    let path = match uri_to_path(uri) {
+        Some(path) => path,
+        None => return uri.to_string(),
+    };
+    let relative = workspace_root
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(&path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Convert an LSP position (line, UTF-16 character) back to a byte offset in `text`.
+///
+/// Returns `None` when the line is past the end of the document. A character
+/// past the end of its line is clamped to the line end.
+fn lsp_position_to_byte(line: u32, character: u32, text: &str) -> Option<usize> {
+    let line_start = if line == 0 {
+        0
+    } else {
+        text.match_indices('\n').nth(line as usize - 1)?.0 + 1
+    };
+    let line_text = text[line_start..].split('\n').next().unwrap_or("");
+
+    let mut units = 0;
+    for (i, c) in line_text.char_indices() {
+        if units >= character {
+            return Some(line_start + i);
+        }
+        units += c.len_utf16() as u32;
+    }
+    Some(line_start + line_text.len())
+}
+
+/// Known expression contexts and a short description of each.
+const CONTEXT_DOCS: &[(&str, &str)] = &[
+    ("github", "Information about the workflow run and the event that triggered it."),
+    ("env", "Environment variables set in the workflow, job, or step."),
+    ("vars", "Configuration variables set at the repository, environment, or organization level."),
+    ("secrets", "Secrets available to this workflow run. Not available in `if:` conditions."),
+    ("needs", "Outputs and results of the jobs this job depends on through `needs:`."),
+    ("steps", "Outputs and results of steps with an `id` that ran earlier in the current job."),
+    ("inputs", "Inputs passed to a reusable workflow or a manually dispatched workflow."),
+    ("matrix", "Matrix values for the current job combination."),
+    ("strategy", "Matrix execution strategy of the current job, like `fail-fast` and `job-index`."),
+    ("job", "Information about the current job, like its container and services."),
+    ("jobs", "Outputs and results of jobs in a reusable workflow. Only available in `on.workflow_call.outputs`."),
+    ("runner", "Information about the runner executing the current job, like `os` and `temp`."),
+];
+
+/// Descriptions of the most commonly used `github` context properties.
+const GITHUB_PROPERTY_DOCS: &[(&str, &str)] = &[
+    ("sha", "The commit SHA that triggered the workflow."),
+    (
+        "ref",
+        "The fully-formed ref that triggered the workflow, e.g. `refs/heads/main`.",
+    ),
+    (
+        "ref_name",
+        "The short ref name of the branch or tag that triggered the workflow.",
+    ),
+    (
+        "head_ref",
+        "The source branch of a pull request. Controlled by the PR author.",
+    ),
+    ("base_ref", "The target branch of a pull request."),
+    (
+        "event_name",
+        "The name of the event that triggered the workflow.",
+    ),
+    ("event", "The full webhook payload of the triggering event."),
+    (
+        "actor",
+        "The username of the user that triggered the initial workflow run.",
+    ),
+    (
+        "repository",
+        "The owner and repository name, e.g. `octocat/hello-world`.",
+    ),
+    ("repository_owner", "The repository owner's username."),
+    (
+        "run_id",
+        "A unique number for each workflow run within a repository.",
+    ),
+    (
+        "run_number",
+        "A unique number for each run of a particular workflow in a repository.",
+    ),
+    (
+        "workflow",
+        "The name of the workflow, or its file path if it has no name.",
+    ),
+    (
+        "workspace",
+        "The default working directory on the runner for steps.",
+    ),
+    (
+        "token",
+        "A token to authenticate on behalf of the GitHub App installed on the repository.",
+    ),
+];
+
+/// Whether `cursor` falls inside an expression in `scalar`.
 ///
-/// LSP positions use zero-based line numbers and character offsets measured
-/// in UTF-16 code units. For ASCII text, UTF-16 code units equal byte offsets
-/// within the line. For non-ASCII text, we must count UTF-16 code units properly.
-fn byte_to_lsp_position(byte_offset: usize, text: &str) -> (u32, u32) {
-    let clamped = byte_offset.min(text.len());
-    let bytes_before = &text[..clamped];
-    let line = bytes_before.matches('\n').count() as u32;
-    let last_newline = bytes_before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+/// That's either inside a `${{ }}` block or anywhere in an `if:` value, where
+/// the braces are optional.
+fn is_in_expression(scalar: &str, cursor: usize, is_if_value: bool) -> bool {
+    if is_if_value {
+        return true;
+    }
+    let before = &scalar[..cursor];
+    match before.rfind("${{") {
+        Some(open) => !before[open..].contains("}}") && scalar[cursor..].contains("}}"),
+        None => false,
+    }
+}
 
-    // Count UTF-16 code units from last_newline to byte_offset
-    let line_bytes = &text[last_newline..clamped];
-    let character = line_bytes
-        .chars()
-        .map(|c| c.len_utf16() as u32)
-        .sum::<u32>();
+/// Whether the end of `line` is inside an expression that may not be closed yet.
+///
+/// That's after a `${{` with no `}}` following it, or anywhere in the value
+/// of an `if:` key, where the braces are optional.
+fn is_in_open_expression(line: &str) -> bool {
+    if let Some(open) = line.rfind("${{") {
+        return !line[open..].contains("}}");
+    }
+    let key = line.trim_start().trim_start_matches("- ").trim_start();
+    key.strip_prefix("if:")
+        .is_some_and(|value| value.starts_with(char::is_whitespace))
+}
+
+/// Whether `node` is (part of) the value of an `if:` key.
+fn is_if_value(node: tree_sitter::Node, text: &str) -> bool {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        if n.kind() == "block_mapping_pair" || n.kind() == "flow_pair" {
+            return n.child(0).is_some_and(|key| {
+                key.end_byte() <= node.start_byte() && text[key.byte_range()].trim() == "if"
+            });
+        }
+        current = n.parent();
+    }
+    false
+}
+
+/// Byte range of the dotted reference (e.g. `steps.build.outputs.x`) around `cursor`.
+fn reference_bounds(scalar: &str, cursor: usize) -> Option<(usize, usize)> {
+    let is_ref_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '*');
+    let start = scalar[..cursor]
+        .rfind(|c: char| !is_ref_char(c))
+        .map_or(0, |i| i + 1);
+    let end = scalar[cursor..]
+        .find(|c: char| !is_ref_char(c))
+        .map_or(scalar.len(), |i| cursor + i);
+    if start >= end {
+        None
+    } else {
+        Some((start, end))
+    }
+}
+
+/// Markdown hover text for a context reference, or `None` for unknown contexts.
+fn describe_reference(reference: &str) -> Option<String> {
+    let segments: Vec<&str> = reference.split('.').collect();
+    let context = segments[0];
+    let context_doc = CONTEXT_DOCS
+        .iter()
+        .find(|(name, _)| *name == context)
+        .map(|(_, doc)| *doc)?;
+
+    let detail = match (context, &segments[1..]) {
+        ("github", [property, ..]) => GITHUB_PROPERTY_DOCS
+            .iter()
+            .find(|(name, _)| name == property)
+            .map(|(_, doc)| doc.to_string()),
+        ("steps", [id, "outputs", name, ..]) => Some(format!(
+            "Output `{}` set by the step with `id: {}`.",
+            name, id
+        )),
+        ("steps", [id, "outcome" | "conclusion"]) => Some(format!(
+            "Result of the step with `id: {}`: `success`, `failure`, `cancelled`, or `skipped`.",
+            id
+        )),
+        ("needs", [job, "outputs", name, ..]) => Some(format!(
+            "Output `{}` of job `{}`, declared in its `outputs:`.",
+            name, job
+        )),
+        ("needs", [job, "result"]) => Some(format!(
+            "Result of job `{}`: `success`, `failure`, `cancelled`, or `skipped`.",
+            job
+        )),
+        ("secrets", [name, ..]) => Some(format!("The secret `{}`.", name)),
+        ("inputs", [name, ..]) => Some(format!("The input `{}`.", name)),
+        ("env", [name, ..]) => Some(format!("The environment variable `{}`.", name)),
+        ("vars", [name, ..]) => Some(format!("The configuration variable `{}`.", name)),
+        ("matrix", [name, ..]) => {
+            Some(format!("The matrix value `{}` for this combination.", name))
+        }
+        _ => None,
+    };
+
+    let mut markdown = format!("**`{}`**\n\n{}", reference, context_doc);
+    if let Some(detail) = detail {
+        markdown.push_str("\n\n");
+        markdown.push_str(&detail);
+    }
+    Some(markdown)
+}
+
+// LSP CompletionItemKind values used for expression completions
+const COMPLETION_FIELD: u32 = 5;
+const COMPLETION_MODULE: u32 = 9;
+const COMPLETION_PROPERTY: u32 = 10;
+
+fn completion_item(label: &str, kind: u32, documentation: Option<&str>) -> Value {
+    let mut item = serde_json::json!({
+        "label": label,
+        "kind": kind,
+    });
+    if let Some(documentation) = documentation {
+        item["documentation"] = serde_json::json!({
+            "kind": "markdown",
+            "value": documentation
+        });
+    }
+    item
+}
+
+/// The job IDs a job lists in `needs:`, as a single ID or a sequence.
+fn job_needs(job: tree_sitter::Node, text: &str) -> Vec<String> {
+    match mapping_value(job, "needs", text) {
+        Some(needs) if matches!(needs.kind(), "block_sequence" | "flow_sequence") => {
+            named_children(needs)
+                .into_iter()
+                .filter_map(|item| match item.kind() {
+                    "block_sequence_item" => item.named_child(0),
+                    _ => Some(item),
+                })
+                .map(|item| scalar_text(item, text))
+                .collect()
+        }
+        Some(needs) => vec![scalar_text(needs, text)],
+        None => Vec::new(),
+    }
+}
+
+/// The keys of a mapping node.
+fn mapping_keys(mapping: tree_sitter::Node, text: &str) -> Vec<String> {
+    named_children(mapping)
+        .into_iter()
+        .filter(|n| matches!(n.kind(), "block_mapping_pair" | "flow_pair"))
+        .filter_map(|pair| pair.child_by_field_name("key"))
+        .map(|key| scalar_text(key, text))
+        .collect()
+}
+
+// LSP SymbolKind values used in the workflow outline
+const SYMBOL_NAMESPACE: u32 = 3;
+const SYMBOL_METHOD: u32 = 6;
+const SYMBOL_FUNCTION: u32 = 12;
+const SYMBOL_STRING: u32 = 15;
+const SYMBOL_EVENT: u32 = 24;
+
+/// Build the `DocumentSymbol` tree for a workflow.
+///
+/// Top-level symbols are the workflow `name`, `on` with one child per trigger,
+/// and `jobs` with one child per job. Each job lists its steps as children.
+fn workflow_symbols(tree: &tree_sitter::Tree, text: &str) -> Vec<Value> {
+    let index = WorkflowIndex::new(tree, text);
+    let mut symbols = Vec::new();
+
+    if let Some(name) = index.top_level("name") {
+        let pair = enclosing_pair(name).unwrap_or(name);
+        symbols.push(document_symbol(
+            scalar_text(name, text),
+            Some("name"),
+            SYMBOL_STRING,
+            pair,
+            name,
+            text,
+            Vec::new(),
+        ));
+    }
+
+    if let Some(on) = index.top_level("on") {
+        let pair = enclosing_pair(on).unwrap_or(on);
+        let triggers = trigger_nodes(on)
+            .into_iter()
+            .map(|(key, range)| {
+                document_symbol(
+                    scalar_text(key, text),
+                    None,
+                    SYMBOL_EVENT,
+                    range,
+                    key,
+                    text,
+                    Vec::new(),
+                )
+            })
+            .collect();
+        symbols.push(document_symbol(
+            "on".to_string(),
+            None,
+            SYMBOL_NAMESPACE,
+            pair,
+            pair.child(0).unwrap_or(pair),
+            text,
+            triggers,
+        ));
+    }
+
+    if let Some(jobs_node) = index.jobs_node() {
+        let pair = enclosing_pair(jobs_node).unwrap_or(jobs_node);
+        let jobs = index
+            .jobs()
+            .iter()
+            .map(|job| {
+                let steps = job
+                    .steps
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &step)| step_symbol(step, i, text))
+                    .collect();
+                let runs_on = mapping_value(job.value, "runs-on", text)
+                    .map(|node| collapse_whitespace(&scalar_text(node, text)));
+                document_symbol(
+                    job.name.to_string(),
+                    runs_on.as_deref(),
+                    SYMBOL_FUNCTION,
+                    enclosing_pair(job.value).unwrap_or(job.key),
+                    job.key,
+                    text,
+                    steps,
+                )
+            })
+            .collect();
+        symbols.push(document_symbol(
+            "jobs".to_string(),
+            None,
+            SYMBOL_NAMESPACE,
+            pair,
+            pair.child(0).unwrap_or(pair),
+            text,
+            jobs,
+        ));
+    }
+
+    symbols
+}
+
+/// A step symbol, named by its `name`, then `uses`, then the first line of `run`.
+fn step_symbol(step: tree_sitter::Node, position: usize, text: &str) -> Value {
+    let (label, selection) = ["name", "uses", "run"]
+        .iter()
+        .find_map(|key| {
+            let node = mapping_value(step, key, text)?;
+            let value = scalar_text(node, text);
+            // Skip the `|`/`>` header of a block scalar
+            let header = usize::from(node.kind() == "block_scalar");
+            let first_line = value
+                .lines()
+                .skip(header)
+                .map(str::trim)
+                .find(|l| !l.is_empty())?;
+            Some((first_line.to_string(), node))
+        })
+        .unwrap_or_else(|| (format!("step {}", position + 1), step));
+
+    // A block step spans its `- ` marker as well
+    let range = step
+        .parent()
+        .and_then(|n| n.parent())
+        .filter(|n| n.kind() == "block_sequence_item")
+        .unwrap_or(step);
+    document_symbol(
+        label,
+        None,
+        SYMBOL_METHOD,
+        range,
+        selection,
+        text,
+        Vec::new(),
+    )
+}
+
+/// Trigger names under `on`, as `(name node, full range node)` pairs.
+///
+/// Handles the scalar (`on: push`), sequence (`on: [push, pull_request]`), and
+/// mapping forms.
+fn trigger_nodes(on: tree_sitter::Node) -> Vec<(tree_sitter::Node, tree_sitter::Node)> {
+    match on.kind() {
+        "block_mapping" | "flow_mapping" => named_children(on)
+            .into_iter()
+            .filter(|n| matches!(n.kind(), "block_mapping_pair" | "flow_pair"))
+            .filter_map(|pair| Some((pair.child_by_field_name("key")?, pair)))
+            .collect(),
+        "block_sequence" | "flow_sequence" => named_children(on)
+            .into_iter()
+            .filter_map(|item| {
+                let scalar = if item.kind() == "block_sequence_item" {
+                    item.named_child(0)?
+                } else {
+                    item
+                };
+                Some((scalar, scalar))
+            })
+            .collect(),
+        _ => vec![(on, on)],
+    }
+}
 
-    (line, character)
+/// The unwrapped value of `key` in a mapping node.
+fn mapping_value<'a>(
+    mapping: tree_sitter::Node<'a>,
+    key: &str,
+    text: &str,
+) -> Option<tree_sitter::Node<'a>> {
+    named_children(mapping)
+        .into_iter()
+        .filter(|n| matches!(n.kind(), "block_mapping_pair" | "flow_pair"))
+        .find(|pair| {
+            pair.child_by_field_name("key")
+                .is_some_and(|k| scalar_text(k, text) == key)
+        })
+        .and_then(|pair| pair.child_by_field_name("value"))
+        .map(unwrap_value)
+}
+
+/// Descend through `block_node`/`flow_node` wrappers to the value itself.
+fn unwrap_value(mut node: tree_sitter::Node) -> tree_sitter::Node {
+    while matches!(node.kind(), "block_node" | "flow_node") {
+        match node.named_child(0) {
+            Some(child) => node = child,
+            None => break,
+        }
+    }
+    node
+}
+
+/// The mapping pair a value belongs to.
+fn enclosing_pair(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if matches!(n.kind(), "block_mapping_pair" | "flow_pair") {
+            return Some(n);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+fn named_children(node: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor).collect()
+}
+
+/// A node's text with surrounding whitespace and quotes removed.
+fn scalar_text(node: tree_sitter::Node, text: &str) -> String {
+    let raw = text[node.byte_range()].trim();
+    let unquoted = ['"', '\'']
+        .iter()
+        .find_map(|q| raw.strip_prefix(*q).and_then(|r| r.strip_suffix(*q)))
+        .unwrap_or(raw);
+    unquoted.to_string()
+}
+
+fn collapse_whitespace(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn document_symbol(
+    name: String,
+    detail: Option<&str>,
+    kind: u32,
+    range: tree_sitter::Node,
+    selection: tree_sitter::Node,
+    text: &str,
+    children: Vec<Value>,
+) -> Value {
+    let mut symbol = serde_json::json!({
+        "name": name,
+        "kind": kind,
+        "range": lsp_range(range.start_byte(), range.end_byte(), text),
+        "selectionRange": lsp_range(selection.start_byte(), selection.end_byte(), text),
+        "children": children,
+    });
+    if let Some(detail) = detail {
+        symbol["detail"] = Value::from(detail);
+    }
+    symbol
+}
+
+fn lsp_range(start: usize, end: usize, text: &str) -> Value {
+    let (start_line, start_char) = byte_to_utf16_position(start, text);
+    let (end_line, end_char) = byte_to_utf16_position(end, text);
+    serde_json::json!({
+        "start": { "line": start_line, "character": start_char },
+        "end": { "line": end_line, "character": end_char }
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentSymbolParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletionParams {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeActionParams {
+    text_document: TextDocumentIdentifier,
+    context: CodeActionContext,
+}
+
+#[derive(Debug, Deserialize)]
+struct CodeActionContext {
+    /// The client's copies of the diagnostics at the requested range.
+    diagnostics: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HoverParams {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+}
+
+#[derive(Debug, Deserialize)]
+struct Position {
+    line: u32,
+    character: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -449,28 +1433,30 @@ enum LspOutgoing {
     Notification(LspNotification),
 }
 
-/// Run the LSP server on stdin/stdout.
-///
-/// Returns `Ok(true)` if shutdown was clean (shutdown request received before exit),
-/// `Ok(false)` if exit was received without a prior shutdown request.
-pub fn run() -> io::Result<bool> {
-    let stdin = io::stdin();
-    let mut reader = BufReader::new(stdin.lock());
-    let mut stdout = io::stdout();
-    let mut server = LspServer::new();
+/// Cap content length at 100 MB to prevent memory exhaustion
+const MAX_CONTENT_LENGTH: usize = 100 * 1024 * 1024;
 
+/// A message read from the client.
+enum Incoming {
+    /// The message's JSON content.
+    Content(Vec<u8>),
+    /// A message whose `Content-Length` is over [`MAX_CONTENT_LENGTH`].
+    /// Its content is not read.
+    TooLarge(usize),
+}
+
+/// Read the next message from the client, or `None` once it disconnects.
+fn read_message(reader: &mut impl BufRead) -> io::Result<Option<Incoming>> {
     loop {
         // Read headers until empty line
         let mut content_length: Option<usize> = None;
-        let mut eof = false;
         loop {
             let mut line = String::new();
             let bytes_read = reader.read_line(&mut line)?;
 
             // EOF — client disconnected
             if bytes_read == 0 {
-                eof = true;
-                break;
+                return Ok(None);
             }
 
             let trimmed = line.trim();
@@ -485,70 +1471,625 @@ pub fn run() -> io::Result<bool> {
             }
         }
 
-        if eof {
-            break;
+        match content_length {
+            Some(len) if len > 0 && len <= MAX_CONTENT_LENGTH => {
+                // Read exactly content_length bytes
+                let mut content = vec![0u8; len];
+                reader.read_exact(&mut content)?;
+                return Ok(Some(Incoming::Content(content)));
+            }
+            Some(len) if len > MAX_CONTENT_LENGTH => return Ok(Some(Incoming::TooLarge(len))),
+            _ => continue,
+        }
+    }
+}
+
+/// Write one message with its `Content-Length` header.
+fn write_message(stdout: &mut impl Write, json: &str) -> io::Result<()> {
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", json.len(), json)?;
+    stdout.flush()
+}
+
+/// Run the LSP server on stdin/stdout.
+///
+/// Messages are read on a separate thread so that every message the client
+/// has already sent can be handled before changed documents are analyzed.
+/// While the server is busy analyzing, a run of `didChange` notifications
+/// queues up and only the last text of each document is analyzed.
+///
+/// Returns `Ok(true)` if shutdown was clean (shutdown request received before exit),
+/// `Ok(false)` if exit was received without a prior shutdown request.
+pub fn run() -> io::Result<bool> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(io::stdin().lock());
+        // Stop at EOF, after passing on a read error, or once the server exits
+        while let Some(incoming) = read_message(&mut reader).transpose() {
+            let failed = incoming.is_err();
+            if sender.send(incoming).is_err() || failed {
+                break;
+            }
         }
+    });
+
+    let mut stdout = io::stdout();
+    let mut server = LspServer::new();
+
+    // Block for the next message, then take whatever else has arrived
+    while let Ok(first) = receiver.recv() {
+        for incoming in std::iter::once(first).chain(receiver.try_iter()) {
+            let content = match incoming? {
+                Incoming::Content(content) => content,
+                Incoming::TooLarge(len) => {
+                    // Reject oversized messages
+                    let error_response = serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": {
+                            "code": -32600,
+                            "message": format!("Content-Length {} exceeds maximum of {} bytes", len, MAX_CONTENT_LENGTH)
+                        }
+                    });
+                    write_message(&mut stdout, &serde_json::to_string(&error_response)?)?;
+                    continue;
+                }
+            };
+
+            let message = serde_json::from_slice::<Value>(&content)
+                .ok()
+                .and_then(parse_lsp_message);
 
-        // Cap content length at 100 MB to prevent memory exhaustion
-        const MAX_CONTENT_LENGTH: usize = 100 * 1024 * 1024;
-        let content_length = match content_length {
-            Some(len) if len > 0 && len <= MAX_CONTENT_LENGTH => len,
-            Some(len) if len > MAX_CONTENT_LENGTH => {
-                // Reject oversized messages
+            if let Some(message) = message {
+                for outgoing in server.handle_message(message) {
+                    let json = match &outgoing {
+                        LspOutgoing::Response(r) => serde_json::to_string(r)?,
+                        LspOutgoing::Notification(n) => serde_json::to_string(n)?,
+                    };
+                    write_message(&mut stdout, &json)?;
+                }
+            } else {
+                // Send parse error response for malformed JSON
                 let error_response = serde_json::json!({
                     "jsonrpc": "2.0",
                     "id": null,
                     "error": {
-                        "code": -32600,
-                        "message": format!("Content-Length {} exceeds maximum of {} bytes", len, MAX_CONTENT_LENGTH)
+                        "code": -32700,
+                        "message": "Parse error: invalid JSON"
                     }
                 });
-                let json = serde_json::to_string(&error_response)?;
-                write!(stdout, "Content-Length: {}\r\n\r\n{}", json.len(), json)?;
-                stdout.flush()?;
-                continue;
+                write_message(&mut stdout, &serde_json::to_string(&error_response)?)?;
             }
-            _ => continue,
-        };
 
-        // Read exactly content_length bytes
-        let mut content = vec![0u8; content_length];
-        reader.read_exact(&mut content)?;
+            if server.exit_received {
+                return Ok(server.shutdown_requested);
+            }
+        }
 
-        let message = serde_json::from_slice::<Value>(&content)
-            .ok()
-            .and_then(parse_lsp_message);
+        for notification in server.flush_pending() {
+            write_message(&mut stdout, &serde_json::to_string(&notification)?)?;
+        }
+    }
 
-        if let Some(message) = message {
-            let responses = server.handle_message(message);
+    Ok(server.shutdown_requested)
+}
 
-            for outgoing in responses {
-                let json = match &outgoing {
-                    LspOutgoing::Response(r) => serde_json::to_string(r)?,
-                    LspOutgoing::Notification(n) => serde_json::to_string(n)?,
-                };
-                write!(stdout, "Content-Length: {}\r\n\r\n{}", json.len(), json)?;
-                stdout.flush()?;
-            }
-        } else {
-            // Send parse error response for malformed JSON
-            let error_response = serde_json::json!({
-                "jsonrpc": "2.0",
-                "id": null,
-                "error": {
-                    "code": -32700,
-                    "message": "Parse error: invalid JSON"
-                }
-            });
-            let json = serde_json::to_string(&error_response)?;
-            write!(stdout, "Content-Length: {}\r\n\r\n{}", json.len(), json)?;
-            stdout.flush()?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_and_hover(text: &str, line: u32, character: u32) -> Option<Value> {
+        let mut server = LspServer::new();
+        server.initialized = true;
+        server.handle_did_open(
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///ci.yml".to_string(),
+                    language_id: "yaml".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            },
+            &mut Vec::new(),
+        );
+        server.handle_hover(HoverParams {
+            text_document: TextDocumentIdentifier {
+                uri: "file:///ci.yml".to_string(),
+            },
+            position: Position { line, character },
+        })
+    }
+
+    fn hover_text(hover: &Value) -> &str {
+        hover["contents"]["value"].as_str().unwrap()
+    }
+
+    const WORKFLOW: &str = "on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: build
+        run: echo \"sha=${{ github.sha }}\" >> \"$GITHUB_OUTPUT\"
+      - if: steps.build.outputs.sha != ''
+        run: echo ${{ steps.build.outputs.sha }}
+";
+
+    #[test]
+    fn position_round_trips_through_byte_offset() {
+        let text = "a: é\nb: ${{ x }}\n";
+        for offset in [0, 3, 6, 9, 12] {
+            let (line, character) = byte_to_utf16_position(offset, text);
+            assert_eq!(lsp_position_to_byte(line, character, text), Some(offset));
         }
+        assert_eq!(lsp_position_to_byte(5, 0, text), None);
+    }
 
-        if server.exit_received {
-            break;
+    #[test]
+    fn hover_describes_github_property() {
+        let hover = open_and_hover(WORKFLOW, 6, 34).expect("hover over github.sha");
+        let text = hover_text(&hover);
+        assert!(text.contains("`github.sha`"));
+        assert!(text.contains("commit SHA"));
+        assert_eq!(hover["range"]["start"]["character"], 27);
+    }
+
+    #[test]
+    fn hover_describes_step_output() {
+        let hover = open_and_hover(WORKFLOW, 8, 30).expect("hover over step output");
+        assert!(hover_text(&hover).contains("Output `sha` set by the step with `id: build`"));
+    }
+
+    #[test]
+    fn hover_works_in_bare_if_condition() {
+        let hover = open_and_hover(WORKFLOW, 7, 16).expect("hover in if condition");
+        assert!(hover_text(&hover).contains("`steps.build.outputs.sha`"));
+    }
+
+    #[test]
+    fn hover_outside_expression_is_none() {
+        assert!(open_and_hover(WORKFLOW, 3, 15).is_none());
+        assert!(open_and_hover(WORKFLOW, 6, 18).is_none());
+    }
+
+    fn completion_labels(text: &str, line: u32, character: u32) -> Option<Vec<String>> {
+        let mut server = LspServer::new();
+        server.initialized = true;
+        server.handle_did_open(
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///ci.yml".to_string(),
+                    language_id: "yaml".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            },
+            &mut Vec::new(),
+        );
+        let items = server.handle_completion(CompletionParams {
+            text_document: TextDocumentIdentifier {
+                uri: "file:///ci.yml".to_string(),
+            },
+            position: Position { line, character },
+        })?;
+        Some(
+            items
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["label"].as_str().unwrap().to_string())
+                .collect(),
+        )
+    }
+
+    const COMPLETION_WORKFLOW: &str = "on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    outputs:
+      version: ${{ steps.v.outputs.version }}
+    steps:
+      - id: checkout
+        uses: actions/checkout@v4
+      - id: v
+        run: echo ${{ steps. }}
+      - id: later
+        run: echo later
+  deploy:
+    needs: [build]
+    if: needs.
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ github.
+";
+
+    #[test]
+    fn completion_suggests_contexts_and_github_properties() {
+        let contexts = completion_labels(COMPLETION_WORKFLOW, 10, 22).expect("inside ${{ }}");
+        assert!(contexts.contains(&"steps".to_string()));
+
+        // Unclosed expression at the end of the document
+        let github = completion_labels(COMPLETION_WORKFLOW, 18, 29).expect("after github.");
+        assert!(github.contains(&"sha".to_string()));
+        assert!(github.contains(&"event_name".to_string()));
+
+        let text = "on: push\njobs:\n  a:\n    runs-on: ${{ m }}\n";
+        let contexts = completion_labels(text, 3, 17).expect("after ${{");
+        assert!(contexts.contains(&"matrix".to_string()));
+        assert!(contexts.contains(&"github".to_string()));
+    }
+
+    #[test]
+    fn completion_suggests_earlier_step_ids_and_needed_jobs() {
+        let steps = completion_labels(COMPLETION_WORKFLOW, 10, 28).expect("after steps.");
+        assert_eq!(steps, ["checkout"]);
+
+        let needs = completion_labels(COMPLETION_WORKFLOW, 15, 14).expect("bare if condition");
+        assert_eq!(needs, ["build"]);
+
+        let text = COMPLETION_WORKFLOW.replace("if: needs.", "if: needs.build.outputs.");
+        let outputs = completion_labels(&text, 15, 28).expect("after outputs.");
+        assert_eq!(outputs, ["version"]);
+    }
+
+    #[test]
+    fn completion_outside_expression_is_none() {
+        assert!(completion_labels(COMPLETION_WORKFLOW, 3, 20).is_none());
+        assert!(completion_labels(COMPLETION_WORKFLOW, 12, 18).is_none());
+    }
+
+    fn outline(text: &str) -> Vec<Value> {
+        let mut server = LspServer::new();
+        server.initialized = true;
+        server.handle_did_open(
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///ci.yml".to_string(),
+                    language_id: "yaml".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            },
+            &mut Vec::new(),
+        );
+        let symbols = server
+            .handle_document_symbol(DocumentSymbolParams {
+                text_document: TextDocumentIdentifier {
+                    uri: "file:///ci.yml".to_string(),
+                },
+            })
+            .expect("symbols for an open document");
+        symbols.as_array().unwrap().clone()
+    }
+
+    fn names(symbols: &Value) -> Vec<&str> {
+        symbols
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn document_symbols_outline_workflow() {
+        let text = "name: CI
+on:
+  push:
+    branches: [main]
+  pull_request:
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - name: Test
+        run: cargo test
+      - run: |
+          cargo build
+          cargo doc
+";
+        let symbols = outline(text);
+        let top: Vec<_> = symbols
+            .iter()
+            .map(|s| s["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(top, ["CI", "on", "jobs"]);
+
+        assert_eq!(names(&symbols[1]["children"]), ["push", "pull_request"]);
+        assert_eq!(symbols[1]["children"][0]["range"]["end"]["line"], 3);
+
+        let build = &symbols[2]["children"][0];
+        assert_eq!(build["name"], "build");
+        assert_eq!(build["detail"], "ubuntu-latest");
+        assert_eq!(build["range"]["start"]["line"], 6);
+        assert_eq!(
+            names(&build["children"]),
+            ["actions/checkout@v4", "Test", "cargo build"]
+        );
+        let test_step = &build["children"][1];
+        assert_eq!(test_step["range"]["start"]["line"], 10);
+        assert_eq!(test_step["selectionRange"]["start"]["line"], 10);
+        assert_eq!(test_step["selectionRange"]["start"]["character"], 14);
+    }
+
+    #[test]
+    fn document_symbols_handle_scalar_and_sequence_triggers() {
+        let symbols = outline("on: push\njobs: {}\n");
+        assert_eq!(names(&symbols[0]["children"]), ["push"]);
+
+        let symbols = outline("on: [push, 'pull_request']\n");
+        assert_eq!(names(&symbols[0]["children"]), ["push", "pull_request"]);
+
+        let symbols = outline("on:\n  - push\n  - workflow_dispatch\n");
+        assert_eq!(
+            names(&symbols[0]["children"]),
+            ["push", "workflow_dispatch"]
+        );
+    }
+
+    #[test]
+    fn list_rules_returns_rule_metadata() {
+        let mut server = LspServer::new();
+        server.initialized = true;
+        let rules = server.handle_list_rules();
+        let rules = rules.as_array().unwrap();
+
+        assert_eq!(rules.len(), server.engine.rules().count());
+        let pinning = rules
+            .iter()
+            .find(|rule| rule["id"] == "action_pinning")
+            .expect("action_pinning is listed");
+        assert_eq!(pinning["category"], "security");
+        assert_eq!(pinning["defaultSeverity"], "warning");
+        assert!(pinning["description"].as_str().unwrap().ends_with('.'));
+    }
+
+    fn published(notification: &LspNotification) -> (&str, Vec<(&str, u64)>) {
+        let params = notification.params.as_ref().unwrap();
+        let diagnostics = params["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| {
+                let message = d["message"].as_str().unwrap();
+                (message, d["severity"].as_u64().unwrap())
+            })
+            .collect();
+        (params["uri"].as_str().unwrap(), diagnostics)
+    }
+
+    #[test]
+    fn related_locations_become_related_information() {
+        let text = "on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: build
+        run: echo \"sha=${{ github.sha }}\" >> \"$GITHUB_OUTPUT\"
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - run: echo ${{ steps.build.outputs.sha }}
+";
+        let mut server = LspServer::new();
+        server.initialized = true;
+        let mut notifications = Vec::new();
+        server.handle_did_open(
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///ci.yml".to_string(),
+                    language_id: "yaml".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            },
+            &mut notifications,
+        );
+
+        let diagnostics = notifications[0].params.as_ref().unwrap()["diagnostics"]
+            .as_array()
+            .unwrap();
+        let cross_job = diagnostics
+            .iter()
+            .find(|d| d["message"].as_str().unwrap().contains("is in job 'build'"))
+            .unwrap();
+        let related = &cross_job["relatedInformation"][0];
+        assert_eq!(related["location"]["uri"], "file:///ci.yml");
+        assert_eq!(related["location"]["range"]["start"]["line"], 5);
+        assert_eq!(related["location"]["range"]["start"]["character"], 12);
+        assert_eq!(related["location"]["range"]["end"]["character"], 17);
+        assert!(related["message"]
+            .as_str()
+            .unwrap()
+            .contains("defined here"));
+        // Diagnostics without related locations don't carry the field
+        assert!(diagnostics
+            .iter()
+            .filter(|d| d != &cross_job)
+            .all(|d| d.get("relatedInformation").is_none()));
+    }
+
+    #[test]
+    fn code_action_rewrites_set_output() {
+        let text = "on: push
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - id: v
+        run: |
+          echo \"::set-output name=version::1.0\"
+          echo '::save-state name=pid::'\"$$\"
+";
+        let mut server = LspServer::new();
+        server.initialized = true;
+        let mut notifications = Vec::new();
+        server.handle_did_open(
+            DidOpenTextDocumentParams {
+                text_document: TextDocumentItem {
+                    uri: "file:///ci.yml".to_string(),
+                    language_id: "yaml".to_string(),
+                    version: 1,
+                    text: text.to_string(),
+                },
+            },
+            &mut notifications,
+        );
+        let diagnostics: Vec<Value> = notifications[0].params.as_ref().unwrap()["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|d| d["code"] == "step_set_output")
+            .cloned()
+            .collect();
+        assert_eq!(diagnostics.len(), 2);
+
+        let actions = server.handle_code_action(CodeActionParams {
+            text_document: TextDocumentIdentifier {
+                uri: "file:///ci.yml".to_string(),
+            },
+            context: CodeActionContext { diagnostics },
+        });
+        // The second command continues past its span, so only the first is fixed
+        let actions = actions.as_array().unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0]["kind"], "quickfix");
+        let edit = &actions[0]["edit"]["changes"]["file:///ci.yml"][0];
+        assert_eq!(
+            edit["newText"],
+            "echo \"version=1.0\" >> \"$GITHUB_OUTPUT\""
+        );
+        assert_eq!(edit["range"]["start"]["line"], 7);
+        assert_eq!(edit["range"]["start"]["character"], 10);
+        assert_eq!(edit["range"]["end"]["character"], 47);
+    }
+
+    #[test]
+    fn workspace_config_applies_to_diagnostics() {
+        let root = std::env::temp_dir().join(format!("truss lsp-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(
+            root.join(".truss.yml"),
+            "rules:\n  action_pinning:\n    enabled: false\n  timeout:\n    severity: warning\nignore:\n  - \"vendor/**\"\n",
+        )
+        .unwrap();
+        let root_uri = format!("file://{}", root.display()).replace(' ', "%20");
+
+        let mut server = LspServer::new();
+        server.handle_message(LspMessage::Request(LspRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Value::from(1),
+            method: "initialize".to_string(),
+            params: Some(serde_json::json!({ "rootUri": root_uri })),
+        }));
+
+        let text = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    timeout-minutes: 0\n    steps:\n      - uses: actions/checkout@v4\n";
+        let mut notifications = Vec::new();
+        for path in [".github/workflows/ci.yml", "vendor/ci.yml"] {
+            server.handle_did_open(
+                DidOpenTextDocumentParams {
+                    text_document: TextDocumentItem {
+                        uri: format!("{}/{}", root_uri, path),
+                        language_id: "yaml".to_string(),
+                        version: 1,
+                        text: text.to_string(),
+                    },
+                },
+                &mut notifications,
+            );
+        }
+
+        let (_, workflow) = published(&notifications[0]);
+        assert!(!workflow.is_empty());
+        assert!(workflow.iter().all(|(m, _)| !m.contains("pinned")));
+        assert!(workflow.iter().all(|(_, severity)| *severity == 2));
+        let (_, vendored) = published(&notifications[1]);
+        assert!(vendored.is_empty(), "ignored files publish no diagnostics");
+
+        // Dropping the config and notifying the server restores the defaults
+        std::fs::remove_file(root.join(".truss.yml")).unwrap();
+        let republished = server.handle_notification(LspNotification {
+            jsonrpc: "2.0".to_string(),
+            method: "workspace/didChangeConfiguration".to_string(),
+            params: Some(serde_json::json!({ "settings": {} })),
+        });
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(republished.len(), 2);
+        for notification in &republished {
+            let (_, diagnostics) = published(notification);
+            assert!(diagnostics.iter().any(|(m, _)| m.contains("pinned")));
+            assert!(diagnostics.iter().any(|(_, severity)| *severity == 1));
         }
     }
 
-    Ok(server.shutdown_requested)
+    #[test]
+    fn changes_are_analyzed_once_for_the_latest_version() {
+        let mut server = LspServer::new();
+        server.initialized = true;
+        let change = |version: i32, text: &str| {
+            LspMessage::Notification(LspNotification {
+                jsonrpc: "2.0".to_string(),
+                method: "textDocument/didChange".to_string(),
+                params: Some(serde_json::json!({
+                    "textDocument": { "uri": "file:///ci.yml", "version": version },
+                    "contentChanges": [{ "text": text }]
+                })),
+            })
+        };
+        let missing_runs_on = "on: push\njobs:\n  build:\n    steps:\n      - run: make\n";
+        let valid = "on: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n    steps:\n      - run: make\n";
+
+        // Changes queued before analysis are only recorded
+        assert!(server.handle_message(change(2, missing_runs_on)).is_empty());
+        assert!(server.handle_message(change(3, valid)).is_empty());
+        // An out-of-order older version doesn't replace the latest text
+        assert!(server.handle_message(change(2, missing_runs_on)).is_empty());
+
+        let published = server.flush_pending();
+        assert_eq!(published.len(), 1);
+        let params = published[0].params.as_ref().unwrap();
+        assert_eq!(params["version"], 3);
+        assert_eq!(params["diagnostics"], serde_json::json!([]));
+        assert!(server.flush_pending().is_empty());
+
+        // A request sees the latest text, so pending changes are published first
+        server.handle_message(change(4, missing_runs_on));
+        let outgoing = server.handle_message(LspMessage::Request(LspRequest {
+            jsonrpc: "2.0".to_string(),
+            id: Value::from(1),
+            method: "shutdown".to_string(),
+            params: None,
+        }));
+        assert_eq!(outgoing.len(), 2);
+        match &outgoing[0] {
+            LspOutgoing::Notification(n) => {
+                let params = n.params.as_ref().unwrap();
+                assert_eq!(params["version"], 4);
+                assert!(!params["diagnostics"].as_array().unwrap().is_empty());
+            }
+            LspOutgoing::Response(_) => panic!("diagnostics should precede the response"),
+        }
+    }
+
+    #[test]
+    fn config_path_is_relative_to_workspace_root() {
+        let root = Path::new("/home/me/my repo");
+        assert_eq!(
+            config_path(
+                "file:///home/me/my%20repo/.github/workflows/ci.yml",
+                Some(root)
+            ),
+            ".github/workflows/ci.yml"
+        );
+        assert_eq!(
+            config_path("file:///elsewhere/ci.yml", Some(root)),
+            "/elsewhere/ci.yml"
+        );
+        assert_eq!(
+            config_path("untitled:Untitled-1", Some(root)),
+            "untitled:Untitled-1"
+        );
+    }
 }
@@ -13,7 +13,7 @@
 //! const diagnostics = JSON.parse(result);
 //! ```
 
-use truss_core::TrussEngine;
+use truss_core::{LineIndex, TrussEngine};
 use wasm_bindgen::prelude::*;
 
 // ---------------------------------------------------------------------------
@@ -150,7 +150,8 @@ mod wasm_compat {
 /// Validate a GitHub Actions workflow YAML string.
 ///
 /// Returns a JSON string containing an array of diagnostics.
-/// Each diagnostic has: `message`, `severity`, `span` (with `start` and `end`).
+/// Each diagnostic has: `message`, `severity`, `span` (with `start` and `end`),
+/// and a `related` array of `{ span, message }` when it points elsewhere too.
 ///
 /// # Example
 ///
@@ -176,6 +177,49 @@ pub fn validate_pretty(source: &str) -> String {
     serde_json::to_string_pretty(&result).unwrap_or_else(|_| r#"{"diagnostics":[]}"#.to_string())
 }
 
+/// Validate and return diagnostics with line/column positions.
+///
+/// Same as `validate()`, but each diagnostic also has zero-based
+/// `startLine`, `startColumn`, `endLine`, and `endColumn`. Columns count
+/// UTF-16 code units, matching JavaScript string indices and the LSP server,
+/// so multi-byte characters don't shift positions.
+///
+/// # Example
+///
+/// ```js
+/// const { diagnostics } = JSON.parse(validate_with_positions(source));
+/// for (const d of diagnostics) {
+///   console.log(`${d.startLine + 1}:${d.startColumn + 1} ${d.message}`);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn validate_with_positions(source: &str) -> String {
+    let mut engine = TrussEngine::new();
+    let mut result = engine.analyze(source);
+    let lines = result
+        .line_index
+        .take()
+        .unwrap_or_else(|| LineIndex::new(source));
+
+    let diagnostics: Vec<serde_json::Value> = result
+        .diagnostics
+        .iter()
+        .filter_map(|d| {
+            let (start_line, start_column) = lines.line_col(d.span.start);
+            let (end_line, end_column) = lines.line_col(d.span.end);
+            let mut value = serde_json::to_value(d).ok()?;
+            let fields = value.as_object_mut()?;
+            fields.insert("startLine".to_string(), start_line.into());
+            fields.insert("startColumn".to_string(), start_column.into());
+            fields.insert("endLine".to_string(), end_line.into());
+            fields.insert("endColumn".to_string(), end_column.into());
+            Some(value)
+        })
+        .collect();
+
+    serde_json::json!({ "diagnostics": diagnostics }).to_string()
+}
+
 /// Get the version of the Truss engine.
 #[wasm_bindgen]
 pub fn version() -> String {
@@ -123,6 +123,9 @@ def load_tool_results(results_dir: Path, tool_name: str) -> Dict[str, Any]:
                 if not content:
                     continue
                 data = json.loads(content)
+                # Truss wraps its per-file results in a {files, summary} envelope
+                if isinstance(data, dict) and isinstance(data.get('files'), list):
+                    data = data['files']
                 # Handle both single file results and arrays
                 if isinstance(data, list):
                     for item in data: